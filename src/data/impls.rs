@@ -1,5 +1,12 @@
 use crate::Token;
-use crate::data::{ Keyword, TokenType };
+use crate::data::{ Ast, Binding, CType, DataflowState, Diagnostic, EntityId, Keyword, Member, NodeId, NodeKind, ParseError, ParserState, SemanticState, Symbol, SymbolTable, SymbolTableState, Target, TokenType, TypeTable, WarningFlag, Warnings };
+use crate::data::structures::{ Allocation, Analyses, BasicBlock, Block, CallGraph, Entity, FieldLayout, Frame, Global, Instruction, InterpreterState, Interner, IrFunction, IrParserState, Liveness, Loop, LoweringState, Module, PassOptions, RecordLayout, Scope, SourceMap, TypeLayout, VerifierState };
+use crate::data::types::{ AllocationKind, Analysis, ArithmeticOperator, ArraySize, AssignmentOperator, Attribute, BinaryOperator, BlockId, CastKind, ComparePredicate, ConversionOperator, Constant, ConstantValue, Declarator, Definition, DerivedDeclarator, DumpFormat, EntityKind, FloatSuffix, InlineHint, InstructionKind, IntegerSuffix, IrToken, IrType, Node, Operand, Parameter, PassName, PostfixOperator, Qualifiers, Register, ScopeKind, Severity, Span, UnaryOperator, ValueCategory };
+use std::collections::{ HashMap, HashSet };
+use std::fmt;
+use std::hash::{ Hash, Hasher };
+use std::io::Write;
+use std::ops::{ Index, IndexMut };
 use std::str::FromStr;
 use phf::phf_map;
 
@@ -22,38 +29,2952 @@ impl TokenType {
 }
 
 
+impl<'a> ParserState<'a> {
+    pub fn new(tokens: &'a [Token]) -> Self {
+        let mut ast = Ast::default();
+        let builtins = HashMap::from([(ast.symbols.intern("__builtin_va_list"), true)]);
+
+        Self { tokens, iterator: 0, ast, typedef_scopes: vec![builtins], attributes: Vec::new(), errors: Vec::new() }
+    }
+
+
+    pub fn push_node(&mut self, kind: NodeKind, start: u16) -> NodeId {
+        let end = self.iterator.checked_sub(1).and_then(|index| self.tokens.get(index)).map_or(start, |token| token.line);
+
+        self.ast.push(kind, Span { start, end: end.max(start) })
+    }
+
+
+    pub fn start_of(&self, id: NodeId) -> u16 {
+        self.ast[id].span.start
+    }
+
+
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        self.ast.symbols.intern(name)
+    }
+
+
+    pub fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.iterator)
+    }
+
+
+    pub fn peek_at(&self, offset: usize) -> Option<&'a Token> {
+        self.tokens.get(self.iterator + offset)
+    }
+
+
+    pub fn advance(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.iterator);
+        self.iterator += 1;
+
+        token
+    }
+
+
+    pub fn line(&self) -> u16 {
+        self.peek().or(self.tokens.last()).map_or(0, |token| token.line)
+    }
+
+
+    pub fn is_operator(&self, operator: &str) -> bool {
+        self.is_operator_at(0, operator)
+    }
+
+
+    pub fn is_operator_at(&self, offset: usize, operator: &str) -> bool {
+        matches!(self.peek_at(offset).map(|token| &token.token_type), Some(TokenType::Operator(s)) if s == operator)
+    }
+
+
+    pub fn keyword(&self) -> Option<Keyword> {
+        self.keyword_at(0)
+    }
+
+
+    pub fn keyword_at(&self, offset: usize) -> Option<Keyword> {
+        match self.peek_at(offset).map(|token| &token.token_type) {
+            Some(TokenType::Keyword(s)) => Keyword::from_str(s).ok(),
+            _ => None,
+        }
+    }
+
+
+    pub fn identifier_at(&self, offset: usize) -> Option<&'a str> {
+        match self.peek_at(offset).map(|token| &token.token_type) {
+            Some(TokenType::Identifier(s)) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+
+    pub fn push_scope(&mut self) {
+        self.typedef_scopes.push(HashMap::new());
+    }
+
+
+    pub fn pop_scope(&mut self) {
+        self.typedef_scopes.pop();
+    }
+
+
+    pub fn declare(&mut self, name: Symbol, is_typedef: bool) {
+        if let Some(scope) = self.typedef_scopes.last_mut() {
+            scope.insert(name, is_typedef);
+        }
+    }
+
+
+    pub fn is_typedef_name(&self, name: &str) -> bool {
+        let Some(name) = self.ast.symbols.get(name) else {
+            return false;
+        };
+
+        self.typedef_scopes.iter().rev().find_map(|scope| scope.get(&name).copied()).unwrap_or(false)
+    }
+}
+
+
+impl ParseError {
+    pub fn new(line: u16, expected: &str, found: &str) -> Self {
+        Self { line, expected: expected.to_string(), found: found.to_string() }
+    }
+}
+
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected {}, found {}", self.line, self.expected, self.found)
+    }
+}
+
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: String) -> Self {
+        Self { severity, message }
+    }
+}
+
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Error => write!(f, "error: {}", self.message),
+            Severity::Warning => write!(f, "warning: {}", self.message),
+            Severity::Note => write!(f, "note: {}", self.message),
+        }
+    }
+}
+
+
+impl<'a> SemanticState<'a> {
+    pub fn new(ast: &'a Ast, table: &'a SymbolTable, target: &'a Target) -> Self {
+        let declared = table.entities.iter().enumerate()
+            .filter(|(_, entity)| !matches!(entity.kind, EntityKind::Tag(_) | EntityKind::Label | EntityKind::Member) && entity.scope != ScopeKind::Prototype)
+            .map(|(index, entity)| ((entity.node, entity.name, entity.kind == EntityKind::Parameter), EntityId(index as u32)))
+            .collect();
+
+        Self {
+            ast,
+            table,
+            target,
+            types: TypeTable::default(),
+            declared,
+            bindings: HashMap::new(),
+            depth: 0,
+            casts: HashMap::new(),
+            erroneous: HashSet::new(),
+            function: String::new(),
+            function_noreturn: false,
+            return_type: None,
+            loop_depth: 0,
+            switches: Vec::new(),
+            labels: HashMap::new(),
+            gotos: Vec::new(),
+            variably_modified: Vec::new(),
+            node: ast.root,
+            diagnostics: Vec::new(),
+        }
+    }
+
+
+    pub fn name(&self, symbol: Symbol) -> &'a str {
+        self.ast.symbols.resolve(symbol)
+    }
+
+
+    pub fn push_scope(&mut self) {
+        self.depth += 1;
+    }
+
+
+    pub fn pop_scope(&mut self) {
+        self.depth -= 1;
+
+        let depth = self.depth;
+        self.variably_modified.retain(|&(scope, _)| scope <= depth);
+    }
+
+
+    pub fn entity(&self, node: NodeId, name: Symbol, parameter: bool) -> Option<EntityId> {
+        self.declared.get(&(node, name, parameter)).copied()
+    }
+
+
+    pub fn declare(&mut self, entity: EntityId, node: NodeId, mut binding: Binding) {
+        let previous = self.table[entity].previous.and_then(|previous| self.bindings.get(&previous));
+
+        if let Some(previous) = previous {
+            let mut attributes = previous.attributes.clone();
+            attributes.extend(binding.attributes);
+            binding.attributes = attributes;
+
+            match previous.ctype.composite(&binding.ctype) {
+                Some(composite) => binding.ctype = composite,
+
+                None => {
+                    let message = format!("conflicting types for '{}'; have '{}'", self.name(self.table[entity].name), binding.ctype);
+                    self.report(Severity::Error, node, message);
+                }
+            }
+        }
+
+        self.bindings.insert(entity, binding);
+    }
+
+
+    pub fn binding(&self, entity: EntityId) -> Option<&Binding> {
+        self.bindings.get(&entity)
+    }
+
+
+    pub fn lookup(&self, identifier: NodeId) -> Option<&Binding> {
+        self.table.resolve(identifier).and_then(|entity| self.binding(entity))
+    }
+
+
+    pub fn lookup_typedef(&self, node: NodeId, name: Symbol) -> Option<&Binding> {
+        self.table.typedef_names.get(&(node, name)).and_then(|&entity| self.binding(entity))
+    }
+
+
+    pub fn lookup_tag(&self, tag: &str) -> Option<&Vec<Member>> {
+        self.types.records.get(tag)
+    }
+
+
+    pub fn find_member(&self, tag: &str, name: Symbol) -> Option<CType> {
+        let members = self.lookup_tag(tag)?;
+
+        members.iter().find_map(|member| match (member.name, member.ctype.unqualified()) {
+            (Some(member_name), _) if member_name == name => Some(member.ctype.clone()),
+            (None, CType::Record { tag, .. }) => self.find_member(tag, name),
+            _ => None,
+        })
+    }
+
+
+    pub fn report(&mut self, severity: Severity, node: NodeId, message: String) {
+        let line = self.ast[node].span.start;
+
+        let message = if self.function.is_empty() {
+            format!("{}: {}", line, message)
+        } else {
+            format!("{}: in function '{}': {}", line, self.function, message)
+        };
+
+        self.diagnostics.push(Diagnostic::new(severity, message));
+    }
+}
+
+
+impl<'a> SymbolTableState<'a> {
+    pub fn new(ast: &'a Ast) -> Self {
+        Self {
+            ast,
+            table: SymbolTable::default(),
+            scopes: vec![Scope::new(ScopeKind::File)],
+            linked: HashMap::new(),
+            labels: HashMap::new(),
+            gotos: Vec::new(),
+            function_typedefs: HashSet::new(),
+            undeclared: HashSet::new(),
+            node: ast.root,
+            diagnostics: Vec::new(),
+        }
+    }
+
+
+    pub fn name(&self, symbol: Symbol) -> &'a str {
+        self.ast.symbols.resolve(symbol)
+    }
+
+
+    pub fn push_scope(&mut self, kind: ScopeKind) {
+        self.scopes.push(Scope::new(kind));
+    }
+
+
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+
+    pub fn scope_kind(&self) -> ScopeKind {
+        self.scopes.last().map_or(ScopeKind::File, |scope| scope.kind)
+    }
+
+
+    pub fn lookup(&self, name: Symbol) -> Option<EntityId> {
+        self.scopes.iter().rev().find_map(|scope| scope.ordinary.get(&name).copied())
+    }
+
+
+    pub fn lookup_tag(&self, name: Symbol) -> Option<EntityId> {
+        self.scopes.iter().rev().find_map(|scope| scope.tags.get(&name).copied())
+    }
+
+
+    pub fn push_entity(&mut self, entity: Entity) -> EntityId {
+        self.table.entities.push(entity);
+
+        EntityId(self.table.entities.len() as u32 - 1)
+    }
+
+
+    pub fn line(&self, id: EntityId) -> u16 {
+        self.ast[self.table[id].node].span.start
+    }
+
+
+    pub fn report(&mut self, severity: Severity, line: u16, message: String) {
+        self.diagnostics.push(Diagnostic::new(severity, format!("{}: {}", line, message)));
+    }
+}
+
+
+impl<'a> DataflowState<'a> {
+    pub fn new(ast: &'a Ast, table: &'a SymbolTable, types: &'a TypeTable, warnings: &'a Warnings) -> Self {
+        let declared = table.entities.iter().enumerate()
+            .filter(|(_, entity)| matches!(entity.kind, EntityKind::Object | EntityKind::Parameter))
+            .map(|(index, entity)| ((entity.node, entity.name), EntityId(index as u32)))
+            .collect();
+
+        let mut references = HashMap::new();
+
+        for &entity in table.references.values() {
+            *references.entry(entity).or_insert(0) += 1;
+        }
+
+        Self {
+            ast,
+            table,
+            types,
+            warnings,
+            declared,
+            references,
+            unused_allowed: HashSet::new(),
+            blocks: Vec::new(),
+            current: 0,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+            switches: Vec::new(),
+            labels: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+
+    pub fn name(&self, symbol: Symbol) -> &'a str {
+        self.ast.symbols.resolve(symbol)
+    }
+
+
+    pub fn new_block(&mut self) -> usize {
+        self.blocks.push(BasicBlock::default());
+        self.blocks.len() - 1
+    }
+
+
+    pub fn edge(&mut self, from: usize, to: usize) {
+        self.blocks[from].successors.push(to);
+        self.blocks[to].predecessors.push(from);
+    }
+
+
+    pub fn report(&mut self, flag: WarningFlag, line: u16, message: String) {
+        if self.warnings.is_enabled(flag) {
+            self.diagnostics.push(Diagnostic::new(Severity::Warning, format!("{}: {} [-W{}]", line, message, flag.name())));
+        }
+    }
+}
+
+
+impl Scope {
+    pub fn new(kind: ScopeKind) -> Self {
+        Self { kind, ordinary: HashMap::new(), tags: HashMap::new() }
+    }
+}
+
+
+impl SymbolTable {
+    pub fn resolve(&self, node: NodeId) -> Option<EntityId> {
+        self.references.get(&node).copied()
+    }
+
+
+    pub fn definition(&self, id: EntityId) -> Option<EntityId> {
+        let mut tentative = None;
+        let mut current = Some(id);
+
+        while let Some(id) = current {
+            match self[id].definition {
+                Definition::Defined => return Some(id),
+                Definition::Tentative => tentative = Some(id),
+                Definition::Declared => {}
+            }
+
+            current = self[id].previous;
+        }
+
+        tentative
+    }
+
+
+    pub fn first_declaration(&self, mut id: EntityId) -> EntityId {
+        while let Some(previous) = self[id].previous {
+            id = previous;
+        }
+
+        id
+    }
+}
+
+
+impl Index<EntityId> for SymbolTable {
+    type Output = Entity;
+
+    fn index(&self, id: EntityId) -> &Entity {
+        &self.entities[id.0 as usize]
+    }
+}
+
+
+impl CType {
+    pub fn unqualified(&self) -> &CType {
+        match self {
+            CType::Qualified(_, inner) => inner,
+            _ => self,
+        }
+    }
+
+
+    pub fn qualifiers(&self) -> Qualifiers {
+        match self {
+            CType::Qualified(qualifiers, _) => *qualifiers,
+            CType::Array(element, _) => element.qualifiers(),
+            _ => Qualifiers::default(),
+        }
+    }
+
+
+    pub fn qualified(self, qualifiers: Qualifiers) -> CType {
+        if qualifiers == Qualifiers::default() {
+            return self;
+        }
+
+        match self {
+            CType::Qualified(existing, inner) => CType::Qualified(existing.merge(qualifiers), inner),
+            CType::Array(element, size) => CType::Array(Box::new(element.qualified(qualifiers)), size),
+            _ => CType::Qualified(qualifiers, Box::new(self)),
+        }
+    }
+
+
+    pub fn strip_qualifiers(&self) -> CType {
+        match self.unqualified() {
+            CType::Array(element, size) => CType::Array(Box::new(element.strip_qualifiers()), *size),
+            unqualified => unqualified.clone(),
+        }
+    }
+
+
+    pub fn decay(&self) -> CType {
+        match self.unqualified() {
+            CType::Array(element, _) => CType::Pointer(element.clone()),
+            function @ CType::Function { .. } => CType::Pointer(Box::new(function.clone())),
+            unqualified => unqualified.clone(),
+        }
+    }
+
+
+    pub fn pointee(&self) -> Option<&CType> {
+        match self.unqualified() {
+            CType::Pointer(pointee) => Some(pointee),
+            _ => None,
+        }
+    }
+
+
+    pub fn is_integer(&self) -> bool {
+        matches!(self.unqualified(), CType::Bool | CType::Char | CType::SignedChar | CType::UnsignedChar
+            | CType::Short | CType::UnsignedShort | CType::Int | CType::UnsignedInt | CType::Long
+            | CType::UnsignedLong | CType::LongLong | CType::UnsignedLongLong | CType::Enum(_))
+    }
+
+
+    pub fn is_floating(&self) -> bool {
+        matches!(self.unqualified(), CType::Float | CType::Double | CType::LongDouble)
+    }
+
+
+    pub fn is_arithmetic(&self) -> bool {
+        self.is_integer() || self.is_floating()
+    }
+
+
+    pub fn is_pointer(&self) -> bool {
+        matches!(self.unqualified(), CType::Pointer(_))
+    }
+
+
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self.unqualified(), CType::Bool | CType::UnsignedChar | CType::UnsignedShort
+            | CType::UnsignedInt | CType::UnsignedLong | CType::UnsignedLongLong)
+    }
+
+
+    pub fn integer_rank(&self) -> u8 {
+        match self.unqualified() {
+            CType::Bool => 0,
+            CType::Char | CType::SignedChar | CType::UnsignedChar => 1,
+            CType::Short | CType::UnsignedShort => 2,
+            CType::Int | CType::UnsignedInt | CType::Enum(_) => 3,
+            CType::Long | CType::UnsignedLong => 4,
+            _ => 5,
+        }
+    }
+
+
+    pub fn promote(&self) -> CType {
+        if self.is_integer() && self.integer_rank() < 3 || matches!(self.unqualified(), CType::Enum(_)) {
+            return CType::Int;
+        }
+
+        self.unqualified().clone()
+    }
+
+
+    pub fn usual_arithmetic(left: &CType, right: &CType) -> CType {
+        for floating in [CType::LongDouble, CType::Double, CType::Float] {
+            if *left.unqualified() == floating || *right.unqualified() == floating {
+                return floating;
+            }
+        }
+
+        let left = left.promote();
+        let right = right.promote();
+
+        if left.is_unsigned() == right.is_unsigned() {
+            return if left.integer_rank() >= right.integer_rank() { left } else { right };
+        }
+
+        let (unsigned, signed) = if left.is_unsigned() { (left, right) } else { (right, left) };
+
+        if unsigned.integer_rank() >= signed.integer_rank() {
+            unsigned
+        } else if signed.integer_rank() > unsigned.integer_rank() + 1 || unsigned.integer_rank() < 4 {
+            signed
+        } else {
+            signed.to_unsigned()
+        }
+    }
+
+
+    pub fn to_unsigned(&self) -> CType {
+        match self.unqualified() {
+            CType::Char | CType::SignedChar => CType::UnsignedChar,
+            CType::Short => CType::UnsignedShort,
+            CType::Int | CType::Enum(_) => CType::UnsignedInt,
+            CType::Long => CType::UnsignedLong,
+            CType::LongLong => CType::UnsignedLongLong,
+            unqualified => unqualified.clone(),
+        }
+    }
+
+
+    pub fn is_compatible(&self, other: &CType) -> bool {
+        match (self, other) {
+            (CType::Qualified(left_qualifiers, left), CType::Qualified(right_qualifiers, right)) => {
+                left_qualifiers == right_qualifiers && left.is_compatible(right)
+            }
+
+            (CType::Pointer(left), CType::Pointer(right)) => left.is_compatible(right),
+
+            (CType::Array(left, left_size), CType::Array(right, right_size)) => {
+                left.is_compatible(right) && match (left_size, right_size) {
+                    (ArraySize::Fixed(left_size), ArraySize::Fixed(right_size)) => left_size == right_size,
+                    _ => true,
+                }
+            }
+
+            (CType::Function { result: left_result, parameters: left_parameters, variadic: left_variadic, prototyped: left_prototyped },
+             CType::Function { result: right_result, parameters: right_parameters, variadic: right_variadic, prototyped: right_prototyped }) => {
+                left_result.is_compatible(right_result)
+                    && (!*left_prototyped || !*right_prototyped || (left_variadic == right_variadic
+                        && left_parameters.len() == right_parameters.len()
+                        && left_parameters.iter().zip(right_parameters).all(|(left, right)| left.strip_qualifiers().is_compatible(&right.strip_qualifiers()))))
+            }
+
+            _ => self == other,
+        }
+    }
+
+
+    pub fn composite(&self, other: &CType) -> Option<CType> {
+        if !self.is_compatible(other) {
+            return None;
+        }
+
+        match (self, other) {
+            (CType::Qualified(qualifiers, left), CType::Qualified(_, right)) => Some(left.composite(right)?.qualified(*qualifiers)),
+            (CType::Pointer(left), CType::Pointer(right)) => Some(CType::Pointer(Box::new(left.composite(right)?))),
+
+            (CType::Array(left, left_size), CType::Array(right, right_size)) => {
+                let size = match (left_size, right_size) {
+                    (ArraySize::Fixed(_), _) => *left_size,
+                    (_, ArraySize::Fixed(_)) => *right_size,
+                    (ArraySize::Variable, _) | (_, ArraySize::Variable) => ArraySize::Variable,
+                    _ => ArraySize::Incomplete,
+                };
+
+                Some(CType::Array(Box::new(left.composite(right)?), size))
+            }
+
+            (CType::Function { result: left_result, parameters: left_parameters, variadic: left_variadic, prototyped: left_prototyped },
+             CType::Function { result: right_result, parameters: right_parameters, variadic: right_variadic, prototyped: right_prototyped }) => {
+                let result = Box::new(left_result.composite(right_result)?);
+
+                let (parameters, variadic) = match (*left_prototyped, *right_prototyped) {
+                    (true, true) => (left_parameters.iter().zip(right_parameters).map(|(left, right)| {
+                        left.strip_qualifiers().composite(&right.strip_qualifiers())
+                    }).collect::<Option<Vec<CType>>>()?, *left_variadic),
+                    (false, true) => (right_parameters.clone(), *right_variadic),
+                    _ => (left_parameters.clone(), *left_variadic),
+                };
+
+                Some(CType::Function { result, parameters, variadic, prototyped: *left_prototyped || *right_prototyped })
+            }
+
+            _ => Some(self.clone()),
+        }
+    }
+
+
+    fn render(&self, declarator: String) -> String {
+        let join = |name: &str| if declarator.is_empty() || declarator.starts_with('[') {
+            format!("{}{}", name, declarator)
+        } else {
+            format!("{} {}", name, declarator)
+        };
+
+        match self {
+            CType::Void => join("void"),
+            CType::Bool => join("bool"),
+            CType::Char => join("char"),
+            CType::SignedChar => join("signed char"),
+            CType::UnsignedChar => join("unsigned char"),
+            CType::Short => join("short"),
+            CType::UnsignedShort => join("unsigned short"),
+            CType::Int => join("int"),
+            CType::UnsignedInt => join("unsigned int"),
+            CType::Long => join("long"),
+            CType::UnsignedLong => join("unsigned long"),
+            CType::LongLong => join("long long"),
+            CType::UnsignedLongLong => join("unsigned long long"),
+            CType::Float => join("float"),
+            CType::Double => join("double"),
+            CType::LongDouble => join("long double"),
+            CType::Enum(tag) if tag.starts_with('<') => join("enum <anonymous>"),
+            CType::Enum(tag) => join(&format!("enum {}", tag.split('#').next().unwrap_or(tag))),
+
+            CType::Record { kind, tag } => {
+                let kind = if *kind == Keyword::Union { "union" } else { "struct" };
+                let tag = if tag.starts_with('<') { "<anonymous>" } else { tag.split('#').next().unwrap_or(tag) };
+
+                join(&format!("{} {}", kind, tag))
+            }
+
+            CType::Pointer(pointee) => pointee.render_pointer(String::new(), declarator),
+
+            CType::Qualified(qualifiers, inner) => match inner.as_ref() {
+                CType::Pointer(pointee) => pointee.render_pointer(qualifiers.to_string(), declarator),
+                _ => format!("{} {}", qualifiers, inner.render(declarator)),
+            },
+
+            CType::Array(element, size) => {
+                let size = match size {
+                    ArraySize::Fixed(size) => size.to_string(),
+                    ArraySize::Incomplete => String::new(),
+                    ArraySize::Variable => "*".to_string(),
+                };
+
+                element.render(format!("{}[{}]", declarator, size))
+            }
+
+            CType::Function { result, parameters, variadic, prototyped } => {
+                let mut parameters: Vec<String> = parameters.iter().map(|parameter| parameter.to_string()).collect();
+
+                if *variadic {
+                    parameters.push("...".to_string());
+                } else if parameters.is_empty() && *prototyped {
+                    parameters.push("void".to_string());
+                }
+
+                result.render(format!("{}({})", declarator, parameters.join(", ")))
+            }
+        }
+    }
+
+
+    fn render_pointer(&self, qualifiers: String, declarator: String) -> String {
+        let mut pointer = format!("*{}", qualifiers);
+
+        if !declarator.is_empty() {
+            if !qualifiers.is_empty() {
+                pointer.push(' ');
+            }
+
+            pointer.push_str(&declarator);
+        }
+
+        if matches!(self.unqualified(), CType::Array(..) | CType::Function { .. }) {
+            pointer = format!("({})", pointer);
+        }
+
+        self.render(pointer)
+    }
+}
+
+
+impl fmt::Display for CType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(String::new()))
+    }
+}
+
+
+impl Qualifiers {
+    pub fn from_keywords(keywords: &[Keyword]) -> Self {
+        Self {
+            is_const: keywords.contains(&Keyword::Const),
+            is_volatile: keywords.contains(&Keyword::Volatile),
+            is_restrict: keywords.contains(&Keyword::Restrict),
+        }
+    }
+
+
+    pub fn merge(self, other: Qualifiers) -> Self {
+        Self {
+            is_const: self.is_const || other.is_const,
+            is_volatile: self.is_volatile || other.is_volatile,
+            is_restrict: self.is_restrict || other.is_restrict,
+        }
+    }
+
+
+    pub fn without(self, other: Qualifiers) -> Self {
+        Self {
+            is_const: self.is_const && !other.is_const,
+            is_volatile: self.is_volatile && !other.is_volatile,
+            is_restrict: self.is_restrict && !other.is_restrict,
+        }
+    }
+}
+
+
+impl fmt::Display for CastKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+impl fmt::Display for ValueCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueCategory::LValue => write!(f, "lvalue"),
+            ValueCategory::RValue => write!(f, "rvalue"),
+            ValueCategory::Function => write!(f, "function designator"),
+        }
+    }
+}
+
+
+impl fmt::Display for Qualifiers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = [(self.is_const, "const"), (self.is_volatile, "volatile"), (self.is_restrict, "restrict")]
+            .into_iter()
+            .filter_map(|(present, name)| present.then_some(name))
+            .collect();
+
+        write!(f, "{}", names.join(" "))
+    }
+}
+
+
+impl Target {
+    pub fn x86_64_sysv() -> Self {
+        let layout = |size| TypeLayout { size, align: size };
+
+        Self {
+            short: layout(2),
+            int: layout(4),
+            long: layout(8),
+            long_long: layout(8),
+            pointer: layout(8),
+            float: layout(4),
+            double: layout(8),
+            long_double: layout(16),
+        }
+    }
+}
+
+
+impl TypeTable {
+    pub fn alignment(&self, declaration: NodeId) -> u64 {
+        self.alignments.get(&declaration).copied().unwrap_or(1)
+    }
+
+
+    pub fn layout(&self, ctype: &CType, target: &Target) -> Option<TypeLayout> {
+        match ctype {
+            CType::Void | CType::Function { .. } => None,
+            CType::Bool | CType::Char | CType::SignedChar | CType::UnsignedChar => Some(TypeLayout { size: 1, align: 1 }),
+            CType::Short | CType::UnsignedShort => Some(target.short),
+            CType::Int | CType::UnsignedInt | CType::Enum(_) => Some(target.int),
+            CType::Long | CType::UnsignedLong => Some(target.long),
+            CType::LongLong | CType::UnsignedLongLong => Some(target.long_long),
+            CType::Float => Some(target.float),
+            CType::Double => Some(target.double),
+            CType::LongDouble => Some(target.long_double),
+            CType::Pointer(_) => Some(target.pointer),
+            CType::Qualified(_, inner) => self.layout(inner, target),
+
+            CType::Array(element, ArraySize::Fixed(length)) => {
+                let element = self.layout(element, target)?;
+                Some(TypeLayout { size: element.size * length, align: element.align })
+            }
+
+            CType::Array(..) => None,
+
+            CType::Record { kind, tag } => {
+                let record = self.record_layout(*kind, tag, target)?;
+                Some(TypeLayout { size: record.size, align: record.align })
+            }
+        }
+    }
+
+
+    pub fn member_offset(&self, kind: Keyword, tag: &str, name: Symbol, target: &Target) -> Option<u64> {
+        let layout = self.record_layout(kind, tag, target)?;
+
+        self.records.get(tag)?.iter().zip(&layout.fields).find_map(|(member, field)| match (member.name, member.ctype.unqualified()) {
+            (Some(member_name), _) if member_name == name => Some(field.offset),
+            (None, CType::Record { kind, tag }) => Some(field.offset + self.member_offset(*kind, tag, name, target)?),
+            _ => None,
+        })
+    }
+
+
+    pub fn record_layout(&self, kind: Keyword, tag: &str, target: &Target) -> Option<RecordLayout> {
+        let members = self.records.get(tag)?;
+        let is_union = kind == Keyword::Union;
+
+        let mut bits: u64 = 0;
+        let mut size: u64 = 0;
+        let mut align: u64 = 1;
+        let mut fields = Vec::new();
+
+        for (i, member) in members.iter().enumerate() {
+            let is_flexible = !is_union && i + 1 == members.len() && matches!(member.ctype.unqualified(), CType::Array(_, ArraySize::Incomplete));
+
+            let layout = match member.ctype.unqualified() {
+                CType::Array(element, ArraySize::Incomplete) if is_flexible => TypeLayout { size: 0, ..self.layout(element, target)? },
+                _ => self.layout(&member.ctype, target)?,
+            };
+
+            let layout = TypeLayout { align: layout.align.max(member.align.unwrap_or(1)), ..layout };
+
+            let unit = layout.size.max(1) * 8;
+            let start = if is_union { 0 } else { bits };
+
+            let (offset, bit_offset, end) = match member.bit_width {
+                Some(0) => {
+                    let offset = start.next_multiple_of(layout.align * 8);
+                    (offset / 8, None, offset)
+                }
+
+                Some(width) => {
+                    let offset = if start / unit != (start + width - 1) / unit { start.next_multiple_of(unit) } else { start };
+                    let storage = offset / unit * unit / 8;
+                    (storage, Some(offset - storage * 8), offset + width)
+                }
+
+                None => {
+                    let offset = start.next_multiple_of(layout.align * 8);
+                    (offset / 8, None, offset + layout.size * 8)
+                }
+            };
+
+            if member.bit_width != Some(0) && (member.bit_width.is_none() || member.name.is_some()) {
+                align = align.max(layout.align);
+            }
+
+            fields.push(FieldLayout { offset, bit_offset });
+            size = size.max(end.div_ceil(8));
+
+            if !is_union {
+                bits = end;
+            }
+        }
+
+        Some(RecordLayout { size: size.next_multiple_of(align), align, fields })
+    }
+}
+
+
+impl Declarator {
+    pub fn function_parameters(&self) -> Option<&Vec<Parameter>> {
+        match self.derived.last() {
+            Some(DerivedDeclarator::Function { parameters, .. }) => Some(parameters),
+            _ => None,
+        }
+    }
+}
+
+
+impl Ast {
+    pub fn push(&mut self, kind: NodeKind, span: Span) -> NodeId {
+        self.nodes.push(Node { kind, span });
+
+        NodeId(self.nodes.len() as u32 - 1)
+    }
+
+
+    pub fn kind(&self, id: NodeId) -> &NodeKind {
+        &self.nodes[id.0 as usize].kind
+    }
+}
+
+
+impl Index<NodeId> for Ast {
+    type Output = Node;
+
+    fn index(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0 as usize]
+    }
+}
+
+
+impl IndexMut<NodeId> for Ast {
+    fn index_mut(&mut self, id: NodeId) -> &mut Node {
+        &mut self.nodes[id.0 as usize]
+    }
+}
+
+
+impl Interner {
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(&symbol) = self.indices.get(name) {
+            return symbol;
+        }
+
+        let symbol = Symbol(self.names.len() as u32);
+        self.names.push(name.to_string());
+        self.indices.insert(name.to_string(), symbol);
+
+        symbol
+    }
+
+
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.indices.get(name).copied()
+    }
+
+
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.names[symbol.0 as usize]
+    }
+}
+
+
+impl Constant {
+    pub fn ctype(&self) -> CType {
+        let (value, suffix) = match *self {
+            Constant::Integer { value, suffix } => (value, suffix),
+            Constant::Character(_) => return CType::Int,
+            Constant::Bool(_) => return CType::Bool,
+
+            Constant::Floating { suffix, .. } => return match suffix {
+                FloatSuffix::None => CType::Double,
+                FloatSuffix::Float => CType::Float,
+                FloatSuffix::Long => CType::LongDouble,
+            },
+        };
+
+        let candidates: &[CType] = match suffix {
+            IntegerSuffix::None => &[CType::Int, CType::Long],
+            IntegerSuffix::Unsigned => &[CType::UnsignedInt, CType::UnsignedLong],
+            IntegerSuffix::Long => &[CType::Long],
+            IntegerSuffix::UnsignedLong => &[CType::UnsignedLong],
+            IntegerSuffix::LongLong => &[CType::LongLong],
+            IntegerSuffix::UnsignedLongLong => &[CType::UnsignedLongLong],
+        };
+
+        let fits = |ctype: &CType| match ctype {
+            CType::Int => value <= i32::MAX as u64,
+            CType::UnsignedInt => value <= u32::MAX as u64,
+            CType::Long | CType::LongLong => value <= i64::MAX as u64,
+            _ => true,
+        };
+
+        candidates.iter().find(|ctype| fits(ctype)).cloned().unwrap_or(CType::UnsignedLong)
+    }
+}
+
+
+impl FromStr for Constant {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "true" => return Ok(Constant::Bool(true)),
+            "false" => return Ok(Constant::Bool(false)),
+            _ => {}
+        }
+
+        if let Some(character) = s.strip_prefix('\'') {
+            return character.chars().next().map(Constant::Character).ok_or(());
+        }
+
+        let digits = s.trim_end_matches(['u', 'U', 'l', 'L', 'f', 'F']);
+        let suffix = s[digits.len()..].to_ascii_lowercase();
+
+        if suffix.contains('f') || digits.contains(['.', 'e', 'E']) {
+            let suffix = match suffix.as_str() {
+                "f" => FloatSuffix::Float,
+                "l" | "lf" => FloatSuffix::Long,
+                _ => FloatSuffix::None,
+            };
+
+            return digits.parse().map(|value| Constant::Floating { value, suffix }).map_err(|_| ());
+        }
+
+        let suffix = match (suffix.contains('u'), suffix.matches('l').count()) {
+            (false, 0) => IntegerSuffix::None,
+            (true, 0) => IntegerSuffix::Unsigned,
+            (false, 1) => IntegerSuffix::Long,
+            (true, 1) => IntegerSuffix::UnsignedLong,
+            (false, _) => IntegerSuffix::LongLong,
+            (true, _) => IntegerSuffix::UnsignedLongLong,
+        };
+
+        digits.parse().map(|value| Constant::Integer { value, suffix }).map_err(|_| ())
+    }
+}
+
+
+impl BinaryOperator {
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinaryOperator::Comma => 0,
+            BinaryOperator::LogicalOr => 1,
+            BinaryOperator::LogicalAnd => 2,
+            BinaryOperator::BitOr => 3,
+            BinaryOperator::BitXor => 4,
+            BinaryOperator::BitAnd => 5,
+            BinaryOperator::Equal | BinaryOperator::NotEqual => 6,
+            BinaryOperator::Less | BinaryOperator::Greater | BinaryOperator::LessEqual | BinaryOperator::GreaterEqual => 7,
+            BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => 8,
+            BinaryOperator::Add | BinaryOperator::Subtract => 9,
+            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Remainder => 10,
+        }
+    }
+
+
+    pub fn is_comparison(self) -> bool {
+        matches!(self, BinaryOperator::Equal | BinaryOperator::NotEqual | BinaryOperator::Less
+            | BinaryOperator::Greater | BinaryOperator::LessEqual | BinaryOperator::GreaterEqual)
+    }
+
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinaryOperator::Comma => ",",
+            BinaryOperator::LogicalOr => "||",
+            BinaryOperator::LogicalAnd => "&&",
+            BinaryOperator::BitOr => "|",
+            BinaryOperator::BitXor => "^",
+            BinaryOperator::BitAnd => "&",
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::Less => "<",
+            BinaryOperator::Greater => ">",
+            BinaryOperator::LessEqual => "<=",
+            BinaryOperator::GreaterEqual => ">=",
+            BinaryOperator::ShiftLeft => "<<",
+            BinaryOperator::ShiftRight => ">>",
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Remainder => "%",
+        }
+    }
+}
+
+
+impl AssignmentOperator {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            AssignmentOperator::Assign => "=",
+            AssignmentOperator::Compound(BinaryOperator::Add) => "+=",
+            AssignmentOperator::Compound(BinaryOperator::Subtract) => "-=",
+            AssignmentOperator::Compound(BinaryOperator::Multiply) => "*=",
+            AssignmentOperator::Compound(BinaryOperator::Divide) => "/=",
+            AssignmentOperator::Compound(BinaryOperator::Remainder) => "%=",
+            AssignmentOperator::Compound(BinaryOperator::BitAnd) => "&=",
+            AssignmentOperator::Compound(BinaryOperator::BitXor) => "^=",
+            AssignmentOperator::Compound(BinaryOperator::BitOr) => "|=",
+            AssignmentOperator::Compound(BinaryOperator::ShiftLeft) => "<<=",
+            AssignmentOperator::Compound(BinaryOperator::ShiftRight) => ">>=",
+            AssignmentOperator::Compound(_) => "?=",
+        }
+    }
+}
+
+
+impl UnaryOperator {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            UnaryOperator::Address => "&",
+            UnaryOperator::Dereference => "*",
+            UnaryOperator::Plus => "+",
+            UnaryOperator::Minus => "-",
+            UnaryOperator::BitNot => "~",
+            UnaryOperator::Not => "!",
+            UnaryOperator::PreIncrement => "++",
+            UnaryOperator::PreDecrement => "--",
+            UnaryOperator::Sizeof => "sizeof",
+        }
+    }
+}
+
+
+impl PostfixOperator {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            PostfixOperator::Increment => "++",
+            PostfixOperator::Decrement => "--",
+        }
+    }
+}
+
+
+impl NodeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeKind::TranslationUnit(_) => "TranslationUnit",
+            NodeKind::FunctionDefinition { .. } => "FunctionDefinition",
+            NodeKind::Declaration { .. } => "Declaration",
+            NodeKind::StaticAssert { .. } => "StaticAssert",
+            NodeKind::Attributed { .. } => "Attributed",
+            NodeKind::Compound(_) => "Compound",
+            NodeKind::ExpressionStatement(_) => "ExpressionStatement",
+            NodeKind::If { .. } => "If",
+            NodeKind::While { .. } => "While",
+            NodeKind::DoWhile { .. } => "DoWhile",
+            NodeKind::For { .. } => "For",
+            NodeKind::Switch { .. } => "Switch",
+            NodeKind::Case { .. } => "Case",
+            NodeKind::Default(_) => "Default",
+            NodeKind::Label { .. } => "Label",
+            NodeKind::Goto(_) => "Goto",
+            NodeKind::Break => "Break",
+            NodeKind::Continue => "Continue",
+            NodeKind::Return(_) => "Return",
+            NodeKind::BinaryOperation { .. } => "BinaryOperation",
+            NodeKind::Assignment { .. } => "Assignment",
+            NodeKind::UnaryOperation { .. } => "UnaryOperation",
+            NodeKind::PostfixOperation { .. } => "PostfixOperation",
+            NodeKind::Conditional { .. } => "Conditional",
+            NodeKind::Call { .. } => "Call",
+            NodeKind::Index { .. } => "Index",
+            NodeKind::Member { .. } => "Member",
+            NodeKind::Cast { .. } => "Cast",
+            NodeKind::CompoundLiteral { .. } => "CompoundLiteral",
+            NodeKind::GenericSelection { .. } => "GenericSelection",
+            NodeKind::ImplicitCast { .. } => "ImplicitCast",
+            NodeKind::InitializerList(_) => "InitializerList",
+            NodeKind::SizeofType(_) => "SizeofType",
+            NodeKind::AlignofType(_) => "AlignofType",
+            NodeKind::Identifier(_) => "Identifier",
+            NodeKind::StringLiteral(_) => "StringLiteral",
+            NodeKind::Constant(_) => "Constant",
+            NodeKind::Error => "Error",
+        }
+    }
+}
+
+
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constant::Integer { value, suffix } => {
+                let suffix = match suffix {
+                    IntegerSuffix::None => "",
+                    IntegerSuffix::Unsigned => "u",
+                    IntegerSuffix::Long => "l",
+                    IntegerSuffix::UnsignedLong => "ul",
+                    IntegerSuffix::LongLong => "ll",
+                    IntegerSuffix::UnsignedLongLong => "ull",
+                };
+
+                write!(f, "{}{}", value, suffix)
+            }
+
+            Constant::Floating { value, suffix } => {
+                let suffix = match suffix {
+                    FloatSuffix::None => "",
+                    FloatSuffix::Float => "f",
+                    FloatSuffix::Long => "l",
+                };
+
+                write!(f, "{:?}{}", value, suffix)
+            }
+
+            Constant::Character(character) => write!(f, "'{}'", escape_c(&character.to_string(), '\'')),
+            Constant::Bool(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+
+pub fn escape_c(value: &str, quote: char) -> String {
+    let mut escaped = String::new();
+
+    for character in value.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\0' => escaped.push_str("\\0"),
+            _ if character == quote => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            _ if character.is_control() => escaped.push_str(&format!("\\x{:02x}", character as u32)),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let spelling = match self {
+            Keyword::Alignas => "alignas",
+            Keyword::Alignof => "alignof",
+            Keyword::Auto => "auto",
+            Keyword::Bool => "bool",
+            Keyword::Break => "break",
+            Keyword::Case => "case",
+            Keyword::Char => "char",
+            Keyword::Const => "const",
+            Keyword::Continue => "continue",
+            Keyword::Default => "default",
+            Keyword::Do => "do",
+            Keyword::Double => "double",
+            Keyword::Else => "else",
+            Keyword::Enum => "enum",
+            Keyword::Extern => "extern",
+            Keyword::False => "false",
+            Keyword::Float => "float",
+            Keyword::For => "for",
+            Keyword::Generic => "_Generic",
+            Keyword::Goto => "goto",
+            Keyword::If => "if",
+            Keyword::Inline => "inline",
+            Keyword::Int => "int",
+            Keyword::Long => "long",
+            Keyword::Register => "register",
+            Keyword::Restrict => "restrict",
+            Keyword::Return => "return",
+            Keyword::Short => "short",
+            Keyword::Signed => "signed",
+            Keyword::Sizeof => "sizeof",
+            Keyword::Static => "static",
+            Keyword::StaticAssert => "static_assert",
+            Keyword::Struct => "struct",
+            Keyword::Switch => "switch",
+            Keyword::True => "true",
+            Keyword::Typedef => "typedef",
+            Keyword::Typeof => "typeof",
+            Keyword::TypeofUnqual => "typeof_unqual",
+            Keyword::Union => "union",
+            Keyword::Unsigned => "unsigned",
+            Keyword::Void => "void",
+            Keyword::Volatile => "volatile",
+            Keyword::While => "while",
+        };
+
+        write!(f, "{}", spelling)
+    }
+}
+
+
+impl Attribute {
+    pub fn is(&self, name: &str) -> bool {
+        self.name == name && self.prefix.as_deref().is_none_or(|prefix| prefix == "gnu")
+    }
+
+
+    pub fn message(&self) -> Option<String> {
+        let argument = self.arguments.first()?;
+
+        Some(argument.trim_matches('"').to_string())
+    }
+}
+
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(prefix) = &self.prefix {
+            write!(f, "{}::", prefix)?;
+        }
+
+        write!(f, "{}", self.name)?;
+
+        if !self.arguments.is_empty() {
+            write!(f, "({})", self.arguments.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+
+impl FromStr for DumpFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tree" => Ok(DumpFormat::Tree),
+            "json" => Ok(DumpFormat::Json),
+            "dot" => Ok(DumpFormat::Dot),
+            _ => Err(format!("unknown AST dump format '{}' (expected tree, json or dot)", s)),
+        }
+    }
+}
+
+
+impl WarningFlag {
+    pub const ALL: [WarningFlag; 9] = [
+        WarningFlag::Uninitialized,
+        WarningFlag::MaybeUninitialized,
+        WarningFlag::UnusedVariable,
+        WarningFlag::UnusedButSetVariable,
+        WarningFlag::UnusedParameter,
+        WarningFlag::UnusedFunction,
+        WarningFlag::DeadStore,
+        WarningFlag::UnreachableCode,
+        WarningFlag::Parentheses,
+    ];
+
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WarningFlag::Uninitialized => "uninitialized",
+            WarningFlag::MaybeUninitialized => "maybe-uninitialized",
+            WarningFlag::UnusedVariable => "unused-variable",
+            WarningFlag::UnusedButSetVariable => "unused-but-set-variable",
+            WarningFlag::UnusedParameter => "unused-parameter",
+            WarningFlag::UnusedFunction => "unused-function",
+            WarningFlag::DeadStore => "dead-store",
+            WarningFlag::UnreachableCode => "unreachable-code",
+            WarningFlag::Parentheses => "parentheses",
+        }
+    }
+}
+
+
+impl FromStr for WarningFlag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WarningFlag::ALL.into_iter()
+            .find(|flag| flag.name() == s)
+            .ok_or_else(|| format!("unknown warning option '-W{}'", s))
+    }
+}
+
+
+impl Default for Warnings {
+    fn default() -> Self {
+        let enabled = WarningFlag::ALL.into_iter().filter(|&flag| flag != WarningFlag::UnusedParameter).collect();
+        Self { enabled }
+    }
+}
+
+
+impl Warnings {
+    pub fn apply(&mut self, option: &str) -> Result<(), String> {
+        match option {
+            "all" => self.enabled.extend(Warnings::default().enabled),
+            "extra" => self.enabled.extend(WarningFlag::ALL),
+
+            _ => match option.strip_prefix("no-") {
+                Some(name) => {
+                    self.enabled.remove(&name.parse::<WarningFlag>()?);
+                }
+
+                None => {
+                    self.enabled.insert(option.parse::<WarningFlag>()?);
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+
+    pub fn is_enabled(&self, flag: WarningFlag) -> bool {
+        self.enabled.contains(&flag)
+    }
+}
+
+
 impl FromStr for Keyword {
     type Err = ();
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        static KEYWORDS: phf::Map<&'static str, Keyword> = phf_map! {
-            "bool" => Keyword::Bool,
-            "break" => Keyword::Break,
-            "case" => Keyword::Case,
-            "char" => Keyword::Char,
-            "continue" => Keyword::Continue,
-            "default" => Keyword::Default,
-            "do" => Keyword::Do,
-            "double" => Keyword::Double,
-            "else" => Keyword::Else,
-            "enum" => Keyword::Enum,
-            "false" => Keyword::False,
-            "float" => Keyword::Float,
-            "for" => Keyword::For,
-            "if" => Keyword::If,
-            "int" => Keyword::Int,
-            "long" => Keyword::Long,
-            "return" => Keyword::Return,
-            "short" => Keyword::Short,
-            "signed" => Keyword::Signed,
-            "struct" => Keyword::Struct,
-            "switch" => Keyword::Switch,
-            "true" => Keyword::True,
-            "unsigned" => Keyword::Unsigned,
-            "void" => Keyword::Void,
-            "while" => Keyword::While,
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        static KEYWORDS: phf::Map<&'static str, Keyword> = phf_map! {
+            "_Bool" => Keyword::Bool,
+            "alignas" => Keyword::Alignas,
+            "_Alignas" => Keyword::Alignas,
+            "alignof" => Keyword::Alignof,
+            "_Alignof" => Keyword::Alignof,
+            "__alignof__" => Keyword::Alignof,
+            "auto" => Keyword::Auto,
+            "bool" => Keyword::Bool,
+            "break" => Keyword::Break,
+            "case" => Keyword::Case,
+            "char" => Keyword::Char,
+            "const" => Keyword::Const,
+            "__const" => Keyword::Const,
+            "continue" => Keyword::Continue,
+            "default" => Keyword::Default,
+            "do" => Keyword::Do,
+            "double" => Keyword::Double,
+            "else" => Keyword::Else,
+            "enum" => Keyword::Enum,
+            "extern" => Keyword::Extern,
+            "false" => Keyword::False,
+            "float" => Keyword::Float,
+            "for" => Keyword::For,
+            "_Generic" => Keyword::Generic,
+            "goto" => Keyword::Goto,
+            "if" => Keyword::If,
+            "inline" => Keyword::Inline,
+            "__inline" => Keyword::Inline,
+            "__inline__" => Keyword::Inline,
+            "int" => Keyword::Int,
+            "long" => Keyword::Long,
+            "register" => Keyword::Register,
+            "restrict" => Keyword::Restrict,
+            "__restrict" => Keyword::Restrict,
+            "__restrict__" => Keyword::Restrict,
+            "return" => Keyword::Return,
+            "short" => Keyword::Short,
+            "signed" => Keyword::Signed,
+            "__signed__" => Keyword::Signed,
+            "sizeof" => Keyword::Sizeof,
+            "static" => Keyword::Static,
+            "static_assert" => Keyword::StaticAssert,
+            "_Static_assert" => Keyword::StaticAssert,
+            "struct" => Keyword::Struct,
+            "switch" => Keyword::Switch,
+            "true" => Keyword::True,
+            "typedef" => Keyword::Typedef,
+            "typeof" => Keyword::Typeof,
+            "__typeof" => Keyword::Typeof,
+            "__typeof__" => Keyword::Typeof,
+            "typeof_unqual" => Keyword::TypeofUnqual,
+            "__typeof_unqual__" => Keyword::TypeofUnqual,
+            "union" => Keyword::Union,
+            "unsigned" => Keyword::Unsigned,
+            "void" => Keyword::Void,
+            "volatile" => Keyword::Volatile,
+            "__volatile__" => Keyword::Volatile,
+            "while" => Keyword::While,
+        };
+
+        KEYWORDS.get(s).copied().ok_or(())
+    }
+}
+
+
+impl<'a> LoweringState<'a> {
+    pub fn new(ast: &'a Ast, table: &'a SymbolTable, types: &'a TypeTable, target: &'a Target) -> Self {
+        let declared = table.entities.iter().enumerate()
+            .filter(|(_, entity)| matches!(entity.kind, EntityKind::Object | EntityKind::Parameter))
+            .map(|(index, entity)| ((entity.node, entity.name), EntityId(index as u32)))
+            .collect();
+
+        let mut enumerators = HashMap::new();
+        let mut type_names = HashMap::new();
+
+        for (index, node) in ast.nodes.iter().enumerate() {
+            let id = NodeId(index as u32);
+
+            match (&node.kind, types.constants.get(&id), types.expressions.get(&id)) {
+                (NodeKind::Identifier(name), Some(ConstantValue::Integer(value, _)), _) => {
+                    enumerators.insert(*name, *value);
+                }
+
+                (NodeKind::Cast { .. } | NodeKind::CompoundLiteral { .. }, _, Some(ctype)) => {
+                    type_names.insert(id, ctype.clone());
+                }
+
+                _ => {}
+            }
+        }
+
+        Self {
+            ast,
+            table,
+            types,
+            target,
+            declared,
+            enumerators,
+            type_names,
+            module: Module::default(),
+            externals: HashMap::new(),
+            strings: HashMap::new(),
+            statics: 0,
+            function: None,
+            current: BlockId(0),
+            locals: HashMap::new(),
+            variable_sizes: HashMap::new(),
+            return_type: CType::Void,
+            return_slot: None,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+            switches: Vec::new(),
+            labels: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+
+    pub fn name(&self, symbol: Symbol) -> &'a str {
+        self.ast.symbols.resolve(symbol)
+    }
+
+
+    pub fn function(&mut self) -> &mut IrFunction {
+        self.function.as_mut().expect("lowering outside of a function")
+    }
+
+
+    pub fn new_block(&mut self) -> BlockId {
+        let function = self.function();
+        let id = BlockId(function.blocks.len() as u32);
+        function.blocks.push(Block { id, instructions: Vec::new() });
+
+        id
+    }
+
+
+    pub fn is_terminated(&mut self) -> bool {
+        let current = self.current;
+        self.function().blocks[current.0 as usize].instructions.last().is_some_and(Instruction::is_terminator)
+    }
+
+
+    pub fn emit(&mut self, ty: IrType, kind: InstructionKind) -> Operand {
+        if self.is_terminated() {
+            let block = self.new_block();
+            self.current = block;
+        }
+
+        let current = self.current;
+        let function = self.function();
+        let result = Instruction::defines_value(ty, &kind).then(|| function.new_register());
+        function.blocks[current.0 as usize].instructions.push(Instruction { result, ty, kind });
+
+        result.map_or(Operand::Undef, Operand::Register)
+    }
+
+
+    pub fn terminate(&mut self, kind: InstructionKind) {
+        self.emit(IrType::Void, kind);
+    }
+}
+
+
+impl IrFunction {
+    pub fn new_register(&mut self) -> Register {
+        self.next_register += 1;
+        Register(self.next_register - 1)
+    }
+
+
+    pub fn is_declaration(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+
+    pub fn instruction_count(&self) -> usize {
+        self.blocks.iter().map(|block| block.instructions.len()).sum()
+    }
+
+
+    pub fn reverse_postorder(&self) -> Vec<BlockId> {
+        let mut visited = HashSet::from([BlockId(0)]);
+        let mut postorder = Vec::new();
+        let mut stack = vec![(BlockId(0), self.blocks[0].successors())];
+
+        while let Some((block, successors)) = stack.last_mut() {
+            match successors.pop() {
+                Some(successor) if (successor.0 as usize) < self.blocks.len() && visited.insert(successor) => {
+                    let successors = self.blocks[successor.0 as usize].successors();
+                    stack.push((successor, successors));
+                }
+
+                Some(_) => {}
+
+                None => {
+                    postorder.push(*block);
+                    stack.pop();
+                }
+            }
+        }
+
+        postorder.reverse();
+        postorder
+    }
+
+
+    pub fn predecessors(&self) -> Vec<Vec<BlockId>> {
+        let mut predecessors = vec![Vec::new(); self.blocks.len()];
+
+        for block in &self.blocks {
+            for successor in block.successors() {
+                if let Some(list) = predecessors.get_mut(successor.0 as usize) && !list.contains(&block.id) {
+                    list.push(block.id);
+                }
+            }
+        }
+
+        predecessors
+    }
+
+
+    pub fn dominators(&self) -> Vec<Option<BlockId>> {
+        let order = self.reverse_postorder();
+        let predecessors = self.predecessors();
+        let mut position = vec![usize::MAX; self.blocks.len()];
+        let mut dominators = vec![None; self.blocks.len()];
+        dominators[0] = Some(BlockId(0));
+
+        for (index, block) in order.iter().enumerate() {
+            position[block.0 as usize] = index;
+        }
+
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for block in order.iter().skip(1) {
+                let mut dominator: Option<BlockId> = None;
+
+                for &predecessor in &predecessors[block.0 as usize] {
+                    if dominators[predecessor.0 as usize].is_none() {
+                        continue;
+                    }
+
+                    let Some(mut other) = dominator else {
+                        dominator = Some(predecessor);
+                        continue;
+                    };
+
+                    let mut current = predecessor;
+
+                    while current != other {
+                        while position[current.0 as usize] > position[other.0 as usize] {
+                            current = dominators[current.0 as usize].unwrap_or(BlockId(0));
+                        }
+
+                        while position[other.0 as usize] > position[current.0 as usize] {
+                            other = dominators[other.0 as usize].unwrap_or(BlockId(0));
+                        }
+                    }
+
+                    dominator = Some(current);
+                }
+
+                if dominators[block.0 as usize] != dominator {
+                    dominators[block.0 as usize] = dominator;
+                    changed = true;
+                }
+            }
+        }
+
+        dominators
+    }
+
+
+    pub fn dominator_tree(dominators: &[Option<BlockId>]) -> Vec<Vec<BlockId>> {
+        let mut children = vec![Vec::new(); dominators.len()];
+
+        for (index, dominator) in dominators.iter().enumerate().skip(1) {
+            if let Some(dominator) = dominator {
+                children[dominator.0 as usize].push(BlockId(index as u32));
+            }
+        }
+
+        children
+    }
+
+
+    pub fn dominance_frontiers(dominators: &[Option<BlockId>], predecessors: &[Vec<BlockId>]) -> Vec<Vec<BlockId>> {
+        let mut frontiers = vec![Vec::new(); dominators.len()];
+
+        for (index, block_predecessors) in predecessors.iter().enumerate() {
+            let block = BlockId(index as u32);
+
+            let Some(dominator) = dominators[index] else {
+                continue;
+            };
+
+            if block_predecessors.len() < 2 {
+                continue;
+            }
+
+            for &predecessor in block_predecessors {
+                let mut runner = predecessor;
+
+                while runner != dominator && dominators[runner.0 as usize].is_some() {
+                    let frontier: &mut Vec<BlockId> = &mut frontiers[runner.0 as usize];
+
+                    if !frontier.contains(&block) {
+                        frontier.push(block);
+                    }
+
+                    if runner == BlockId(0) {
+                        break;
+                    }
+
+                    runner = dominators[runner.0 as usize].unwrap_or(BlockId(0));
+                }
+            }
+        }
+
+        frontiers
+    }
+
+
+    pub fn loops(&self, dominators: &[Option<BlockId>]) -> Vec<Loop> {
+        let predecessors = self.predecessors();
+        let mut loops: Vec<Loop> = Vec::new();
+
+        for block in &self.blocks {
+            if dominators[block.id.0 as usize].is_none() {
+                continue;
+            }
+
+            for successor in block.successors() {
+                if !IrFunction::dominates(dominators, successor, block.id) {
+                    continue;
+                }
+
+                match loops.iter_mut().find(|existing| existing.header == successor) {
+                    Some(existing) => existing.latches.push(block.id),
+                    None => loops.push(Loop { header: successor, blocks: Vec::new(), latches: vec![block.id], parent: None, depth: 1 }),
+                }
+            }
+        }
+
+        for natural in &mut loops {
+            let mut blocks = HashSet::from([natural.header]);
+            let mut worklist = natural.latches.clone();
+
+            while let Some(block) = worklist.pop() {
+                if blocks.insert(block) {
+                    worklist.extend(predecessors[block.0 as usize].iter().filter(|predecessor| dominators[predecessor.0 as usize].is_some()));
+                }
+            }
+
+            natural.blocks = blocks.into_iter().collect();
+            natural.blocks.sort();
+        }
+
+        loops.sort_by_key(|natural| (std::cmp::Reverse(natural.blocks.len()), natural.header));
+
+        for index in 0..loops.len() {
+            let parent = (0..index).rev().find(|&outer| loops[outer].blocks.contains(&loops[index].header));
+            loops[index].parent = parent;
+            loops[index].depth = parent.map_or(1, |parent| loops[parent].depth + 1);
+        }
+
+        loops
+    }
+
+
+    pub fn liveness(&self) -> Liveness {
+        let count = self.blocks.len();
+        let mut uses = vec![HashSet::new(); count];
+        let mut definitions = vec![HashSet::new(); count];
+        let mut phi_uses: Vec<HashSet<Register>> = vec![HashSet::new(); count];
+        let mut phi_definitions = vec![HashSet::new(); count];
+
+        for (index, block) in self.blocks.iter().enumerate() {
+            for instruction in &block.instructions {
+                if let InstructionKind::Phi { incoming } = &instruction.kind {
+                    for (value, predecessor) in incoming {
+                        if let (Operand::Register(register), Some(set)) = (value, phi_uses.get_mut(predecessor.0 as usize)) {
+                            set.insert(*register);
+                        }
+                    }
+
+                    phi_definitions[index].extend(instruction.result);
+                    continue;
+                }
+
+                for operand in instruction.clone().operands_mut() {
+                    if let Operand::Register(register) = operand && !definitions[index].contains(register) {
+                        uses[index].insert(*register);
+                    }
+                }
+
+                definitions[index].extend(instruction.result);
+            }
+        }
+
+        let mut live_in = vec![HashSet::new(); count];
+        let mut live_out = vec![HashSet::new(); count];
+        let order = self.reverse_postorder();
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for block in order.iter().rev() {
+                let index = block.0 as usize;
+                let mut out: HashSet<Register> = phi_uses[index].clone();
+
+                for successor in self.blocks[index].successors() {
+                    out.extend(live_in[successor.0 as usize].difference(&phi_definitions[successor.0 as usize]));
+                }
+
+                let mut live: HashSet<Register> = out.difference(&definitions[index]).copied().collect();
+                live.extend(uses[index].iter().copied());
+                live.retain(|register| !phi_definitions[index].contains(register));
+
+                if live != live_in[index] || out != live_out[index] {
+                    live_in[index] = live;
+                    live_out[index] = out;
+                    changed = true;
+                }
+            }
+        }
+
+        Liveness { live_in, live_out }
+    }
+
+
+    pub fn post_dominators(&self) -> Vec<Option<BlockId>> {
+        let exit = self.blocks.len();
+        let mut successors: Vec<Vec<usize>> = self.blocks.iter().map(|block| block.successors().iter().map(|successor| successor.0 as usize).collect()).collect();
+        let mut predecessors = vec![Vec::new(); exit + 1];
+
+        for (index, block_successors) in successors.iter_mut().enumerate() {
+            if block_successors.is_empty() {
+                block_successors.push(exit);
+            }
+
+            for &successor in block_successors.iter() {
+                predecessors[successor].push(index);
+            }
+        }
+
+        let mut visited = vec![false; exit + 1];
+        let mut postorder = Vec::new();
+        let mut stack = vec![(exit, 0)];
+        visited[exit] = true;
+
+        while let Some((node, next)) = stack.last_mut() {
+            match predecessors[*node].get(*next).copied() {
+                Some(predecessor) => {
+                    *next += 1;
+
+                    if !visited[predecessor] {
+                        visited[predecessor] = true;
+                        stack.push((predecessor, 0));
+                    }
+                }
+
+                None => {
+                    postorder.push(*node);
+                    stack.pop();
+                }
+            }
+        }
+
+        let mut position = vec![usize::MAX; exit + 1];
+
+        for (index, node) in postorder.iter().enumerate() {
+            position[*node] = index;
+        }
+
+        let mut post_dominators: Vec<Option<usize>> = vec![None; exit + 1];
+        post_dominators[exit] = Some(exit);
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for &node in postorder.iter().rev().skip(1) {
+                let mut dominator: Option<usize> = None;
+
+                for &successor in &successors[node] {
+                    if post_dominators[successor].is_none() {
+                        continue;
+                    }
+
+                    let Some(mut other) = dominator else {
+                        dominator = Some(successor);
+                        continue;
+                    };
+
+                    let mut current = successor;
+
+                    while current != other {
+                        while position[current] < position[other] {
+                            current = post_dominators[current].unwrap_or(exit);
+                        }
+
+                        while position[other] < position[current] {
+                            other = post_dominators[other].unwrap_or(exit);
+                        }
+                    }
+
+                    dominator = Some(current);
+                }
+
+                if post_dominators[node] != dominator {
+                    post_dominators[node] = dominator;
+                    changed = true;
+                }
+            }
+        }
+
+        post_dominators.into_iter().map(|dominator| dominator.map(|dominator| BlockId(dominator as u32))).collect()
+    }
+
+
+    pub fn replace_operands(&mut self, replacements: &HashMap<Register, Operand>) {
+        for instruction in self.blocks.iter_mut().flat_map(|block| &mut block.instructions) {
+            for operand in instruction.operands_mut() {
+                operand.substitute(replacements);
+            }
+        }
+    }
+
+
+    pub fn remove_trivial_phis(&mut self) -> bool {
+        let mut changed = false;
+
+        loop {
+            let mut replacements = HashMap::new();
+
+            for block in &mut self.blocks {
+                block.instructions.retain(|instruction| {
+                    let (Some(result), InstructionKind::Phi { incoming }) = (instruction.result, &instruction.kind) else {
+                        return true;
+                    };
+
+                    let mut unique: Option<&Operand> = None;
+
+                    for (value, _) in incoming {
+                        if *value == Operand::Register(result) || unique == Some(value) {
+                            continue;
+                        }
+
+                        if unique.is_some() {
+                            return true;
+                        }
+
+                        unique = Some(value);
+                    }
+
+                    let mut value = unique.cloned().unwrap_or(Operand::Undef);
+                    value.substitute(&replacements);
+
+                    if value == Operand::Register(result) {
+                        value = Operand::Undef;
+                    }
+
+                    replacements.insert(result, value);
+                    false
+                });
+            }
+
+            if replacements.is_empty() {
+                return changed;
+            }
+
+            self.replace_operands(&replacements);
+            changed = true;
+        }
+    }
+
+
+    pub fn preheader(&self, natural: &Loop) -> Option<BlockId> {
+        let predecessors = self.predecessors();
+
+        let outside: Vec<BlockId> = predecessors[natural.header.0 as usize].iter()
+            .filter(|predecessor| !natural.blocks.contains(predecessor))
+            .copied()
+            .collect();
+
+        match outside[..] {
+            [block] if self.blocks[block.0 as usize].successors() == [natural.header] => Some(block),
+            _ => None,
+        }
+    }
+
+
+    pub fn insert_preheaders(&mut self, loops: &[Loop]) -> bool {
+        let mut changed = false;
+
+        for natural in loops {
+            let header = natural.header.0 as usize;
+
+            let outside: Vec<BlockId> = self.predecessors()[header].iter()
+                .filter(|predecessor| !natural.blocks.contains(predecessor))
+                .copied()
+                .collect();
+
+            if outside.is_empty() || self.preheader(natural).is_some() {
+                continue;
+            }
+
+            let preheader = BlockId(self.blocks.len() as u32);
+            let mut instructions = Vec::new();
+
+            for index in 0..self.blocks[header].instructions.len() {
+                let ty = self.blocks[header].instructions[index].ty;
+
+                let InstructionKind::Phi { incoming } = &mut self.blocks[header].instructions[index].kind else {
+                    continue;
+                };
+
+                let (entering, staying): (Vec<_>, Vec<_>) = std::mem::take(incoming).into_iter().partition(|(_, predecessor)| outside.contains(predecessor));
+                *incoming = staying;
+
+                let value = match &entering[..] {
+                    [(value, _)] => value.clone(),
+
+                    _ => {
+                        let result = self.new_register();
+                        instructions.push(Instruction { result: Some(result), ty, kind: InstructionKind::Phi { incoming: entering } });
+                        Operand::Register(result)
+                    }
+                };
+
+                if let InstructionKind::Phi { incoming } = &mut self.blocks[header].instructions[index].kind {
+                    incoming.push((value, preheader));
+                }
+            }
+
+            for block in &outside {
+                if let Some(terminator) = self.blocks[block.0 as usize].instructions.last_mut() {
+                    terminator.retarget(natural.header, preheader);
+                }
+            }
+
+            instructions.push(Instruction { result: None, ty: IrType::Void, kind: InstructionKind::Jump(natural.header) });
+            self.blocks.push(Block { id: preheader, instructions });
+            changed = true;
+        }
+
+        changed
+    }
+
+
+    pub fn remove_incoming(&mut self, block: BlockId, predecessor: BlockId) {
+        for instruction in &mut self.blocks[block.0 as usize].instructions {
+            if let InstructionKind::Phi { incoming } = &mut instruction.kind {
+                incoming.retain(|(_, source)| *source != predecessor);
+            }
+        }
+    }
+
+
+    pub fn dominates(dominators: &[Option<BlockId>], dominator: BlockId, block: BlockId) -> bool {
+        let mut current = block;
+
+        loop {
+            if current == dominator {
+                return true;
+            }
+
+            match dominators[current.0 as usize] {
+                Some(parent) if parent != current => current = parent,
+                _ => return false,
+            }
+        }
+    }
+}
+
+
+impl PassName {
+    pub const ALL: [PassName; 10] = [
+        PassName::Mem2Reg,
+        PassName::Sccp,
+        PassName::Gvn,
+        PassName::Licm,
+        PassName::IndVars,
+        PassName::LoopUnroll,
+        PassName::Inline,
+        PassName::Adce,
+        PassName::SimplifyCfg,
+        PassName::OutOfSsa,
+    ];
+
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PassName::Mem2Reg => "mem2reg",
+            PassName::Sccp => "sccp",
+            PassName::Gvn => "gvn",
+            PassName::Licm => "licm",
+            PassName::IndVars => "indvars",
+            PassName::LoopUnroll => "loop-unroll",
+            PassName::Inline => "inline",
+            PassName::Adce => "adce",
+            PassName::SimplifyCfg => "simplify-cfg",
+            PassName::OutOfSsa => "out-of-ssa",
+        }
+    }
+
+
+    pub fn preserves(self) -> &'static [Analysis] {
+        match self {
+            PassName::Mem2Reg | PassName::Gvn | PassName::OutOfSsa => &[Analysis::Dominators, Analysis::Loops],
+            PassName::Sccp | PassName::Licm | PassName::IndVars | PassName::LoopUnroll | PassName::Inline | PassName::Adce | PassName::SimplifyCfg => &[],
+        }
+    }
+
+
+    pub fn is_module_pass(self) -> bool {
+        matches!(self, PassName::Inline)
+    }
+}
+
+
+impl FromStr for PassName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PassName::ALL.into_iter()
+            .find(|pass| pass.name() == s)
+            .ok_or_else(|| format!("unknown pass '{}'", s))
+    }
+}
+
+
+impl InlineHint {
+    pub const KEYWORDS: [InlineHint; 3] = [InlineHint::Hint, InlineHint::Always, InlineHint::Never];
+
+
+    pub fn keyword(self) -> Option<&'static str> {
+        match self {
+            InlineHint::None => None,
+            InlineHint::Hint => Some("inlinehint"),
+            InlineHint::Always => Some("alwaysinline"),
+            InlineHint::Never => Some("noinline"),
+        }
+    }
+}
+
+
+impl CallGraph {
+    pub fn new(module: &Module) -> Self {
+        let mut call_graph = CallGraph {
+            indices: module.functions.iter().enumerate().map(|(index, function)| (function.name.clone(), index)).collect(),
+            callees: vec![Vec::new(); module.functions.len()],
+            call_sites: vec![0; module.functions.len()],
+            address_taken: HashSet::new(),
+        };
+
+        for global in &module.globals {
+            for relocation in &global.relocations {
+                call_graph.address_taken.extend(call_graph.indices.get(&relocation.symbol));
+            }
+        }
+
+        for (caller, function) in module.functions.iter().enumerate() {
+            for instruction in function.blocks.iter().flat_map(|block| &block.instructions) {
+                let mut instruction = instruction.clone();
+
+                if let InstructionKind::Call { callee: Operand::Global(name), .. } = &instruction.kind && let Some(&callee) = call_graph.indices.get(name) {
+                    call_graph.call_sites[callee] += 1;
+
+                    if !call_graph.callees[caller].contains(&callee) {
+                        call_graph.callees[caller].push(callee);
+                    }
+                }
+
+                let operands = match &mut instruction.kind {
+                    InstructionKind::Call { arguments, .. } => arguments.iter_mut().map(|(_, argument)| argument).collect(),
+                    _ => instruction.operands_mut(),
+                };
+
+                for operand in operands {
+                    if let Operand::Global(name) = operand {
+                        call_graph.address_taken.extend(call_graph.indices.get(name));
+                    }
+                }
+            }
+        }
+
+        call_graph
+    }
+
+
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let count = self.callees.len();
+        let mut order: Vec<Option<usize>> = vec![None; count];
+        let mut lowlink = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        for root in 0..count {
+            if order[root].is_some() {
+                continue;
+            }
+
+            let mut work = vec![(root, 0)];
+
+            while let Some((node, edge)) = work.pop() {
+                if edge == 0 {
+                    order[node] = Some(counter);
+                    lowlink[node] = counter;
+                    counter += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+
+                if let Some(&next) = self.callees[node].get(edge) {
+                    work.push((node, edge + 1));
+
+                    match order[next] {
+                        None => work.push((next, 0)),
+                        Some(next_order) if on_stack[next] => lowlink[node] = lowlink[node].min(next_order),
+                        Some(_) => {}
+                    }
+
+                    continue;
+                }
+
+                if Some(lowlink[node]) == order[node] {
+                    let mut component = Vec::new();
+
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+
+                        if member == node {
+                            break;
+                        }
+                    }
+
+                    components.push(component);
+                }
+
+                if let Some(&(parent, _)) = work.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+            }
+        }
+
+        components
+    }
+}
+
+
+impl PassOptions {
+    pub fn pipeline(&self) -> Vec<PassName> {
+        if let Some(passes) = &self.passes {
+            return passes.clone();
+        }
+
+        match self.level {
+            0 => Vec::new(),
+            1 => vec![PassName::Mem2Reg, PassName::Sccp, PassName::Gvn, PassName::Adce, PassName::SimplifyCfg],
+
+            _ => vec![
+                PassName::Mem2Reg, PassName::Sccp, PassName::Adce, PassName::SimplifyCfg, PassName::Inline,
+                PassName::Sccp, PassName::Gvn, PassName::Licm, PassName::IndVars, PassName::LoopUnroll,
+                PassName::Sccp, PassName::Gvn, PassName::Adce, PassName::SimplifyCfg,
+            ],
+        }
+    }
+}
+
+
+impl Analyses {
+    pub fn dominators(&mut self, function: &IrFunction) -> &[Option<BlockId>] {
+        self.dominators.get_or_insert_with(|| function.dominators())
+    }
+
+
+    pub fn loops(&mut self, function: &IrFunction) -> &[Loop] {
+        if self.loops.is_none() {
+            let loops = function.loops(self.dominators(function));
+            self.loops = Some(loops);
+        }
+
+        self.loops.as_deref().unwrap_or_default()
+    }
+
+
+    pub fn canonical_loops(&mut self, function: &mut IrFunction) -> (Vec<Loop>, bool) {
+        let loops = self.loops(function).to_vec();
+        let inserted = function.insert_preheaders(&loops);
+
+        if inserted {
+            self.invalidate(&[]);
+        }
+
+        (self.loops(function).to_vec(), inserted)
+    }
+
+
+    pub fn liveness(&mut self, function: &IrFunction) -> &Liveness {
+        self.liveness.get_or_insert_with(|| function.liveness())
+    }
+
+
+    pub fn invalidate(&mut self, preserved: &[Analysis]) {
+        if !preserved.contains(&Analysis::Dominators) {
+            self.dominators = None;
+        }
+
+        if !preserved.contains(&Analysis::Loops) {
+            self.loops = None;
+        }
+
+        if !preserved.contains(&Analysis::Liveness) {
+            self.liveness = None;
+        }
+    }
+}
+
+
+impl Block {
+    pub fn successors(&self) -> Vec<BlockId> {
+        self.instructions.last().map(Instruction::successors).unwrap_or_default()
+    }
+}
+
+
+impl Instruction {
+    pub fn terminates(kind: &InstructionKind) -> bool {
+        matches!(kind, InstructionKind::Jump(_) | InstructionKind::Branch { .. } | InstructionKind::Switch { .. }
+            | InstructionKind::Return(_) | InstructionKind::Unreachable)
+    }
+
+
+    pub fn defines_value(ty: IrType, kind: &InstructionKind) -> bool {
+        match kind {
+            InstructionKind::Store { .. } | InstructionKind::Copy { .. } | InstructionKind::Zero { .. } => false,
+            InstructionKind::Call { .. } => ty != IrType::Void,
+            _ => !Instruction::terminates(kind),
+        }
+    }
+
+
+    pub fn is_terminator(&self) -> bool {
+        Instruction::terminates(&self.kind)
+    }
+
+
+    pub fn result_type(&self) -> IrType {
+        match self.kind {
+            InstructionKind::Compare { .. } => IrType::I1,
+            _ => self.ty,
+        }
+    }
+
+
+    pub fn successors(&self) -> Vec<BlockId> {
+        match &self.kind {
+            InstructionKind::Jump(target) => vec![*target],
+            InstructionKind::Branch { then_block, else_block, .. } => vec![*then_block, *else_block],
+
+            InstructionKind::Switch { default, cases, .. } => {
+                let mut successors = vec![*default];
+
+                for (_, target) in cases {
+                    if !successors.contains(target) {
+                        successors.push(*target);
+                    }
+                }
+
+                successors
+            }
+
+            _ => Vec::new(),
+        }
+    }
+
+
+    pub fn retarget(&mut self, from: BlockId, to: BlockId) {
+        let targets: Vec<&mut BlockId> = match &mut self.kind {
+            InstructionKind::Jump(target) => vec![target],
+            InstructionKind::Branch { then_block, else_block, .. } => vec![then_block, else_block],
+            InstructionKind::Switch { default, cases, .. } => std::iter::once(default).chain(cases.iter_mut().map(|(_, target)| target)).collect(),
+            _ => Vec::new(),
         };
 
-        KEYWORDS.get(s).copied().ok_or(())
+        for target in targets {
+            if *target == from {
+                *target = to;
+            }
+        }
+    }
+
+
+    pub fn has_side_effects(&self) -> bool {
+        match &self.kind {
+            InstructionKind::Store { .. } | InstructionKind::Copy { .. } | InstructionKind::Zero { .. } | InstructionKind::Call { .. } => true,
+            InstructionKind::Load { volatile, .. } => *volatile,
+            _ => self.is_terminator(),
+        }
+    }
+
+
+    pub fn operands_mut(&mut self) -> Vec<&mut Operand> {
+        match &mut self.kind {
+            InstructionKind::Binary { left, right, .. } | InstructionKind::Compare { left, right, .. } => vec![left, right],
+            InstructionKind::Convert { value, .. } => vec![value],
+            InstructionKind::Alloca { size, .. } => vec![size],
+            InstructionKind::Load { address, .. } | InstructionKind::Zero { address, .. } => vec![address],
+            InstructionKind::Store { value, address, .. } => vec![value, address],
+            InstructionKind::PtrAdd { base, offset } => vec![base, offset],
+            InstructionKind::Copy { destination, source, .. } => vec![destination, source],
+            InstructionKind::Call { callee, arguments } => std::iter::once(callee).chain(arguments.iter_mut().map(|(_, argument)| argument)).collect(),
+            InstructionKind::Phi { incoming } => incoming.iter_mut().map(|(value, _)| value).collect(),
+            InstructionKind::Branch { condition, .. } => vec![condition],
+            InstructionKind::Switch { value, .. } => vec![value],
+            InstructionKind::Return(value) => value.iter_mut().collect(),
+            InstructionKind::Jump(_) | InstructionKind::Unreachable => Vec::new(),
+        }
+    }
+}
+
+
+impl IrType {
+    pub fn size(self) -> u64 {
+        match self {
+            IrType::Void => 0,
+            IrType::I1 | IrType::I8 => 1,
+            IrType::I16 => 2,
+            IrType::I32 | IrType::F32 => 4,
+            IrType::I64 | IrType::F64 | IrType::Ptr => 8,
+        }
+    }
+
+
+    pub fn bits(self) -> u32 {
+        match self {
+            IrType::I1 => 1,
+            _ => self.size() as u32 * 8,
+        }
+    }
+
+
+    pub fn is_integer(self) -> bool {
+        matches!(self, IrType::I1 | IrType::I8 | IrType::I16 | IrType::I32 | IrType::I64)
+    }
+
+
+    pub fn is_floating(self) -> bool {
+        matches!(self, IrType::F32 | IrType::F64)
+    }
+
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IrType::Void => "void",
+            IrType::I1 => "i1",
+            IrType::I8 => "i8",
+            IrType::I16 => "i16",
+            IrType::I32 => "i32",
+            IrType::I64 => "i64",
+            IrType::F32 => "f32",
+            IrType::F64 => "f64",
+            IrType::Ptr => "ptr",
+        }
+    }
+}
+
+
+impl ArithmeticOperator {
+    pub fn name(self) -> &'static str {
+        match self {
+            ArithmeticOperator::Add => "add",
+            ArithmeticOperator::Sub => "sub",
+            ArithmeticOperator::Mul => "mul",
+            ArithmeticOperator::SDiv => "sdiv",
+            ArithmeticOperator::UDiv => "udiv",
+            ArithmeticOperator::SRem => "srem",
+            ArithmeticOperator::URem => "urem",
+            ArithmeticOperator::And => "and",
+            ArithmeticOperator::Or => "or",
+            ArithmeticOperator::Xor => "xor",
+            ArithmeticOperator::Shl => "shl",
+            ArithmeticOperator::LShr => "lshr",
+            ArithmeticOperator::AShr => "ashr",
+            ArithmeticOperator::FAdd => "fadd",
+            ArithmeticOperator::FSub => "fsub",
+            ArithmeticOperator::FMul => "fmul",
+            ArithmeticOperator::FDiv => "fdiv",
+        }
+    }
+
+
+    pub fn is_floating(self) -> bool {
+        matches!(self, ArithmeticOperator::FAdd | ArithmeticOperator::FSub | ArithmeticOperator::FMul | ArithmeticOperator::FDiv)
+    }
+}
+
+
+impl ComparePredicate {
+    pub fn name(self) -> &'static str {
+        match self {
+            ComparePredicate::Eq => "eq",
+            ComparePredicate::Ne => "ne",
+            ComparePredicate::Slt => "slt",
+            ComparePredicate::Sle => "sle",
+            ComparePredicate::Sgt => "sgt",
+            ComparePredicate::Sge => "sge",
+            ComparePredicate::Ult => "ult",
+            ComparePredicate::Ule => "ule",
+            ComparePredicate::Ugt => "ugt",
+            ComparePredicate::Uge => "uge",
+            ComparePredicate::FEq => "oeq",
+            ComparePredicate::FNe => "une",
+            ComparePredicate::FLt => "olt",
+            ComparePredicate::FLe => "ole",
+            ComparePredicate::FGt => "ogt",
+            ComparePredicate::FGe => "oge",
+        }
+    }
+
+
+    pub fn is_floating(self) -> bool {
+        matches!(self, ComparePredicate::FEq | ComparePredicate::FNe | ComparePredicate::FLt
+            | ComparePredicate::FLe | ComparePredicate::FGt | ComparePredicate::FGe)
+    }
+}
+
+
+impl ConversionOperator {
+    pub fn name(self) -> &'static str {
+        match self {
+            ConversionOperator::Trunc => "trunc",
+            ConversionOperator::ZExt => "zext",
+            ConversionOperator::SExt => "sext",
+            ConversionOperator::FpTrunc => "fptrunc",
+            ConversionOperator::FpExt => "fpext",
+            ConversionOperator::FpToSi => "fptosi",
+            ConversionOperator::FpToUi => "fptoui",
+            ConversionOperator::SiToFp => "sitofp",
+            ConversionOperator::UiToFp => "uitofp",
+            ConversionOperator::PtrToInt => "ptrtoint",
+            ConversionOperator::IntToPtr => "inttoptr",
+        }
+    }
+}
+
+
+impl fmt::Display for IrType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "%{}", self.0)
+    }
+}
+
+
+impl fmt::Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bb{}", self.0)
+    }
+}
+
+
+impl Eq for Operand {}
+
+
+impl Hash for Operand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+
+        match self {
+            Operand::Register(register) => register.hash(state),
+            Operand::Integer(value) => value.hash(state),
+            Operand::Floating(value) => value.to_bits().hash(state),
+            Operand::Global(name) => name.hash(state),
+            Operand::Null | Operand::Undef => {}
+        }
+    }
+}
+
+
+impl Operand {
+    pub fn substitute(&mut self, replacements: &HashMap<Register, Operand>) {
+        while let Operand::Register(register) = self {
+            match replacements.get(register) {
+                Some(replacement) if *replacement != Operand::Register(*register) => *self = replacement.clone(),
+                _ => return,
+            }
+        }
+    }
+}
+
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Register(register) => write!(f, "{}", register),
+            Operand::Integer(value) => write!(f, "{}", value),
+            Operand::Floating(value) => write!(f, "{:?}", value),
+            Operand::Global(name) => write!(f, "@{}", name),
+            Operand::Null => write!(f, "null"),
+            Operand::Undef => write!(f, "undef"),
+        }
+    }
+}
+
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(result) = self.result {
+            write!(f, "{} = ", result)?;
+        }
+
+        let ty = self.ty;
+
+        match &self.kind {
+            InstructionKind::Binary { operator, left, right, no_signed_wrap } => {
+                let flag = if *no_signed_wrap { " nsw" } else { "" };
+                write!(f, "{}{} {} {}, {}", operator.name(), flag, ty, left, right)
+            }
+
+
+            InstructionKind::Compare { predicate, left, right } => {
+                let instruction = if predicate.is_floating() { "fcmp" } else { "icmp" };
+                write!(f, "{} {} {} {}, {}", instruction, predicate.name(), ty, left, right)
+            }
+
+            InstructionKind::Convert { operator, value, from } => write!(f, "{} {} {} to {}", operator.name(), from, value, ty),
+            InstructionKind::Alloca { size, align } => write!(f, "alloca {} align {}", size, align),
+            InstructionKind::Load { address, volatile } => write!(f, "load {}{} {}", if *volatile { "volatile " } else { "" }, ty, address),
+            InstructionKind::Store { value, address, volatile } => write!(f, "store {}{} {}, {}", if *volatile { "volatile " } else { "" }, ty, value, address),
+            InstructionKind::PtrAdd { base, offset } => write!(f, "ptradd {}, {}", base, offset),
+            InstructionKind::Copy { destination, source, size } => write!(f, "copy {}, {}, {}", destination, source, size),
+            InstructionKind::Zero { address, size } => write!(f, "zero {}, {}", address, size),
+
+            InstructionKind::Call { callee, arguments } => {
+                let arguments: Vec<String> = arguments.iter().map(|(ty, argument)| format!("{} {}", ty, argument)).collect();
+                write!(f, "call {} {}({})", ty, callee, arguments.join(", "))
+            }
+
+            InstructionKind::Phi { incoming } => {
+                let incoming: Vec<String> = incoming.iter().map(|(value, block)| format!("[{}, {}]", value, block)).collect();
+                write!(f, "phi {} {}", ty, incoming.join(", "))
+            }
+
+            InstructionKind::Jump(target) => write!(f, "jmp {}", target),
+            InstructionKind::Branch { condition, then_block, else_block } => write!(f, "br {}, {}, {}", condition, then_block, else_block),
+
+            InstructionKind::Switch { value, default, cases } => {
+                let cases: Vec<String> = cases.iter().map(|(value, target)| format!("{}: {}", value, target)).collect();
+                write!(f, "switch {} {}, {} [{}]", ty, value, default, cases.join(", "))
+            }
+
+            InstructionKind::Return(Some(value)) => write!(f, "ret {} {}", ty, value),
+            InstructionKind::Return(None) => write!(f, "ret void"),
+            InstructionKind::Unreachable => write!(f, "unreachable"),
+        }
+    }
+}
+
+
+impl fmt::Display for Global {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.external {
+            return write!(f, "@{} = external global", self.name);
+        }
+
+        let linkage = if self.internal { "internal " } else { "" };
+        let kind = if self.constant { "constant" } else { "global" };
+        write!(f, "@{} = {}{} {} align {}", self.name, linkage, kind, self.size, self.align)?;
+
+        if !self.data.is_empty() {
+            let bytes: String = self.data.iter().map(|&byte| match byte {
+                b'"' | b'\\' => format!("\\{:02x}", byte),
+                0x20..=0x7e => (byte as char).to_string(),
+                _ => format!("\\{:02x}", byte),
+            }).collect();
+
+            write!(f, " \"{}\"", bytes)?;
+        }
+
+        if !self.relocations.is_empty() {
+            let relocations: Vec<String> = self.relocations.iter()
+                .map(|relocation| format!("{}: @{} + {}", relocation.offset, relocation.symbol, relocation.addend))
+                .collect();
+
+            write!(f, " [{}]", relocations.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+
+impl fmt::Display for IrFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parameters: Vec<String> = self.parameters.iter()
+            .map(|(ty, register)| if self.is_declaration() { ty.to_string() } else { format!("{} {}", ty, register) })
+            .collect();
+
+        if self.variadic {
+            parameters.push("...".to_string());
+        }
+
+        if self.is_declaration() {
+            return write!(f, "declare {} @{}({})", self.return_type, self.name, parameters.join(", "));
+        }
+
+        let linkage = if self.internal { "internal " } else { "" };
+        let hint = self.inline_hint.keyword().map_or(String::new(), |keyword| format!(" {}", keyword));
+        writeln!(f, "define {}{} @{}({}){} {{", linkage, self.return_type, self.name, parameters.join(", "), hint)?;
+
+        for block in &self.blocks {
+            writeln!(f, "{}:", block.id)?;
+
+            for instruction in &block.instructions {
+                writeln!(f, "  {}", instruction)?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for global in &self.globals {
+            writeln!(f, "{}", global)?;
+        }
+
+        for (index, function) in self.functions.iter().enumerate() {
+            let grouped = index > 0 && function.is_declaration() && self.functions[index - 1].is_declaration();
+
+            if !grouped && (index > 0 || !self.globals.is_empty()) {
+                writeln!(f)?;
+            }
+
+            writeln!(f, "{}", function)?;
+        }
+
+        Ok(())
+    }
+}
+
+
+impl IrParserState {
+    pub fn new(tokens: Vec<(IrToken, u16)>) -> Self {
+        Self { tokens, iterator: 0, module: Module::default(), registers: HashMap::new(), blocks: HashMap::new(), source_map: SourceMap::default(), errors: Vec::new() }
+    }
+
+
+    pub fn peek(&self) -> Option<&IrToken> {
+        self.tokens.get(self.iterator).map(|(token, _)| token)
+    }
+
+
+    pub fn advance(&mut self) -> Option<IrToken> {
+        let token = self.tokens.get(self.iterator).map(|(token, _)| token.clone());
+        self.iterator += 1;
+
+        token
+    }
+
+
+    pub fn line(&self) -> u16 {
+        self.tokens.get(self.iterator).or(self.tokens.last()).map_or(0, |(_, line)| *line)
+    }
+
+
+    pub fn is_punctuation(&self, punctuation: char) -> bool {
+        self.peek() == Some(&IrToken::Punctuation(punctuation))
+    }
+
+
+    pub fn is_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(IrToken::Word(current)) if current == word)
+    }
+
+
+    pub fn register(&mut self, name: String) -> Register {
+        let line = self.line();
+        let next = Register(self.registers.len() as u32);
+
+        self.registers.entry(name).or_insert((next, line)).0
+    }
+
+
+    pub fn block(&mut self, name: String) -> BlockId {
+        let line = self.line();
+        let next = BlockId(self.blocks.len() as u32);
+
+        self.blocks.entry(name).or_insert((next, line)).0
+    }
+}
+
+
+impl<'a> VerifierState<'a> {
+    pub fn new(module: &'a Module, source_map: Option<&'a SourceMap>) -> Self {
+        let globals = module.globals.iter().map(|global| (global.name.as_str(), None));
+        let functions = module.functions.iter().map(|function| (function.name.as_str(), Some(function)));
+
+        Self {
+            symbols: globals.chain(functions).collect(),
+            function: None,
+            source_map,
+            source: None,
+            register_types: HashMap::new(),
+            definitions: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+
+    pub fn block_name(&self, block: BlockId) -> String {
+        self.source.and_then(|source| source.blocks.get(&block)).cloned().unwrap_or_else(|| block.to_string())
+    }
+
+
+    pub fn register_name(&self, register: Register) -> String {
+        self.source.and_then(|source| source.registers.get(&register)).map_or_else(|| register.to_string(), |name| format!("%{}", name))
     }
-}
\ No newline at end of file
+
+
+    pub fn operand_name(&self, operand: &Operand) -> String {
+        match operand {
+            Operand::Register(register) => self.register_name(*register),
+            _ => operand.to_string(),
+        }
+    }
+
+
+    pub fn source_line(&self, block: BlockId, index: usize) -> Option<(u16, &'a str)> {
+        let line = *self.source?.instructions.get(&(block, index))?;
+        let text = self.source_map?.lines.get(line as usize - 1)?;
+
+        Some((line, text.split(';').next().unwrap_or_default().trim()))
+    }
+}
+
+
+impl fmt::Display for IrToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IrToken::Word(word) => write!(f, "`{}`", word),
+            IrToken::Register(name) => write!(f, "`%{}`", name),
+            IrToken::Global(name) => write!(f, "`@{}`", name),
+            IrToken::String(_) => write!(f, "string literal"),
+            IrToken::Punctuation(punctuation) => write!(f, "`{}`", punctuation),
+            IrToken::Ellipsis => write!(f, "`...`"),
+            IrToken::Newline => write!(f, "end of line"),
+        }
+    }
+}
+
+
+impl<'a> InterpreterState<'a> {
+    pub fn new(module: &'a Module, stdout: &'a mut dyn Write) -> Self {
+        let functions = module.functions.iter()
+            .filter(|function| !function.is_declaration())
+            .map(|function| (function.name.as_str(), function))
+            .collect();
+
+        let null = Allocation { data: Vec::new(), kind: AllocationKind::Global, live: false, writable: false };
+
+        Self {
+            functions,
+            symbols: HashMap::new(),
+            allocations: vec![null],
+            released: Vec::new(),
+            frames: Vec::new(),
+            input: Vec::new(),
+            input_position: 0,
+            input_closed: false,
+            output: Vec::new(),
+            stdout,
+            exit_code: None,
+        }
+    }
+
+
+    pub fn allocate(&mut self, size: u64, kind: AllocationKind) -> u64 {
+        let allocation = Allocation { data: vec![0; size as usize], kind, live: true, writable: true };
+
+        if allocation.kind == AllocationKind::Stack && let Some(index) = self.released.pop() {
+            self.allocations[index] = allocation;
+            return (index as u64) << 32;
+        }
+
+        self.allocations.push(allocation);
+
+        ((self.allocations.len() - 1) as u64) << 32
+    }
+
+
+    pub fn frame(&mut self) -> &mut Frame<'a> {
+        self.frames.last_mut().expect("no active frame")
+    }
+}
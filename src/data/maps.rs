@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::sync::LazyLock;
 
-use crate::data::types::{ SingleOperator, DoubleOperator, TripleOperator };
+use crate::data::types::{ ArithmeticOperator, AssignmentOperator, BinaryOperator, ComparePredicate, ConversionOperator, FormatKind, IrType, SingleOperator, DoubleOperator, TripleOperator, UnaryOperator };
 
 
 pub static SINGLE_OPERATOR_MAP: LazyLock<HashMap<char, SingleOperator>> = LazyLock::new(|| {
@@ -67,4 +67,157 @@ pub static TRIPLE_OPERATOR_MAP: LazyLock<HashMap<&str, TripleOperator>> = LazyLo
         ("<<=", TripleOperator::LeftShiftEqual),
         (">>=", TripleOperator::RightShiftEqual),
     ])
-});
\ No newline at end of file
+});
+
+
+pub static BINARY_OPERATOR_MAP: LazyLock<HashMap<&str, BinaryOperator>> = LazyLock::new(|| {
+    HashMap::from([
+        ("||", BinaryOperator::LogicalOr),
+        ("&&", BinaryOperator::LogicalAnd),
+        ("|", BinaryOperator::BitOr),
+        ("^", BinaryOperator::BitXor),
+        ("&", BinaryOperator::BitAnd),
+        ("==", BinaryOperator::Equal),
+        ("!=", BinaryOperator::NotEqual),
+        ("<", BinaryOperator::Less),
+        (">", BinaryOperator::Greater),
+        ("<=", BinaryOperator::LessEqual),
+        (">=", BinaryOperator::GreaterEqual),
+        ("<<", BinaryOperator::ShiftLeft),
+        (">>", BinaryOperator::ShiftRight),
+        ("+", BinaryOperator::Add),
+        ("-", BinaryOperator::Subtract),
+        ("*", BinaryOperator::Multiply),
+        ("/", BinaryOperator::Divide),
+        ("%", BinaryOperator::Remainder),
+    ])
+});
+
+
+pub static ASSIGNMENT_OPERATOR_MAP: LazyLock<HashMap<&str, AssignmentOperator>> = LazyLock::new(|| {
+    HashMap::from([
+        ("=", AssignmentOperator::Assign),
+        ("+=", AssignmentOperator::Compound(BinaryOperator::Add)),
+        ("-=", AssignmentOperator::Compound(BinaryOperator::Subtract)),
+        ("*=", AssignmentOperator::Compound(BinaryOperator::Multiply)),
+        ("/=", AssignmentOperator::Compound(BinaryOperator::Divide)),
+        ("%=", AssignmentOperator::Compound(BinaryOperator::Remainder)),
+        ("&=", AssignmentOperator::Compound(BinaryOperator::BitAnd)),
+        ("^=", AssignmentOperator::Compound(BinaryOperator::BitXor)),
+        ("|=", AssignmentOperator::Compound(BinaryOperator::BitOr)),
+        ("<<=", AssignmentOperator::Compound(BinaryOperator::ShiftLeft)),
+        (">>=", AssignmentOperator::Compound(BinaryOperator::ShiftRight)),
+    ])
+});
+
+
+pub static UNARY_OPERATOR_MAP: LazyLock<HashMap<&str, UnaryOperator>> = LazyLock::new(|| {
+    HashMap::from([
+        ("&", UnaryOperator::Address),
+        ("*", UnaryOperator::Dereference),
+        ("+", UnaryOperator::Plus),
+        ("-", UnaryOperator::Minus),
+        ("~", UnaryOperator::BitNot),
+        ("!", UnaryOperator::Not),
+        ("++", UnaryOperator::PreIncrement),
+        ("--", UnaryOperator::PreDecrement),
+    ])
+});
+
+
+pub static FORMAT_FUNCTION_MAP: LazyLock<HashMap<&str, (FormatKind, usize, usize)>> = LazyLock::new(|| {
+    HashMap::from([
+        ("printf", (FormatKind::Printf, 1, 2)),
+        ("fprintf", (FormatKind::Printf, 2, 3)),
+        ("dprintf", (FormatKind::Printf, 2, 3)),
+        ("sprintf", (FormatKind::Printf, 2, 3)),
+        ("snprintf", (FormatKind::Printf, 3, 4)),
+        ("vprintf", (FormatKind::Printf, 1, 0)),
+        ("vfprintf", (FormatKind::Printf, 2, 0)),
+        ("vsprintf", (FormatKind::Printf, 2, 0)),
+        ("vsnprintf", (FormatKind::Printf, 3, 0)),
+        ("scanf", (FormatKind::Scanf, 1, 2)),
+        ("fscanf", (FormatKind::Scanf, 2, 3)),
+        ("sscanf", (FormatKind::Scanf, 2, 3)),
+        ("vscanf", (FormatKind::Scanf, 1, 0)),
+        ("vfscanf", (FormatKind::Scanf, 2, 0)),
+        ("vsscanf", (FormatKind::Scanf, 2, 0)),
+    ])
+});
+
+
+pub static IR_TYPE_MAP: LazyLock<HashMap<&str, IrType>> = LazyLock::new(|| {
+    HashMap::from([
+        ("void", IrType::Void),
+        ("i1", IrType::I1),
+        ("i8", IrType::I8),
+        ("i16", IrType::I16),
+        ("i32", IrType::I32),
+        ("i64", IrType::I64),
+        ("f32", IrType::F32),
+        ("f64", IrType::F64),
+        ("ptr", IrType::Ptr),
+    ])
+});
+
+
+pub static ARITHMETIC_OPERATOR_MAP: LazyLock<HashMap<&str, ArithmeticOperator>> = LazyLock::new(|| {
+    HashMap::from([
+        ("add", ArithmeticOperator::Add),
+        ("sub", ArithmeticOperator::Sub),
+        ("mul", ArithmeticOperator::Mul),
+        ("sdiv", ArithmeticOperator::SDiv),
+        ("udiv", ArithmeticOperator::UDiv),
+        ("srem", ArithmeticOperator::SRem),
+        ("urem", ArithmeticOperator::URem),
+        ("and", ArithmeticOperator::And),
+        ("or", ArithmeticOperator::Or),
+        ("xor", ArithmeticOperator::Xor),
+        ("shl", ArithmeticOperator::Shl),
+        ("lshr", ArithmeticOperator::LShr),
+        ("ashr", ArithmeticOperator::AShr),
+        ("fadd", ArithmeticOperator::FAdd),
+        ("fsub", ArithmeticOperator::FSub),
+        ("fmul", ArithmeticOperator::FMul),
+        ("fdiv", ArithmeticOperator::FDiv),
+    ])
+});
+
+
+pub static COMPARE_PREDICATE_MAP: LazyLock<HashMap<&str, ComparePredicate>> = LazyLock::new(|| {
+    HashMap::from([
+        ("eq", ComparePredicate::Eq),
+        ("ne", ComparePredicate::Ne),
+        ("slt", ComparePredicate::Slt),
+        ("sle", ComparePredicate::Sle),
+        ("sgt", ComparePredicate::Sgt),
+        ("sge", ComparePredicate::Sge),
+        ("ult", ComparePredicate::Ult),
+        ("ule", ComparePredicate::Ule),
+        ("ugt", ComparePredicate::Ugt),
+        ("uge", ComparePredicate::Uge),
+        ("oeq", ComparePredicate::FEq),
+        ("une", ComparePredicate::FNe),
+        ("olt", ComparePredicate::FLt),
+        ("ole", ComparePredicate::FLe),
+        ("ogt", ComparePredicate::FGt),
+        ("oge", ComparePredicate::FGe),
+    ])
+});
+
+
+pub static CONVERSION_OPERATOR_MAP: LazyLock<HashMap<&str, ConversionOperator>> = LazyLock::new(|| {
+    HashMap::from([
+        ("trunc", ConversionOperator::Trunc),
+        ("zext", ConversionOperator::ZExt),
+        ("sext", ConversionOperator::SExt),
+        ("fptrunc", ConversionOperator::FpTrunc),
+        ("fpext", ConversionOperator::FpExt),
+        ("fptosi", ConversionOperator::FpToSi),
+        ("fptoui", ConversionOperator::FpToUi),
+        ("sitofp", ConversionOperator::SiToFp),
+        ("uitofp", ConversionOperator::UiToFp),
+        ("ptrtoint", ConversionOperator::PtrToInt),
+        ("inttoptr", ConversionOperator::IntToPtr),
+    ])
+});
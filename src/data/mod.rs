@@ -1,4 +1,5 @@
 mod impls;
+pub use impls::escape_c;
 
 
 pub mod maps;
@@ -6,10 +7,34 @@ pub mod maps;
 
 pub mod structures;
 pub use structures::Token;
+pub use structures::Ast;
 pub use structures::ParserState;
+pub use structures::ParseError;
+pub use structures::Diagnostic;
+pub use structures::SemanticState;
+pub use structures::Binding;
+pub use structures::Member;
+pub use structures::TypeTable;
+pub use structures::Target;
+pub use structures::Entity;
+pub use structures::SymbolTable;
+pub use structures::SymbolTableState;
+pub use structures::Warnings;
+pub use structures::DataflowState;
+pub use structures::LoweringState;
+pub use structures::Module;
+pub use structures::PassOptions;
 
 
 pub mod types;
 pub use types::TokenType;
 pub use types::Keyword;
-pub use types::AstNode;
\ No newline at end of file
+pub use types::NodeId;
+pub use types::NodeKind;
+pub use types::Symbol;
+pub use types::CType;
+pub use types::EntityId;
+pub use types::WarningFlag;
+
+
+pub mod visit;
@@ -1,4 +1,8 @@
+use std::collections::{ HashMap, HashSet };
+use std::io::Write;
+
 use crate::data::TokenType;
+use crate::data::types::{ AccessKind, AllocationKind, Attribute, BlockId, CastKind, ConstantValue, CType, Definition, EntityId, EntityKind, InstructionKind, InlineHint, IrToken, IrType, Lattice, Linkage, MemoryBase, Node, NodeId, Operand, PassName, Register, ScopeKind, Severity, StorageDuration, Symbol, ValueCategory, WarningFlag };
 
 
 #[derive(Debug)]
@@ -7,8 +11,462 @@ pub struct Token {
     pub line: u16,
 }
 
+#[derive(Debug, Default)]
+pub struct Ast {
+    pub nodes: Vec<Node>,
+    pub symbols: Interner,
+    pub root: NodeId,
+    pub parenthesized: HashSet<NodeId>,
+}
+
+#[derive(Debug, Default)]
+pub struct Interner {
+    pub names: Vec<String>,
+    pub indices: HashMap<String, Symbol>,
+}
+
 #[derive(Debug)]
 pub struct ParserState<'a> {
-    pub tokens: &'a Vec<Token>,
-    pub iterator: usize
-}
\ No newline at end of file
+    pub tokens: &'a [Token],
+    pub iterator: usize,
+    pub ast: Ast,
+    pub typedef_scopes: Vec<HashMap<Symbol, bool>>,
+    pub attributes: Vec<Attribute>,
+    pub errors: Vec<ParseError>,
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: u16,
+    pub expected: String,
+    pub found: String,
+}
+
+
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub ctype: CType,
+    pub is_typedef: bool,
+    pub is_static: bool,
+    pub constant: Option<i128>,
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub name: Option<Symbol>,
+    pub ctype: CType,
+    pub bit_width: Option<u64>,
+    pub align: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct TypeTable {
+    pub expressions: HashMap<NodeId, CType>,
+    pub declarations: HashMap<NodeId, Vec<(Symbol, CType)>>,
+    pub records: HashMap<String, Vec<Member>>,
+    pub categories: HashMap<NodeId, ValueCategory>,
+    pub constants: HashMap<NodeId, ConstantValue>,
+    pub selections: HashMap<NodeId, usize>,
+    pub alignments: HashMap<NodeId, u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub short: TypeLayout,
+    pub int: TypeLayout,
+    pub long: TypeLayout,
+    pub long_long: TypeLayout,
+    pub pointer: TypeLayout,
+    pub float: TypeLayout,
+    pub double: TypeLayout,
+    pub long_double: TypeLayout,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TypeLayout {
+    pub size: u64,
+    pub align: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordLayout {
+    pub size: u64,
+    pub align: u64,
+    pub fields: Vec<FieldLayout>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct FieldLayout {
+    pub offset: u64,
+    pub bit_offset: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct SemanticState<'a> {
+    pub ast: &'a Ast,
+    pub target: &'a Target,
+    pub types: TypeTable,
+    pub table: &'a SymbolTable,
+    pub declared: HashMap<(NodeId, Symbol, bool), EntityId>,
+    pub bindings: HashMap<EntityId, Binding>,
+    pub depth: usize,
+    pub casts: HashMap<NodeId, Vec<(CastKind, CType)>>,
+    pub erroneous: HashSet<NodeId>,
+    pub function: String,
+    pub function_noreturn: bool,
+    pub return_type: Option<CType>,
+    pub loop_depth: usize,
+    pub switches: Vec<SwitchLabels>,
+    pub labels: HashMap<Symbol, Vec<NodeId>>,
+    pub gotos: Vec<(NodeId, Symbol, Vec<NodeId>)>,
+    pub variably_modified: Vec<(usize, NodeId)>,
+    pub node: NodeId,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug)]
+pub struct FormatArgument {
+    pub description: String,
+    pub expected: CType,
+}
+
+#[derive(Debug)]
+pub struct SwitchLabels {
+    pub ctype: CType,
+    pub cases: HashSet<i128>,
+    pub has_default: bool,
+    pub variably_modified: usize,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Entity {
+    pub name: Symbol,
+    pub kind: EntityKind,
+    pub scope: ScopeKind,
+    pub linkage: Linkage,
+    pub storage: Option<StorageDuration>,
+    pub definition: Definition,
+    pub node: NodeId,
+    pub previous: Option<EntityId>,
+}
+
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    pub entities: Vec<Entity>,
+    pub references: HashMap<NodeId, EntityId>,
+    pub typedef_names: HashMap<(NodeId, Symbol), EntityId>,
+    pub tags: HashMap<(NodeId, Symbol), EntityId>,
+}
+
+#[derive(Debug)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub ordinary: HashMap<Symbol, EntityId>,
+    pub tags: HashMap<Symbol, EntityId>,
+}
+
+#[derive(Debug)]
+pub struct SymbolTableState<'a> {
+    pub ast: &'a Ast,
+    pub table: SymbolTable,
+    pub scopes: Vec<Scope>,
+    pub linked: HashMap<Symbol, EntityId>,
+    pub labels: HashMap<Symbol, EntityId>,
+    pub gotos: Vec<(NodeId, Symbol)>,
+    pub function_typedefs: HashSet<EntityId>,
+    pub undeclared: HashSet<Symbol>,
+    pub node: NodeId,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Warnings {
+    pub enabled: HashSet<WarningFlag>,
+}
+
+#[derive(Debug, Default)]
+pub struct BasicBlock {
+    pub items: Vec<(NodeId, bool)>,
+    pub statement: Option<NodeId>,
+    pub successors: Vec<usize>,
+    pub predecessors: Vec<usize>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Access {
+    pub entity: EntityId,
+    pub node: NodeId,
+    pub kind: AccessKind,
+}
+
+#[derive(Debug)]
+pub struct DataflowState<'a> {
+    pub ast: &'a Ast,
+    pub table: &'a SymbolTable,
+    pub types: &'a TypeTable,
+    pub warnings: &'a Warnings,
+    pub declared: HashMap<(NodeId, Symbol), EntityId>,
+    pub references: HashMap<EntityId, usize>,
+    pub unused_allowed: HashSet<EntityId>,
+    pub blocks: Vec<BasicBlock>,
+    pub current: usize,
+    pub breaks: Vec<usize>,
+    pub continues: Vec<usize>,
+    pub switches: Vec<(usize, bool)>,
+    pub labels: HashMap<Symbol, usize>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct Module {
+    pub globals: Vec<Global>,
+    pub functions: Vec<IrFunction>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Global {
+    pub name: String,
+    pub internal: bool,
+    pub constant: bool,
+    pub external: bool,
+    pub size: u64,
+    pub align: u64,
+    pub data: Vec<u8>,
+    pub relocations: Vec<Relocation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relocation {
+    pub offset: u64,
+    pub symbol: String,
+    pub addend: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct IrFunction {
+    pub name: String,
+    pub internal: bool,
+    pub inline_hint: InlineHint,
+    pub return_type: IrType,
+    pub parameters: Vec<(IrType, Register)>,
+    pub variadic: bool,
+    pub blocks: Vec<Block>,
+    pub next_register: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub id: BlockId,
+    pub instructions: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    pub result: Option<Register>,
+    pub ty: IrType,
+    pub kind: InstructionKind,
+}
+
+pub type BitField = (FieldLayout, u64);
+
+#[derive(Debug, Default)]
+pub struct SwitchTargets {
+    pub cases: Vec<(i64, BlockId)>,
+    pub default: Option<BlockId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InitializerElement {
+    pub offset: u64,
+    pub ctype: CType,
+    pub bit_field: Option<BitField>,
+    pub value: NodeId,
+}
+
+#[derive(Debug)]
+pub struct LoweringState<'a> {
+    pub ast: &'a Ast,
+    pub table: &'a SymbolTable,
+    pub types: &'a TypeTable,
+    pub target: &'a Target,
+    pub declared: HashMap<(NodeId, Symbol), EntityId>,
+    pub enumerators: HashMap<Symbol, i128>,
+    pub type_names: HashMap<NodeId, CType>,
+    pub module: Module,
+    pub externals: HashMap<String, CType>,
+    pub strings: HashMap<NodeId, String>,
+    pub statics: usize,
+    pub function: Option<IrFunction>,
+    pub current: BlockId,
+    pub locals: HashMap<EntityId, Operand>,
+    pub variable_sizes: HashMap<EntityId, Operand>,
+    pub return_type: CType,
+    pub return_slot: Option<Operand>,
+    pub breaks: Vec<BlockId>,
+    pub continues: Vec<BlockId>,
+    pub switches: Vec<SwitchTargets>,
+    pub labels: HashMap<Symbol, BlockId>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug)]
+pub struct IrParserState {
+    pub tokens: Vec<(IrToken, u16)>,
+    pub iterator: usize,
+    pub module: Module,
+    pub registers: HashMap<String, (Register, u16)>,
+    pub blocks: HashMap<String, (BlockId, u16)>,
+    pub source_map: SourceMap,
+    pub errors: Vec<ParseError>,
+}
+
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    pub lines: Vec<String>,
+    pub functions: HashMap<String, FunctionSource>,
+}
+
+#[derive(Debug, Default)]
+pub struct FunctionSource {
+    pub blocks: HashMap<BlockId, String>,
+    pub registers: HashMap<Register, String>,
+    pub instructions: HashMap<(BlockId, usize), u16>,
+}
+
+#[derive(Debug)]
+pub struct VerifierState<'a> {
+    pub symbols: HashMap<&'a str, Option<&'a IrFunction>>,
+    pub function: Option<&'a IrFunction>,
+    pub source_map: Option<&'a SourceMap>,
+    pub source: Option<&'a FunctionSource>,
+    pub register_types: HashMap<Register, IrType>,
+    pub definitions: HashMap<Register, (BlockId, usize)>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PassOptions {
+    pub level: u8,
+    pub passes: Option<Vec<PassName>>,
+    pub print_after: Vec<PassName>,
+    pub time_passes: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Loop {
+    pub header: BlockId,
+    pub blocks: Vec<BlockId>,
+    pub latches: Vec<BlockId>,
+    pub parent: Option<usize>,
+    pub depth: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Liveness {
+    pub live_in: Vec<HashSet<Register>>,
+    pub live_out: Vec<HashSet<Register>>,
+}
+
+#[derive(Debug, Default)]
+pub struct Analyses {
+    pub dominators: Option<Vec<Option<BlockId>>>,
+    pub loops: Option<Vec<Loop>>,
+    pub liveness: Option<Liveness>,
+}
+
+#[derive(Debug, Default)]
+pub struct SccpState {
+    pub values: HashMap<Register, Lattice>,
+    pub executable: HashSet<BlockId>,
+    pub edges: HashSet<(BlockId, BlockId)>,
+    pub flow_worklist: Vec<(BlockId, BlockId)>,
+    pub value_worklist: Vec<Register>,
+    pub uses: HashMap<Register, Vec<(BlockId, usize)>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub base: MemoryBase,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AvailableValue {
+    pub address: Operand,
+    pub location: Location,
+    pub ty: IrType,
+    pub value: Operand,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ValueTable {
+    pub expressions: HashMap<(IrType, InstructionKind), Operand>,
+    pub memory: Vec<AvailableValue>,
+}
+
+#[derive(Debug, Default)]
+pub struct GvnState {
+    pub locations: HashMap<Register, Location>,
+    pub escaped: HashSet<Register>,
+    pub replacements: HashMap<Register, Operand>,
+}
+
+#[derive(Debug, Default)]
+pub struct CallGraph {
+    pub indices: HashMap<String, usize>,
+    pub callees: Vec<Vec<usize>>,
+    pub call_sites: Vec<usize>,
+    pub address_taken: HashSet<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct InductionState {
+    pub preheader: BlockId,
+    pub latch: BlockId,
+    pub inside: HashSet<BlockId>,
+    pub definitions: HashMap<Register, Instruction>,
+    pub recurrences: HashMap<Register, Option<Vec<Operand>>>,
+    pub pending: Vec<Instruction>,
+    pub next_register: u32,
+}
+
+#[derive(Debug)]
+pub struct Allocation {
+    pub data: Vec<u8>,
+    pub kind: AllocationKind,
+    pub live: bool,
+    pub writable: bool,
+}
+
+#[derive(Debug)]
+pub struct Frame<'a> {
+    pub function: &'a IrFunction,
+    pub block: BlockId,
+    pub position: usize,
+    pub registers: Vec<u64>,
+    pub allocas: Vec<usize>,
+}
+
+pub struct InterpreterState<'a> {
+    pub functions: HashMap<&'a str, &'a IrFunction>,
+    pub symbols: HashMap<&'a str, u64>,
+    pub allocations: Vec<Allocation>,
+    pub released: Vec<usize>,
+    pub frames: Vec<Frame<'a>>,
+    pub input: Vec<u8>,
+    pub input_position: usize,
+    pub input_closed: bool,
+    pub output: Vec<u8>,
+    pub stdout: &'a mut dyn Write,
+    pub exit_code: Option<i32>,
+}
@@ -7,44 +7,863 @@ pub enum TokenType {
 }
 
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub u32);
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(pub u32);
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EntityId(pub u32);
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Register(pub u32);
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BlockId(pub u32);
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: u16,
+    pub end: u16,
+}
+
+
 #[derive(Debug, Clone)]
-pub enum AstNode {
+pub struct Node {
+    pub kind: NodeKind,
+    pub span: Span,
+}
+
+
+#[derive(Debug, Clone)]
+pub enum NodeKind {
+    TranslationUnit(Vec<NodeId>),
+
+    FunctionDefinition {
+        attributes: Vec<Attribute>,
+        specifiers: DeclSpecifiers,
+        declarator: Declarator,
+        body: NodeId,
+    },
+
+    Declaration {
+        attributes: Vec<Attribute>,
+        specifiers: DeclSpecifiers,
+        declarators: Vec<InitDeclarator>,
+    },
+
+    StaticAssert {
+        condition: NodeId,
+        message: Option<Symbol>,
+    },
+
+    Attributed {
+        attributes: Vec<Attribute>,
+        statement: NodeId,
+    },
+
+    Compound(Vec<NodeId>),
+    ExpressionStatement(Option<NodeId>),
+
+    If {
+        condition: NodeId,
+        then_branch: NodeId,
+        else_branch: Option<NodeId>,
+    },
+
+    While {
+        condition: NodeId,
+        body: NodeId,
+    },
+
+    DoWhile {
+        body: NodeId,
+        condition: NodeId,
+    },
+
+    For {
+        init: Option<NodeId>,
+        condition: Option<NodeId>,
+        step: Option<NodeId>,
+        body: NodeId,
+    },
+
+    Switch {
+        condition: NodeId,
+        body: NodeId,
+    },
+
+    Case {
+        value: NodeId,
+        body: NodeId,
+    },
+
+    Default(NodeId),
+
+    Label {
+        name: Symbol,
+        body: NodeId,
+    },
+
+    Goto(Symbol),
+    Break,
+    Continue,
+    Return(Option<NodeId>),
+
     BinaryOperation {
-        left: Box<AstNode>,
-        operator: String,
-        right: Box<AstNode>,
+        left: NodeId,
+        operator: BinaryOperator,
+        right: NodeId,
+    },
+
+    Assignment {
+        left: NodeId,
+        operator: AssignmentOperator,
+        right: NodeId,
+    },
+
+    UnaryOperation {
+        operator: UnaryOperator,
+        operand: NodeId,
+    },
+
+    PostfixOperation {
+        operator: PostfixOperator,
+        operand: NodeId,
+    },
+
+    Conditional {
+        condition: NodeId,
+        then_value: NodeId,
+        else_value: NodeId,
+    },
+
+    Call {
+        function: NodeId,
+        arguments: Vec<NodeId>,
+    },
+
+    Index {
+        array: NodeId,
+        index: NodeId,
     },
 
-    Value(String),
+    Member {
+        object: NodeId,
+        member: Symbol,
+        arrow: bool,
+    },
+
+    Cast {
+        type_name: TypeName,
+        operand: NodeId,
+    },
+
+    CompoundLiteral {
+        type_name: TypeName,
+        initializer: NodeId,
+    },
+
+    GenericSelection {
+        controlling: NodeId,
+        associations: Vec<GenericAssociation>,
+    },
+
+    ImplicitCast {
+        kind: CastKind,
+        operand: NodeId,
+    },
+
+    InitializerList(Vec<Initializer>),
+    SizeofType(TypeName),
+    AlignofType(TypeName),
+    Identifier(Symbol),
+    StringLiteral(Symbol),
+    Constant(Constant),
+    Error,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Constant {
+    Integer {
+        value: u64,
+        suffix: IntegerSuffix,
+    },
+
+    Floating {
+        value: f64,
+        suffix: FloatSuffix,
+    },
+
+    Character(char),
+    Bool(bool),
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerSuffix {
+    None,
+    Unsigned,
+    Long,
+    UnsignedLong,
+    LongLong,
+    UnsignedLongLong,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatSuffix {
+    None,
+    Float,
+    Long,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOperator {
+    Comma,
+    LogicalOr,
+    LogicalAnd,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    ShiftLeft,
+    ShiftRight,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssignmentOperator {
+    Assign,
+    Compound(BinaryOperator),
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstantValue {
+    Integer(i128, CType),
+    Floating(f64, CType),
+    Address { base: NodeId, offset: i64 },
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvaluationError {
+    NotConstant,
+    DivisionByZero,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastKind {
+    LValueToRValue,
+    ArrayToPointer,
+    FunctionToPointer,
+    IntegralCast,
+    IntegralToFloating,
+    FloatingToIntegral,
+    FloatingCast,
+    ToBoolean,
+    NullToPointer,
+    IntegralToPointer,
+    PointerToIntegral,
+    PointerCast,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueCategory {
+    LValue,
+    RValue,
+    Function,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryOperator {
+    Address,
+    Dereference,
+    Plus,
+    Minus,
+    BitNot,
+    Not,
+    PreIncrement,
+    PreDecrement,
+    Sizeof,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PostfixOperator {
+    Increment,
+    Decrement,
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct DeclSpecifiers {
+    pub storage: Vec<Keyword>,
+    pub qualifiers: Vec<Keyword>,
+    pub function: Vec<Keyword>,
+    pub alignment: Vec<TypeofArgument>,
+    pub specifiers: Vec<TypeSpecifier>,
+}
+
+
+#[derive(Debug, Clone)]
+pub enum TypeSpecifier {
+    Basic(Keyword),
+    TypedefName(Symbol),
+
+    Record {
+        kind: Keyword,
+        tag: Option<Symbol>,
+        members: Option<Vec<NodeId>>,
+    },
+
+    Enum {
+        tag: Option<Symbol>,
+        enumerators: Option<Vec<Enumerator>>,
+    },
+
+    Typeof {
+        unqualified: bool,
+        argument: TypeofArgument,
+    },
+}
+
+
+#[derive(Debug, Clone)]
+pub enum TypeofArgument {
+    Expression(NodeId),
+    Type(TypeName),
+}
+
+
+#[derive(Debug, Clone)]
+pub struct GenericAssociation {
+    pub type_name: Option<TypeName>,
+    pub value: NodeId,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Enumerator {
+    pub name: Symbol,
+    pub value: Option<NodeId>,
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct Declarator {
+    pub name: Option<Symbol>,
+    pub derived: Vec<DerivedDeclarator>,
+}
+
+
+#[derive(Debug, Clone)]
+pub enum DerivedDeclarator {
+    Pointer(Vec<Keyword>),
+    Array(Option<NodeId>),
+
+    Function {
+        parameters: Vec<Parameter>,
+        variadic: bool,
+    },
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    pub attributes: Vec<Attribute>,
+    pub specifiers: DeclSpecifiers,
+    pub declarator: Declarator,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct InitDeclarator {
+    pub attributes: Vec<Attribute>,
+    pub declarator: Declarator,
+    pub initializer: Option<NodeId>,
+    pub bit_width: Option<NodeId>,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Initializer {
+    pub designators: Vec<Designator>,
+    pub value: NodeId,
+}
+
+
+#[derive(Debug, Clone)]
+pub enum Designator {
+    Index(NodeId),
+    Member(Symbol),
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    pub prefix: Option<String>,
+    pub name: String,
+    pub arguments: Vec<String>,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct TypeName {
+    pub specifiers: DeclSpecifiers,
+    pub declarator: Declarator,
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CType {
+    Void,
+    Bool,
+    Char,
+    SignedChar,
+    UnsignedChar,
+    Short,
+    UnsignedShort,
+    Int,
+    UnsignedInt,
+    Long,
+    UnsignedLong,
+    LongLong,
+    UnsignedLongLong,
+    Float,
+    Double,
+    LongDouble,
+    Pointer(Box<CType>),
+    Array(Box<CType>, ArraySize),
+
+    Function {
+        result: Box<CType>,
+        parameters: Vec<CType>,
+        variadic: bool,
+        prototyped: bool,
+    },
+
+    Record {
+        kind: Keyword,
+        tag: String,
+    },
+
+    Enum(String),
+    Qualified(Qualifiers, Box<CType>),
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArraySize {
+    Fixed(u64),
+    Incomplete,
+    Variable,
+}
+
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Qualifiers {
+    pub is_const: bool,
+    pub is_volatile: bool,
+    pub is_restrict: bool,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EntityKind {
+    Object,
+    Function,
+    Parameter,
+    Typedef,
+    EnumConstant,
+    Tag(Keyword),
+    Label,
+    Member,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScopeKind {
+    File,
+    Function,
+    Block,
+    Prototype,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Linkage {
+    External,
+    Internal,
+    None,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StorageDuration {
+    Static,
+    Automatic,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Definition {
+    Declared,
+    Tentative,
+    Defined,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum WarningFlag {
+    Uninitialized,
+    MaybeUninitialized,
+    UnusedVariable,
+    UnusedButSetVariable,
+    UnusedParameter,
+    UnusedFunction,
+    DeadStore,
+    UnreachableCode,
+    Parentheses,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AccessKind {
+    Read,
+    Store { discarded: bool },
+    Update { discarded: bool },
+    Initialize { constant: bool },
+    Declare,
+    AddressTaken,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FormatKind {
+    Printf,
+    Scanf,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IrType {
+    Void,
+    I1,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    Ptr,
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Register(Register),
+    Integer(i64),
+    Floating(f64),
+    Global(String),
+    Null,
+    Undef,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ArithmeticOperator {
+    Add,
+    Sub,
+    Mul,
+    SDiv,
+    UDiv,
+    SRem,
+    URem,
+    And,
+    Or,
+    Xor,
+    Shl,
+    LShr,
+    AShr,
+    FAdd,
+    FSub,
+    FMul,
+    FDiv,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ComparePredicate {
+    Eq,
+    Ne,
+    Slt,
+    Sle,
+    Sgt,
+    Sge,
+    Ult,
+    Ule,
+    Ugt,
+    Uge,
+    FEq,
+    FNe,
+    FLt,
+    FLe,
+    FGt,
+    FGe,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ConversionOperator {
+    Trunc,
+    ZExt,
+    SExt,
+    FpTrunc,
+    FpExt,
+    FpToSi,
+    FpToUi,
+    SiToFp,
+    UiToFp,
+    PtrToInt,
+    IntToPtr,
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InstructionKind {
+    Binary {
+        operator: ArithmeticOperator,
+        left: Operand,
+        right: Operand,
+        no_signed_wrap: bool,
+    },
+
+    Compare {
+        predicate: ComparePredicate,
+        left: Operand,
+        right: Operand,
+    },
+
+    Convert {
+        operator: ConversionOperator,
+        value: Operand,
+        from: IrType,
+    },
+
+    Alloca {
+        size: Operand,
+        align: u64,
+    },
+
+    Load {
+        address: Operand,
+        volatile: bool,
+    },
+
+    Store {
+        value: Operand,
+        address: Operand,
+        volatile: bool,
+    },
+
+    PtrAdd {
+        base: Operand,
+        offset: Operand,
+    },
+
+    Copy {
+        destination: Operand,
+        source: Operand,
+        size: u64,
+    },
+
+    Zero {
+        address: Operand,
+        size: u64,
+    },
+
+    Call {
+        callee: Operand,
+        arguments: Vec<(IrType, Operand)>,
+    },
+
+    Phi {
+        incoming: Vec<(Operand, BlockId)>,
+    },
+
+    Jump(BlockId),
+
+    Branch {
+        condition: Operand,
+        then_block: BlockId,
+        else_block: BlockId,
+    },
+
+    Switch {
+        value: Operand,
+        default: BlockId,
+        cases: Vec<(i64, BlockId)>,
+    },
+
+    Return(Option<Operand>),
+    Unreachable,
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AllocationKind {
+    Global,
+    Stack,
+    Heap,
+    Function(String),
+    Stream(i32),
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PassName {
+    Mem2Reg,
+    Sccp,
+    Gvn,
+    Licm,
+    IndVars,
+    LoopUnroll,
+    Inline,
+    Adce,
+    SimplifyCfg,
+    OutOfSsa,
+}
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum InlineHint {
+    #[default]
+    None,
+    Hint,
+    Always,
+    Never,
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MemoryBase {
+    Slot(Register),
+    Global(String),
+    Unknown(Operand),
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lattice {
+    Top,
+    Constant(Operand),
+    Bottom,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Analysis {
+    Dominators,
+    Loops,
+    Liveness,
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IrToken {
+    Word(String),
+    Register(String),
+    Global(String),
+    String(Vec<u8>),
+    Punctuation(char),
+    Ellipsis,
+    Newline,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DumpFormat {
+    Tree,
+    Json,
+    Dot,
 }
 
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Keyword {
+    Alignas,
+    Alignof,
+    Auto,
     Bool,
     Break,
     Case,
     Char,
+    Const,
     Continue,
     Default,
     Do,
     Double,
     Else,
     Enum,
+    Extern,
     False,
     Float,
     For,
+    Generic,
+    Goto,
     If,
+    Inline,
     Int,
     Long,
+    Register,
+    Restrict,
     Return,
     Short,
     Signed,
+    Sizeof,
+    Static,
+    StaticAssert,
     Struct,
     Switch,
     True,
+    Typedef,
+    Typeof,
+    TypeofUnqual,
+    Union,
     Unsigned,
     Void,
+    Volatile,
     While,
 }
 
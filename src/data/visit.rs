@@ -0,0 +1,1138 @@
+use crate::data::{ Ast, NodeId, NodeKind, Symbol };
+use crate::data::types::{
+    AssignmentOperator, Attribute, BinaryOperator, CastKind, Constant, DeclSpecifiers, Declarator, DerivedDeclarator, Designator, Enumerator,
+    GenericAssociation, InitDeclarator, Initializer, Parameter, PostfixOperator, TypeName, TypeSpecifier, TypeofArgument, UnaryOperator,
+};
+
+
+pub trait Visitor<'a>: Sized {
+    fn visit_node(&mut self, ast: &'a Ast, id: NodeId) {
+        walk_node(self, ast, id);
+    }
+
+
+    fn visit_translation_unit(&mut self, ast: &'a Ast, _id: NodeId, items: &'a [NodeId]) {
+        walk_translation_unit(self, ast, items);
+    }
+
+
+    fn visit_function_definition(&mut self, ast: &'a Ast, _id: NodeId, _attributes: &'a [Attribute], specifiers: &'a DeclSpecifiers, declarator: &'a Declarator, body: NodeId) {
+        walk_function_definition(self, ast, specifiers, declarator, body);
+    }
+
+
+    fn visit_declaration(&mut self, ast: &'a Ast, _id: NodeId, _attributes: &'a [Attribute], specifiers: &'a DeclSpecifiers, declarators: &'a [InitDeclarator]) {
+        walk_declaration(self, ast, specifiers, declarators);
+    }
+
+
+    fn visit_static_assert(&mut self, ast: &'a Ast, _id: NodeId, condition: NodeId, _message: Option<Symbol>) {
+        walk_static_assert(self, ast, condition);
+    }
+
+
+    fn visit_attributed(&mut self, ast: &'a Ast, _id: NodeId, _attributes: &'a [Attribute], statement: NodeId) {
+        walk_attributed(self, ast, statement);
+    }
+
+
+    fn visit_compound(&mut self, ast: &'a Ast, _id: NodeId, items: &'a [NodeId]) {
+        walk_compound(self, ast, items);
+    }
+
+
+    fn visit_expression_statement(&mut self, ast: &'a Ast, _id: NodeId, expression: Option<NodeId>) {
+        walk_expression_statement(self, ast, expression);
+    }
+
+
+    fn visit_if(&mut self, ast: &'a Ast, _id: NodeId, condition: NodeId, then_branch: NodeId, else_branch: Option<NodeId>) {
+        walk_if(self, ast, condition, then_branch, else_branch);
+    }
+
+
+    fn visit_while(&mut self, ast: &'a Ast, _id: NodeId, condition: NodeId, body: NodeId) {
+        walk_while(self, ast, condition, body);
+    }
+
+
+    fn visit_do_while(&mut self, ast: &'a Ast, _id: NodeId, body: NodeId, condition: NodeId) {
+        walk_do_while(self, ast, body, condition);
+    }
+
+
+    fn visit_for(&mut self, ast: &'a Ast, _id: NodeId, init: Option<NodeId>, condition: Option<NodeId>, step: Option<NodeId>, body: NodeId) {
+        walk_for(self, ast, init, condition, step, body);
+    }
+
+
+    fn visit_switch(&mut self, ast: &'a Ast, _id: NodeId, condition: NodeId, body: NodeId) {
+        walk_switch(self, ast, condition, body);
+    }
+
+
+    fn visit_case(&mut self, ast: &'a Ast, _id: NodeId, value: NodeId, body: NodeId) {
+        walk_case(self, ast, value, body);
+    }
+
+
+    fn visit_default(&mut self, ast: &'a Ast, _id: NodeId, body: NodeId) {
+        walk_default(self, ast, body);
+    }
+
+
+    fn visit_label(&mut self, ast: &'a Ast, _id: NodeId, _name: Symbol, body: NodeId) {
+        walk_label(self, ast, body);
+    }
+
+
+    fn visit_goto(&mut self, ast: &'a Ast, _id: NodeId, _name: Symbol) {
+        walk_goto(self, ast);
+    }
+
+
+    fn visit_break(&mut self, ast: &'a Ast, _id: NodeId) {
+        walk_break(self, ast);
+    }
+
+
+    fn visit_continue(&mut self, ast: &'a Ast, _id: NodeId) {
+        walk_continue(self, ast);
+    }
+
+
+    fn visit_return(&mut self, ast: &'a Ast, _id: NodeId, value: Option<NodeId>) {
+        walk_return(self, ast, value);
+    }
+
+
+    fn visit_binary_operation(&mut self, ast: &'a Ast, _id: NodeId, left: NodeId, _operator: BinaryOperator, right: NodeId) {
+        walk_binary_operation(self, ast, left, right);
+    }
+
+
+    fn visit_assignment(&mut self, ast: &'a Ast, _id: NodeId, left: NodeId, _operator: AssignmentOperator, right: NodeId) {
+        walk_assignment(self, ast, left, right);
+    }
+
+
+    fn visit_unary_operation(&mut self, ast: &'a Ast, _id: NodeId, _operator: UnaryOperator, operand: NodeId) {
+        walk_unary_operation(self, ast, operand);
+    }
+
+
+    fn visit_postfix_operation(&mut self, ast: &'a Ast, _id: NodeId, _operator: PostfixOperator, operand: NodeId) {
+        walk_postfix_operation(self, ast, operand);
+    }
+
+
+    fn visit_conditional(&mut self, ast: &'a Ast, _id: NodeId, condition: NodeId, then_value: NodeId, else_value: NodeId) {
+        walk_conditional(self, ast, condition, then_value, else_value);
+    }
+
+
+    fn visit_call(&mut self, ast: &'a Ast, _id: NodeId, function: NodeId, arguments: &'a [NodeId]) {
+        walk_call(self, ast, function, arguments);
+    }
+
+
+    fn visit_index(&mut self, ast: &'a Ast, _id: NodeId, array: NodeId, index: NodeId) {
+        walk_index(self, ast, array, index);
+    }
+
+
+    fn visit_member(&mut self, ast: &'a Ast, _id: NodeId, object: NodeId, _member: Symbol, _arrow: bool) {
+        walk_member(self, ast, object);
+    }
+
+
+    fn visit_cast(&mut self, ast: &'a Ast, _id: NodeId, type_name: &'a TypeName, operand: NodeId) {
+        walk_cast(self, ast, type_name, operand);
+    }
+
+
+    fn visit_compound_literal(&mut self, ast: &'a Ast, _id: NodeId, type_name: &'a TypeName, initializer: NodeId) {
+        walk_compound_literal(self, ast, type_name, initializer);
+    }
+
+
+    fn visit_generic_selection(&mut self, ast: &'a Ast, _id: NodeId, controlling: NodeId, associations: &'a [GenericAssociation]) {
+        walk_generic_selection(self, ast, controlling, associations);
+    }
+
+
+    fn visit_implicit_cast(&mut self, ast: &'a Ast, _id: NodeId, _kind: CastKind, operand: NodeId) {
+        walk_implicit_cast(self, ast, operand);
+    }
+
+
+    fn visit_initializer_list(&mut self, ast: &'a Ast, _id: NodeId, initializers: &'a [Initializer]) {
+        walk_initializer_list(self, ast, initializers);
+    }
+
+
+    fn visit_sizeof_type(&mut self, ast: &'a Ast, _id: NodeId, type_name: &'a TypeName) {
+        walk_sizeof_type(self, ast, type_name);
+    }
+
+
+    fn visit_alignof_type(&mut self, ast: &'a Ast, _id: NodeId, type_name: &'a TypeName) {
+        walk_alignof_type(self, ast, type_name);
+    }
+
+
+    fn visit_identifier(&mut self, ast: &'a Ast, _id: NodeId, _name: Symbol) {
+        walk_identifier(self, ast);
+    }
+
+
+    fn visit_string_literal(&mut self, ast: &'a Ast, _id: NodeId, _value: Symbol) {
+        walk_string_literal(self, ast);
+    }
+
+
+    fn visit_constant(&mut self, ast: &'a Ast, _id: NodeId, _constant: Constant) {
+        walk_constant(self, ast);
+    }
+
+
+    fn visit_decl_specifiers(&mut self, ast: &'a Ast, specifiers: &'a DeclSpecifiers) {
+        walk_decl_specifiers(self, ast, specifiers);
+    }
+
+
+    fn visit_type_specifier(&mut self, ast: &'a Ast, specifier: &'a TypeSpecifier) {
+        walk_type_specifier(self, ast, specifier);
+    }
+
+
+    fn visit_enumerator(&mut self, ast: &'a Ast, enumerator: &'a Enumerator) {
+        walk_optional(self, ast, enumerator.value);
+    }
+
+
+    fn visit_declarator(&mut self, ast: &'a Ast, declarator: &'a Declarator) {
+        walk_declarator(self, ast, declarator);
+    }
+
+
+    fn visit_parameter(&mut self, ast: &'a Ast, parameter: &'a Parameter) {
+        self.visit_decl_specifiers(ast, &parameter.specifiers);
+        self.visit_declarator(ast, &parameter.declarator);
+    }
+
+
+    fn visit_init_declarator(&mut self, ast: &'a Ast, init_declarator: &'a InitDeclarator) {
+        walk_init_declarator(self, ast, init_declarator);
+    }
+
+
+    fn visit_initializer(&mut self, ast: &'a Ast, initializer: &'a Initializer) {
+        walk_initializer(self, ast, initializer);
+    }
+
+
+    fn visit_type_name(&mut self, ast: &'a Ast, type_name: &'a TypeName) {
+        self.visit_decl_specifiers(ast, &type_name.specifiers);
+        self.visit_declarator(ast, &type_name.declarator);
+    }
+
+
+    fn visit_generic_association(&mut self, ast: &'a Ast, association: &'a GenericAssociation) {
+        if let Some(type_name) = &association.type_name {
+            self.visit_type_name(ast, type_name);
+        }
+
+        self.visit_node(ast, association.value);
+    }
+}
+
+
+pub fn walk_node<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, id: NodeId) {
+    match ast.kind(id) {
+        NodeKind::TranslationUnit(items) => visitor.visit_translation_unit(ast, id, items),
+        NodeKind::FunctionDefinition { attributes, specifiers, declarator, body } => visitor.visit_function_definition(ast, id, attributes, specifiers, declarator, *body),
+        NodeKind::Declaration { attributes, specifiers, declarators } => visitor.visit_declaration(ast, id, attributes, specifiers, declarators),
+        NodeKind::StaticAssert { condition, message } => visitor.visit_static_assert(ast, id, *condition, *message),
+        NodeKind::Attributed { attributes, statement } => visitor.visit_attributed(ast, id, attributes, *statement),
+        NodeKind::Compound(items) => visitor.visit_compound(ast, id, items),
+        NodeKind::ExpressionStatement(expression) => visitor.visit_expression_statement(ast, id, *expression),
+        NodeKind::If { condition, then_branch, else_branch } => visitor.visit_if(ast, id, *condition, *then_branch, *else_branch),
+        NodeKind::While { condition, body } => visitor.visit_while(ast, id, *condition, *body),
+        NodeKind::DoWhile { body, condition } => visitor.visit_do_while(ast, id, *body, *condition),
+        NodeKind::For { init, condition, step, body } => visitor.visit_for(ast, id, *init, *condition, *step, *body),
+        NodeKind::Switch { condition, body } => visitor.visit_switch(ast, id, *condition, *body),
+        NodeKind::Case { value, body } => visitor.visit_case(ast, id, *value, *body),
+        NodeKind::Default(body) => visitor.visit_default(ast, id, *body),
+        NodeKind::Label { name, body } => visitor.visit_label(ast, id, *name, *body),
+        NodeKind::Goto(name) => visitor.visit_goto(ast, id, *name),
+        NodeKind::Break => visitor.visit_break(ast, id),
+        NodeKind::Continue => visitor.visit_continue(ast, id),
+        NodeKind::Return(value) => visitor.visit_return(ast, id, *value),
+        NodeKind::BinaryOperation { left, operator, right } => visitor.visit_binary_operation(ast, id, *left, *operator, *right),
+        NodeKind::Assignment { left, operator, right } => visitor.visit_assignment(ast, id, *left, *operator, *right),
+        NodeKind::UnaryOperation { operator, operand } => visitor.visit_unary_operation(ast, id, *operator, *operand),
+        NodeKind::PostfixOperation { operator, operand } => visitor.visit_postfix_operation(ast, id, *operator, *operand),
+        NodeKind::Conditional { condition, then_value, else_value } => visitor.visit_conditional(ast, id, *condition, *then_value, *else_value),
+        NodeKind::Call { function, arguments } => visitor.visit_call(ast, id, *function, arguments),
+        NodeKind::Index { array, index } => visitor.visit_index(ast, id, *array, *index),
+        NodeKind::Member { object, member, arrow } => visitor.visit_member(ast, id, *object, *member, *arrow),
+        NodeKind::Cast { type_name, operand } => visitor.visit_cast(ast, id, type_name, *operand),
+        NodeKind::CompoundLiteral { type_name, initializer } => visitor.visit_compound_literal(ast, id, type_name, *initializer),
+        NodeKind::GenericSelection { controlling, associations } => visitor.visit_generic_selection(ast, id, *controlling, associations),
+        NodeKind::ImplicitCast { kind, operand } => visitor.visit_implicit_cast(ast, id, *kind, *operand),
+        NodeKind::InitializerList(initializers) => visitor.visit_initializer_list(ast, id, initializers),
+        NodeKind::SizeofType(type_name) => visitor.visit_sizeof_type(ast, id, type_name),
+        NodeKind::AlignofType(type_name) => visitor.visit_alignof_type(ast, id, type_name),
+        NodeKind::Identifier(name) => visitor.visit_identifier(ast, id, *name),
+        NodeKind::StringLiteral(value) => visitor.visit_string_literal(ast, id, *value),
+        NodeKind::Constant(constant) => visitor.visit_constant(ast, id, *constant),
+        NodeKind::Error => {}
+    }
+}
+
+
+pub fn walk_items<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, items: &'a [NodeId]) {
+    for &item in items {
+        visitor.visit_node(ast, item);
+    }
+}
+
+
+pub fn walk_optional<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, id: Option<NodeId>) {
+    if let Some(id) = id {
+        visitor.visit_node(ast, id);
+    }
+}
+
+
+pub fn walk_translation_unit<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, items: &'a [NodeId]) {
+    walk_items(visitor, ast, items);
+}
+
+
+pub fn walk_function_definition<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, specifiers: &'a DeclSpecifiers, declarator: &'a Declarator, body: NodeId) {
+    visitor.visit_decl_specifiers(ast, specifiers);
+    visitor.visit_declarator(ast, declarator);
+    visitor.visit_node(ast, body);
+}
+
+
+pub fn walk_declaration<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, specifiers: &'a DeclSpecifiers, declarators: &'a [InitDeclarator]) {
+    visitor.visit_decl_specifiers(ast, specifiers);
+
+    for init_declarator in declarators {
+        visitor.visit_init_declarator(ast, init_declarator);
+    }
+}
+
+
+pub fn walk_static_assert<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, condition: NodeId) {
+    visitor.visit_node(ast, condition);
+}
+
+
+pub fn walk_attributed<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, statement: NodeId) {
+    visitor.visit_node(ast, statement);
+}
+
+
+pub fn walk_compound<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, items: &'a [NodeId]) {
+    walk_items(visitor, ast, items);
+}
+
+
+pub fn walk_expression_statement<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, expression: Option<NodeId>) {
+    walk_optional(visitor, ast, expression);
+}
+
+
+pub fn walk_if<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, condition: NodeId, then_branch: NodeId, else_branch: Option<NodeId>) {
+    visitor.visit_node(ast, condition);
+    visitor.visit_node(ast, then_branch);
+    walk_optional(visitor, ast, else_branch);
+}
+
+
+pub fn walk_while<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, condition: NodeId, body: NodeId) {
+    visitor.visit_node(ast, condition);
+    visitor.visit_node(ast, body);
+}
+
+
+pub fn walk_do_while<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, body: NodeId, condition: NodeId) {
+    visitor.visit_node(ast, body);
+    visitor.visit_node(ast, condition);
+}
+
+
+pub fn walk_for<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, init: Option<NodeId>, condition: Option<NodeId>, step: Option<NodeId>, body: NodeId) {
+    for child in [init, condition, step].into_iter().flatten() {
+        visitor.visit_node(ast, child);
+    }
+
+    visitor.visit_node(ast, body);
+}
+
+
+pub fn walk_switch<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, condition: NodeId, body: NodeId) {
+    visitor.visit_node(ast, condition);
+    visitor.visit_node(ast, body);
+}
+
+
+pub fn walk_case<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, value: NodeId, body: NodeId) {
+    visitor.visit_node(ast, value);
+    visitor.visit_node(ast, body);
+}
+
+
+pub fn walk_default<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, body: NodeId) {
+    visitor.visit_node(ast, body);
+}
+
+
+pub fn walk_label<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, body: NodeId) {
+    visitor.visit_node(ast, body);
+}
+
+
+pub fn walk_goto<'a, V: Visitor<'a>>(_visitor: &mut V, _ast: &'a Ast) {}
+
+
+pub fn walk_break<'a, V: Visitor<'a>>(_visitor: &mut V, _ast: &'a Ast) {}
+
+
+pub fn walk_continue<'a, V: Visitor<'a>>(_visitor: &mut V, _ast: &'a Ast) {}
+
+
+pub fn walk_return<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, value: Option<NodeId>) {
+    walk_optional(visitor, ast, value);
+}
+
+
+pub fn walk_binary_operation<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, left: NodeId, right: NodeId) {
+    visitor.visit_node(ast, left);
+    visitor.visit_node(ast, right);
+}
+
+
+pub fn walk_assignment<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, left: NodeId, right: NodeId) {
+    visitor.visit_node(ast, left);
+    visitor.visit_node(ast, right);
+}
+
+
+pub fn walk_unary_operation<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, operand: NodeId) {
+    visitor.visit_node(ast, operand);
+}
+
+
+pub fn walk_postfix_operation<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, operand: NodeId) {
+    visitor.visit_node(ast, operand);
+}
+
+
+pub fn walk_conditional<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, condition: NodeId, then_value: NodeId, else_value: NodeId) {
+    visitor.visit_node(ast, condition);
+    visitor.visit_node(ast, then_value);
+    visitor.visit_node(ast, else_value);
+}
+
+
+pub fn walk_call<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, function: NodeId, arguments: &'a [NodeId]) {
+    visitor.visit_node(ast, function);
+    walk_items(visitor, ast, arguments);
+}
+
+
+pub fn walk_index<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, array: NodeId, index: NodeId) {
+    visitor.visit_node(ast, array);
+    visitor.visit_node(ast, index);
+}
+
+
+pub fn walk_member<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, object: NodeId) {
+    visitor.visit_node(ast, object);
+}
+
+
+pub fn walk_cast<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, type_name: &'a TypeName, operand: NodeId) {
+    visitor.visit_type_name(ast, type_name);
+    visitor.visit_node(ast, operand);
+}
+
+
+pub fn walk_compound_literal<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, type_name: &'a TypeName, initializer: NodeId) {
+    visitor.visit_type_name(ast, type_name);
+    visitor.visit_node(ast, initializer);
+}
+
+
+pub fn walk_generic_selection<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, controlling: NodeId, associations: &'a [GenericAssociation]) {
+    visitor.visit_node(ast, controlling);
+
+    for association in associations {
+        visitor.visit_generic_association(ast, association);
+    }
+}
+
+
+pub fn walk_implicit_cast<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, operand: NodeId) {
+    visitor.visit_node(ast, operand);
+}
+
+
+pub fn walk_initializer_list<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, initializers: &'a [Initializer]) {
+    for initializer in initializers {
+        visitor.visit_initializer(ast, initializer);
+    }
+}
+
+
+pub fn walk_sizeof_type<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, type_name: &'a TypeName) {
+    visitor.visit_type_name(ast, type_name);
+}
+
+
+pub fn walk_alignof_type<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, type_name: &'a TypeName) {
+    visitor.visit_type_name(ast, type_name);
+}
+
+
+pub fn walk_identifier<'a, V: Visitor<'a>>(_visitor: &mut V, _ast: &'a Ast) {}
+
+
+pub fn walk_string_literal<'a, V: Visitor<'a>>(_visitor: &mut V, _ast: &'a Ast) {}
+
+
+pub fn walk_constant<'a, V: Visitor<'a>>(_visitor: &mut V, _ast: &'a Ast) {}
+
+
+pub fn walk_decl_specifiers<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, specifiers: &'a DeclSpecifiers) {
+    for alignment in &specifiers.alignment {
+        match alignment {
+            TypeofArgument::Expression(expression) => visitor.visit_node(ast, *expression),
+            TypeofArgument::Type(type_name) => visitor.visit_type_name(ast, type_name),
+        }
+    }
+
+    for specifier in &specifiers.specifiers {
+        visitor.visit_type_specifier(ast, specifier);
+    }
+}
+
+
+pub fn walk_type_specifier<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, specifier: &'a TypeSpecifier) {
+    match specifier {
+        TypeSpecifier::Record { members: Some(members), .. } => {
+            for &member in members {
+                visitor.visit_node(ast, member);
+            }
+        }
+
+        TypeSpecifier::Enum { enumerators: Some(enumerators), .. } => {
+            for enumerator in enumerators {
+                visitor.visit_enumerator(ast, enumerator);
+            }
+        }
+
+        TypeSpecifier::Typeof { argument: TypeofArgument::Expression(expression), .. } => visitor.visit_node(ast, *expression),
+        TypeSpecifier::Typeof { argument: TypeofArgument::Type(type_name), .. } => visitor.visit_type_name(ast, type_name),
+
+        TypeSpecifier::Basic(_) | TypeSpecifier::TypedefName(_) | TypeSpecifier::Record { members: None, .. }
+        | TypeSpecifier::Enum { enumerators: None, .. } => {}
+    }
+}
+
+
+pub fn walk_declarator<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, declarator: &'a Declarator) {
+    for derived in &declarator.derived {
+        match derived {
+            DerivedDeclarator::Array(Some(size)) => visitor.visit_node(ast, *size),
+
+            DerivedDeclarator::Function { parameters, .. } => {
+                for parameter in parameters {
+                    visitor.visit_parameter(ast, parameter);
+                }
+            }
+
+            DerivedDeclarator::Pointer(_) | DerivedDeclarator::Array(None) => {}
+        }
+    }
+}
+
+
+pub fn walk_init_declarator<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, init_declarator: &'a InitDeclarator) {
+    visitor.visit_declarator(ast, &init_declarator.declarator);
+
+    for &child in [&init_declarator.bit_width, &init_declarator.initializer].into_iter().flatten() {
+        visitor.visit_node(ast, child);
+    }
+}
+
+
+pub fn walk_initializer<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast, initializer: &'a Initializer) {
+    for designator in &initializer.designators {
+        if let Designator::Index(index) = designator {
+            visitor.visit_node(ast, *index);
+        }
+    }
+
+    visitor.visit_node(ast, initializer.value);
+}
+
+
+struct ChildCollector {
+    children: Vec<NodeId>,
+}
+
+
+impl<'a> Visitor<'a> for ChildCollector {
+    fn visit_node(&mut self, _ast: &'a Ast, id: NodeId) {
+        self.children.push(id);
+    }
+}
+
+
+pub fn children(ast: &Ast, id: NodeId) -> Vec<NodeId> {
+    let mut collector = ChildCollector { children: Vec::new() };
+    walk_node(&mut collector, ast, id);
+
+    collector.children
+}
+
+
+/* * * * * * * * */
+/*  - FOLDER -  */
+/* * * * * * * * */
+pub trait Folder: Sized {
+    fn fold_node(&mut self, ast: &mut Ast, id: NodeId) -> NodeId {
+        walk_fold_node(self, ast, id)
+    }
+
+
+    fn fold_translation_unit(&mut self, ast: &mut Ast, _id: NodeId, items: &mut [NodeId]) {
+        walk_fold_translation_unit(self, ast, items);
+    }
+
+
+    fn fold_function_definition(&mut self, ast: &mut Ast, _id: NodeId, _attributes: &mut [Attribute], specifiers: &mut DeclSpecifiers, declarator: &mut Declarator, body: &mut NodeId) {
+        walk_fold_function_definition(self, ast, specifiers, declarator, body);
+    }
+
+
+    fn fold_declaration(&mut self, ast: &mut Ast, _id: NodeId, _attributes: &mut [Attribute], specifiers: &mut DeclSpecifiers, declarators: &mut [InitDeclarator]) {
+        walk_fold_declaration(self, ast, specifiers, declarators);
+    }
+
+
+    fn fold_static_assert(&mut self, ast: &mut Ast, _id: NodeId, condition: &mut NodeId, _message: &mut Option<Symbol>) {
+        walk_fold_static_assert(self, ast, condition);
+    }
+
+
+    fn fold_attributed(&mut self, ast: &mut Ast, _id: NodeId, _attributes: &mut [Attribute], statement: &mut NodeId) {
+        walk_fold_attributed(self, ast, statement);
+    }
+
+
+    fn fold_compound(&mut self, ast: &mut Ast, _id: NodeId, items: &mut [NodeId]) {
+        walk_fold_compound(self, ast, items);
+    }
+
+
+    fn fold_expression_statement(&mut self, ast: &mut Ast, _id: NodeId, expression: &mut Option<NodeId>) {
+        walk_fold_expression_statement(self, ast, expression);
+    }
+
+
+    fn fold_if(&mut self, ast: &mut Ast, _id: NodeId, condition: &mut NodeId, then_branch: &mut NodeId, else_branch: &mut Option<NodeId>) {
+        walk_fold_if(self, ast, condition, then_branch, else_branch);
+    }
+
+
+    fn fold_while(&mut self, ast: &mut Ast, _id: NodeId, condition: &mut NodeId, body: &mut NodeId) {
+        walk_fold_while(self, ast, condition, body);
+    }
+
+
+    fn fold_do_while(&mut self, ast: &mut Ast, _id: NodeId, body: &mut NodeId, condition: &mut NodeId) {
+        walk_fold_do_while(self, ast, body, condition);
+    }
+
+
+    fn fold_for(&mut self, ast: &mut Ast, _id: NodeId, init: &mut Option<NodeId>, condition: &mut Option<NodeId>, step: &mut Option<NodeId>, body: &mut NodeId) {
+        walk_fold_for(self, ast, init, condition, step, body);
+    }
+
+
+    fn fold_switch(&mut self, ast: &mut Ast, _id: NodeId, condition: &mut NodeId, body: &mut NodeId) {
+        walk_fold_switch(self, ast, condition, body);
+    }
+
+
+    fn fold_case(&mut self, ast: &mut Ast, _id: NodeId, value: &mut NodeId, body: &mut NodeId) {
+        walk_fold_case(self, ast, value, body);
+    }
+
+
+    fn fold_default(&mut self, ast: &mut Ast, _id: NodeId, body: &mut NodeId) {
+        walk_fold_default(self, ast, body);
+    }
+
+
+    fn fold_label(&mut self, ast: &mut Ast, _id: NodeId, _name: &mut Symbol, body: &mut NodeId) {
+        walk_fold_label(self, ast, body);
+    }
+
+
+    fn fold_goto(&mut self, ast: &mut Ast, _id: NodeId, _name: &mut Symbol) {
+        walk_fold_goto(self, ast);
+    }
+
+
+    fn fold_break(&mut self, ast: &mut Ast, _id: NodeId) {
+        walk_fold_break(self, ast);
+    }
+
+
+    fn fold_continue(&mut self, ast: &mut Ast, _id: NodeId) {
+        walk_fold_continue(self, ast);
+    }
+
+
+    fn fold_return(&mut self, ast: &mut Ast, _id: NodeId, value: &mut Option<NodeId>) {
+        walk_fold_return(self, ast, value);
+    }
+
+
+    fn fold_binary_operation(&mut self, ast: &mut Ast, _id: NodeId, left: &mut NodeId, _operator: &mut BinaryOperator, right: &mut NodeId) {
+        walk_fold_binary_operation(self, ast, left, right);
+    }
+
+
+    fn fold_assignment(&mut self, ast: &mut Ast, _id: NodeId, left: &mut NodeId, _operator: &mut AssignmentOperator, right: &mut NodeId) {
+        walk_fold_assignment(self, ast, left, right);
+    }
+
+
+    fn fold_unary_operation(&mut self, ast: &mut Ast, _id: NodeId, _operator: &mut UnaryOperator, operand: &mut NodeId) {
+        walk_fold_unary_operation(self, ast, operand);
+    }
+
+
+    fn fold_postfix_operation(&mut self, ast: &mut Ast, _id: NodeId, _operator: &mut PostfixOperator, operand: &mut NodeId) {
+        walk_fold_postfix_operation(self, ast, operand);
+    }
+
+
+    fn fold_conditional(&mut self, ast: &mut Ast, _id: NodeId, condition: &mut NodeId, then_value: &mut NodeId, else_value: &mut NodeId) {
+        walk_fold_conditional(self, ast, condition, then_value, else_value);
+    }
+
+
+    fn fold_call(&mut self, ast: &mut Ast, _id: NodeId, function: &mut NodeId, arguments: &mut [NodeId]) {
+        walk_fold_call(self, ast, function, arguments);
+    }
+
+
+    fn fold_index(&mut self, ast: &mut Ast, _id: NodeId, array: &mut NodeId, index: &mut NodeId) {
+        walk_fold_index(self, ast, array, index);
+    }
+
+
+    fn fold_member(&mut self, ast: &mut Ast, _id: NodeId, object: &mut NodeId, _member: &mut Symbol, _arrow: &mut bool) {
+        walk_fold_member(self, ast, object);
+    }
+
+
+    fn fold_cast(&mut self, ast: &mut Ast, _id: NodeId, type_name: &mut TypeName, operand: &mut NodeId) {
+        walk_fold_cast(self, ast, type_name, operand);
+    }
+
+
+    fn fold_compound_literal(&mut self, ast: &mut Ast, _id: NodeId, type_name: &mut TypeName, initializer: &mut NodeId) {
+        walk_fold_compound_literal(self, ast, type_name, initializer);
+    }
+
+
+    fn fold_generic_selection(&mut self, ast: &mut Ast, _id: NodeId, controlling: &mut NodeId, associations: &mut [GenericAssociation]) {
+        walk_fold_generic_selection(self, ast, controlling, associations);
+    }
+
+
+    fn fold_implicit_cast(&mut self, ast: &mut Ast, _id: NodeId, _kind: &mut CastKind, operand: &mut NodeId) {
+        walk_fold_implicit_cast(self, ast, operand);
+    }
+
+
+    fn fold_initializer_list(&mut self, ast: &mut Ast, _id: NodeId, initializers: &mut [Initializer]) {
+        walk_fold_initializer_list(self, ast, initializers);
+    }
+
+
+    fn fold_sizeof_type(&mut self, ast: &mut Ast, _id: NodeId, type_name: &mut TypeName) {
+        walk_fold_sizeof_type(self, ast, type_name);
+    }
+
+
+    fn fold_alignof_type(&mut self, ast: &mut Ast, _id: NodeId, type_name: &mut TypeName) {
+        walk_fold_alignof_type(self, ast, type_name);
+    }
+
+
+    fn fold_identifier(&mut self, ast: &mut Ast, _id: NodeId, _name: &mut Symbol) {
+        walk_fold_identifier(self, ast);
+    }
+
+
+    fn fold_string_literal(&mut self, ast: &mut Ast, _id: NodeId, _value: &mut Symbol) {
+        walk_fold_string_literal(self, ast);
+    }
+
+
+    fn fold_constant(&mut self, ast: &mut Ast, _id: NodeId, _constant: &mut Constant) {
+        walk_fold_constant(self, ast);
+    }
+
+
+    fn fold_decl_specifiers(&mut self, ast: &mut Ast, specifiers: &mut DeclSpecifiers) {
+        walk_fold_decl_specifiers(self, ast, specifiers);
+    }
+
+
+    fn fold_declarator(&mut self, ast: &mut Ast, declarator: &mut Declarator) {
+        walk_fold_declarator(self, ast, declarator);
+    }
+
+
+    fn fold_type_name(&mut self, ast: &mut Ast, type_name: &mut TypeName) {
+        self.fold_decl_specifiers(ast, &mut type_name.specifiers);
+        self.fold_declarator(ast, &mut type_name.declarator);
+    }
+}
+
+
+pub fn walk_fold_node<F: Folder>(folder: &mut F, ast: &mut Ast, id: NodeId) -> NodeId {
+    let mut kind = std::mem::replace(&mut ast[id].kind, NodeKind::Error);
+
+    match &mut kind {
+        NodeKind::TranslationUnit(items) => folder.fold_translation_unit(ast, id, items),
+        NodeKind::FunctionDefinition { attributes, specifiers, declarator, body } => folder.fold_function_definition(ast, id, attributes, specifiers, declarator, body),
+        NodeKind::Declaration { attributes, specifiers, declarators } => folder.fold_declaration(ast, id, attributes, specifiers, declarators),
+        NodeKind::StaticAssert { condition, message } => folder.fold_static_assert(ast, id, condition, message),
+        NodeKind::Attributed { attributes, statement } => folder.fold_attributed(ast, id, attributes, statement),
+        NodeKind::Compound(items) => folder.fold_compound(ast, id, items),
+        NodeKind::ExpressionStatement(expression) => folder.fold_expression_statement(ast, id, expression),
+        NodeKind::If { condition, then_branch, else_branch } => folder.fold_if(ast, id, condition, then_branch, else_branch),
+        NodeKind::While { condition, body } => folder.fold_while(ast, id, condition, body),
+        NodeKind::DoWhile { body, condition } => folder.fold_do_while(ast, id, body, condition),
+        NodeKind::For { init, condition, step, body } => folder.fold_for(ast, id, init, condition, step, body),
+        NodeKind::Switch { condition, body } => folder.fold_switch(ast, id, condition, body),
+        NodeKind::Case { value, body } => folder.fold_case(ast, id, value, body),
+        NodeKind::Default(body) => folder.fold_default(ast, id, body),
+        NodeKind::Label { name, body } => folder.fold_label(ast, id, name, body),
+        NodeKind::Goto(name) => folder.fold_goto(ast, id, name),
+        NodeKind::Break => folder.fold_break(ast, id),
+        NodeKind::Continue => folder.fold_continue(ast, id),
+        NodeKind::Return(value) => folder.fold_return(ast, id, value),
+        NodeKind::BinaryOperation { left, operator, right } => folder.fold_binary_operation(ast, id, left, operator, right),
+        NodeKind::Assignment { left, operator, right } => folder.fold_assignment(ast, id, left, operator, right),
+        NodeKind::UnaryOperation { operator, operand } => folder.fold_unary_operation(ast, id, operator, operand),
+        NodeKind::PostfixOperation { operator, operand } => folder.fold_postfix_operation(ast, id, operator, operand),
+        NodeKind::Conditional { condition, then_value, else_value } => folder.fold_conditional(ast, id, condition, then_value, else_value),
+        NodeKind::Call { function, arguments } => folder.fold_call(ast, id, function, arguments),
+        NodeKind::Index { array, index } => folder.fold_index(ast, id, array, index),
+        NodeKind::Member { object, member, arrow } => folder.fold_member(ast, id, object, member, arrow),
+        NodeKind::Cast { type_name, operand } => folder.fold_cast(ast, id, type_name, operand),
+        NodeKind::CompoundLiteral { type_name, initializer } => folder.fold_compound_literal(ast, id, type_name, initializer),
+        NodeKind::GenericSelection { controlling, associations } => folder.fold_generic_selection(ast, id, controlling, associations),
+        NodeKind::ImplicitCast { kind, operand } => folder.fold_implicit_cast(ast, id, kind, operand),
+        NodeKind::InitializerList(initializers) => folder.fold_initializer_list(ast, id, initializers),
+        NodeKind::SizeofType(type_name) => folder.fold_sizeof_type(ast, id, type_name),
+        NodeKind::AlignofType(type_name) => folder.fold_alignof_type(ast, id, type_name),
+        NodeKind::Identifier(name) => folder.fold_identifier(ast, id, name),
+        NodeKind::StringLiteral(value) => folder.fold_string_literal(ast, id, value),
+        NodeKind::Constant(constant) => folder.fold_constant(ast, id, constant),
+        NodeKind::Error => {}
+    }
+
+    ast[id].kind = kind;
+
+    id
+}
+
+
+pub fn walk_fold_translation_unit<F: Folder>(folder: &mut F, ast: &mut Ast, items: &mut [NodeId]) {
+    fold_all(folder, ast, items);
+}
+
+
+pub fn walk_fold_function_definition<F: Folder>(folder: &mut F, ast: &mut Ast, specifiers: &mut DeclSpecifiers, declarator: &mut Declarator, body: &mut NodeId) {
+    folder.fold_decl_specifiers(ast, specifiers);
+    folder.fold_declarator(ast, declarator);
+    *body = folder.fold_node(ast, *body);
+}
+
+
+pub fn walk_fold_declaration<F: Folder>(folder: &mut F, ast: &mut Ast, specifiers: &mut DeclSpecifiers, declarators: &mut [InitDeclarator]) {
+    folder.fold_decl_specifiers(ast, specifiers);
+
+    for init_declarator in declarators {
+        folder.fold_declarator(ast, &mut init_declarator.declarator);
+        fold_optional(folder, ast, &mut init_declarator.bit_width);
+        fold_optional(folder, ast, &mut init_declarator.initializer);
+    }
+}
+
+
+pub fn walk_fold_static_assert<F: Folder>(folder: &mut F, ast: &mut Ast, condition: &mut NodeId) {
+    *condition = folder.fold_node(ast, *condition);
+}
+
+
+pub fn walk_fold_attributed<F: Folder>(folder: &mut F, ast: &mut Ast, statement: &mut NodeId) {
+    *statement = folder.fold_node(ast, *statement);
+}
+
+
+pub fn walk_fold_compound<F: Folder>(folder: &mut F, ast: &mut Ast, items: &mut [NodeId]) {
+    fold_all(folder, ast, items);
+}
+
+
+pub fn walk_fold_expression_statement<F: Folder>(folder: &mut F, ast: &mut Ast, expression: &mut Option<NodeId>) {
+    fold_optional(folder, ast, expression);
+}
+
+
+pub fn walk_fold_if<F: Folder>(folder: &mut F, ast: &mut Ast, condition: &mut NodeId, then_branch: &mut NodeId, else_branch: &mut Option<NodeId>) {
+    *condition = folder.fold_node(ast, *condition);
+    *then_branch = folder.fold_node(ast, *then_branch);
+    fold_optional(folder, ast, else_branch);
+}
+
+
+pub fn walk_fold_while<F: Folder>(folder: &mut F, ast: &mut Ast, condition: &mut NodeId, body: &mut NodeId) {
+    *condition = folder.fold_node(ast, *condition);
+    *body = folder.fold_node(ast, *body);
+}
+
+
+pub fn walk_fold_do_while<F: Folder>(folder: &mut F, ast: &mut Ast, body: &mut NodeId, condition: &mut NodeId) {
+    *body = folder.fold_node(ast, *body);
+    *condition = folder.fold_node(ast, *condition);
+}
+
+
+pub fn walk_fold_for<F: Folder>(folder: &mut F, ast: &mut Ast, init: &mut Option<NodeId>, condition: &mut Option<NodeId>, step: &mut Option<NodeId>, body: &mut NodeId) {
+    fold_optional(folder, ast, init);
+    fold_optional(folder, ast, condition);
+    fold_optional(folder, ast, step);
+    *body = folder.fold_node(ast, *body);
+}
+
+
+pub fn walk_fold_switch<F: Folder>(folder: &mut F, ast: &mut Ast, condition: &mut NodeId, body: &mut NodeId) {
+    *condition = folder.fold_node(ast, *condition);
+    *body = folder.fold_node(ast, *body);
+}
+
+
+pub fn walk_fold_case<F: Folder>(folder: &mut F, ast: &mut Ast, value: &mut NodeId, body: &mut NodeId) {
+    *value = folder.fold_node(ast, *value);
+    *body = folder.fold_node(ast, *body);
+}
+
+
+pub fn walk_fold_default<F: Folder>(folder: &mut F, ast: &mut Ast, body: &mut NodeId) {
+    *body = folder.fold_node(ast, *body);
+}
+
+
+pub fn walk_fold_label<F: Folder>(folder: &mut F, ast: &mut Ast, body: &mut NodeId) {
+    *body = folder.fold_node(ast, *body);
+}
+
+
+pub fn walk_fold_goto<F: Folder>(_folder: &mut F, _ast: &mut Ast) {}
+
+
+pub fn walk_fold_break<F: Folder>(_folder: &mut F, _ast: &mut Ast) {}
+
+
+pub fn walk_fold_continue<F: Folder>(_folder: &mut F, _ast: &mut Ast) {}
+
+
+pub fn walk_fold_return<F: Folder>(folder: &mut F, ast: &mut Ast, value: &mut Option<NodeId>) {
+    fold_optional(folder, ast, value);
+}
+
+
+pub fn walk_fold_binary_operation<F: Folder>(folder: &mut F, ast: &mut Ast, left: &mut NodeId, right: &mut NodeId) {
+    *left = folder.fold_node(ast, *left);
+    *right = folder.fold_node(ast, *right);
+}
+
+
+pub fn walk_fold_assignment<F: Folder>(folder: &mut F, ast: &mut Ast, left: &mut NodeId, right: &mut NodeId) {
+    *left = folder.fold_node(ast, *left);
+    *right = folder.fold_node(ast, *right);
+}
+
+
+pub fn walk_fold_unary_operation<F: Folder>(folder: &mut F, ast: &mut Ast, operand: &mut NodeId) {
+    *operand = folder.fold_node(ast, *operand);
+}
+
+
+pub fn walk_fold_postfix_operation<F: Folder>(folder: &mut F, ast: &mut Ast, operand: &mut NodeId) {
+    *operand = folder.fold_node(ast, *operand);
+}
+
+
+pub fn walk_fold_conditional<F: Folder>(folder: &mut F, ast: &mut Ast, condition: &mut NodeId, then_value: &mut NodeId, else_value: &mut NodeId) {
+    *condition = folder.fold_node(ast, *condition);
+    *then_value = folder.fold_node(ast, *then_value);
+    *else_value = folder.fold_node(ast, *else_value);
+}
+
+
+pub fn walk_fold_call<F: Folder>(folder: &mut F, ast: &mut Ast, function: &mut NodeId, arguments: &mut [NodeId]) {
+    *function = folder.fold_node(ast, *function);
+    fold_all(folder, ast, arguments);
+}
+
+
+pub fn walk_fold_index<F: Folder>(folder: &mut F, ast: &mut Ast, array: &mut NodeId, index: &mut NodeId) {
+    *array = folder.fold_node(ast, *array);
+    *index = folder.fold_node(ast, *index);
+}
+
+
+pub fn walk_fold_member<F: Folder>(folder: &mut F, ast: &mut Ast, object: &mut NodeId) {
+    *object = folder.fold_node(ast, *object);
+}
+
+
+pub fn walk_fold_cast<F: Folder>(folder: &mut F, ast: &mut Ast, type_name: &mut TypeName, operand: &mut NodeId) {
+    folder.fold_type_name(ast, type_name);
+    *operand = folder.fold_node(ast, *operand);
+}
+
+
+pub fn walk_fold_compound_literal<F: Folder>(folder: &mut F, ast: &mut Ast, type_name: &mut TypeName, initializer: &mut NodeId) {
+    folder.fold_type_name(ast, type_name);
+    *initializer = folder.fold_node(ast, *initializer);
+}
+
+
+pub fn walk_fold_generic_selection<F: Folder>(folder: &mut F, ast: &mut Ast, controlling: &mut NodeId, associations: &mut [GenericAssociation]) {
+    *controlling = folder.fold_node(ast, *controlling);
+
+    for association in associations {
+        if let Some(type_name) = &mut association.type_name {
+            folder.fold_type_name(ast, type_name);
+        }
+
+        association.value = folder.fold_node(ast, association.value);
+    }
+}
+
+
+pub fn walk_fold_implicit_cast<F: Folder>(folder: &mut F, ast: &mut Ast, operand: &mut NodeId) {
+    *operand = folder.fold_node(ast, *operand);
+}
+
+
+pub fn walk_fold_initializer_list<F: Folder>(folder: &mut F, ast: &mut Ast, initializers: &mut [Initializer]) {
+    for initializer in initializers {
+        for designator in &mut initializer.designators {
+            if let Designator::Index(index) = designator {
+                *index = folder.fold_node(ast, *index);
+            }
+        }
+
+        initializer.value = folder.fold_node(ast, initializer.value);
+    }
+}
+
+
+pub fn walk_fold_sizeof_type<F: Folder>(folder: &mut F, ast: &mut Ast, type_name: &mut TypeName) {
+    folder.fold_type_name(ast, type_name);
+}
+
+
+pub fn walk_fold_alignof_type<F: Folder>(folder: &mut F, ast: &mut Ast, type_name: &mut TypeName) {
+    folder.fold_type_name(ast, type_name);
+}
+
+
+pub fn walk_fold_identifier<F: Folder>(_folder: &mut F, _ast: &mut Ast) {}
+
+
+pub fn walk_fold_string_literal<F: Folder>(_folder: &mut F, _ast: &mut Ast) {}
+
+
+pub fn walk_fold_constant<F: Folder>(_folder: &mut F, _ast: &mut Ast) {}
+
+
+pub fn walk_fold_decl_specifiers<F: Folder>(folder: &mut F, ast: &mut Ast, specifiers: &mut DeclSpecifiers) {
+    for alignment in &mut specifiers.alignment {
+        match alignment {
+            TypeofArgument::Expression(expression) => *expression = folder.fold_node(ast, *expression),
+            TypeofArgument::Type(type_name) => folder.fold_type_name(ast, type_name),
+        }
+    }
+
+    for specifier in &mut specifiers.specifiers {
+        match specifier {
+            TypeSpecifier::Record { members: Some(members), .. } => fold_all(folder, ast, members),
+
+            TypeSpecifier::Enum { enumerators: Some(enumerators), .. } => {
+                for enumerator in enumerators {
+                    fold_optional(folder, ast, &mut enumerator.value);
+                }
+            }
+
+            TypeSpecifier::Typeof { argument: TypeofArgument::Expression(expression), .. } => *expression = folder.fold_node(ast, *expression),
+            TypeSpecifier::Typeof { argument: TypeofArgument::Type(type_name), .. } => folder.fold_type_name(ast, type_name),
+
+            TypeSpecifier::Basic(_) | TypeSpecifier::TypedefName(_) | TypeSpecifier::Record { members: None, .. }
+            | TypeSpecifier::Enum { enumerators: None, .. } => {}
+        }
+    }
+}
+
+
+pub fn walk_fold_declarator<F: Folder>(folder: &mut F, ast: &mut Ast, declarator: &mut Declarator) {
+    for derived in &mut declarator.derived {
+        match derived {
+            DerivedDeclarator::Array(size) => fold_optional(folder, ast, size),
+
+            DerivedDeclarator::Function { parameters, .. } => {
+                for parameter in parameters {
+                    folder.fold_decl_specifiers(ast, &mut parameter.specifiers);
+                    folder.fold_declarator(ast, &mut parameter.declarator);
+                }
+            }
+
+            DerivedDeclarator::Pointer(_) => {}
+        }
+    }
+}
+
+
+fn fold_all<F: Folder>(folder: &mut F, ast: &mut Ast, ids: &mut [NodeId]) {
+    for id in ids {
+        *id = folder.fold_node(ast, *id);
+    }
+}
+
+
+fn fold_optional<F: Folder>(folder: &mut F, ast: &mut Ast, id: &mut Option<NodeId>) {
+    if let Some(id) = id {
+        *id = folder.fold_node(ast, *id);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{ HashMap, HashSet };
+
+use crate::data::structures::{ Analyses, IrFunction };
+use crate::data::types::{ BlockId, InstructionKind, Operand, Register };
+
+
+pub fn adce(function: &mut IrFunction, _analyses: &mut Analyses) -> bool {
+    let post_dominators = function.post_dominators();
+    let controllers = control_dependences(function, &post_dominators);
+    let live = mark_live(function, &post_dominators, &controllers);
+
+    sweep(function, &post_dominators, &live)
+}
+
+
+/* * * * * * * * * */
+/*  - LIVENESS -  */
+/* * * * * * * * * */
+fn control_dependences(function: &IrFunction, post_dominators: &[Option<BlockId>]) -> Vec<Vec<BlockId>> {
+    let mut controllers = vec![Vec::new(); function.blocks.len()];
+
+    for block in &function.blocks {
+        let successors = block.successors();
+
+        let Some(boundary) = post_dominators[block.id.0 as usize] else {
+            continue;
+        };
+
+        if successors.len() < 2 {
+            continue;
+        }
+
+        for successor in successors {
+            let mut runner = successor;
+
+            while runner != boundary && (runner.0 as usize) < function.blocks.len() {
+                let controlled: &mut Vec<BlockId> = &mut controllers[runner.0 as usize];
+
+                if !controlled.contains(&block.id) {
+                    controlled.push(block.id);
+                }
+
+                match post_dominators[runner.0 as usize] {
+                    Some(next) => runner = next,
+                    None => break,
+                }
+            }
+        }
+    }
+
+    controllers
+}
+
+
+fn mark_live(function: &IrFunction, post_dominators: &[Option<BlockId>], controllers: &[Vec<BlockId>]) -> HashSet<(usize, usize)> {
+    let mut definitions: HashMap<Register, (usize, usize)> = HashMap::new();
+    let mut worklist = Vec::new();
+
+    for (block_index, block) in function.blocks.iter().enumerate() {
+        for (index, instruction) in block.instructions.iter().enumerate() {
+            if let Some(result) = instruction.result {
+                definitions.insert(result, (block_index, index));
+            }
+
+            let conditional = matches!(instruction.kind, InstructionKind::Branch { .. } | InstructionKind::Switch { .. });
+            let escapes = conditional && (post_dominators[block_index].is_none_or(|boundary| boundary.0 as usize >= function.blocks.len())
+                || block.successors().iter().any(|successor| post_dominators[successor.0 as usize].is_none()));
+
+            if escapes || (!conditional && !matches!(instruction.kind, InstructionKind::Jump(_)) && instruction.has_side_effects()) {
+                worklist.push((block_index, index));
+            }
+        }
+    }
+
+    let mut live = HashSet::new();
+    let mut live_blocks = HashSet::new();
+
+    while let Some((block_index, index)) = worklist.pop() {
+        if !live.insert((block_index, index)) {
+            continue;
+        }
+
+        let mut blocks = vec![block_index];
+        let mut instruction = function.blocks[block_index].instructions[index].clone();
+
+        if let InstructionKind::Phi { incoming } = &instruction.kind {
+            blocks.extend(incoming.iter().map(|(_, predecessor)| predecessor.0 as usize));
+        }
+
+        for block in blocks {
+            if !live_blocks.insert(block) {
+                continue;
+            }
+
+            for controller in &controllers[block] {
+                let terminator = function.blocks[controller.0 as usize].instructions.len() - 1;
+                worklist.push((controller.0 as usize, terminator));
+            }
+        }
+
+        for operand in instruction.operands_mut() {
+            if let Operand::Register(register) = operand && let Some(definition) = definitions.get(register) {
+                worklist.push(*definition);
+            }
+        }
+    }
+
+    live
+}
+
+
+/* * * * * * * * */
+/*  - SWEEP -  */
+/* * * * * * * * */
+fn sweep(function: &mut IrFunction, post_dominators: &[Option<BlockId>], live: &HashSet<(usize, usize)>) -> bool {
+    let mut changed = false;
+    let mut detached = Vec::new();
+
+    for (block_index, boundary) in post_dominators.iter().enumerate().take(function.blocks.len()) {
+        let block = BlockId(block_index as u32);
+        let successors = function.blocks[block_index].successors();
+        let instructions = std::mem::take(&mut function.blocks[block_index].instructions);
+        let count = instructions.len();
+        let mut kept = Vec::with_capacity(count);
+
+        for (index, mut instruction) in instructions.into_iter().enumerate() {
+            if live.contains(&(block_index, index)) || matches!(instruction.kind, InstructionKind::Jump(_)) {
+                kept.push(instruction);
+                continue;
+            }
+
+            changed = true;
+
+            if index + 1 != count || !instruction.is_terminator() {
+                continue;
+            }
+
+            let Some(target) = *boundary else {
+                kept.push(instruction);
+                continue;
+            };
+
+            instruction.kind = InstructionKind::Jump(target);
+            kept.push(instruction);
+
+            detached.extend(successors.iter().filter(|successor| **successor != target).map(|successor| (*successor, block)));
+        }
+
+        function.blocks[block_index].instructions = kept;
+    }
+
+    for (successor, block) in detached {
+        function.remove_incoming(successor, block);
+    }
+
+    changed
+}
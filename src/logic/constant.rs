@@ -0,0 +1,406 @@
+use crate::data::{ Ast, CType, NodeId, NodeKind, Symbol };
+use crate::data::structures::TypeLayout;
+use crate::data::types::{ BinaryOperator, CastKind, Constant, ConstantValue, EvaluationError, TypeName, UnaryOperator };
+
+
+pub trait ConstantContext {
+    fn ast(&self) -> &Ast;
+    fn enumerator(&self, identifier: NodeId) -> Option<i128>;
+    fn is_static(&self, identifier: NodeId) -> bool;
+    fn type_of(&self, expression: NodeId) -> Option<CType>;
+    fn type_name(&self, node: NodeId, type_name: &TypeName) -> CType;
+    fn layout(&self, ctype: &CType) -> Option<TypeLayout>;
+    fn member_offset(&self, record: &CType, member: Symbol) -> Option<u64>;
+}
+
+
+pub struct Evaluator<'a, C: ConstantContext> {
+    context: &'a C,
+    pub overflow: bool,
+}
+
+
+impl<'a, C: ConstantContext> Evaluator<'a, C> {
+    pub fn new(context: &'a C) -> Self {
+        Self { context, overflow: false }
+    }
+
+
+    pub fn evaluate(&mut self, expression: NodeId) -> Result<ConstantValue, EvaluationError> {
+        let ast = self.context.ast();
+
+        match ast.kind(expression) {
+            NodeKind::Constant(constant @ Constant::Floating { value, .. }) => Ok(ConstantValue::Floating(*value, constant.ctype())),
+            NodeKind::Constant(Constant::Character(value)) => Ok(ConstantValue::Integer(*value as i128, CType::Int)),
+            NodeKind::Constant(Constant::Bool(value)) => Ok(ConstantValue::Integer(*value as i128, CType::Bool)),
+
+            NodeKind::Constant(constant @ Constant::Integer { value, .. }) => {
+                let ctype = constant.ctype();
+                Ok(ConstantValue::Integer(self.wrap(*value as i128, &ctype), ctype))
+            }
+
+            NodeKind::StringLiteral(_) => Ok(ConstantValue::Address { base: expression, offset: 0 }),
+
+            NodeKind::Identifier(_) => {
+                if let Some(value) = self.context.enumerator(expression) {
+                    return Ok(ConstantValue::Integer(value, CType::Int));
+                }
+
+                match self.context.type_of(expression).as_ref().map(CType::unqualified) {
+                    Some(CType::Array(..) | CType::Function { .. }) if self.context.is_static(expression) => Ok(ConstantValue::Address { base: expression, offset: 0 }),
+                    _ => Err(EvaluationError::NotConstant),
+                }
+            }
+
+            NodeKind::Member { .. } | NodeKind::Index { .. } | NodeKind::CompoundLiteral { .. }
+                if self.context.type_of(expression).is_some_and(|ctype| matches!(ctype.unqualified(), CType::Array(..))) => self.address(expression),
+
+            NodeKind::UnaryOperation { operator, operand } => self.unary(*operator, *operand),
+
+            NodeKind::SizeofType(type_name) => {
+                let ctype = self.context.type_name(expression, type_name);
+                self.size_of(&ctype)
+            }
+
+            NodeKind::AlignofType(type_name) => {
+                let ctype = self.context.type_name(expression, type_name);
+                self.align_of(&ctype)
+            }
+
+            NodeKind::BinaryOperation { left, operator, right } => self.binary(*left, *operator, *right),
+
+            NodeKind::Conditional { condition, then_value, else_value } => {
+                let (chosen, other) = if self.truth(*condition)? { (*then_value, *else_value) } else { (*else_value, *then_value) };
+                let value = self.evaluate(chosen)?;
+
+                match (self.context.type_of(chosen), self.context.type_of(other)) {
+                    (Some(left), Some(right)) if left.is_arithmetic() && right.is_arithmetic() => self.convert(value, &CType::usual_arithmetic(&left, &right)),
+                    _ => Ok(value),
+                }
+            }
+
+            NodeKind::Cast { type_name, operand } => {
+                let target = self.context.type_name(expression, type_name);
+
+                if matches!(target.unqualified(), CType::Void) {
+                    return Err(EvaluationError::NotConstant);
+                }
+
+                let value = self.evaluate(*operand)?;
+                self.convert(value, &target)
+            }
+
+            NodeKind::ImplicitCast { kind: CastKind::ArrayToPointer | CastKind::FunctionToPointer, operand } => self.address(*operand),
+            NodeKind::ImplicitCast { kind: CastKind::LValueToRValue, operand } => self.evaluate(*operand),
+
+            NodeKind::ImplicitCast { operand, .. } => {
+                let value = self.evaluate(*operand)?;
+
+                match self.context.type_of(expression) {
+                    Some(target) => self.convert(value, &target),
+                    None => Ok(value),
+                }
+            }
+
+            _ => Err(EvaluationError::NotConstant),
+        }
+    }
+
+
+    pub fn evaluate_integer(&mut self, expression: NodeId) -> Result<i128, EvaluationError> {
+        match self.evaluate(expression)? {
+            ConstantValue::Integer(value, ctype) if !ctype.is_pointer() => Ok(value),
+            _ => Err(EvaluationError::NotConstant),
+        }
+    }
+
+
+    fn truth(&mut self, expression: NodeId) -> Result<bool, EvaluationError> {
+        match self.evaluate(expression)? {
+            ConstantValue::Integer(value, _) => Ok(value != 0),
+            ConstantValue::Floating(value, _) => Ok(value != 0.0),
+            ConstantValue::Address { .. } => Ok(true),
+        }
+    }
+
+
+    fn size_of(&self, ctype: &CType) -> Result<ConstantValue, EvaluationError> {
+        let layout = self.context.layout(ctype).ok_or(EvaluationError::NotConstant)?;
+        Ok(ConstantValue::Integer(layout.size as i128, CType::UnsignedLong))
+    }
+
+
+    fn align_of(&self, ctype: &CType) -> Result<ConstantValue, EvaluationError> {
+        let layout = self.context.layout(ctype).ok_or(EvaluationError::NotConstant)?;
+        Ok(ConstantValue::Integer(layout.align as i128, CType::UnsignedLong))
+    }
+
+
+    fn unary(&mut self, operator: UnaryOperator, operand: NodeId) -> Result<ConstantValue, EvaluationError> {
+        match operator {
+            UnaryOperator::Sizeof => {
+                let ctype = self.context.type_of(operand).ok_or(EvaluationError::NotConstant)?;
+                self.size_of(&ctype)
+            }
+
+            UnaryOperator::Address => self.address(operand),
+            UnaryOperator::Not => Ok(ConstantValue::Integer(!self.truth(operand)? as i128, CType::Int)),
+
+            UnaryOperator::Plus | UnaryOperator::Minus | UnaryOperator::BitNot => match self.evaluate(operand)? {
+                ConstantValue::Integer(value, ctype) if !ctype.is_pointer() => {
+                    let ctype = ctype.promote();
+
+                    let result = match operator {
+                        UnaryOperator::Minus => -value,
+                        UnaryOperator::BitNot => !value,
+                        _ => value,
+                    };
+
+                    Ok(ConstantValue::Integer(self.checked(result, &ctype), ctype))
+                }
+
+                ConstantValue::Floating(value, ctype) if operator != UnaryOperator::BitNot => {
+                    Ok(ConstantValue::Floating(if operator == UnaryOperator::Minus { -value } else { value }, ctype))
+                }
+
+                _ => Err(EvaluationError::NotConstant),
+            },
+
+            _ => Err(EvaluationError::NotConstant),
+        }
+    }
+
+
+    fn binary(&mut self, left: NodeId, operator: BinaryOperator, right: NodeId) -> Result<ConstantValue, EvaluationError> {
+        match operator {
+            BinaryOperator::Comma => return Err(EvaluationError::NotConstant),
+            BinaryOperator::LogicalAnd => return Ok(ConstantValue::Integer((self.truth(left)? && self.truth(right)?) as i128, CType::Int)),
+            BinaryOperator::LogicalOr => return Ok(ConstantValue::Integer((self.truth(left)? || self.truth(right)?) as i128, CType::Int)),
+            _ => {}
+        }
+
+        let left_value = self.evaluate(left)?;
+        let right_value = self.evaluate(right)?;
+
+        match (left_value, right_value) {
+            (ConstantValue::Address { base, offset }, ConstantValue::Integer(value, ctype)) if !ctype.is_pointer() => {
+                let scale = self.pointee_size(left)?;
+
+                match operator {
+                    BinaryOperator::Add => Ok(ConstantValue::Address { base, offset: offset + (value * scale) as i64 }),
+                    BinaryOperator::Subtract => Ok(ConstantValue::Address { base, offset: offset - (value * scale) as i64 }),
+                    _ => Err(EvaluationError::NotConstant),
+                }
+            }
+
+            (ConstantValue::Integer(value, ctype), ConstantValue::Address { base, offset }) if operator == BinaryOperator::Add && !ctype.is_pointer() => {
+                let scale = self.pointee_size(right)?;
+                Ok(ConstantValue::Address { base, offset: offset + (value * scale) as i64 })
+            }
+
+            (ConstantValue::Address { base: left_base, offset: left_offset }, ConstantValue::Address { base: right_base, offset: right_offset })
+                if operator == BinaryOperator::Subtract && left_base == right_base => {
+                let scale = self.pointee_size(left)?;
+                Ok(ConstantValue::Integer((left_offset - right_offset) as i128 / scale, CType::Long))
+            }
+
+            (ConstantValue::Integer(left_value, left_type), ConstantValue::Integer(right_value, right_type)) => {
+                self.integer_binary(operator, (left_value, left_type), (right_value, right_type))
+            }
+
+            (left_value @ (ConstantValue::Integer(..) | ConstantValue::Floating(..)), right_value @ (ConstantValue::Integer(..) | ConstantValue::Floating(..))) => {
+                let common = CType::usual_arithmetic(&value_type(&left_value), &value_type(&right_value));
+                let (ConstantValue::Floating(left_value, _), ConstantValue::Floating(right_value, _)) = (self.convert(left_value, &common)?, self.convert(right_value, &common)?) else {
+                    return Err(EvaluationError::NotConstant);
+                };
+
+                let compared = |result: bool| Ok(ConstantValue::Integer(result as i128, CType::Int));
+
+                match operator {
+                    BinaryOperator::Add => Ok(ConstantValue::Floating(round_floating(left_value + right_value, &common), common)),
+                    BinaryOperator::Subtract => Ok(ConstantValue::Floating(round_floating(left_value - right_value, &common), common)),
+                    BinaryOperator::Multiply => Ok(ConstantValue::Floating(round_floating(left_value * right_value, &common), common)),
+                    BinaryOperator::Divide => Ok(ConstantValue::Floating(round_floating(left_value / right_value, &common), common)),
+                    BinaryOperator::Equal => compared(left_value == right_value),
+                    BinaryOperator::NotEqual => compared(left_value != right_value),
+                    BinaryOperator::Less => compared(left_value < right_value),
+                    BinaryOperator::Greater => compared(left_value > right_value),
+                    BinaryOperator::LessEqual => compared(left_value <= right_value),
+                    BinaryOperator::GreaterEqual => compared(left_value >= right_value),
+                    _ => Err(EvaluationError::NotConstant),
+                }
+            }
+
+            _ => Err(EvaluationError::NotConstant),
+        }
+    }
+
+
+    fn integer_binary(&mut self, operator: BinaryOperator, (left, left_type): (i128, CType), (right, right_type): (i128, CType)) -> Result<ConstantValue, EvaluationError> {
+        if matches!(operator, BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight) {
+            let ctype = left_type.promote();
+            let width = self.context.layout(&ctype).map_or(32, |layout| layout.size as i128 * 8);
+
+            if right < 0 || right >= width {
+                self.overflow = true;
+                return Ok(ConstantValue::Integer(0, ctype));
+            }
+
+            let result = if operator == BinaryOperator::ShiftLeft { left << right } else { left >> right };
+            return Ok(ConstantValue::Integer(self.checked(result, &ctype), ctype));
+        }
+
+        let common = if left_type.is_pointer() || right_type.is_pointer() {
+            CType::UnsignedLong
+        } else {
+            CType::usual_arithmetic(&left_type, &right_type)
+        };
+
+        let left = self.wrap(left, &common);
+        let right = self.wrap(right, &common);
+        let compared = |result: bool| Ok(ConstantValue::Integer(result as i128, CType::Int));
+
+        let result = match operator {
+            BinaryOperator::Equal => return compared(left == right),
+            BinaryOperator::NotEqual => return compared(left != right),
+            BinaryOperator::Less => return compared(left < right),
+            BinaryOperator::Greater => return compared(left > right),
+            BinaryOperator::LessEqual => return compared(left <= right),
+            BinaryOperator::GreaterEqual => return compared(left >= right),
+            BinaryOperator::Add => left + right,
+            BinaryOperator::Subtract => left - right,
+            BinaryOperator::Multiply => left * right,
+            BinaryOperator::BitAnd => left & right,
+            BinaryOperator::BitOr => left | right,
+            BinaryOperator::BitXor => left ^ right,
+
+            BinaryOperator::Divide | BinaryOperator::Remainder => {
+                if right == 0 {
+                    return Err(EvaluationError::DivisionByZero);
+                }
+
+                if operator == BinaryOperator::Divide { left / right } else { left % right }
+            }
+
+            _ => return Err(EvaluationError::NotConstant),
+        };
+
+        Ok(ConstantValue::Integer(self.checked(result, &common), common))
+    }
+
+
+    fn address(&mut self, expression: NodeId) -> Result<ConstantValue, EvaluationError> {
+        let ast = self.context.ast();
+
+        let (base, offset) = match ast.kind(expression) {
+            NodeKind::Identifier(_) if self.context.is_static(expression) => return Ok(ConstantValue::Address { base: expression, offset: 0 }),
+            NodeKind::StringLiteral(_) | NodeKind::CompoundLiteral { .. } => return Ok(ConstantValue::Address { base: expression, offset: 0 }),
+            NodeKind::UnaryOperation { operator: UnaryOperator::Dereference, operand } => return self.evaluate(*operand),
+
+            NodeKind::Index { array, index } => {
+                let (pointer, index) = if self.context.type_of(*array).is_some_and(|ctype| ctype.decay().is_pointer()) { (*array, *index) } else { (*index, *array) };
+                let element = self.context.type_of(expression).and_then(|ctype| self.context.layout(&ctype)).ok_or(EvaluationError::NotConstant)?;
+                let index = self.evaluate_integer(index)?;
+
+                (self.evaluate(pointer)?, index * element.size as i128)
+            }
+
+            NodeKind::Member { object, member, arrow } => {
+                let record = self.context.type_of(*object).ok_or(EvaluationError::NotConstant)?;
+                let record = if *arrow { record.decay().pointee().cloned().ok_or(EvaluationError::NotConstant)? } else { record };
+                let offset = self.context.member_offset(&record, *member).ok_or(EvaluationError::NotConstant)?;
+                let base = if *arrow { self.evaluate(*object)? } else { self.address(*object)? };
+
+                (base, offset as i128)
+            }
+
+            _ => return Err(EvaluationError::NotConstant),
+        };
+
+        match base {
+            ConstantValue::Address { base, offset: base_offset } => Ok(ConstantValue::Address { base, offset: base_offset + offset as i64 }),
+            ConstantValue::Integer(value, ctype) if ctype.is_pointer() => Ok(ConstantValue::Integer(value + offset, ctype)),
+            _ => Err(EvaluationError::NotConstant),
+        }
+    }
+
+
+    fn pointee_size(&self, pointer: NodeId) -> Result<i128, EvaluationError> {
+        let pointee = self.context.type_of(pointer).and_then(|ctype| ctype.decay().pointee().cloned()).ok_or(EvaluationError::NotConstant)?;
+
+        match pointee.unqualified() {
+            CType::Void => Ok(1),
+            _ => self.context.layout(&pointee).map(|layout| layout.size as i128).ok_or(EvaluationError::NotConstant),
+        }
+    }
+
+
+    pub fn convert(&mut self, value: ConstantValue, target: &CType) -> Result<ConstantValue, EvaluationError> {
+        let target = target.strip_qualifiers();
+
+        match value {
+            ConstantValue::Integer(value, _) if matches!(target, CType::Bool) => Ok(ConstantValue::Integer((value != 0) as i128, target)),
+            ConstantValue::Integer(value, _) if target.is_integer() || target.is_pointer() => Ok(ConstantValue::Integer(self.wrap(value, &target), target)),
+            ConstantValue::Integer(value, _) if target.is_floating() => Ok(ConstantValue::Floating(round_floating(value as f64, &target), target)),
+            ConstantValue::Floating(value, _) if matches!(target, CType::Bool) => Ok(ConstantValue::Integer((value != 0.0) as i128, target)),
+
+            ConstantValue::Floating(value, _) if target.is_integer() => {
+                let truncated = value.trunc() as i128;
+
+                if value.is_nan() || truncated != self.wrap(truncated, &target) {
+                    self.overflow = true;
+                }
+
+                Ok(ConstantValue::Integer(self.wrap(truncated, &target), target))
+            }
+
+            ConstantValue::Floating(value, _) if target.is_floating() => Ok(ConstantValue::Floating(round_floating(value, &target), target)),
+            address @ ConstantValue::Address { .. } if target.is_pointer() => Ok(address),
+            _ => Err(EvaluationError::NotConstant),
+        }
+    }
+
+
+    fn wrap(&self, value: i128, ctype: &CType) -> i128 {
+        if matches!(ctype.unqualified(), CType::Bool) {
+            return (value != 0) as i128;
+        }
+
+        let bits = self.context.layout(ctype).map_or(64, |layout| layout.size * 8) as u32;
+        let modulus = 1i128 << bits;
+        let wrapped = value.rem_euclid(modulus);
+
+        if !ctype.is_unsigned() && !ctype.is_pointer() && wrapped >= modulus / 2 {
+            wrapped - modulus
+        } else {
+            wrapped
+        }
+    }
+
+
+    fn checked(&mut self, value: i128, ctype: &CType) -> i128 {
+        let wrapped = self.wrap(value, ctype);
+
+        if wrapped != value && !ctype.is_unsigned() {
+            self.overflow = true;
+        }
+
+        wrapped
+    }
+}
+
+
+fn value_type(value: &ConstantValue) -> CType {
+    match value {
+        ConstantValue::Integer(_, ctype) | ConstantValue::Floating(_, ctype) => ctype.clone(),
+        ConstantValue::Address { .. } => CType::Pointer(Box::new(CType::Void)),
+    }
+}
+
+
+fn round_floating(value: f64, ctype: &CType) -> f64 {
+    match ctype.unqualified() {
+        CType::Float => value as f32 as f64,
+        _ => value,
+    }
+}
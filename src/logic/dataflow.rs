@@ -0,0 +1,776 @@
+use std::collections::{ HashMap, HashSet };
+
+use crate::data::{ Ast, CType, DataflowState, Diagnostic, EntityId, NodeId, NodeKind, Symbol, SymbolTable, TypeTable, WarningFlag, Warnings };
+use crate::data::structures::{ Access, BasicBlock };
+use crate::data::visit::children;
+use crate::data::types::{ AccessKind, AssignmentOperator, Attribute, BinaryOperator, Constant, EntityKind, Keyword, Linkage, StorageDuration, UnaryOperator };
+
+
+const ENTRY: usize = 0;
+const EXIT: usize = 1;
+
+
+pub fn dataflow_start(ast: &Ast, table: &SymbolTable, types: &TypeTable, warnings: &Warnings) -> Vec<Diagnostic> {
+    let mut dataflow_state = DataflowState::new(ast, table, types, warnings);
+
+    if let NodeKind::TranslationUnit(items) = ast.kind(ast.root) {
+        for &item in items {
+            if matches!(ast.kind(item), NodeKind::FunctionDefinition { .. }) {
+                process_function(&mut dataflow_state, item);
+            }
+        }
+    }
+
+    check_unused_functions(&mut dataflow_state);
+
+    dataflow_state.diagnostics
+}
+
+
+fn process_function(dataflow_state: &mut DataflowState, function: NodeId) {
+    let ast = dataflow_state.ast;
+
+    let NodeKind::FunctionDefinition { declarator, body, .. } = ast.kind(function) else {
+        return;
+    };
+
+    let mut parameters = Vec::new();
+
+    for parameter in declarator.function_parameters().into_iter().flatten() {
+        let Some(&entity) = parameter.declarator.name.and_then(|name| dataflow_state.declared.get(&(function, name))) else {
+            continue;
+        };
+
+        if is_marked_unused(&parameter.attributes) {
+            dataflow_state.unused_allowed.insert(entity);
+        }
+
+        parameters.push(entity);
+    }
+
+    dataflow_state.blocks.clear();
+    dataflow_state.labels.clear();
+    dataflow_state.new_block();
+    dataflow_state.new_block();
+    dataflow_state.current = ENTRY;
+
+    build_statement(dataflow_state, *body);
+
+    let current = dataflow_state.current;
+    dataflow_state.edge(current, EXIT);
+
+    let items: Vec<Vec<(NodeId, bool)>> = dataflow_state.blocks.iter().map(|block| block.items.clone()).collect();
+
+    let accesses: Vec<Vec<Access>> = items.into_iter().map(|items| {
+        let mut accesses = Vec::new();
+
+        for (item, discarded) in items {
+            collect_accesses(dataflow_state, item, discarded, &mut accesses);
+        }
+
+        accesses
+    }).collect();
+
+    let address_taken: HashSet<EntityId> = accesses.iter().flatten()
+        .filter(|access| access.kind == AccessKind::AddressTaken)
+        .map(|access| access.entity)
+        .collect();
+
+    let locals: Vec<EntityId> = accesses.iter().flatten()
+        .filter(|access| matches!(access.kind, AccessKind::Declare | AccessKind::Initialize { .. }))
+        .map(|access| access.entity)
+        .collect();
+
+    let tracked: HashSet<EntityId> = locals.iter().chain(&parameters).copied()
+        .filter(|entity| !address_taken.contains(entity) && is_trackable(dataflow_state, *entity))
+        .collect();
+
+    let reachable = reachable_blocks(&dataflow_state.blocks);
+
+    check_unreachable(dataflow_state, &reachable);
+    check_uninitialized(dataflow_state, &accesses, &reachable, &tracked, &parameters);
+    check_dead_stores(dataflow_state, &accesses, &reachable, &tracked);
+    check_unused_variables(dataflow_state, &accesses, &locals, &parameters);
+}
+
+
+/* * * * * * * * * * * * * */
+/*  - CONTROL FLOW GRAPH -  */
+/* * * * * * * * * * * * * */
+fn build_statement(dataflow_state: &mut DataflowState, statement: NodeId) {
+    let ast = dataflow_state.ast;
+    let current = dataflow_state.current;
+
+    if !matches!(ast.kind(statement), NodeKind::Compound(_) | NodeKind::Declaration { .. } | NodeKind::StaticAssert { .. } | NodeKind::Attributed { .. }
+        | NodeKind::Case { .. } | NodeKind::Default(_) | NodeKind::Label { .. } | NodeKind::Break | NodeKind::ExpressionStatement(None)) {
+        let block = &mut dataflow_state.blocks[current];
+
+        if block.statement.is_none() && block.items.is_empty() {
+            block.statement = Some(statement);
+        }
+    }
+
+    match ast.kind(statement) {
+        NodeKind::Compound(items) => {
+            for &item in items {
+                build_statement(dataflow_state, item);
+            }
+        }
+
+        NodeKind::Declaration { .. } => dataflow_state.blocks[current].items.push((statement, false)),
+        NodeKind::ExpressionStatement(Some(expression)) => dataflow_state.blocks[current].items.push((*expression, true)),
+        NodeKind::Attributed { statement, .. } => build_statement(dataflow_state, *statement),
+
+        NodeKind::If { condition, then_branch, else_branch } => {
+            check_condition(dataflow_state, *condition);
+            dataflow_state.blocks[current].items.push((*condition, false));
+
+            let join = dataflow_state.new_block();
+            dataflow_state.current = branch(dataflow_state, current);
+            build_statement(dataflow_state, *then_branch);
+            dataflow_state.edge(dataflow_state.current, join);
+
+            match else_branch {
+                Some(else_branch) => {
+                    dataflow_state.current = branch(dataflow_state, current);
+                    build_statement(dataflow_state, *else_branch);
+                    dataflow_state.edge(dataflow_state.current, join);
+                }
+
+                None => dataflow_state.edge(current, join),
+            }
+
+            dataflow_state.current = join;
+        }
+
+        NodeKind::While { condition, body } => {
+            check_condition(dataflow_state, *condition);
+
+            let header = branch(dataflow_state, current);
+            let exit = dataflow_state.new_block();
+            dataflow_state.blocks[header].items.push((*condition, false));
+
+            if constant_truth(ast, *condition) != Some(true) {
+                dataflow_state.edge(header, exit);
+            }
+
+            dataflow_state.current = branch(dataflow_state, header);
+            build_loop_body(dataflow_state, *body, exit, header);
+            dataflow_state.edge(dataflow_state.current, header);
+            dataflow_state.current = exit;
+        }
+
+        NodeKind::DoWhile { body, condition } => {
+            check_condition(dataflow_state, *condition);
+
+            let entry = branch(dataflow_state, current);
+            let latch = dataflow_state.new_block();
+            let exit = dataflow_state.new_block();
+
+            dataflow_state.current = entry;
+            build_loop_body(dataflow_state, *body, exit, latch);
+            dataflow_state.edge(dataflow_state.current, latch);
+            dataflow_state.blocks[latch].items.push((*condition, false));
+
+            let truth = constant_truth(ast, *condition);
+
+            if truth != Some(false) {
+                dataflow_state.edge(latch, entry);
+            }
+
+            if truth != Some(true) {
+                dataflow_state.edge(latch, exit);
+            }
+
+            dataflow_state.current = exit;
+        }
+
+        NodeKind::For { init, condition, step, body } => {
+            if let Some(init) = init {
+                let discarded = !matches!(ast.kind(*init), NodeKind::Declaration { .. });
+                dataflow_state.blocks[current].items.push((*init, discarded));
+            }
+
+            let header = branch(dataflow_state, current);
+            let exit = dataflow_state.new_block();
+            let latch = dataflow_state.new_block();
+
+            if let Some(condition) = condition {
+                check_condition(dataflow_state, *condition);
+                dataflow_state.blocks[header].items.push((*condition, false));
+
+                if constant_truth(ast, *condition) != Some(true) {
+                    dataflow_state.edge(header, exit);
+                }
+            }
+
+            dataflow_state.current = branch(dataflow_state, header);
+            build_loop_body(dataflow_state, *body, exit, latch);
+            dataflow_state.edge(dataflow_state.current, latch);
+
+            if let Some(step) = step {
+                dataflow_state.blocks[latch].items.push((*step, true));
+            }
+
+            dataflow_state.edge(latch, header);
+            dataflow_state.current = exit;
+        }
+
+        NodeKind::Switch { condition, body } => {
+            dataflow_state.blocks[current].items.push((*condition, false));
+
+            let exit = dataflow_state.new_block();
+            dataflow_state.switches.push((current, false));
+            dataflow_state.breaks.push(exit);
+            dataflow_state.current = dataflow_state.new_block();
+
+            build_statement(dataflow_state, *body);
+            dataflow_state.edge(dataflow_state.current, exit);
+
+            dataflow_state.breaks.pop();
+
+            if let Some((_, false)) = dataflow_state.switches.pop() {
+                dataflow_state.edge(current, exit);
+            }
+
+            dataflow_state.current = exit;
+        }
+
+        NodeKind::Case { body, .. } | NodeKind::Default(body) => {
+            let block = dataflow_state.new_block();
+            dataflow_state.edge(current, block);
+
+            if let Some((head, has_default)) = dataflow_state.switches.last_mut() {
+                *has_default |= matches!(ast.kind(statement), NodeKind::Default(_));
+
+                let head = *head;
+                dataflow_state.edge(head, block);
+            }
+
+            dataflow_state.current = block;
+            build_statement(dataflow_state, *body);
+        }
+
+        NodeKind::Label { name, body } => {
+            let block = label_block(dataflow_state, *name);
+            dataflow_state.edge(current, block);
+            dataflow_state.current = block;
+            build_statement(dataflow_state, *body);
+        }
+
+        NodeKind::Goto(name) => {
+            let block = label_block(dataflow_state, *name);
+            dataflow_state.edge(current, block);
+            dataflow_state.current = dataflow_state.new_block();
+        }
+
+        NodeKind::Break | NodeKind::Continue => {
+            let targets = if matches!(ast.kind(statement), NodeKind::Break) { &dataflow_state.breaks } else { &dataflow_state.continues };
+
+            if let Some(&target) = targets.last() {
+                dataflow_state.edge(current, target);
+            }
+
+            dataflow_state.current = dataflow_state.new_block();
+        }
+
+        NodeKind::Return(value) => {
+            if let Some(value) = value {
+                dataflow_state.blocks[current].items.push((*value, false));
+            }
+
+            dataflow_state.edge(current, EXIT);
+            dataflow_state.current = dataflow_state.new_block();
+        }
+
+        _ => {}
+    }
+}
+
+
+fn build_loop_body(dataflow_state: &mut DataflowState, body: NodeId, exit: usize, next: usize) {
+    dataflow_state.breaks.push(exit);
+    dataflow_state.continues.push(next);
+
+    build_statement(dataflow_state, body);
+
+    dataflow_state.breaks.pop();
+    dataflow_state.continues.pop();
+}
+
+
+fn branch(dataflow_state: &mut DataflowState, from: usize) -> usize {
+    let block = dataflow_state.new_block();
+    dataflow_state.edge(from, block);
+
+    block
+}
+
+
+fn label_block(dataflow_state: &mut DataflowState, name: Symbol) -> usize {
+    if let Some(&block) = dataflow_state.labels.get(&name) {
+        return block;
+    }
+
+    let block = dataflow_state.new_block();
+    dataflow_state.labels.insert(name, block);
+
+    block
+}
+
+
+fn reachable_blocks(blocks: &[BasicBlock]) -> Vec<bool> {
+    let mut reachable = vec![false; blocks.len()];
+    let mut worklist = vec![ENTRY];
+
+    while let Some(block) = worklist.pop() {
+        if !std::mem::replace(&mut reachable[block], true) {
+            worklist.extend(&blocks[block].successors);
+        }
+    }
+
+    reachable
+}
+
+
+/* * * * * * * * * */
+/*  - ACCESSES -  */
+/* * * * * * * * * */
+fn collect_accesses(dataflow_state: &mut DataflowState, node: NodeId, discarded: bool, accesses: &mut Vec<Access>) {
+    let ast = dataflow_state.ast;
+
+    match ast.kind(node) {
+        NodeKind::Declaration { attributes, declarators, .. } => {
+            for init_declarator in declarators {
+                let Some(&entity) = init_declarator.declarator.name.and_then(|name| dataflow_state.declared.get(&(node, name))) else {
+                    continue;
+                };
+
+                if is_marked_unused(attributes) || is_marked_unused(&init_declarator.attributes) {
+                    dataflow_state.unused_allowed.insert(entity);
+                }
+
+                let kind = match init_declarator.initializer {
+                    Some(initializer) => {
+                        collect_accesses(dataflow_state, initializer, false, accesses);
+                        AccessKind::Initialize { constant: is_constant(ast, initializer) }
+                    }
+
+                    None => AccessKind::Declare,
+                };
+
+                accesses.push(Access { entity, node, kind });
+            }
+        }
+
+        NodeKind::Assignment { left, operator, right } => match variable(dataflow_state, *left) {
+            Some((entity, target)) => {
+                collect_accesses(dataflow_state, *right, false, accesses);
+
+                let kind = match operator {
+                    AssignmentOperator::Assign => AccessKind::Store { discarded },
+                    AssignmentOperator::Compound(_) => AccessKind::Update { discarded },
+                };
+
+                accesses.push(Access { entity, node: target, kind });
+            }
+
+            None => {
+                collect_accesses(dataflow_state, *left, false, accesses);
+                collect_accesses(dataflow_state, *right, false, accesses);
+            }
+        },
+
+        NodeKind::UnaryOperation { operator: UnaryOperator::Sizeof, .. } | NodeKind::SizeofType(_) | NodeKind::AlignofType(_) => {}
+
+        NodeKind::UnaryOperation { operator, operand } => match variable(dataflow_state, *operand) {
+            Some((entity, target)) if *operator == UnaryOperator::Address => accesses.push(Access { entity, node: target, kind: AccessKind::AddressTaken }),
+
+            Some((entity, target)) if matches!(operator, UnaryOperator::PreIncrement | UnaryOperator::PreDecrement) => {
+                accesses.push(Access { entity, node: target, kind: AccessKind::Update { discarded: false } });
+            }
+
+            _ => collect_accesses(dataflow_state, *operand, false, accesses),
+        },
+
+        NodeKind::PostfixOperation { operand, .. } => match variable(dataflow_state, *operand) {
+            Some((entity, target)) => accesses.push(Access { entity, node: target, kind: AccessKind::Update { discarded: false } }),
+            None => collect_accesses(dataflow_state, *operand, false, accesses),
+        },
+
+        NodeKind::BinaryOperation { left, operator: BinaryOperator::Comma, right } => {
+            collect_accesses(dataflow_state, *left, true, accesses);
+            collect_accesses(dataflow_state, *right, discarded, accesses);
+        }
+
+        NodeKind::Conditional { condition, then_value, else_value } => {
+            collect_accesses(dataflow_state, *condition, false, accesses);
+            collect_accesses(dataflow_state, *then_value, discarded, accesses);
+            collect_accesses(dataflow_state, *else_value, discarded, accesses);
+        }
+
+        NodeKind::GenericSelection { associations, .. } => {
+            for association in associations {
+                collect_accesses(dataflow_state, association.value, false, accesses);
+            }
+        }
+
+        NodeKind::Identifier(_) => {
+            if let Some((entity, target)) = variable(dataflow_state, node) {
+                accesses.push(Access { entity, node: target, kind: AccessKind::Read });
+            }
+        }
+
+        _ => {
+            for child in children(ast, node) {
+                collect_accesses(dataflow_state, child, false, accesses);
+            }
+        }
+    }
+}
+
+
+fn variable(dataflow_state: &DataflowState, node: NodeId) -> Option<(EntityId, NodeId)> {
+    let node = strip_implicit_casts(dataflow_state.ast, node);
+
+    if !matches!(dataflow_state.ast.kind(node), NodeKind::Identifier(_)) {
+        return None;
+    }
+
+    let entity = dataflow_state.table.resolve(node)?;
+
+    matches!(dataflow_state.table[entity].kind, EntityKind::Object | EntityKind::Parameter).then_some((entity, node))
+}
+
+
+fn is_trackable(dataflow_state: &DataflowState, entity: EntityId) -> bool {
+    let declaration = &dataflow_state.table[entity];
+
+    declaration.storage == Some(StorageDuration::Automatic)
+        && entity_type(dataflow_state, entity).is_some_and(|ctype| {
+            (ctype.is_arithmetic() || ctype.is_pointer()) && !ctype.qualifiers().is_volatile
+        })
+}
+
+
+fn entity_type<'a>(dataflow_state: &DataflowState<'a>, entity: EntityId) -> Option<&'a CType> {
+    let ast = dataflow_state.ast;
+    let declaration = &dataflow_state.table[entity];
+    let declared = dataflow_state.types.declarations.get(&declaration.node)?;
+
+    match (declaration.kind, ast.kind(declaration.node)) {
+        (EntityKind::Parameter, NodeKind::FunctionDefinition { declarator, .. }) => {
+            let index = declarator.function_parameters()?.iter().position(|parameter| parameter.declarator.name == Some(declaration.name))?;
+
+            match declared.first().map(|(_, ctype)| ctype.unqualified()) {
+                Some(CType::Function { parameters, .. }) => parameters.get(index),
+                _ => None,
+            }
+        }
+
+        _ => declared.iter().find(|(name, _)| *name == declaration.name).map(|(_, ctype)| ctype),
+    }
+}
+
+
+fn strip_implicit_casts(ast: &Ast, mut node: NodeId) -> NodeId {
+    while let NodeKind::ImplicitCast { operand, .. } = ast.kind(node) {
+        node = *operand;
+    }
+
+    node
+}
+
+
+fn is_constant(ast: &Ast, node: NodeId) -> bool {
+    match ast.kind(node) {
+        NodeKind::Constant(_) | NodeKind::StringLiteral(_) | NodeKind::SizeofType(_) | NodeKind::AlignofType(_) => true,
+        NodeKind::ImplicitCast { operand, .. } | NodeKind::Cast { operand, .. } => is_constant(ast, *operand),
+        NodeKind::UnaryOperation { operator: UnaryOperator::Minus | UnaryOperator::Plus | UnaryOperator::BitNot, operand } => is_constant(ast, *operand),
+        _ => false,
+    }
+}
+
+
+fn constant_truth(ast: &Ast, node: NodeId) -> Option<bool> {
+    match ast.kind(strip_implicit_casts(ast, node)) {
+        NodeKind::Constant(Constant::Integer { value, .. }) => Some(*value != 0),
+        NodeKind::Constant(Constant::Floating { value, .. }) => Some(*value != 0.0),
+        NodeKind::Constant(Constant::Character(value)) => Some(*value != '\0'),
+        NodeKind::Constant(Constant::Bool(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+
+fn is_marked_unused(attributes: &[Attribute]) -> bool {
+    attributes.iter().any(|attribute| attribute.is("unused") || attribute.is("maybe_unused"))
+}
+
+
+/* * * * * * * * */
+/*  - CHECKS -  */
+/* * * * * * * * */
+fn check_condition(dataflow_state: &mut DataflowState, condition: NodeId) {
+    let ast = dataflow_state.ast;
+    let condition = strip_implicit_casts(ast, condition);
+
+    if matches!(ast.kind(condition), NodeKind::Assignment { operator: AssignmentOperator::Assign, .. }) && !ast.parenthesized.contains(&condition) {
+        let line = ast[condition].span.start;
+        dataflow_state.report(WarningFlag::Parentheses, line, "suggest parentheses around assignment used as truth value".to_string());
+    }
+}
+
+
+fn check_unreachable(dataflow_state: &mut DataflowState, reachable: &[bool]) {
+    let mut covered = reachable.to_vec();
+
+    for block in 0..dataflow_state.blocks.len() {
+        let Some(statement) = dataflow_state.blocks[block].statement.filter(|_| !covered[block]) else {
+            continue;
+        };
+
+        let line = dataflow_state.ast[statement].span.start;
+        dataflow_state.report(WarningFlag::UnreachableCode, line, "code will never be executed".to_string());
+
+        let mut worklist = vec![block];
+
+        while let Some(block) = worklist.pop() {
+            if !std::mem::replace(&mut covered[block], true) {
+                worklist.extend(&dataflow_state.blocks[block].successors);
+            }
+        }
+    }
+}
+
+
+fn check_uninitialized(dataflow_state: &mut DataflowState, accesses: &[Vec<Access>], reachable: &[bool], tracked: &HashSet<EntityId>, parameters: &[EntityId]) {
+    let count = accesses.len();
+    let parameters: HashSet<EntityId> = parameters.iter().copied().collect();
+
+    let mut must_out = vec![tracked.clone(); count];
+    let mut may_out = vec![HashSet::new(); count];
+    let mut changed = true;
+
+    let entry_state = |must_out: &[HashSet<EntityId>], may_out: &[HashSet<EntityId>], block: usize| {
+        if block == ENTRY {
+            return (parameters.clone(), parameters.clone());
+        }
+
+        let predecessors: Vec<usize> = dataflow_state.blocks[block].predecessors.iter().copied().filter(|&predecessor| reachable[predecessor]).collect();
+
+        let must = predecessors.iter()
+            .map(|&predecessor| must_out[predecessor].clone())
+            .reduce(|left, right| left.intersection(&right).copied().collect())
+            .unwrap_or_default();
+
+        let may = predecessors.iter().flat_map(|&predecessor| may_out[predecessor].iter().copied()).collect();
+
+        (must, may)
+    };
+
+    while changed {
+        changed = false;
+
+        for block in (0..count).filter(|&block| reachable[block]) {
+            let (mut must, mut may) = entry_state(&must_out, &may_out, block);
+
+            for access in &accesses[block] {
+                transfer_initialized(access, &mut must);
+                transfer_initialized(access, &mut may);
+            }
+
+            if must != must_out[block] || may != may_out[block] {
+                must_out[block] = must;
+                may_out[block] = may;
+                changed = true;
+            }
+        }
+    }
+
+    let mut reported = HashSet::new();
+    let mut warnings = Vec::new();
+
+    for block in (0..count).filter(|&block| reachable[block]) {
+        let (mut must, mut may) = entry_state(&must_out, &may_out, block);
+
+        for access in &accesses[block] {
+            if matches!(access.kind, AccessKind::Read | AccessKind::Update { .. }) && tracked.contains(&access.entity)
+                && !must.contains(&access.entity) && reported.insert(access.entity) {
+                warnings.push((*access, may.contains(&access.entity)));
+            }
+
+            transfer_initialized(access, &mut must);
+            transfer_initialized(access, &mut may);
+        }
+    }
+
+    for (access, maybe) in warnings {
+        let name = dataflow_state.name(dataflow_state.table[access.entity].name);
+        let line = dataflow_state.ast[access.node].span.start;
+
+        match maybe {
+            true => dataflow_state.report(WarningFlag::MaybeUninitialized, line, format!("'{}' may be used uninitialized", name)),
+            false => dataflow_state.report(WarningFlag::Uninitialized, line, format!("'{}' is used uninitialized", name)),
+        }
+    }
+}
+
+
+fn transfer_initialized(access: &Access, initialized: &mut HashSet<EntityId>) {
+    match access.kind {
+        AccessKind::Store { .. } | AccessKind::Update { .. } | AccessKind::Initialize { .. } => {
+            initialized.insert(access.entity);
+        }
+
+        AccessKind::Declare => {
+            initialized.remove(&access.entity);
+        }
+
+        AccessKind::Read | AccessKind::AddressTaken => {}
+    }
+}
+
+
+fn check_dead_stores(dataflow_state: &mut DataflowState, accesses: &[Vec<Access>], reachable: &[bool], tracked: &HashSet<EntityId>) {
+    let count = accesses.len();
+    let mut live_in = vec![HashSet::new(); count];
+    let mut changed = true;
+
+    let read: HashSet<EntityId> = accesses.iter().flatten()
+        .filter(|access| matches!(access.kind, AccessKind::Read | AccessKind::Update { .. }))
+        .map(|access| access.entity)
+        .collect();
+
+    let live_out = |live_in: &[HashSet<EntityId>], block: usize| -> HashSet<EntityId> {
+        dataflow_state.blocks[block].successors.iter().flat_map(|&successor| live_in[successor].iter().copied()).collect()
+    };
+
+    while changed {
+        changed = false;
+
+        for block in (0..count).rev().filter(|&block| reachable[block]) {
+            let mut live = live_out(&live_in, block);
+
+            for access in accesses[block].iter().rev() {
+                transfer_live(access, &mut live);
+            }
+
+            if live != live_in[block] {
+                live_in[block] = live;
+                changed = true;
+            }
+        }
+    }
+
+    let mut warnings = Vec::new();
+
+    for block in (0..count).filter(|&block| reachable[block]) {
+        let mut live = live_out(&live_in, block);
+
+        for access in accesses[block].iter().rev() {
+            let dead = tracked.contains(&access.entity) && read.contains(&access.entity) && !live.contains(&access.entity);
+
+            match access.kind {
+                AccessKind::Store { discarded: true } | AccessKind::Update { discarded: true } if dead => warnings.push((*access, "")),
+                AccessKind::Initialize { constant: false } if dead => warnings.push((*access, " during its initialization")),
+                _ => {}
+            }
+
+            transfer_live(access, &mut live);
+        }
+    }
+
+    warnings.sort_by_key(|(access, _)| access.node);
+
+    for (access, context) in warnings {
+        let name = dataflow_state.name(dataflow_state.table[access.entity].name);
+        let line = dataflow_state.ast[access.node].span.start;
+
+        dataflow_state.report(WarningFlag::DeadStore, line, format!("value stored to '{}'{} is never read", name, context));
+    }
+}
+
+
+fn transfer_live(access: &Access, live: &mut HashSet<EntityId>) {
+    match access.kind {
+        AccessKind::Read | AccessKind::Update { .. } => {
+            live.insert(access.entity);
+        }
+
+        AccessKind::Store { .. } | AccessKind::Initialize { .. } | AccessKind::Declare => {
+            live.remove(&access.entity);
+        }
+
+        AccessKind::AddressTaken => {}
+    }
+}
+
+
+fn check_unused_variables(dataflow_state: &mut DataflowState, accesses: &[Vec<Access>], locals: &[EntityId], parameters: &[EntityId]) {
+    let mut stores: HashMap<EntityId, usize> = HashMap::new();
+
+    for access in accesses.iter().flatten().filter(|access| matches!(access.kind, AccessKind::Store { .. })) {
+        *stores.entry(access.entity).or_insert(0) += 1;
+    }
+
+    let mut seen = HashSet::new();
+
+    for &entity in locals {
+        let declaration = &dataflow_state.table[entity];
+
+        if !seen.insert(entity) || declaration.linkage != Linkage::None || dataflow_state.unused_allowed.contains(&entity) {
+            continue;
+        }
+
+        let name = dataflow_state.name(declaration.name);
+        let line = dataflow_state.ast[declaration.node].span.start;
+        let references = dataflow_state.references.get(&entity).copied().unwrap_or(0);
+
+        if references == 0 {
+            dataflow_state.report(WarningFlag::UnusedVariable, line, format!("unused variable '{}'", name));
+        } else if stores.get(&entity) == Some(&references) {
+            dataflow_state.report(WarningFlag::UnusedButSetVariable, line, format!("variable '{}' set but not used", name));
+        }
+    }
+
+    for &entity in parameters {
+        if dataflow_state.references.contains_key(&entity) || dataflow_state.unused_allowed.contains(&entity) {
+            continue;
+        }
+
+        let declaration = &dataflow_state.table[entity];
+        let name = dataflow_state.name(declaration.name);
+        let line = dataflow_state.ast[declaration.node].span.start;
+
+        dataflow_state.report(WarningFlag::UnusedParameter, line, format!("unused parameter '{}'", name));
+    }
+}
+
+
+fn check_unused_functions(dataflow_state: &mut DataflowState) {
+    let ast = dataflow_state.ast;
+    let table = dataflow_state.table;
+
+    let first_declaration = |entity: EntityId| table.first_declaration(entity);
+
+    let used: HashSet<EntityId> = table.references.values().map(|&entity| first_declaration(entity)).collect();
+
+    for (index, declaration) in table.entities.iter().enumerate() {
+        if declaration.kind != EntityKind::Function || declaration.linkage != Linkage::Internal
+            || used.contains(&first_declaration(EntityId(index as u32))) {
+            continue;
+        }
+
+        let NodeKind::FunctionDefinition { attributes, specifiers, .. } = ast.kind(declaration.node) else {
+            continue;
+        };
+
+        if specifiers.function.contains(&Keyword::Inline) || is_marked_unused(attributes) {
+            continue;
+        }
+
+        let line = ast[declaration.node].span.start;
+        dataflow_state.report(WarningFlag::UnusedFunction, line, format!("'{}' defined but not used", dataflow_state.name(declaration.name)));
+    }
+}
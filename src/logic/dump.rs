@@ -0,0 +1,276 @@
+use crate::data::{ Ast, NodeId, NodeKind, TypeTable, escape_c };
+use crate::data::visit::children;
+use crate::data::types::{ Attribute, DeclSpecifiers, DumpFormat };
+use crate::logic::printer::print_designators;
+
+use std::collections::HashMap;
+
+
+pub fn dump_start(ast: &Ast, types: &TypeTable, format: DumpFormat) -> String {
+    let mut output = String::new();
+
+    match format {
+        DumpFormat::Tree => dump_tree(ast, types, ast.root, None, "", "", &mut output),
+
+        DumpFormat::Json => {
+            dump_json(ast, types, ast.root, None, 0, &mut output);
+            output.push('\n');
+        }
+
+        DumpFormat::Dot => {
+            output.push_str("digraph AST {\n");
+            output.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+            dump_dot(ast, types, ast.root, None, &mut output);
+            output.push_str("}\n");
+        }
+    }
+
+    output
+}
+
+
+fn node_fields(ast: &Ast, id: NodeId, designation: Option<&String>) -> Vec<(&'static str, String)> {
+    let mut fields = Vec::new();
+
+    if let Some(designation) = designation {
+        fields.push(("designator", designation.clone()));
+    }
+
+    match ast.kind(id) {
+        NodeKind::FunctionDefinition { attributes, specifiers, .. } => {
+            push_specifiers(&mut fields, specifiers);
+            push_attributes(&mut fields, attributes);
+        }
+
+        NodeKind::Declaration { attributes, specifiers, .. } => {
+            push_specifiers(&mut fields, specifiers);
+            push_attributes(&mut fields, attributes);
+        }
+
+        NodeKind::StaticAssert { message: Some(message), .. } => fields.push(("message", ast.symbols.resolve(*message).to_string())),
+        NodeKind::Attributed { attributes, .. } => push_attributes(&mut fields, attributes),
+        NodeKind::Label { name, .. } | NodeKind::Goto(name) | NodeKind::Identifier(name) => fields.push(("name", ast.symbols.resolve(*name).to_string())),
+        NodeKind::BinaryOperation { operator, .. } => fields.push(("operator", operator.symbol().to_string())),
+        NodeKind::Assignment { operator, .. } => fields.push(("operator", operator.symbol().to_string())),
+        NodeKind::UnaryOperation { operator, .. } => fields.push(("operator", operator.symbol().to_string())),
+        NodeKind::PostfixOperation { operator, .. } => fields.push(("operator", operator.symbol().to_string())),
+        NodeKind::Member { member, arrow, .. } => fields.push(("member", format!("{}{}", if *arrow { "->" } else { "." }, ast.symbols.resolve(*member)))),
+        NodeKind::StringLiteral(value) => fields.push(("value", format!("\"{}\"", escape_c(ast.symbols.resolve(*value), '"')))),
+        NodeKind::Constant(constant) => fields.push(("value", constant.to_string())),
+        NodeKind::ImplicitCast { kind, .. } => fields.push(("cast", kind.to_string())),
+        _ => {}
+    }
+
+    fields
+}
+
+
+fn designations(ast: &Ast, id: NodeId) -> HashMap<NodeId, String> {
+    match ast.kind(id) {
+        NodeKind::InitializerList(initializers) => initializers.iter()
+            .filter(|initializer| !initializer.designators.is_empty())
+            .map(|initializer| (initializer.value, print_designators(ast, &initializer.designators)))
+            .collect(),
+
+        _ => HashMap::new(),
+    }
+}
+
+
+fn push_specifiers(fields: &mut Vec<(&'static str, String)>, specifiers: &DeclSpecifiers) {
+    let keywords: Vec<String> = specifiers.storage.iter().chain(&specifiers.function).map(|keyword| keyword.to_string()).collect();
+
+    if !keywords.is_empty() {
+        fields.push(("storage", keywords.join(" ")));
+    }
+}
+
+
+fn push_attributes(fields: &mut Vec<(&'static str, String)>, attributes: &[Attribute]) {
+    if !attributes.is_empty() {
+        let attributes: Vec<String> = attributes.iter().map(|attribute| attribute.to_string()).collect();
+        fields.push(("attributes", attributes.join(", ")));
+    }
+}
+
+
+fn declared<'a>(ast: &'a Ast, types: &'a TypeTable, id: NodeId) -> Vec<(&'a str, String)> {
+    types.declarations.get(&id).into_iter().flatten()
+        .map(|(name, ctype)| (ast.symbols.resolve(*name), ctype.to_string()))
+        .collect()
+}
+
+
+fn format_span(ast: &Ast, id: NodeId) -> String {
+    let span = ast[id].span;
+
+    if span.start == span.end {
+        format!("<line:{}>", span.start)
+    } else {
+        format!("<line:{}, line:{}>", span.start, span.end)
+    }
+}
+
+
+/* * * * * * * * */
+/*  - TREE -  */
+/* * * * * * * * */
+fn dump_tree(ast: &Ast, types: &TypeTable, id: NodeId, designation: Option<&String>, prefix: &str, child_prefix: &str, output: &mut String) {
+    output.push_str(prefix);
+    output.push_str(ast.kind(id).name());
+    output.push(' ');
+    output.push_str(&format_span(ast, id));
+
+    for (key, value) in node_fields(ast, id, designation) {
+        match key {
+            "designator" => output.push_str(&format!(" {} =", value)),
+            "operator" => output.push_str(&format!(" '{}'", value)),
+            "attributes" => output.push_str(&format!(" [[{}]]", value)),
+            "message" => output.push_str(&format!(" \"{}\"", escape_c(&value, '"'))),
+            "cast" => output.push_str(&format!(" <{}>", value)),
+            _ => output.push_str(&format!(" {}", value)),
+        }
+    }
+
+    let declarations: Vec<String> = declared(ast, types, id).into_iter().map(|(name, ctype)| format!("{} '{}'", name, ctype)).collect();
+
+    if !declarations.is_empty() {
+        output.push(' ');
+        output.push_str(&declarations.join(", "));
+    }
+
+    if let Some(ctype) = types.expressions.get(&id) {
+        output.push_str(&format!(" '{}'", ctype));
+    }
+
+    if let Some(category) = types.categories.get(&id) {
+        output.push_str(&format!(" {}", category));
+    }
+
+    output.push('\n');
+
+    let children = children(ast, id);
+    let designations = designations(ast, id);
+
+    for (index, &child) in children.iter().enumerate() {
+        if index + 1 == children.len() {
+            dump_tree(ast, types, child, designations.get(&child), &format!("{}`-", child_prefix), &format!("{}  ", child_prefix), output);
+        } else {
+            dump_tree(ast, types, child, designations.get(&child), &format!("{}|-", child_prefix), &format!("{}| ", child_prefix), output);
+        }
+    }
+}
+
+
+/* * * * * * * * */
+/*  - JSON -  */
+/* * * * * * * * */
+fn dump_json(ast: &Ast, types: &TypeTable, id: NodeId, designation: Option<&String>, depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth + 1);
+    let span = ast[id].span;
+
+    output.push_str("{\n");
+    output.push_str(&format!("{}\"id\": {},\n", indent, id.0));
+    output.push_str(&format!("{}\"kind\": \"{}\",\n", indent, ast.kind(id).name()));
+    output.push_str(&format!("{}\"span\": {{ \"start\": {}, \"end\": {} }},\n", indent, span.start, span.end));
+
+    for (key, value) in node_fields(ast, id, designation) {
+        output.push_str(&format!("{}\"{}\": \"{}\",\n", indent, key, json_escape(&value)));
+    }
+
+    let declarations = declared(ast, types, id);
+
+    if !declarations.is_empty() {
+        let declarations: Vec<String> = declarations.iter()
+            .map(|(name, ctype)| format!("{{ \"name\": \"{}\", \"type\": \"{}\" }}", json_escape(name), json_escape(ctype)))
+            .collect();
+
+        output.push_str(&format!("{}\"declarations\": [{}],\n", indent, declarations.join(", ")));
+    }
+
+    if let Some(ctype) = types.expressions.get(&id) {
+        output.push_str(&format!("{}\"type\": \"{}\",\n", indent, json_escape(&ctype.to_string())));
+    }
+
+    if let Some(category) = types.categories.get(&id) {
+        output.push_str(&format!("{}\"category\": \"{}\",\n", indent, category));
+    }
+
+    let children = children(ast, id);
+    let designations = designations(ast, id);
+
+    if children.is_empty() {
+        output.push_str(&format!("{}\"children\": []\n", indent));
+    } else {
+        output.push_str(&format!("{}\"children\": [\n", indent));
+
+        for (index, &child) in children.iter().enumerate() {
+            output.push_str(&"  ".repeat(depth + 2));
+            dump_json(ast, types, child, designations.get(&child), depth + 2, output);
+
+            if index + 1 < children.len() {
+                output.push(',');
+            }
+
+            output.push('\n');
+        }
+
+        output.push_str(&format!("{}]\n", indent));
+    }
+
+    output.push_str(&"  ".repeat(depth));
+    output.push('}');
+}
+
+
+fn json_escape(value: &str) -> String {
+    let mut escaped = String::new();
+
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            _ if character.is_control() => escaped.push_str(&format!("\\u{:04x}", character as u32)),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+
+/* * * * * * * * */
+/*  - DOT -  */
+/* * * * * * * * */
+fn dump_dot(ast: &Ast, types: &TypeTable, id: NodeId, designation: Option<&String>, output: &mut String) {
+    let mut label = vec![ast.kind(id).name().to_string(), format_span(ast, id)];
+
+    for (key, value) in node_fields(ast, id, designation) {
+        label.push(format!("{}: {}", key, value));
+    }
+
+    for (name, ctype) in declared(ast, types, id) {
+        label.push(format!("{}: {}", name, ctype));
+    }
+
+    if let Some(ctype) = types.expressions.get(&id) {
+        label.push(format!("type: {}", ctype));
+    }
+
+    if let Some(category) = types.categories.get(&id) {
+        label.push(format!("category: {}", category));
+    }
+
+    let label: Vec<String> = label.iter().map(|line| json_escape(line)).collect();
+    output.push_str(&format!("    n{} [label=\"{}\"];\n", id.0, label.join("\\n")));
+
+    let designations = designations(ast, id);
+
+    for child in children(ast, id) {
+        output.push_str(&format!("    n{} -> n{};\n", id.0, child.0));
+        dump_dot(ast, types, child, designations.get(&child), output);
+    }
+}
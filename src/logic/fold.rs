@@ -0,0 +1,201 @@
+use crate::data::types::{ ArithmeticOperator, ComparePredicate, ConversionOperator, IrType, Operand };
+
+
+pub fn fold_binary(operator: ArithmeticOperator, ty: IrType, no_signed_wrap: bool, left: &Operand, right: &Operand) -> Option<Operand> {
+    match (left, right) {
+        (Operand::Integer(left), Operand::Integer(right)) if ty.is_integer() => fold_integer(operator, ty, no_signed_wrap, *left, *right),
+        (Operand::Floating(left), Operand::Floating(right)) if ty.is_floating() => fold_floating(operator, ty, *left, *right),
+        _ => None,
+    }
+}
+
+
+pub fn fold_compare(predicate: ComparePredicate, ty: IrType, left: &Operand, right: &Operand) -> Option<Operand> {
+    let result = match (left, right) {
+        (Operand::Integer(left), Operand::Integer(right)) if ty.is_integer() => compare_integers(predicate, ty, *left, *right)?,
+        (Operand::Floating(left), Operand::Floating(right)) if ty.is_floating() => compare_floats(predicate, *left, *right)?,
+        (Operand::Null | Operand::Global(_), Operand::Null | Operand::Global(_)) => compare_addresses(predicate, left, right)?,
+        _ => return None,
+    };
+
+    Some(Operand::Integer(result as i64))
+}
+
+
+pub fn fold_convert(operator: ConversionOperator, from: IrType, to: IrType, value: &Operand) -> Option<Operand> {
+    match (operator, value) {
+        (ConversionOperator::Trunc | ConversionOperator::ZExt, Operand::Integer(value)) => Some(integer(unsigned(*value, from) as i128, to)),
+        (ConversionOperator::SExt, Operand::Integer(value)) => Some(integer(signed(*value, from) as i128, to)),
+        (ConversionOperator::FpTrunc | ConversionOperator::FpExt, Operand::Floating(value)) => Some(Operand::Floating(round(*value, to))),
+        (ConversionOperator::SiToFp, Operand::Integer(value)) => Some(Operand::Floating(round(signed(*value, from) as f64, to))),
+        (ConversionOperator::UiToFp, Operand::Integer(value)) => Some(Operand::Floating(round(unsigned(*value, from) as f64, to))),
+        (ConversionOperator::FpToSi, Operand::Floating(value)) => float_to_integer(*value, to, true),
+        (ConversionOperator::FpToUi, Operand::Floating(value)) => float_to_integer(*value, to, false),
+        (ConversionOperator::PtrToInt, Operand::Null) => Some(Operand::Integer(0)),
+        (ConversionOperator::IntToPtr, Operand::Integer(0)) => Some(Operand::Null),
+        _ => None,
+    }
+}
+
+
+pub fn integer(value: i128, ty: IrType) -> Operand {
+    let value = value as u64;
+
+    Operand::Integer(match ty.bits() {
+        1 => (value & 1) as i64,
+        bits @ 2..64 => ((value << (64 - bits)) as i64) >> (64 - bits),
+        _ => value as i64,
+    })
+}
+
+
+pub fn signed(value: i64, ty: IrType) -> i64 {
+    match ty.bits() {
+        bits @ 1..64 => (value << (64 - bits)) >> (64 - bits),
+        _ => value,
+    }
+}
+
+
+pub fn unsigned(value: i64, ty: IrType) -> u64 {
+    match ty.bits() {
+        bits @ 1..64 => value as u64 & ((1 << bits) - 1),
+        _ => value as u64,
+    }
+}
+
+
+/* * * * * * * * * * */
+/*  - ARITHMETIC -  */
+/* * * * * * * * * * */
+fn fold_integer(operator: ArithmeticOperator, ty: IrType, no_signed_wrap: bool, left: i64, right: i64) -> Option<Operand> {
+    let bits = ty.bits() as u64;
+    let (signed_left, signed_right) = (signed(left, ty) as i128, signed(right, ty) as i128);
+    let (unsigned_left, unsigned_right) = (unsigned(left, ty), unsigned(right, ty));
+    let minimum = -(1i128 << (bits - 1));
+    let maximum = (1i128 << (bits - 1)) - 1;
+
+    let exact = match operator {
+        ArithmeticOperator::Add => signed_left + signed_right,
+        ArithmeticOperator::Sub => signed_left - signed_right,
+        ArithmeticOperator::Mul => signed_left * signed_right,
+        _ => 0,
+    };
+
+    if no_signed_wrap && !(minimum..=maximum).contains(&exact) {
+        return None;
+    }
+
+    let result = match operator {
+        ArithmeticOperator::Add => unsigned_left.wrapping_add(unsigned_right) as i128,
+        ArithmeticOperator::Sub => unsigned_left.wrapping_sub(unsigned_right) as i128,
+        ArithmeticOperator::Mul => unsigned_left.wrapping_mul(unsigned_right) as i128,
+        ArithmeticOperator::And => (unsigned_left & unsigned_right) as i128,
+        ArithmeticOperator::Or => (unsigned_left | unsigned_right) as i128,
+        ArithmeticOperator::Xor => (unsigned_left ^ unsigned_right) as i128,
+
+        ArithmeticOperator::SDiv | ArithmeticOperator::SRem if signed_right == 0 || (signed_left == minimum && signed_right == -1) => return None,
+        ArithmeticOperator::SDiv => signed_left / signed_right,
+        ArithmeticOperator::SRem => signed_left % signed_right,
+        ArithmeticOperator::UDiv | ArithmeticOperator::URem if unsigned_right == 0 => return None,
+        ArithmeticOperator::UDiv => (unsigned_left / unsigned_right) as i128,
+        ArithmeticOperator::URem => (unsigned_left % unsigned_right) as i128,
+
+        ArithmeticOperator::Shl | ArithmeticOperator::LShr | ArithmeticOperator::AShr if unsigned_right >= bits => return None,
+        ArithmeticOperator::Shl => (unsigned_left << unsigned_right) as i128,
+        ArithmeticOperator::LShr => (unsigned_left >> unsigned_right) as i128,
+        ArithmeticOperator::AShr => signed_left >> unsigned_right,
+
+        ArithmeticOperator::FAdd | ArithmeticOperator::FSub | ArithmeticOperator::FMul | ArithmeticOperator::FDiv => return None,
+    };
+
+    Some(integer(result, ty))
+}
+
+
+fn fold_floating(operator: ArithmeticOperator, ty: IrType, left: f64, right: f64) -> Option<Operand> {
+    let result = match operator {
+        ArithmeticOperator::FAdd => left + right,
+        ArithmeticOperator::FSub => left - right,
+        ArithmeticOperator::FMul => left * right,
+        ArithmeticOperator::FDiv => left / right,
+        _ => return None,
+    };
+
+    Some(Operand::Floating(round(result, ty)))
+}
+
+
+/* * * * * * * * * * */
+/*  - COMPARISON -  */
+/* * * * * * * * * * */
+fn compare_integers(predicate: ComparePredicate, ty: IrType, left: i64, right: i64) -> Option<bool> {
+    let (signed_left, signed_right) = (signed(left, ty), signed(right, ty));
+    let (unsigned_left, unsigned_right) = (unsigned(left, ty), unsigned(right, ty));
+
+    Some(match predicate {
+        ComparePredicate::Eq => unsigned_left == unsigned_right,
+        ComparePredicate::Ne => unsigned_left != unsigned_right,
+        ComparePredicate::Slt => signed_left < signed_right,
+        ComparePredicate::Sle => signed_left <= signed_right,
+        ComparePredicate::Sgt => signed_left > signed_right,
+        ComparePredicate::Sge => signed_left >= signed_right,
+        ComparePredicate::Ult => unsigned_left < unsigned_right,
+        ComparePredicate::Ule => unsigned_left <= unsigned_right,
+        ComparePredicate::Ugt => unsigned_left > unsigned_right,
+        ComparePredicate::Uge => unsigned_left >= unsigned_right,
+        _ => return None,
+    })
+}
+
+
+fn compare_floats(predicate: ComparePredicate, left: f64, right: f64) -> Option<bool> {
+    Some(match predicate {
+        ComparePredicate::FEq => left == right,
+        ComparePredicate::FNe => left != right,
+        ComparePredicate::FLt => left < right,
+        ComparePredicate::FLe => left <= right,
+        ComparePredicate::FGt => left > right,
+        ComparePredicate::FGe => left >= right,
+        _ => return None,
+    })
+}
+
+
+fn compare_addresses(predicate: ComparePredicate, left: &Operand, right: &Operand) -> Option<bool> {
+    let equal = left == right;
+
+    match predicate {
+        ComparePredicate::Eq => Some(equal),
+        ComparePredicate::Ne => Some(!equal),
+        _ => None,
+    }
+}
+
+
+/* * * * * * * * */
+/*  - HELPERS -  */
+/* * * * * * * * */
+fn round(value: f64, ty: IrType) -> f64 {
+    match ty {
+        IrType::F32 => value as f32 as f64,
+        _ => value,
+    }
+}
+
+
+fn float_to_integer(value: f64, ty: IrType, signed: bool) -> Option<Operand> {
+    let truncated = value.trunc();
+    let bits = ty.bits() as i32;
+
+    let (minimum, limit) = match signed {
+        true => (-(2f64.powi(bits - 1)), 2f64.powi(bits - 1)),
+        false => (0.0, 2f64.powi(bits)),
+    };
+
+    if truncated.is_nan() || truncated < minimum || truncated >= limit {
+        return None;
+    }
+
+    Some(integer(truncated as i128, ty))
+}
@@ -0,0 +1,243 @@
+use crate::data::structures::{ Analyses, AvailableValue, GvnState, Instruction, IrFunction, Location, ValueTable };
+use crate::data::types::{ ArithmeticOperator, BlockId, ComparePredicate, InstructionKind, IrType, MemoryBase, Operand };
+use crate::logic::fold::{ fold_binary, fold_compare, fold_convert };
+
+
+pub fn gvn(function: &mut IrFunction, analyses: &mut Analyses) -> bool {
+    let children = IrFunction::dominator_tree(analyses.dominators(function));
+    let predecessors = function.predecessors();
+    let mut gvn_state = GvnState::default();
+
+    classify_addresses(&mut gvn_state, function);
+    find_escaped_slots(&mut gvn_state, function);
+
+    let mut stack = vec![(BlockId(0), ValueTable::default())];
+
+    while let Some((block, mut table)) = stack.pop() {
+        number_block(&mut gvn_state, &mut table, &mut function.blocks[block.0 as usize].instructions);
+
+        for child in &children[block.0 as usize] {
+            let mut inherited = table.clone();
+
+            if predecessors[child.0 as usize] != [block] {
+                inherited.memory.clear();
+            }
+
+            stack.push((*child, inherited));
+        }
+    }
+
+    if gvn_state.replacements.is_empty() {
+        return false;
+    }
+
+    function.replace_operands(&gvn_state.replacements);
+    true
+}
+
+
+/* * * * * * * * * */
+/*  - NUMBERING -  */
+/* * * * * * * * * */
+fn number_block(gvn_state: &mut GvnState, table: &mut ValueTable, instructions: &mut Vec<Instruction>) {
+    let mut kept = Vec::with_capacity(instructions.len());
+
+    for mut instruction in std::mem::take(instructions) {
+        for operand in instruction.operands_mut() {
+            operand.substitute(&gvn_state.replacements);
+        }
+
+        canonicalize(&mut instruction);
+
+        if let Some(result) = instruction.result && let Some(value) = lookup(table, &instruction) {
+            gvn_state.replacements.insert(result, value);
+            continue;
+        }
+
+        record(gvn_state, table, &instruction);
+        kept.push(instruction);
+    }
+
+    *instructions = kept;
+}
+
+
+fn lookup(table: &ValueTable, instruction: &Instruction) -> Option<Operand> {
+    let ty = instruction.ty;
+
+    let folded = match &instruction.kind {
+        InstructionKind::Binary { operator, left, right, no_signed_wrap } => fold_binary(*operator, ty, *no_signed_wrap, left, right),
+        InstructionKind::Compare { predicate, left, right } => fold_compare(*predicate, ty, left, right),
+        InstructionKind::Convert { operator, value, from } => fold_convert(*operator, *from, ty, value),
+        InstructionKind::PtrAdd { base, offset: Operand::Integer(0) } => Some(base.clone()),
+        _ => None,
+    };
+
+    if folded.is_some() {
+        return folded;
+    }
+
+    match &instruction.kind {
+        InstructionKind::Binary { .. } | InstructionKind::Compare { .. } | InstructionKind::Convert { .. } | InstructionKind::PtrAdd { .. } => {
+            table.expressions.get(&(ty, instruction.kind.clone())).cloned()
+        }
+
+        InstructionKind::Load { address, volatile: false } => {
+            table.memory.iter().rev().find(|available| available.address == *address && available.ty == ty).map(|available| available.value.clone())
+        }
+
+        _ => None,
+    }
+}
+
+
+fn record(gvn_state: &GvnState, table: &mut ValueTable, instruction: &Instruction) {
+    let ty = instruction.ty;
+
+    match (&instruction.kind, instruction.result) {
+        (InstructionKind::Binary { .. } | InstructionKind::Compare { .. } | InstructionKind::Convert { .. } | InstructionKind::PtrAdd { .. }, Some(result)) => {
+            table.expressions.insert((ty, instruction.kind.clone()), Operand::Register(result));
+        }
+
+        (InstructionKind::Load { address, volatile: false }, Some(result)) => remember(gvn_state, table, address, ty, Operand::Register(result)),
+
+        (InstructionKind::Store { value, address, volatile }, _) => {
+            clobber(gvn_state, table, address, ty.size());
+
+            if !volatile {
+                remember(gvn_state, table, address, ty, value.clone());
+            }
+        }
+
+        (InstructionKind::Copy { destination, size, .. }, _) => clobber(gvn_state, table, destination, *size),
+        (InstructionKind::Zero { address, size }, _) => clobber(gvn_state, table, address, *size),
+
+        (InstructionKind::Call { .. }, _) => {
+            table.memory.retain(|available| matches!(&available.location.base, MemoryBase::Slot(slot) if !gvn_state.escaped.contains(slot)));
+        }
+
+        _ => {}
+    }
+}
+
+
+fn remember(gvn_state: &GvnState, table: &mut ValueTable, address: &Operand, ty: IrType, value: Operand) {
+    table.memory.push(AvailableValue { address: address.clone(), location: location(gvn_state, address), ty, value });
+}
+
+
+fn clobber(gvn_state: &GvnState, table: &mut ValueTable, address: &Operand, size: u64) {
+    let written = location(gvn_state, address);
+    table.memory.retain(|available| !may_alias(gvn_state, &available.location, available.ty.size(), &written, size));
+}
+
+
+fn canonicalize(instruction: &mut Instruction) {
+    let (left, right) = match &mut instruction.kind {
+        InstructionKind::Binary {
+            operator: ArithmeticOperator::Add | ArithmeticOperator::Mul | ArithmeticOperator::And | ArithmeticOperator::Or
+                | ArithmeticOperator::Xor | ArithmeticOperator::FAdd | ArithmeticOperator::FMul,
+            left,
+            right,
+            ..
+        } => (left, right),
+
+        InstructionKind::Compare { predicate: ComparePredicate::Eq | ComparePredicate::Ne | ComparePredicate::FEq | ComparePredicate::FNe, left, right } => (left, right),
+        _ => return,
+    };
+
+    if rank(right) < rank(left) {
+        std::mem::swap(left, right);
+    }
+}
+
+
+fn rank(operand: &Operand) -> (u8, u32) {
+    match operand {
+        Operand::Register(register) => (0, register.0),
+        _ => (1, 0),
+    }
+}
+
+
+/* * * * * * * * * * * * * */
+/*  - ALIAS ANALYSIS -  */
+/* * * * * * * * * * * * * */
+fn classify_addresses(gvn_state: &mut GvnState, function: &IrFunction) {
+    for block in function.reverse_postorder() {
+        for instruction in &function.blocks[block.0 as usize].instructions {
+            let Some(result) = instruction.result else {
+                continue;
+            };
+
+            let location = match &instruction.kind {
+                InstructionKind::Alloca { .. } => Location { base: MemoryBase::Slot(result), offset: Some(0) },
+
+                InstructionKind::PtrAdd { base, offset } => {
+                    let base = location(gvn_state, base);
+
+                    let offset = match (base.offset, offset) {
+                        (Some(start), Operand::Integer(offset)) => start.checked_add(*offset),
+                        _ => None,
+                    };
+
+                    Location { base: base.base, offset }
+                }
+
+                _ => continue,
+            };
+
+            gvn_state.locations.insert(result, location);
+        }
+    }
+}
+
+
+fn find_escaped_slots(gvn_state: &mut GvnState, function: &IrFunction) {
+    for block in &function.blocks {
+        for instruction in &block.instructions {
+            let mut instruction = instruction.clone();
+
+            let escaping = match &mut instruction.kind {
+                InstructionKind::Load { .. } | InstructionKind::Zero { .. } | InstructionKind::Copy { .. } | InstructionKind::PtrAdd { .. }
+                    | InstructionKind::Compare { .. } | InstructionKind::Alloca { .. } => continue,
+
+                InstructionKind::Store { value, .. } => vec![value],
+                _ => instruction.operands_mut(),
+            };
+
+            for operand in escaping {
+                if let Operand::Register(register) = operand
+                    && let Some(Location { base: MemoryBase::Slot(slot), .. }) = gvn_state.locations.get(register) {
+                    gvn_state.escaped.insert(*slot);
+                }
+            }
+        }
+    }
+}
+
+
+fn location(gvn_state: &GvnState, address: &Operand) -> Location {
+    match address {
+        Operand::Register(register) if gvn_state.locations.contains_key(register) => gvn_state.locations[register].clone(),
+        Operand::Global(name) => Location { base: MemoryBase::Global(name.clone()), offset: Some(0) },
+        _ => Location { base: MemoryBase::Unknown(address.clone()), offset: Some(0) },
+    }
+}
+
+
+fn may_alias(gvn_state: &GvnState, left: &Location, left_size: u64, right: &Location, right_size: u64) -> bool {
+    let same_object = match (&left.base, &right.base) {
+        (MemoryBase::Slot(left), MemoryBase::Slot(right)) => left == right,
+        (MemoryBase::Global(left), MemoryBase::Global(right)) => left == right,
+        (MemoryBase::Slot(_), MemoryBase::Global(_)) | (MemoryBase::Global(_), MemoryBase::Slot(_)) => false,
+        (MemoryBase::Slot(slot), MemoryBase::Unknown(_)) | (MemoryBase::Unknown(_), MemoryBase::Slot(slot)) => return gvn_state.escaped.contains(slot),
+        (MemoryBase::Unknown(left), MemoryBase::Unknown(right)) if left == right => true,
+        _ => return true,
+    };
+
+    same_object && match (left.offset, right.offset) {
+        (Some(left), Some(right)) => left < right + right_size as i64 && right < left + left_size as i64,
+        _ => true,
+    }
+}
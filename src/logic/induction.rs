@@ -0,0 +1,470 @@
+use std::collections::{ HashMap, HashSet };
+
+use crate::data::structures::{ Analyses, InductionState, Instruction, IrFunction, Liveness, Loop };
+use crate::data::types::{ Analysis, ArithmeticOperator, BlockId, InstructionKind, IrType, Operand, Register };
+use crate::logic::fold::{ fold_binary, fold_compare, integer };
+
+const TRIP_COUNT_LIMIT: u64 = 1 << 16;
+
+
+pub fn indvars(function: &mut IrFunction, analyses: &mut Analyses) -> bool {
+    let (loops, mut changed) = analyses.canonical_loops(function);
+
+    for natural in loops.iter().rev() {
+        let rewritten = merge_induction_variables(function, natural) | reduce_strength(function, natural);
+
+        if rewritten {
+            analyses.invalidate(&[Analysis::Dominators, Analysis::Loops]);
+        }
+
+        let replaced = replace_exit_values(function, natural, analyses.liveness(function));
+
+        if replaced {
+            analyses.invalidate(&[Analysis::Dominators, Analysis::Loops]);
+        }
+
+        changed |= rewritten | replaced;
+    }
+
+    changed
+}
+
+
+pub fn trip_count(function: &IrFunction, natural: &Loop) -> Option<u64> {
+    let mut induction_state = analyse(function, natural)?;
+    let header = &function.blocks[natural.header.0 as usize];
+
+    for block in &natural.blocks {
+        let terminator = function.blocks[block.0 as usize].instructions.last()?;
+        let leaves = terminator.successors().iter().any(|successor| !induction_state.inside.contains(successor));
+
+        if matches!(terminator.kind, InstructionKind::Return(_) | InstructionKind::Unreachable) || (leaves && *block != natural.header) {
+            return None;
+        }
+    }
+
+    let InstructionKind::Branch { condition: Operand::Register(condition), then_block, else_block } = &header.instructions.last()?.kind else {
+        return None;
+    };
+
+    let exit = match (induction_state.inside.contains(then_block), induction_state.inside.contains(else_block)) {
+        (true, false) => *else_block,
+        (false, true) => *then_block,
+        _ => return None,
+    };
+
+    let Some(Instruction { ty, kind: InstructionKind::Compare { predicate, left, right }, .. }) = induction_state.definitions.get(condition).cloned() else {
+        return None;
+    };
+
+    let mut left = constants(&recurrence(&mut induction_state, &left, ty)?)?;
+    let mut right = constants(&recurrence(&mut induction_state, &right, ty)?)?;
+
+    for iteration in 0..=TRIP_COUNT_LIMIT {
+        let taken = fold_compare(predicate, ty, &Operand::Integer(left[0]), &Operand::Integer(right[0]))?;
+        let target = if taken == Operand::Integer(0) { *else_block } else { *then_block };
+
+        if target == exit {
+            return Some(iteration);
+        }
+
+        advance(&mut left, ty);
+        advance(&mut right, ty);
+    }
+
+    None
+}
+
+
+/* * * * * * * * * * */
+/*  - SIMPLIFYING -  */
+/* * * * * * * * * * */
+fn merge_induction_variables(function: &mut IrFunction, natural: &Loop) -> bool {
+    let Some(induction_state) = analyse(function, natural) else {
+        return false;
+    };
+
+    let mut leaders: HashMap<(IrType, Vec<Operand>), Register> = HashMap::new();
+    let mut replacements = HashMap::new();
+
+    for instruction in &function.blocks[natural.header.0 as usize].instructions {
+        let (Some(result), InstructionKind::Phi { .. }) = (instruction.result, &instruction.kind) else {
+            continue;
+        };
+
+        let Some(Some(recurrence)) = induction_state.recurrences.get(&result) else {
+            continue;
+        };
+
+        match leaders.get(&(instruction.ty, recurrence.clone())) {
+            Some(leader) => {
+                replacements.insert(result, Operand::Register(*leader));
+            }
+
+            None => {
+                leaders.insert((instruction.ty, recurrence.clone()), result);
+            }
+        }
+    }
+
+    if replacements.is_empty() {
+        return false;
+    }
+
+    function.blocks[natural.header.0 as usize].instructions.retain(|instruction| instruction.result.is_none_or(|result| !replacements.contains_key(&result)));
+    function.replace_operands(&replacements);
+
+    true
+}
+
+
+fn reduce_strength(function: &mut IrFunction, natural: &Loop) -> bool {
+    let Some(mut induction_state) = analyse(function, natural) else {
+        return false;
+    };
+
+    let mut candidates: Vec<(Register, IrType)> = induction_state.definitions.iter()
+        .filter(|(_, instruction)| instruction.ty.is_integer() && matches!(instruction.kind, InstructionKind::Binary { operator: ArithmeticOperator::Mul | ArithmeticOperator::Shl, .. }))
+        .map(|(register, instruction)| (*register, instruction.ty))
+        .collect();
+
+    candidates.sort_by_key(|(register, _)| *register);
+
+    let mut replacements = HashMap::new();
+    let mut phis = Vec::new();
+    let mut updates = Vec::new();
+
+    for (result, ty) in candidates {
+        let Some(recurrence) = recurrence(&mut induction_state, &Operand::Register(result), ty).filter(|recurrence| recurrence.len() >= 2) else {
+            continue;
+        };
+
+        let registers: Vec<Register> = (1..recurrence.len()).map(|_| fresh(&mut induction_state)).collect();
+
+        for (index, register) in registers.iter().enumerate() {
+            let step = registers.get(index + 1).map_or_else(|| recurrence[recurrence.len() - 1].clone(), |next| Operand::Register(*next));
+            let update = fresh(&mut induction_state);
+
+            updates.push(Instruction {
+                result: Some(update),
+                ty,
+                kind: InstructionKind::Binary { operator: ArithmeticOperator::Add, left: Operand::Register(*register), right: step, no_signed_wrap: false },
+            });
+
+            phis.push(Instruction {
+                result: Some(*register),
+                ty,
+                kind: InstructionKind::Phi { incoming: vec![(recurrence[index].clone(), induction_state.preheader), (Operand::Register(update), induction_state.latch)] },
+            });
+        }
+
+        replacements.insert(result, Operand::Register(registers[0]));
+    }
+
+    if replacements.is_empty() {
+        return false;
+    }
+
+    for block in &natural.blocks {
+        function.blocks[block.0 as usize].instructions.retain(|instruction| instruction.result.is_none_or(|result| !replacements.contains_key(&result)));
+    }
+
+    insert_before_terminator(function, induction_state.latch, updates);
+    function.blocks[natural.header.0 as usize].instructions.splice(0..0, phis);
+    flush(function, &mut induction_state);
+    function.replace_operands(&replacements);
+
+    true
+}
+
+
+fn replace_exit_values(function: &mut IrFunction, natural: &Loop, liveness: &Liveness) -> bool {
+    let escaping = escaping_registers(function, natural, liveness);
+
+    if escaping.is_empty() {
+        return false;
+    }
+
+    let Some(trip_count) = trip_count(function, natural) else {
+        return false;
+    };
+
+    let Some(mut induction_state) = analyse(function, natural) else {
+        return false;
+    };
+
+    let mut replacements = HashMap::new();
+
+    for instruction in &function.blocks[natural.header.0 as usize].instructions {
+        let Some(result) = instruction.result.filter(|result| instruction.ty.is_integer() && escaping.contains(result)) else {
+            continue;
+        };
+
+        if let Some(mut values) = recurrence(&mut induction_state, &Operand::Register(result), instruction.ty).and_then(|recurrence| constants(&recurrence)) {
+            for _ in 0..trip_count {
+                advance(&mut values, instruction.ty);
+            }
+
+            replacements.insert(result, Operand::Integer(values[0]));
+        }
+    }
+
+    let mut changed = false;
+
+    for block in function.blocks.iter_mut().filter(|block| !induction_state.inside.contains(&block.id)) {
+        for instruction in &mut block.instructions {
+            for operand in instruction.operands_mut() {
+                if matches!(operand, Operand::Register(register) if replacements.contains_key(register)) {
+                    operand.substitute(&replacements);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    changed
+}
+
+
+fn escaping_registers(function: &IrFunction, natural: &Loop, liveness: &Liveness) -> HashSet<Register> {
+    let mut escaping = HashSet::new();
+
+    for block in &natural.blocks {
+        let (inside, exits): (Vec<BlockId>, Vec<BlockId>) = function.blocks[block.0 as usize].successors().into_iter()
+            .partition(|successor| natural.blocks.contains(successor));
+
+        if exits.is_empty() {
+            continue;
+        }
+
+        let continuing: HashSet<Register> = inside.iter().flat_map(|successor| liveness.live_in[successor.0 as usize].iter().copied()).collect();
+        escaping.extend(liveness.live_out[block.0 as usize].iter().filter(|register| !continuing.contains(register)));
+
+        for exit in exits {
+            escaping.extend(liveness.live_in[exit.0 as usize].iter().copied());
+        }
+    }
+
+    escaping
+}
+
+
+/* * * * * * * * * * * */
+/*  - RECURRENCES -  */
+/* * * * * * * * * * * */
+fn analyse(function: &IrFunction, natural: &Loop) -> Option<InductionState> {
+    let preheader = function.preheader(natural)?;
+
+    let [latch] = natural.latches[..] else {
+        return None;
+    };
+
+    let mut induction_state = InductionState {
+        preheader,
+        latch,
+        inside: natural.blocks.iter().copied().collect(),
+        next_register: function.next_register,
+        ..InductionState::default()
+    };
+
+    for block in &natural.blocks {
+        for instruction in &function.blocks[block.0 as usize].instructions {
+            if let Some(result) = instruction.result {
+                induction_state.definitions.insert(result, instruction.clone());
+            }
+        }
+    }
+
+    for instruction in &function.blocks[natural.header.0 as usize].instructions {
+        if let Some(result) = instruction.result && let Some(recurrence) = basic_induction_variable(&induction_state, instruction) {
+            induction_state.recurrences.insert(result, Some(recurrence));
+        }
+    }
+
+    Some(induction_state)
+}
+
+
+fn basic_induction_variable(induction_state: &InductionState, instruction: &Instruction) -> Option<Vec<Operand>> {
+    let InstructionKind::Phi { incoming } = &instruction.kind else {
+        return None;
+    };
+
+    let phi = Operand::Register(instruction.result?);
+    let ty = instruction.ty;
+
+    let [(start, first), (next, second)] = &incoming[..] else {
+        return None;
+    };
+
+    let (start, next) = match (*first == induction_state.preheader, *second == induction_state.preheader) {
+        (true, false) => (start, next),
+        (false, true) => (next, start),
+        _ => return None,
+    };
+
+    let Operand::Register(next) = next else {
+        return None;
+    };
+
+    let step = match &induction_state.definitions.get(next)?.kind {
+        InstructionKind::Binary { operator: ArithmeticOperator::Add, left, right, .. } if *left == phi && invariant(induction_state, right) => right.clone(),
+        InstructionKind::Binary { operator: ArithmeticOperator::Add, left, right, .. } if *right == phi && invariant(induction_state, left) => left.clone(),
+        InstructionKind::Binary { operator: ArithmeticOperator::Sub, left, right: Operand::Integer(value), .. } if *left == phi => integer(-(*value as i128), ty),
+        _ => return None,
+    };
+
+    (ty.is_integer() && invariant(induction_state, start)).then(|| trim(vec![start.clone(), step]))
+}
+
+
+fn recurrence(induction_state: &mut InductionState, operand: &Operand, ty: IrType) -> Option<Vec<Operand>> {
+    if !ty.is_integer() {
+        return None;
+    }
+
+    let Operand::Register(register) = operand else {
+        return Some(vec![operand.clone()]);
+    };
+
+    let Some(instruction) = induction_state.definitions.get(register).cloned() else {
+        return Some(vec![operand.clone()]);
+    };
+
+    if let Some(known) = induction_state.recurrences.get(register) {
+        return known.clone();
+    }
+
+    induction_state.recurrences.insert(*register, None);
+
+    let result = match &instruction.kind {
+        InstructionKind::Binary { operator: operator @ (ArithmeticOperator::Add | ArithmeticOperator::Sub), left, right, .. } => {
+            let left = recurrence(induction_state, left, ty)?;
+            let right = recurrence(induction_state, right, ty)?;
+            let length = left.len().max(right.len());
+
+            (0..length)
+                .map(|index| {
+                    let left = left.get(index).cloned().unwrap_or(Operand::Integer(0));
+                    let right = right.get(index).cloned().unwrap_or(Operand::Integer(0));
+                    coefficient(induction_state, *operator, ty, left, right)
+                })
+                .collect()
+        }
+
+        InstructionKind::Binary { operator: ArithmeticOperator::Mul, left, right, .. } => {
+            let left = recurrence(induction_state, left, ty)?;
+            let right = recurrence(induction_state, right, ty)?;
+            multiply(induction_state, ty, &left, &right)?
+        }
+
+        InstructionKind::Binary { operator: ArithmeticOperator::Shl, left, right: Operand::Integer(amount), .. } if (0..ty.bits() as i64).contains(amount) => {
+            let left = recurrence(induction_state, left, ty)?;
+            multiply(induction_state, ty, &left, &[integer(1i128 << amount, ty)])?
+        }
+
+        _ => return None,
+    };
+
+    let result = Some(trim(result)).filter(|recurrence| recurrence.len() <= 3);
+    induction_state.recurrences.insert(*register, result.clone());
+    result
+}
+
+
+fn multiply(induction_state: &mut InductionState, ty: IrType, left: &[Operand], right: &[Operand]) -> Option<Vec<Operand>> {
+    match (left, right) {
+        ([factor], recurrence) | (recurrence, [factor]) => {
+            Some(recurrence.iter().map(|term| coefficient(induction_state, ArithmeticOperator::Mul, ty, term.clone(), factor.clone())).collect())
+        }
+
+        ([a, b], [c, d]) => {
+            let ac = coefficient(induction_state, ArithmeticOperator::Mul, ty, a.clone(), c.clone());
+            let ad = coefficient(induction_state, ArithmeticOperator::Mul, ty, a.clone(), d.clone());
+            let bc = coefficient(induction_state, ArithmeticOperator::Mul, ty, b.clone(), c.clone());
+            let bd = coefficient(induction_state, ArithmeticOperator::Mul, ty, b.clone(), d.clone());
+            let cross = coefficient(induction_state, ArithmeticOperator::Add, ty, ad, bc);
+            let first = coefficient(induction_state, ArithmeticOperator::Add, ty, cross, bd.clone());
+            let second = coefficient(induction_state, ArithmeticOperator::Add, ty, bd.clone(), bd);
+
+            Some(vec![ac, first, second])
+        }
+
+        _ => None,
+    }
+}
+
+
+fn coefficient(induction_state: &mut InductionState, operator: ArithmeticOperator, ty: IrType, left: Operand, right: Operand) -> Operand {
+    if let Some(folded) = fold_binary(operator, ty, false, &left, &right) {
+        return folded;
+    }
+
+    match (operator, &left, &right) {
+        (ArithmeticOperator::Add | ArithmeticOperator::Sub, _, Operand::Integer(0)) | (ArithmeticOperator::Mul, _, Operand::Integer(1)) => return left,
+        (ArithmeticOperator::Add, Operand::Integer(0), _) | (ArithmeticOperator::Mul, Operand::Integer(1), _) => return right,
+        (ArithmeticOperator::Mul, Operand::Integer(0), _) | (ArithmeticOperator::Mul, _, Operand::Integer(0)) => return Operand::Integer(0),
+        _ => {}
+    }
+
+    let result = fresh(induction_state);
+    induction_state.pending.push(Instruction { result: Some(result), ty, kind: InstructionKind::Binary { operator, left, right, no_signed_wrap: false } });
+
+    Operand::Register(result)
+}
+
+
+/* * * * * * * * */
+/*  - HELPERS -  */
+/* * * * * * * * */
+fn invariant(induction_state: &InductionState, operand: &Operand) -> bool {
+    !matches!(operand, Operand::Register(register) if induction_state.definitions.contains_key(register))
+}
+
+
+fn trim(mut recurrence: Vec<Operand>) -> Vec<Operand> {
+    while recurrence.len() > 1 && recurrence.last() == Some(&Operand::Integer(0)) {
+        recurrence.pop();
+    }
+
+    recurrence
+}
+
+
+fn constants(recurrence: &[Operand]) -> Option<Vec<i64>> {
+    recurrence.iter()
+        .map(|term| match term {
+            Operand::Integer(value) => Some(*value),
+            _ => None,
+        })
+        .collect()
+}
+
+
+fn advance(values: &mut [i64], ty: IrType) {
+    for index in 1..values.len() {
+        if let Operand::Integer(sum) = integer(values[index - 1] as i128 + values[index] as i128, ty) {
+            values[index - 1] = sum;
+        }
+    }
+}
+
+
+fn fresh(induction_state: &mut InductionState) -> Register {
+    induction_state.next_register += 1;
+    Register(induction_state.next_register - 1)
+}
+
+
+fn flush(function: &mut IrFunction, induction_state: &mut InductionState) {
+    function.next_register = induction_state.next_register;
+    insert_before_terminator(function, induction_state.preheader, std::mem::take(&mut induction_state.pending));
+}
+
+
+fn insert_before_terminator(function: &mut IrFunction, block: BlockId, instructions: Vec<Instruction>) {
+    let body = &mut function.blocks[block.0 as usize].instructions;
+    let terminator = body.len() - 1;
+    body.splice(terminator..terminator, instructions);
+}
+
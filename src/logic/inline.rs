@@ -0,0 +1,370 @@
+use std::collections::{ HashMap, HashSet };
+
+use crate::data::Module;
+use crate::data::structures::{ Block, CallGraph, Instruction, IrFunction };
+use crate::data::types::{ BlockId, InlineHint, InstructionKind, IrType, Operand, Register };
+use crate::logic::simplify_cfg::remove_unreachable_blocks;
+
+const INLINE_THRESHOLD: usize = 25;
+const INLINE_HINT_THRESHOLD: usize = 75;
+const SINGLE_CALLER_THRESHOLD: usize = 250;
+const MAX_CALLER_SIZE: usize = 2000;
+
+
+pub fn inline(module: &mut Module) -> bool {
+    let call_graph = CallGraph::new(module);
+    let components = call_graph.components();
+    let mut component_of = vec![0; module.functions.len()];
+    let mut changed = false;
+
+    for (index, component) in components.iter().enumerate() {
+        for &member in component {
+            component_of[member] = index;
+        }
+    }
+
+    for component in &components {
+        for &caller in component {
+            changed |= inline_calls(module, &call_graph, &component_of, caller);
+        }
+    }
+
+    changed | remove_dead_functions(module)
+}
+
+
+/* * * * * * * * * */
+/*  - DECISIONS -  */
+/* * * * * * * * * */
+fn inline_calls(module: &mut Module, call_graph: &CallGraph, component_of: &[usize], caller: usize) -> bool {
+    let mut cloned = HashSet::new();
+    let mut changed = false;
+    let mut block = 0;
+
+    while block < module.functions[caller].blocks.len() {
+        if cloned.contains(&block) {
+            block += 1;
+            continue;
+        }
+
+        let site = module.functions[caller].blocks[block].instructions.iter().enumerate().find_map(|(index, instruction)| {
+            let InstructionKind::Call { callee: Operand::Global(name), arguments } = &instruction.kind else {
+                return None;
+            };
+
+            let callee = *call_graph.indices.get(name)?;
+            let compatible = module.functions[callee].parameters.iter().map(|(ty, _)| *ty).eq(arguments.iter().map(|(ty, _)| *ty))
+                && module.functions[callee].return_type == instruction.ty;
+
+            (compatible && should_inline(module, call_graph, component_of, caller, callee)).then_some((index, callee))
+        });
+
+        if let Some((index, callee)) = site {
+            let body = module.functions[callee].clone();
+            cloned.extend(inline_call(&mut module.functions[caller], block, index, &body));
+            changed = true;
+        }
+
+        block += 1;
+    }
+
+    if changed {
+        remove_unreachable_blocks(&mut module.functions[caller]);
+    }
+
+    changed
+}
+
+
+fn should_inline(module: &Module, call_graph: &CallGraph, component_of: &[usize], caller: usize, callee: usize) -> bool {
+    let function = &module.functions[callee];
+
+    if function.is_declaration() || function.variadic || component_of[caller] == component_of[callee] || function.inline_hint == InlineHint::Never {
+        return false;
+    }
+
+    let dynamic_allocation = function.blocks.iter().enumerate().any(|(index, block)| {
+        block.instructions.iter().any(|instruction| matches!(&instruction.kind, InstructionKind::Alloca { size, .. } if index != 0 || !matches!(size, Operand::Integer(_))))
+    });
+
+    if dynamic_allocation {
+        return false;
+    }
+
+    let size = function.instruction_count();
+
+    if function.inline_hint == InlineHint::Always {
+        return true;
+    }
+
+    if module.functions[caller].instruction_count() + size > MAX_CALLER_SIZE {
+        return false;
+    }
+
+    let single_caller = function.internal && call_graph.call_sites[callee] == 1 && !call_graph.address_taken.contains(&callee);
+
+    match function.inline_hint {
+        InlineHint::Hint => size <= INLINE_HINT_THRESHOLD,
+        _ if single_caller => size <= SINGLE_CALLER_THRESHOLD,
+        _ => size <= INLINE_THRESHOLD,
+    }
+}
+
+
+/* * * * * * * * * */
+/*  - INLINING -  */
+/* * * * * * * * * */
+fn inline_call(caller: &mut IrFunction, block: usize, index: usize, callee: &IrFunction) -> Vec<usize> {
+    let mut instructions = std::mem::take(&mut caller.blocks[block].instructions);
+    let rest = instructions.split_off(index + 1);
+
+    let Some(Instruction { result, kind: InstructionKind::Call { arguments, .. }, .. }) = instructions.pop() else {
+        return Vec::new();
+    };
+
+    let base = caller.blocks.len() as u32;
+    let continuation = BlockId(base + callee.blocks.len() as u32);
+    let mut mapping: HashMap<Register, Operand> = callee.parameters.iter().map(|(_, register)| *register).zip(arguments.into_iter().map(|(_, argument)| argument)).collect();
+
+    for instruction in callee.blocks.iter().flat_map(|block| &block.instructions) {
+        if let Some(register) = instruction.result {
+            mapping.insert(register, Operand::Register(caller.new_register()));
+        }
+    }
+
+    let mut returns = Vec::new();
+    let mut allocations = Vec::new();
+
+    for callee_block in &callee.blocks {
+        let id = BlockId(base + callee_block.id.0);
+        let mut body = Vec::with_capacity(callee_block.instructions.len());
+
+        for instruction in &callee_block.instructions {
+            let mut copy = instruction.clone();
+
+            for operand in copy.operands_mut() {
+                if let Operand::Register(register) = operand && let Some(value) = mapping.get(register) {
+                    *operand = value.clone();
+                }
+            }
+
+            copy.result = copy.result.and_then(|register| match mapping[&register] {
+                Operand::Register(mapped) => Some(mapped),
+                _ => None,
+            });
+
+            relocate(&mut copy, base);
+
+            if let InstructionKind::Return(value) = copy.kind {
+                returns.extend(value.map(|value| (value, id)));
+                copy = Instruction { result: None, ty: IrType::Void, kind: InstructionKind::Jump(continuation) };
+            }
+
+            match copy.kind {
+                InstructionKind::Alloca { .. } if callee_block.id == BlockId(0) => allocations.push(copy),
+                _ => body.push(copy),
+            }
+        }
+
+        caller.blocks.push(Block { id, instructions: body });
+    }
+
+    let mut replacements = HashMap::new();
+    let mut tail = Vec::with_capacity(rest.len() + 1);
+
+    if let Some(result) = result {
+        match &returns[..] {
+            [] => {
+                replacements.insert(result, Operand::Undef);
+            }
+
+            [(value, _)] => {
+                replacements.insert(result, value.clone());
+            }
+
+            _ => tail.push(Instruction { result: Some(result), ty: callee.return_type, kind: InstructionKind::Phi { incoming: returns } }),
+        }
+    }
+
+    tail.extend(rest);
+    let successors = tail.last().map(Instruction::successors).unwrap_or_default();
+    caller.blocks.push(Block { id: continuation, instructions: tail });
+
+    instructions.push(Instruction { result: None, ty: IrType::Void, kind: InstructionKind::Jump(BlockId(base)) });
+    caller.blocks[block].instructions = instructions;
+
+    for successor in successors {
+        for instruction in &mut caller.blocks[successor.0 as usize].instructions {
+            if let InstructionKind::Phi { incoming } = &mut instruction.kind {
+                for (_, predecessor) in incoming.iter_mut().filter(|(_, predecessor)| predecessor.0 as usize == block) {
+                    *predecessor = continuation;
+                }
+            }
+        }
+    }
+
+    caller.blocks[0].instructions.splice(0..0, allocations);
+    caller.replace_operands(&replacements);
+
+    (base as usize..continuation.0 as usize).collect()
+}
+
+
+fn relocate(instruction: &mut Instruction, base: u32) {
+    let targets: Vec<&mut BlockId> = match &mut instruction.kind {
+        InstructionKind::Jump(target) => vec![target],
+        InstructionKind::Branch { then_block, else_block, .. } => vec![then_block, else_block],
+        InstructionKind::Switch { default, cases, .. } => std::iter::once(default).chain(cases.iter_mut().map(|(_, target)| target)).collect(),
+        InstructionKind::Phi { incoming } => incoming.iter_mut().map(|(_, predecessor)| predecessor).collect(),
+        _ => Vec::new(),
+    };
+
+    for target in targets {
+        target.0 += base;
+    }
+}
+
+
+fn remove_dead_functions(module: &mut Module) -> bool {
+    let mut referenced: HashSet<String> = module.globals.iter().flat_map(|global| global.relocations.iter().map(|relocation| relocation.symbol.clone())).collect();
+
+    for function in &module.functions {
+        for instruction in function.blocks.iter().flat_map(|block| &block.instructions) {
+            for operand in instruction.clone().operands_mut() {
+                if let Operand::Global(name) = operand && *name != function.name {
+                    referenced.insert(name.clone());
+                }
+            }
+        }
+    }
+
+    let count = module.functions.len();
+    module.functions.retain(|function| !function.internal || function.is_declaration() || referenced.contains(&function.name));
+
+    module.functions.len() != count
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{ INLINE_THRESHOLD, inline };
+    use crate::data::{ Module, Target };
+    use crate::data::types::{ InstructionKind, Operand, Severity };
+    use crate::logic::{ interpreter_start, ir_start, lexer_start, parser_start, semantic_start, sym_table_start, verifier_start };
+
+
+    fn compile(source: &str) -> Module {
+        let tokens = lexer_start(source).expect("source should lex");
+        let (mut ast, errors) = parser_start(&tokens);
+        assert!(errors.is_empty(), "parse errors: {:?}", errors);
+
+        let target = Target::x86_64_sysv();
+        let (table, mut diagnostics) = sym_table_start(&ast);
+        let (types, semantic_diagnostics) = semantic_start(&mut ast, &table, &target);
+        diagnostics.extend(semantic_diagnostics);
+
+        let (module, ir_diagnostics) = ir_start(&ast, &table, &types, &target);
+        diagnostics.extend(ir_diagnostics);
+
+        let errors: Vec<String> = diagnostics.iter().filter(|diagnostic| diagnostic.severity == Severity::Error).map(ToString::to_string).collect();
+        assert!(errors.is_empty(), "compile errors: {:?}", errors);
+
+        module
+    }
+
+
+    fn run(module: &Module) -> (i32, String) {
+        let mut output = Vec::new();
+        let code = interpreter_start(module, "inline", &mut output).unwrap_or_else(|diagnostic| panic!("{}", diagnostic));
+
+        (code, String::from_utf8(output).expect("output should be UTF-8"))
+    }
+
+
+    fn inline_and_compare(source: &str) -> Module {
+        let mut module = compile(source);
+        let before = run(&module);
+
+        inline(&mut module);
+
+        let diagnostics: Vec<String> = verifier_start(&module).iter().map(ToString::to_string).collect();
+        assert!(diagnostics.is_empty(), "invalid module after inlining: {:?}\n{}", diagnostics, module);
+        assert_eq!(before, run(&module), "inlining changed the behaviour of\n{}", module);
+
+        module
+    }
+
+
+    fn calls(module: &Module, caller: &str, callee: &str) -> bool {
+        let Some(function) = module.functions.iter().find(|function| function.name == caller) else {
+            return false;
+        };
+
+        function.blocks.iter().flat_map(|block| &block.instructions).any(|instruction| {
+            matches!(&instruction.kind, InstructionKind::Call { callee: Operand::Global(name), .. } if name == callee)
+        })
+    }
+
+
+    #[test]
+    fn inlines_small_callee() {
+        let path = format!("{}/examples/oddEven2.c", env!("CARGO_MANIFEST_DIR"));
+        let source = std::fs::read_to_string(&path).expect("example should exist");
+        let module = inline_and_compare(&source);
+
+        assert!(!calls(&module, "main", "check_even"), "check_even was not inlined:\n{}", module);
+    }
+
+
+    #[test]
+    fn keeps_recursive_component() {
+        let module = inline_and_compare("
+            int printf(const char *, ...);
+            static int is_odd(int n);
+            static int is_even(int n) { return n == 0 ? 1 : is_odd(n - 1); }
+            static int is_odd(int n) { return n == 0 ? 0 : is_even(n - 1); }
+            int main(void) { printf(\"%d %d\\n\", is_even(10), is_odd(7)); return is_even(5); }
+        ");
+
+        assert!(calls(&module, "is_even", "is_odd"), "is_odd was inlined into is_even:\n{}", module);
+        assert!(calls(&module, "is_odd", "is_even"), "is_even was inlined into is_odd:\n{}", module);
+    }
+
+
+    #[test]
+    fn keeps_noinline_call() {
+        let module = inline_and_compare("
+            int printf(const char *, ...);
+            [[gnu::noinline]] static int twice(int x) { return x * 2; }
+            int main(void) { printf(\"%d\\n\", twice(21)); return twice(3); }
+        ");
+
+        assert!(calls(&module, "main", "twice"), "noinline callee was inlined:\n{}", module);
+    }
+
+
+    #[test]
+    fn inlines_large_always_inline_callee() {
+        let source = "
+            int printf(const char *, ...);
+            [[gnu::always_inline]] int mix(int a, int b) {
+                int x = a * 3 + b;
+                int y = x ^ (a << 2);
+                int z = y - b * 7;
+                x = x + y * z;
+                y = y ^ (x >> 1);
+                z = z + x - y;
+                x = x * 5 + z;
+                y = y + x % 13;
+                return x + y + z;
+            }
+            int main(void) { printf(\"%d\\n\", mix(3, 4)); return mix(1, 2) & 127; }
+        ";
+
+        let size = compile(source).functions.iter().find(|function| function.name == "mix").map_or(0, |function| function.instruction_count());
+        assert!(size > INLINE_THRESHOLD, "mix has only {} instructions", size);
+
+        let module = inline_and_compare(source);
+        assert!(!calls(&module, "main", "mix"), "always_inline callee was not inlined:\n{}", module);
+    }
+}
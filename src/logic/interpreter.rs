@@ -0,0 +1,1317 @@
+use std::io::{ BufRead, Write };
+
+use crate::data::{ Diagnostic, Module };
+use crate::data::structures::{ Frame, Instruction, InterpreterState, IrFunction };
+use crate::data::types::{ AllocationKind, ArithmeticOperator, BlockId, ComparePredicate, ConversionOperator, InstructionKind, IrType, Operand, Severity };
+
+
+type RunResult<T> = Result<T, String>;
+
+const MAX_FRAMES: usize = 100_000;
+
+
+pub fn interpreter_start(module: &Module, program: &str, stdout: &mut dyn Write) -> Result<i32, Diagnostic> {
+    let mut interpreter_state = InterpreterState::new(module, stdout);
+    let result = initialize(&mut interpreter_state, module, program).and_then(|_| run(&mut interpreter_state));
+    flush(&mut interpreter_state);
+
+    result.map_err(|message| {
+        let location = interpreter_state.frames.last().map(|frame| {
+            let instruction = &frame.function.blocks[frame.block.0 as usize].instructions[frame.position];
+            format!("in `@{}`: {}: `{}`: ", frame.function.name, frame.block, instruction)
+        });
+
+        Diagnostic::new(Severity::Error, format!("runtime error: {}{}", location.unwrap_or_default(), message))
+    })
+}
+
+
+/* * * * * * * * * * * * */
+/*  - INITIALIZATION -  */
+/* * * * * * * * * * * * */
+fn initialize<'a>(interpreter_state: &mut InterpreterState<'a>, module: &'a Module, program: &str) -> RunResult<()> {
+    for global in &module.globals {
+        let address = match global.name.as_str() {
+            "stdin" | "stdout" | "stderr" if global.external => {
+                let descriptor = ["stdin", "stdout", "stderr"].iter().position(|name| *name == global.name).unwrap_or(0);
+                let stream = interpreter_state.allocate(0, AllocationKind::Stream(descriptor as i32));
+                let address = interpreter_state.allocate(IrType::Ptr.size(), AllocationKind::Global);
+                store(interpreter_state, address, IrType::Ptr, stream)?;
+
+                address
+            }
+
+            _ if global.external => interpreter_state.allocate(IrType::Ptr.size(), AllocationKind::Global),
+
+            _ => {
+                let address = interpreter_state.allocate(global.size, AllocationKind::Global);
+                write_bytes(interpreter_state, address, &global.data)?;
+
+                address
+            }
+        };
+
+        interpreter_state.symbols.insert(&global.name, address);
+    }
+
+    for function in &module.functions {
+        let address = interpreter_state.allocate(0, AllocationKind::Function(function.name.clone()));
+        interpreter_state.symbols.insert(&function.name, address);
+    }
+
+    for global in module.globals.iter().filter(|global| !global.external) {
+        let base = interpreter_state.symbols[global.name.as_str()];
+
+        for relocation in &global.relocations {
+            let target = symbol_address(interpreter_state, &relocation.symbol)?;
+            store(interpreter_state, base + relocation.offset, IrType::Ptr, target.wrapping_add(relocation.addend as u64))?;
+        }
+
+        interpreter_state.allocations[(base >> 32) as usize].writable = !global.constant;
+    }
+
+    let Some(&main) = interpreter_state.functions.get("main") else {
+        return Err("no definition of `@main`".to_string());
+    };
+
+    let mut arguments = Vec::new();
+
+    if main.parameters.len() >= 2 {
+        let name = [program.as_bytes(), &[0]].concat();
+        let string = interpreter_state.allocate(name.len() as u64, AllocationKind::Global);
+        write_bytes(interpreter_state, string, &name)?;
+
+        let vector = interpreter_state.allocate(2 * IrType::Ptr.size(), AllocationKind::Global);
+        store(interpreter_state, vector, IrType::Ptr, string)?;
+
+        arguments.push((main.parameters[0].0, 1));
+        arguments.push((IrType::Ptr, vector));
+    }
+
+    push_frame(interpreter_state, main, &arguments)
+}
+
+
+/* * * * * * * * * * */
+/*  - EXECUTION -  */
+/* * * * * * * * * * */
+fn run(interpreter_state: &mut InterpreterState) -> RunResult<i32> {
+    loop {
+        if let Some(code) = interpreter_state.exit_code {
+            return Ok(code);
+        }
+
+        let frame = interpreter_state.frames.last().expect("no active frame");
+        let function = frame.function;
+        let instruction = &function.blocks[frame.block.0 as usize].instructions[frame.position];
+
+        if let Some(code) = execute(interpreter_state, instruction)? {
+            return Ok(code);
+        }
+    }
+}
+
+
+fn push_frame<'a>(interpreter_state: &mut InterpreterState<'a>, function: &'a IrFunction, arguments: &[(IrType, u64)]) -> RunResult<()> {
+    if interpreter_state.frames.len() >= MAX_FRAMES {
+        return Err("call stack overflow".to_string());
+    }
+
+    let size = function.next_register.max(function.parameters.len() as u32) as usize;
+    let mut frame = Frame { function, block: BlockId(0), position: 0, registers: vec![0; size], allocas: Vec::new() };
+
+    for ((ty, register), (_, value)) in function.parameters.iter().zip(arguments) {
+        frame.registers[register.0 as usize] = convert_argument(*value, *ty);
+    }
+
+    interpreter_state.frames.push(frame);
+
+    Ok(())
+}
+
+
+fn execute(interpreter_state: &mut InterpreterState, instruction: &Instruction) -> RunResult<Option<i32>> {
+    let ty = instruction.ty;
+
+    let result = match &instruction.kind {
+        InstructionKind::Binary { operator, left, right, .. } => {
+            let left = value(interpreter_state, left, ty)?;
+            let right = value(interpreter_state, right, ty)?;
+
+            Some(arithmetic(*operator, left, right, ty)?)
+        }
+
+        InstructionKind::Compare { predicate, left, right } => {
+            let left = value(interpreter_state, left, ty)?;
+            let right = value(interpreter_state, right, ty)?;
+
+            Some(compare(*predicate, left, right, ty) as u64)
+        }
+
+        InstructionKind::Convert { operator, value: operand, from } => {
+            let operand = value(interpreter_state, operand, *from)?;
+            Some(convert(*operator, operand, *from, ty))
+        }
+
+        InstructionKind::Alloca { size, .. } => {
+            let size = value(interpreter_state, size, IrType::I64)?;
+
+            if size > u32::MAX as u64 {
+                return Err(format!("stack allocation of {} bytes is too large", size as i64));
+            }
+
+            let address = interpreter_state.allocate(size, AllocationKind::Stack);
+            interpreter_state.frame().allocas.push((address >> 32) as usize);
+
+            Some(address)
+        }
+
+        InstructionKind::Load { address, .. } => {
+            let address = value(interpreter_state, address, IrType::Ptr)?;
+            Some(load(interpreter_state, address, ty)?)
+        }
+
+        InstructionKind::Store { value: stored, address, .. } => {
+            let stored = value(interpreter_state, stored, ty)?;
+            let address = value(interpreter_state, address, IrType::Ptr)?;
+            store(interpreter_state, address, ty, stored)?;
+
+            None
+        }
+
+        InstructionKind::PtrAdd { base, offset } => {
+            let base = value(interpreter_state, base, IrType::Ptr)?;
+            let offset = value(interpreter_state, offset, IrType::I64)?;
+
+            Some(base.wrapping_add(offset))
+        }
+
+        InstructionKind::Copy { destination, source, size } => {
+            let destination = value(interpreter_state, destination, IrType::Ptr)?;
+            let source = value(interpreter_state, source, IrType::Ptr)?;
+            let bytes = read_bytes(interpreter_state, source, *size)?;
+            write_bytes(interpreter_state, destination, &bytes)?;
+
+            None
+        }
+
+        InstructionKind::Zero { address, size } => {
+            let address = value(interpreter_state, address, IrType::Ptr)?;
+            write_bytes(interpreter_state, address, &vec![0; *size as usize])?;
+
+            None
+        }
+
+        InstructionKind::Call { callee, arguments } => return call(interpreter_state, instruction, callee, arguments),
+
+        InstructionKind::Phi { .. } => return Err("phi after the start of a block".to_string()),
+
+        InstructionKind::Jump(target) => {
+            enter_block(interpreter_state, *target)?;
+            return Ok(None);
+        }
+
+        InstructionKind::Branch { condition, then_block, else_block } => {
+            let condition = value(interpreter_state, condition, IrType::I1)?;
+            enter_block(interpreter_state, if condition != 0 { *then_block } else { *else_block })?;
+
+            return Ok(None);
+        }
+
+        InstructionKind::Switch { value: operand, default, cases } => {
+            let operand = value(interpreter_state, operand, ty)?;
+            let target = cases.iter().find(|(case, _)| mask(*case as u64, ty) == operand).map_or(*default, |(_, target)| *target);
+            enter_block(interpreter_state, target)?;
+
+            return Ok(None);
+        }
+
+        InstructionKind::Return(operand) => {
+            let result = match operand {
+                Some(operand) => Some(value(interpreter_state, operand, ty)?),
+                None => None,
+            };
+
+            return leave_frame(interpreter_state, result, ty);
+        }
+
+        InstructionKind::Unreachable => return Err("reached `unreachable`".to_string()),
+    };
+
+    let frame = interpreter_state.frame();
+
+    if let (Some(register), Some(result)) = (instruction.result, result) {
+        frame.registers[register.0 as usize] = result;
+    }
+
+    frame.position += 1;
+
+    Ok(None)
+}
+
+
+fn enter_block(interpreter_state: &mut InterpreterState, target: BlockId) -> RunResult<()> {
+    let frame = interpreter_state.frames.last().expect("no active frame");
+    let previous = frame.block;
+    let block = &frame.function.blocks[target.0 as usize];
+    let mut assignments = Vec::new();
+
+    for instruction in &block.instructions {
+        let InstructionKind::Phi { incoming } = &instruction.kind else {
+            break;
+        };
+
+        let Some((operand, _)) = incoming.iter().find(|(_, block)| *block == previous) else {
+            return Err(format!("phi in {} has no value for {}", target, previous));
+        };
+
+        assignments.push((instruction.result, value(interpreter_state, operand, instruction.ty)?));
+    }
+
+    let frame = interpreter_state.frame();
+    frame.block = target;
+    frame.position = assignments.len();
+
+    for (register, value) in assignments {
+        if let Some(register) = register {
+            frame.registers[register.0 as usize] = value;
+        }
+    }
+
+    Ok(())
+}
+
+
+fn call(interpreter_state: &mut InterpreterState, instruction: &Instruction, callee: &Operand, arguments: &[(IrType, Operand)]) -> RunResult<Option<i32>> {
+    let address = value(interpreter_state, callee, IrType::Ptr)?;
+    let mut values = Vec::new();
+
+    for (ty, argument) in arguments {
+        values.push((*ty, value(interpreter_state, argument, *ty)?));
+    }
+
+    let name = match interpreter_state.allocations.get((address >> 32) as usize) {
+        Some(allocation) if address & 0xffff_ffff == 0 && let AllocationKind::Function(name) = &allocation.kind => name.clone(),
+        _ => return Err(format!("call through pointer {:#x} that does not point to a function", address)),
+    };
+
+    if let Some(&function) = interpreter_state.functions.get(name.as_str()) {
+        return push_frame(interpreter_state, function, &values).map(|_| None);
+    }
+
+    let result = call_host(interpreter_state, &name, &values)?;
+    let frame = interpreter_state.frame();
+
+    if let Some(register) = instruction.result {
+        frame.registers[register.0 as usize] = mask(result, instruction.ty);
+    }
+
+    frame.position += 1;
+
+    Ok(None)
+}
+
+
+fn leave_frame(interpreter_state: &mut InterpreterState, result: Option<u64>, ty: IrType) -> RunResult<Option<i32>> {
+    let frame = interpreter_state.frames.pop().expect("no active frame");
+
+    for index in frame.allocas {
+        let allocation = &mut interpreter_state.allocations[index];
+        allocation.live = false;
+        allocation.data = Vec::new();
+        interpreter_state.released.push(index);
+    }
+
+    let Some(caller) = interpreter_state.frames.last_mut() else {
+        let code = match (result, ty) {
+            (Some(result), ty) if ty.is_integer() => signed(result, ty) as i32,
+            _ => 0,
+        };
+
+        return Ok(Some(code));
+    };
+
+    let function = caller.function;
+    let instruction = &function.blocks[caller.block.0 as usize].instructions[caller.position];
+
+    if let Some(register) = instruction.result {
+        caller.registers[register.0 as usize] = result.map_or(0, |result| mask(result, instruction.ty));
+    }
+
+    caller.position += 1;
+
+    Ok(None)
+}
+
+
+/* * * * * * * * * * */
+/*  - OPERATIONS -  */
+/* * * * * * * * * * */
+fn value(interpreter_state: &InterpreterState, operand: &Operand, ty: IrType) -> RunResult<u64> {
+    match operand {
+        Operand::Register(register) => Ok(interpreter_state.frames.last().expect("no active frame").registers[register.0 as usize]),
+        Operand::Integer(value) => Ok(mask(*value as u64, ty)),
+        Operand::Floating(value) if ty == IrType::F32 => Ok((*value as f32).to_bits() as u64),
+        Operand::Floating(value) => Ok(value.to_bits()),
+        Operand::Global(name) => symbol_address(interpreter_state, name),
+        Operand::Null | Operand::Undef => Ok(0),
+    }
+}
+
+
+fn symbol_address(interpreter_state: &InterpreterState, name: &str) -> RunResult<u64> {
+    interpreter_state.symbols.get(name).copied().ok_or_else(|| format!("reference to undefined symbol `@{}`", name))
+}
+
+
+fn arithmetic(operator: ArithmeticOperator, left: u64, right: u64, ty: IrType) -> RunResult<u64> {
+    let bits = ty.bits();
+
+    let result = match operator {
+        ArithmeticOperator::Add => left.wrapping_add(right),
+        ArithmeticOperator::Sub => left.wrapping_sub(right),
+        ArithmeticOperator::Mul => left.wrapping_mul(right),
+        ArithmeticOperator::And => left & right,
+        ArithmeticOperator::Or => left | right,
+        ArithmeticOperator::Xor => left ^ right,
+
+        ArithmeticOperator::SDiv | ArithmeticOperator::SRem | ArithmeticOperator::UDiv | ArithmeticOperator::URem if right == 0 => {
+            return Err("integer division by zero".to_string());
+        }
+
+        ArithmeticOperator::SDiv => signed(left, ty).wrapping_div(signed(right, ty)) as u64,
+        ArithmeticOperator::SRem => signed(left, ty).wrapping_rem(signed(right, ty)) as u64,
+        ArithmeticOperator::UDiv => left / right,
+        ArithmeticOperator::URem => left % right,
+        ArithmeticOperator::Shl if right >= bits as u64 => 0,
+        ArithmeticOperator::Shl => left << right,
+        ArithmeticOperator::LShr if right >= bits as u64 => 0,
+        ArithmeticOperator::LShr => left >> right,
+        ArithmeticOperator::AShr => (signed(left, ty) >> right.min(63)) as u64,
+
+        ArithmeticOperator::FAdd | ArithmeticOperator::FSub | ArithmeticOperator::FMul | ArithmeticOperator::FDiv => {
+            let (left, right) = (float(left, ty), float(right, ty));
+
+            let result = match operator {
+                ArithmeticOperator::FAdd => left + right,
+                ArithmeticOperator::FSub => left - right,
+                ArithmeticOperator::FMul => left * right,
+                _ => left / right,
+            };
+
+            return Ok(from_float(result, ty));
+        }
+    };
+
+    Ok(mask(result, ty))
+}
+
+
+fn compare(predicate: ComparePredicate, left: u64, right: u64, ty: IrType) -> bool {
+    if predicate.is_floating() {
+        let (left, right) = (float(left, ty), float(right, ty));
+
+        return match predicate {
+            ComparePredicate::FEq => left == right,
+            ComparePredicate::FNe => left != right,
+            ComparePredicate::FLt => left < right,
+            ComparePredicate::FLe => left <= right,
+            ComparePredicate::FGt => left > right,
+            _ => left >= right,
+        };
+    }
+
+    let (signed_left, signed_right) = (signed(left, ty), signed(right, ty));
+
+    match predicate {
+        ComparePredicate::Eq => left == right,
+        ComparePredicate::Ne => left != right,
+        ComparePredicate::Slt => signed_left < signed_right,
+        ComparePredicate::Sle => signed_left <= signed_right,
+        ComparePredicate::Sgt => signed_left > signed_right,
+        ComparePredicate::Sge => signed_left >= signed_right,
+        ComparePredicate::Ult => left < right,
+        ComparePredicate::Ule => left <= right,
+        ComparePredicate::Ugt => left > right,
+        _ => left >= right,
+    }
+}
+
+
+fn convert(operator: ConversionOperator, value: u64, from: IrType, to: IrType) -> u64 {
+    match operator {
+        ConversionOperator::Trunc | ConversionOperator::ZExt | ConversionOperator::PtrToInt | ConversionOperator::IntToPtr => mask(value, to),
+        ConversionOperator::SExt => mask(signed(value, from) as u64, to),
+        ConversionOperator::FpTrunc | ConversionOperator::FpExt => from_float(float(value, from), to),
+        ConversionOperator::FpToSi => mask(float(value, from) as i64 as u64, to),
+        ConversionOperator::FpToUi => mask(float(value, from) as u64, to),
+        ConversionOperator::SiToFp => from_float(signed(value, from) as f64, to),
+        ConversionOperator::UiToFp => from_float(value as f64, to),
+    }
+}
+
+
+fn convert_argument(value: u64, ty: IrType) -> u64 {
+    match ty {
+        IrType::F32 | IrType::F64 | IrType::Ptr => value,
+        _ => mask(value, ty),
+    }
+}
+
+
+fn mask(value: u64, ty: IrType) -> u64 {
+    match ty.bits() {
+        0 | 64.. => value,
+        bits => value & ((1 << bits) - 1),
+    }
+}
+
+
+fn signed(value: u64, ty: IrType) -> i64 {
+    match ty.bits() {
+        0 | 64.. => value as i64,
+        bits => ((value << (64 - bits)) as i64) >> (64 - bits),
+    }
+}
+
+
+fn float(value: u64, ty: IrType) -> f64 {
+    match ty {
+        IrType::F32 => f32::from_bits(value as u32) as f64,
+        _ => f64::from_bits(value),
+    }
+}
+
+
+fn from_float(value: f64, ty: IrType) -> u64 {
+    match ty {
+        IrType::F32 => (value as f32).to_bits() as u64,
+        _ => value.to_bits(),
+    }
+}
+
+
+/* * * * * * * * */
+/*  - MEMORY -  */
+/* * * * * * * * */
+fn resolve(interpreter_state: &InterpreterState, address: u64, size: u64, write: bool) -> RunResult<(usize, usize)> {
+    let index = (address >> 32) as usize;
+    let offset = address & 0xffff_ffff;
+
+    if index == 0 {
+        return Err(format!("null pointer dereference at address {:#x}", address));
+    }
+
+    let Some(allocation) = interpreter_state.allocations.get(index) else {
+        return Err(format!("access through invalid pointer {:#x}", address));
+    };
+
+    if !allocation.live {
+        return Err("use of memory after it was freed".to_string());
+    }
+
+    if offset + size > allocation.data.len() as u64 {
+        return Err(format!("out-of-bounds access of {} bytes at offset {} in an allocation of {} bytes", size, offset as i32, allocation.data.len()));
+    }
+
+    if write && !allocation.writable {
+        return Err("write to read-only memory".to_string());
+    }
+
+    Ok((index, offset as usize))
+}
+
+
+fn read_bytes(interpreter_state: &InterpreterState, address: u64, size: u64) -> RunResult<Vec<u8>> {
+    if size == 0 {
+        return Ok(Vec::new());
+    }
+
+    let (index, offset) = resolve(interpreter_state, address, size, false)?;
+
+    Ok(interpreter_state.allocations[index].data[offset..offset + size as usize].to_vec())
+}
+
+
+fn write_bytes(interpreter_state: &mut InterpreterState, address: u64, bytes: &[u8]) -> RunResult<()> {
+    if bytes.is_empty() {
+        return Ok(());
+    }
+
+    let (index, offset) = resolve(interpreter_state, address, bytes.len() as u64, true)?;
+    interpreter_state.allocations[index].data[offset..offset + bytes.len()].copy_from_slice(bytes);
+
+    Ok(())
+}
+
+
+fn load(interpreter_state: &InterpreterState, address: u64, ty: IrType) -> RunResult<u64> {
+    let bytes = read_bytes(interpreter_state, address, ty.size())?;
+    let mut buffer = [0; 8];
+    buffer[..bytes.len()].copy_from_slice(&bytes);
+
+    Ok(mask(u64::from_le_bytes(buffer), ty))
+}
+
+
+fn store(interpreter_state: &mut InterpreterState, address: u64, ty: IrType, value: u64) -> RunResult<()> {
+    let bytes = value.to_le_bytes();
+    write_bytes(interpreter_state, address, &bytes[..ty.size() as usize])
+}
+
+
+fn read_string(interpreter_state: &InterpreterState, address: u64, limit: Option<usize>) -> RunResult<Vec<u8>> {
+    let (index, offset) = resolve(interpreter_state, address, 0, false)?;
+    let data = &interpreter_state.allocations[index].data[offset..];
+    let data = &data[..limit.map_or(data.len(), |limit| limit.min(data.len()))];
+
+    match data.iter().position(|&byte| byte == 0) {
+        Some(length) => Ok(data[..length].to_vec()),
+        None if limit.is_some_and(|limit| data.len() >= limit) => Ok(data.to_vec()),
+        None => Err("string is not null-terminated within its allocation".to_string()),
+    }
+}
+
+
+/* * * * * * * * * * * * * */
+/*  - HOST FUNCTIONS -  */
+/* * * * * * * * * * * * * */
+fn call_host(interpreter_state: &mut InterpreterState, name: &str, arguments: &[(IrType, u64)]) -> RunResult<u64> {
+    let argument = |index: usize| arguments.get(index).map(|(_, value)| *value).ok_or_else(|| format!("too few arguments to `{}`", name));
+
+    match name {
+        "read" => {
+            let (descriptor, buffer, count) = (signed(argument(0)?, IrType::I32), argument(1)?, argument(2)?);
+
+            if descriptor != 0 {
+                return Ok(-1i64 as u64);
+            }
+
+            if !fill_input(interpreter_state) {
+                return Ok(0);
+            }
+
+            let available = &interpreter_state.input[interpreter_state.input_position..];
+            let bytes = available[..available.len().min(count as usize)].to_vec();
+            interpreter_state.input_position += bytes.len();
+            write_bytes(interpreter_state, buffer, &bytes)?;
+
+            Ok(bytes.len() as u64)
+        }
+
+        "write" => {
+            let (descriptor, buffer, count) = (signed(argument(0)?, IrType::I32), argument(1)?, argument(2)?);
+            let bytes = read_bytes(interpreter_state, buffer, count)?;
+
+            match write_stream(interpreter_state, descriptor, &bytes) {
+                true => Ok(count),
+                false => Ok(-1i64 as u64),
+            }
+        }
+
+        "printf" => {
+            let format = read_string(interpreter_state, argument(0)?, None)?;
+            let text = format_printf(interpreter_state, &format, &arguments[1..])?;
+            write_stream(interpreter_state, 1, &text);
+
+            Ok(text.len() as u64)
+        }
+
+        "scanf" => {
+            let format = read_string(interpreter_state, argument(0)?, None)?;
+            Ok(scan(interpreter_state, &format, &arguments[1..])? as u64)
+        }
+
+        "puts" => {
+            let mut text = read_string(interpreter_state, argument(0)?, None)?;
+            text.push(b'\n');
+            write_stream(interpreter_state, 1, &text);
+
+            Ok(text.len() as u64)
+        }
+
+        "putchar" => {
+            let character = argument(0)?;
+            write_stream(interpreter_state, 1, &[character as u8]);
+
+            Ok(character & 0xff)
+        }
+
+        "strlen" => Ok(read_string(interpreter_state, argument(0)?, None)?.len() as u64),
+
+        "fgets" => {
+            let (buffer, size, stream) = (argument(0)?, signed(argument(1)?, IrType::I32), argument(2)?);
+
+            if !matches!(interpreter_state.allocations.get((stream >> 32) as usize).map(|allocation| &allocation.kind), Some(AllocationKind::Stream(0))) {
+                return Err("`fgets` only supports `stdin`".to_string());
+            }
+
+            if size <= 0 {
+                return Ok(0);
+            }
+
+            let mut line = Vec::new();
+
+            while line.len() + 1 < size as usize && let Some(byte) = next_input(interpreter_state) {
+                line.push(byte);
+
+                if byte == b'\n' {
+                    break;
+                }
+            }
+
+            if line.is_empty() && size > 1 {
+                return Ok(0);
+            }
+
+            line.push(0);
+            write_bytes(interpreter_state, buffer, &line)?;
+
+            Ok(buffer)
+        }
+
+        "malloc" => {
+            let size = argument(0)?;
+
+            match size > u32::MAX as u64 {
+                true => Ok(0),
+                false => Ok(interpreter_state.allocate(size, AllocationKind::Heap)),
+            }
+        }
+
+        "free" => {
+            let address = argument(0)?;
+
+            if address == 0 {
+                return Ok(0);
+            }
+
+            match interpreter_state.allocations.get_mut((address >> 32) as usize) {
+                Some(allocation) if allocation.kind == AllocationKind::Heap && address & 0xffff_ffff == 0 => {
+                    if !allocation.live {
+                        return Err("memory freed twice".to_string());
+                    }
+
+                    allocation.live = false;
+                    allocation.data = Vec::new();
+
+                    Ok(0)
+                }
+
+                _ => Err(format!("`free` of pointer {:#x} that was not returned by `malloc`", address)),
+            }
+        }
+
+        "exit" => {
+            interpreter_state.exit_code = Some(signed(argument(0)?, IrType::I32) as i32);
+            Ok(0)
+        }
+
+        _ => Err(format!("call to unsupported external function `@{}`", name)),
+    }
+}
+
+
+fn write_stream(interpreter_state: &mut InterpreterState, descriptor: i64, bytes: &[u8]) -> bool {
+    match descriptor {
+        1 => {
+            interpreter_state.output.extend_from_slice(bytes);
+
+            if interpreter_state.output.len() > 1 << 16 {
+                flush(interpreter_state);
+            }
+
+            true
+        }
+
+        2 => {
+            flush(interpreter_state);
+            std::io::stderr().write_all(bytes).is_ok()
+        }
+
+        _ => false,
+    }
+}
+
+
+fn flush(interpreter_state: &mut InterpreterState) {
+    let _ = interpreter_state.stdout.write_all(&interpreter_state.output);
+    let _ = interpreter_state.stdout.flush();
+    interpreter_state.output.clear();
+}
+
+
+fn fill_input(interpreter_state: &mut InterpreterState) -> bool {
+    if interpreter_state.input_position < interpreter_state.input.len() {
+        return true;
+    }
+
+    if interpreter_state.input_closed {
+        return false;
+    }
+
+    flush(interpreter_state);
+    interpreter_state.input.clear();
+    interpreter_state.input_position = 0;
+
+    match std::io::stdin().lock().read_until(b'\n', &mut interpreter_state.input) {
+        Ok(0) | Err(_) => {
+            interpreter_state.input_closed = true;
+            false
+        }
+
+        Ok(_) => true,
+    }
+}
+
+
+fn peek_input(interpreter_state: &mut InterpreterState) -> Option<u8> {
+    fill_input(interpreter_state).then(|| interpreter_state.input[interpreter_state.input_position])
+}
+
+
+fn next_input(interpreter_state: &mut InterpreterState) -> Option<u8> {
+    let byte = peek_input(interpreter_state)?;
+    interpreter_state.input_position += 1;
+
+    Some(byte)
+}
+
+
+/* * * * * * * * * * */
+/*  - FORMATTING -  */
+/* * * * * * * * * * */
+fn format_printf(interpreter_state: &mut InterpreterState, format: &[u8], arguments: &[(IrType, u64)]) -> RunResult<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut remaining = arguments.iter();
+    let mut position = 0;
+
+    let mut next = || remaining.next().copied().ok_or_else(|| "too few arguments for format string".to_string());
+
+    while position < format.len() {
+        if format[position] != b'%' {
+            output.push(format[position]);
+            position += 1;
+            continue;
+        }
+
+        position += 1;
+        let mut flags = Vec::new();
+
+        while let Some(&flag) = format.get(position).filter(|flag| b"-+ #0".contains(flag)) {
+            flags.push(flag);
+            position += 1;
+        }
+
+        let mut width = 0;
+
+        if format.get(position) == Some(&b'*') {
+            let value = signed(next()?.1, IrType::I32);
+            position += 1;
+
+            if value < 0 {
+                flags.push(b'-');
+            }
+
+            width = value.unsigned_abs() as usize;
+        }
+
+        while let Some(digit) = format.get(position).filter(|digit| digit.is_ascii_digit()) {
+            width = width * 10 + (digit - b'0') as usize;
+            position += 1;
+        }
+
+        let mut precision = None;
+
+        if format.get(position) == Some(&b'.') {
+            position += 1;
+            let mut value = 0;
+
+            if format.get(position) == Some(&b'*') {
+                let argument = signed(next()?.1, IrType::I32);
+                position += 1;
+                value = argument;
+            }
+
+            while let Some(digit) = format.get(position).filter(|digit| digit.is_ascii_digit()) {
+                value = value * 10 + (digit - b'0') as i64;
+                position += 1;
+            }
+
+            precision = (value >= 0).then_some(value as usize);
+        }
+
+        let length = length_modifier(format, &mut position);
+
+        let Some(&conversion) = format.get(position) else {
+            return Err("incomplete conversion at the end of the format string".to_string());
+        };
+
+        position += 1;
+
+        let (prefix, body, numeric) = match conversion {
+            b'%' => {
+                output.push(b'%');
+                continue;
+            }
+
+            b'd' | b'i' => {
+                let value = signed_argument(next()?.1, length);
+                let sign = if value < 0 { "-" } else if flags.contains(&b'+') { "+" } else if flags.contains(&b' ') { " " } else { "" };
+
+                (sign.to_string(), integer_digits(value.unsigned_abs().to_string(), precision), precision.is_none())
+            }
+
+            b'u' | b'x' | b'X' | b'o' => {
+                let value = unsigned_argument(next()?.1, length);
+
+                let digits = match conversion {
+                    b'u' => value.to_string(),
+                    b'x' => format!("{:x}", value),
+                    b'X' => format!("{:X}", value),
+                    _ => format!("{:o}", value),
+                };
+
+                let mut digits = integer_digits(digits, precision);
+                let mut prefix = String::new();
+
+                if flags.contains(&b'#') {
+                    match conversion {
+                        b'x' if value != 0 => prefix = "0x".to_string(),
+                        b'X' if value != 0 => prefix = "0X".to_string(),
+                        b'o' if !digits.starts_with('0') => digits.insert(0, '0'),
+                        _ => {}
+                    }
+                }
+
+                (prefix, digits, precision.is_none())
+            }
+
+            b'c' => {
+                let character = next()?.1 as u8;
+                pad(&mut output, &[], &[character], width, &flags, false);
+                continue;
+            }
+
+            b's' => {
+                let text = read_string(interpreter_state, next()?.1, precision)?;
+                pad(&mut output, &[], &text, width, &flags, false);
+                continue;
+            }
+
+            b'p' => {
+                let value = next()?.1;
+
+                match value {
+                    0 => (String::new(), "(nil)".to_string(), false),
+                    _ => ("0x".to_string(), format!("{:x}", value), false),
+                }
+            }
+
+            b'f' | b'F' | b'e' | b'E' | b'g' | b'G' => {
+                let (ty, bits) = next()?;
+                let value = if ty.is_floating() { float(bits, ty) } else { f64::from_bits(bits) };
+                let sign = if value.is_sign_negative() { "-" } else if flags.contains(&b'+') { "+" } else if flags.contains(&b' ') { " " } else { "" };
+                let body = format_floating(value.abs(), conversion, precision.unwrap_or(6), flags.contains(&b'#'));
+
+                (sign.to_string(), body, value.is_finite())
+            }
+
+            b'n' => {
+                let address = next()?.1;
+                let ty = match length { "hh" => IrType::I8, "h" => IrType::I16, "" => IrType::I32, _ => IrType::I64 };
+                store(interpreter_state, address, ty, output.len() as u64)?;
+                continue;
+            }
+
+            _ => return Err(format!("unsupported conversion `%{}` in format string", conversion as char)),
+        };
+
+        pad(&mut output, prefix.as_bytes(), body.as_bytes(), width, &flags, numeric);
+    }
+
+    Ok(output)
+}
+
+
+fn length_modifier<'a>(format: &[u8], position: &mut usize) -> &'a str {
+    for modifier in ["hh", "h", "ll", "l", "j", "z", "t", "L"] {
+        if format[*position..].starts_with(modifier.as_bytes()) {
+            *position += modifier.len();
+            return modifier;
+        }
+    }
+
+    ""
+}
+
+
+fn signed_argument(value: u64, length: &str) -> i64 {
+    match length {
+        "hh" => signed(value, IrType::I8),
+        "h" => signed(value, IrType::I16),
+        "" => signed(value, IrType::I32),
+        _ => value as i64,
+    }
+}
+
+
+fn unsigned_argument(value: u64, length: &str) -> u64 {
+    match length {
+        "hh" => mask(value, IrType::I8),
+        "h" => mask(value, IrType::I16),
+        "" => mask(value, IrType::I32),
+        _ => value,
+    }
+}
+
+
+fn integer_digits(digits: String, precision: Option<usize>) -> String {
+    match precision {
+        Some(0) if digits == "0" => String::new(),
+        Some(precision) if digits.len() < precision => format!("{}{}", "0".repeat(precision - digits.len()), digits),
+        _ => digits,
+    }
+}
+
+
+fn pad(output: &mut Vec<u8>, prefix: &[u8], body: &[u8], width: usize, flags: &[u8], zero_allowed: bool) {
+    let length = prefix.len() + body.len();
+    let padding = width.saturating_sub(length);
+
+    if flags.contains(&b'-') {
+        output.extend_from_slice(prefix);
+        output.extend_from_slice(body);
+        output.extend(std::iter::repeat_n(b' ', padding));
+    } else if zero_allowed && flags.contains(&b'0') {
+        output.extend_from_slice(prefix);
+        output.extend(std::iter::repeat_n(b'0', padding));
+        output.extend_from_slice(body);
+    } else {
+        output.extend(std::iter::repeat_n(b' ', padding));
+        output.extend_from_slice(prefix);
+        output.extend_from_slice(body);
+    }
+}
+
+
+fn format_floating(value: f64, conversion: u8, precision: usize, alternate: bool) -> String {
+    let uppercase = conversion.is_ascii_uppercase();
+
+    let body = if value.is_nan() {
+        "nan".to_string()
+    } else if value.is_infinite() {
+        "inf".to_string()
+    } else {
+        match conversion.to_ascii_lowercase() {
+            b'f' => {
+                let text = format!("{:.*}", precision, value);
+                if alternate && precision == 0 { text + "." } else { text }
+            }
+
+            b'e' => exponential(value, precision, alternate),
+
+            _ => {
+                let precision = precision.max(1);
+                let exponent = exponential(value, precision - 1, false).rsplit('e').next().and_then(|exponent| exponent.parse::<i64>().ok()).unwrap_or(0);
+
+                let text = match exponent < -4 || exponent >= precision as i64 {
+                    true => exponential(value, precision - 1, alternate),
+                    false => format!("{:.*}", (precision as i64 - 1 - exponent) as usize, value),
+                };
+
+                if alternate { text } else { strip_zeros(text) }
+            }
+        }
+    };
+
+    if uppercase { body.to_uppercase() } else { body }
+}
+
+
+fn exponential(value: f64, precision: usize, alternate: bool) -> String {
+    let text = format!("{:.*e}", precision, value);
+    let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+    let exponent: i64 = exponent.parse().unwrap_or(0);
+    let point = if alternate && precision == 0 { "." } else { "" };
+
+    format!("{}{}e{}{:02}", mantissa, point, if exponent < 0 { '-' } else { '+' }, exponent.abs())
+}
+
+
+fn strip_zeros(text: String) -> String {
+    let (mantissa, exponent) = match text.find('e') {
+        Some(index) => (&text[..index], &text[index..]),
+        None => (text.as_str(), ""),
+    };
+
+    if !mantissa.contains('.') {
+        return text;
+    }
+
+    format!("{}{}", mantissa.trim_end_matches('0').trim_end_matches('.'), exponent)
+}
+
+
+/* * * * * * * * */
+/*  - SCANNING -  */
+/* * * * * * * * */
+fn scan(interpreter_state: &mut InterpreterState, format: &[u8], arguments: &[(IrType, u64)]) -> RunResult<i32> {
+    let mut assigned = 0;
+    let mut consumed = 0;
+    let mut remaining = arguments.iter();
+    let mut position = 0;
+
+    while position < format.len() {
+        let current = format[position];
+        position += 1;
+
+        if current.is_ascii_whitespace() {
+            consumed += skip_whitespace(interpreter_state);
+            continue;
+        }
+
+        if current != b'%' || format.get(position) == Some(&b'%') {
+            if current == b'%' {
+                position += 1;
+                consumed += skip_whitespace(interpreter_state);
+            }
+
+            match peek_input(interpreter_state) {
+                Some(byte) if byte == current => {
+                    next_input(interpreter_state);
+                    consumed += 1;
+                    continue;
+                }
+
+                Some(_) => return Ok(assigned),
+                None => return Ok(if assigned == 0 { -1 } else { assigned }),
+            }
+        }
+
+        let suppress = format.get(position) == Some(&b'*');
+
+        if suppress {
+            position += 1;
+        }
+
+        let mut width = 0;
+
+        while let Some(digit) = format.get(position).filter(|digit| digit.is_ascii_digit()) {
+            width = width * 10 + (digit - b'0') as usize;
+            position += 1;
+        }
+
+        let length = length_modifier(format, &mut position);
+
+        let Some(&conversion) = format.get(position) else {
+            return Err("incomplete conversion at the end of the format string".to_string());
+        };
+
+        position += 1;
+
+        let set = match conversion {
+            b'[' => Some(scan_set(format, &mut position)),
+            _ => None,
+        };
+
+        if !matches!(conversion, b'c' | b'[' | b'n') {
+            consumed += skip_whitespace(interpreter_state);
+        }
+
+        if conversion != b'n' && peek_input(interpreter_state).is_none() {
+            return Ok(if assigned == 0 { -1 } else { assigned });
+        }
+
+        let limit = if width == 0 { usize::MAX } else { width };
+
+        let bytes = match conversion {
+            b'd' | b'i' | b'u' | b'x' | b'X' | b'o' => {
+                let base = match conversion { b'x' | b'X' => 16, b'o' => 8, b'i' => 0, _ => 10 };
+                let text = scan_while(interpreter_state, limit, |text, byte| integer_character(text, byte, base));
+                consumed += text.len();
+
+                let Some(value) = parse_integer(&text, base) else {
+                    return Ok(assigned);
+                };
+
+                let ty = match length { "hh" => IrType::I8, "h" => IrType::I16, "" => IrType::I32, _ => IrType::I64 };
+                value.to_le_bytes()[..ty.size() as usize].to_vec()
+            }
+
+            b'f' | b'e' | b'g' | b'E' | b'G' | b'a' => {
+                let text = scan_while(interpreter_state, limit, floating_character);
+                consumed += text.len();
+
+                let Ok(value) = String::from_utf8_lossy(&text).parse::<f64>() else {
+                    return Ok(assigned);
+                };
+
+                match length {
+                    "l" | "L" => value.to_le_bytes().to_vec(),
+                    _ => (value as f32).to_le_bytes().to_vec(),
+                }
+            }
+
+            b'c' => {
+                let count = if width == 0 { 1 } else { width };
+                let text = scan_while(interpreter_state, count, |_, _| true);
+                consumed += text.len();
+
+                if text.len() < count {
+                    return Ok(if assigned == 0 { -1 } else { assigned });
+                }
+
+                text
+            }
+
+            b's' | b'[' => {
+                let mut text = match &set {
+                    Some((negated, members)) => scan_while(interpreter_state, limit, |_, byte| members.contains(&byte) != *negated),
+                    None => scan_while(interpreter_state, limit, |_, byte| !byte.is_ascii_whitespace()),
+                };
+
+                consumed += text.len();
+
+                if text.is_empty() {
+                    return Ok(assigned);
+                }
+
+                text.push(0);
+                text
+            }
+
+            b'n' => {
+                if !suppress {
+                    let address = remaining.next().ok_or("too few arguments for format string")?.1;
+                    let ty = match length { "hh" => IrType::I8, "h" => IrType::I16, "" => IrType::I32, _ => IrType::I64 };
+                    store(interpreter_state, address, ty, consumed as u64)?;
+                }
+
+                continue;
+            }
+
+            _ => return Err(format!("unsupported conversion `%{}` in format string", conversion as char)),
+        };
+
+        if suppress {
+            continue;
+        }
+
+        let address = remaining.next().ok_or("too few arguments for format string")?.1;
+        write_bytes(interpreter_state, address, &bytes)?;
+        assigned += 1;
+    }
+
+    Ok(assigned)
+}
+
+
+fn skip_whitespace(interpreter_state: &mut InterpreterState) -> usize {
+    let mut skipped = 0;
+
+    while peek_input(interpreter_state).is_some_and(|byte| byte.is_ascii_whitespace()) {
+        next_input(interpreter_state);
+        skipped += 1;
+    }
+
+    skipped
+}
+
+
+fn scan_while(interpreter_state: &mut InterpreterState, limit: usize, accept: impl Fn(&[u8], u8) -> bool) -> Vec<u8> {
+    let mut text = Vec::new();
+
+    while text.len() < limit && let Some(byte) = peek_input(interpreter_state) {
+        if !accept(&text, byte) {
+            break;
+        }
+
+        text.push(byte);
+        next_input(interpreter_state);
+    }
+
+    text
+}
+
+
+fn scan_set(format: &[u8], position: &mut usize) -> (bool, Vec<u8>) {
+    let negated = format.get(*position) == Some(&b'^');
+
+    if negated {
+        *position += 1;
+    }
+
+    let mut members = Vec::new();
+    let start = *position;
+
+    while let Some(&byte) = format.get(*position) {
+        *position += 1;
+
+        if byte == b']' && *position - 1 > start {
+            break;
+        }
+
+        if format.get(*position) == Some(&b'-') && format.get(*position + 1).is_some_and(|end| *end != b']') {
+            let end = format[*position + 1];
+            members.extend(byte..=end);
+            *position += 2;
+        } else {
+            members.push(byte);
+        }
+    }
+
+    (negated, members)
+}
+
+
+fn integer_character(text: &[u8], byte: u8, base: u32) -> bool {
+    let digits = text.strip_prefix(b"-").or_else(|| text.strip_prefix(b"+")).unwrap_or(text);
+
+    if text.is_empty() && (byte == b'-' || byte == b'+') {
+        return true;
+    }
+
+    if (base == 16 || base == 0) && digits == b"0" && (byte == b'x' || byte == b'X') {
+        return true;
+    }
+
+    let base = match base {
+        0 if digits.starts_with(b"0x") || digits.starts_with(b"0X") => 16,
+        0 if digits.starts_with(b"0") => 8,
+        0 => 10,
+        base => base,
+    };
+
+    (byte as char).is_digit(base)
+}
+
+
+fn parse_integer(text: &[u8], base: u32) -> Option<u64> {
+    let text = String::from_utf8_lossy(text);
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.strip_prefix('+').unwrap_or(&text)),
+    };
+
+    let (base, digits) = match (base, digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X"))) {
+        (0 | 16, Some(digits)) => (16, digits),
+        (0, None) if digits.len() > 1 && digits.starts_with('0') => (8, &digits[1..]),
+        (0, None) => (10, digits),
+        (base, _) => (base, digits),
+    };
+
+    let magnitude = u128::from_str_radix(digits, base).ok()? as u64;
+
+    Some(if negative { magnitude.wrapping_neg() } else { magnitude })
+}
+
+
+fn floating_character(text: &[u8], byte: u8) -> bool {
+    match byte {
+        b'0'..=b'9' | b'.' => true,
+        b'+' | b'-' => text.is_empty() || matches!(text.last(), Some(b'e' | b'E')),
+        b'e' | b'E' => !text.is_empty() && !text.contains(&b'e') && !text.contains(&b'E'),
+        _ => false,
+    }
+}
@@ -0,0 +1,2113 @@
+use std::collections::HashMap;
+
+use crate::data::{ Ast, CType, Diagnostic, Keyword, LoweringState, Module, NodeId, NodeKind, Symbol, SymbolTable, Target, TypeTable };
+use crate::data::structures::{ BitField, TypeLayout, Global, InitializerElement, Instruction, IrFunction, Relocation, SwitchTargets };
+use crate::logic::constant::{ ConstantContext, Evaluator };
+use crate::data::types::{ ArithmeticOperator, ArraySize, Attribute, AssignmentOperator, BinaryOperator, BlockId, CastKind, ComparePredicate, Constant, ConstantValue, ConversionOperator, Designator, DerivedDeclarator, EntityKind, Initializer, InlineHint, InstructionKind, IrType, Operand, PostfixOperator, Register, Severity, TypeName, UnaryOperator };
+
+
+pub fn ir_start(ast: &Ast, table: &SymbolTable, types: &TypeTable, target: &Target) -> (Module, Vec<Diagnostic>) {
+    let mut lowering_state = LoweringState::new(ast, table, types, target);
+
+    if let NodeKind::TranslationUnit(items) = ast.kind(ast.root) {
+        for &item in items {
+            match ast.kind(item) {
+                NodeKind::FunctionDefinition { .. } => lower_function(&mut lowering_state, item),
+                NodeKind::Declaration { .. } => lower_global_declaration(&mut lowering_state, item),
+                _ => {}
+            }
+        }
+    }
+
+    declare_externals(&mut lowering_state);
+
+    (lowering_state.module, lowering_state.diagnostics)
+}
+
+
+/* * * * * * * * * * */
+/*  - FUNCTIONS -  */
+/* * * * * * * * * * */
+fn lower_function(lowering_state: &mut LoweringState, item: NodeId) {
+    let ast = lowering_state.ast;
+
+    let NodeKind::FunctionDefinition { attributes, specifiers, declarator, body } = ast.kind(item) else {
+        return;
+    };
+
+    let Some(name) = declarator.name else {
+        return;
+    };
+
+    let Some(CType::Function { result, parameters, variadic, .. }) = declared_type(lowering_state, item, name).map(|ctype| ctype.unqualified().clone()) else {
+        return;
+    };
+
+    let returns_record = is_aggregate(&result);
+    let mut function = IrFunction {
+        name: lowering_state.name(name).to_string(),
+        internal: specifiers.storage.contains(&Keyword::Static),
+        inline_hint: inline_hint(attributes, specifiers.function.contains(&Keyword::Inline)),
+        return_type: if returns_record { IrType::Void } else { ir_type(&result) },
+        parameters: Vec::new(),
+        variadic,
+        blocks: Vec::new(),
+        next_register: 0,
+    };
+
+    lowering_state.return_slot = returns_record.then(|| {
+        let register = function.new_register();
+        function.parameters.push((IrType::Ptr, register));
+        Operand::Register(register)
+    });
+
+    for parameter in &parameters {
+        let register = function.new_register();
+        function.parameters.push((ir_type(parameter), register));
+    }
+
+    let arguments: Vec<Register> = function.parameters.iter().skip(returns_record as usize).map(|(_, register)| *register).collect();
+
+    lowering_state.function = Some(function);
+    lowering_state.return_type = result.as_ref().clone();
+    lowering_state.locals.clear();
+    lowering_state.variable_sizes.clear();
+    lowering_state.labels.clear();
+    lowering_state.current = lowering_state.new_block();
+
+    for ((parameter, ctype), register) in declarator.function_parameters().into_iter().flatten().zip(&parameters).zip(arguments) {
+        let Some(&entity) = parameter.declarator.name.and_then(|name| lowering_state.declared.get(&(item, name))) else {
+            continue;
+        };
+
+        if is_aggregate(ctype) {
+            lowering_state.locals.insert(entity, Operand::Register(register));
+            continue;
+        }
+
+        let slot = stack_slot(lowering_state, ctype, 1);
+        lowering_state.emit(ir_type(ctype), InstructionKind::Store { value: Operand::Register(register), address: slot.clone(), volatile: ctype.qualifiers().is_volatile });
+        lowering_state.locals.insert(entity, slot);
+    }
+
+    lower_statement(lowering_state, *body);
+    finish_function(lowering_state);
+}
+
+
+fn finish_function(lowering_state: &mut LoweringState) {
+    let is_main = lowering_state.function().name == "main";
+
+    let implicit_return = match lowering_state.return_type.unqualified() {
+        CType::Void => InstructionKind::Return(None),
+        _ if lowering_state.return_slot.is_some() => InstructionKind::Return(None),
+        _ if is_main => InstructionKind::Return(Some(Operand::Integer(0))),
+        _ => InstructionKind::Return(Some(Operand::Undef)),
+    };
+
+    let return_type = lowering_state.function().return_type;
+    let Some(mut function) = lowering_state.function.take() else {
+        return;
+    };
+
+    for block in &mut function.blocks {
+        if !block.instructions.last().is_some_and(Instruction::is_terminator) {
+            block.instructions.push(Instruction { result: None, ty: return_type, kind: implicit_return.clone() });
+        }
+    }
+
+    let order = function.reverse_postorder();
+    let blocks: HashMap<BlockId, BlockId> = order.iter().enumerate().map(|(index, block)| (*block, BlockId(index as u32))).collect();
+    let mut remaining: HashMap<BlockId, _> = function.blocks.drain(..).map(|block| (block.id, block)).collect();
+    function.blocks = order.iter().filter_map(|block| remaining.remove(block)).collect();
+
+    let mut registers = HashMap::new();
+
+    for (_, register) in &mut function.parameters {
+        *register = renumber(&mut registers, *register);
+    }
+
+    for block in &mut function.blocks {
+        block.id = blocks[&block.id];
+
+        for instruction in &mut block.instructions {
+            if let Some(result) = instruction.result {
+                instruction.result = Some(renumber(&mut registers, result));
+            }
+        }
+    }
+
+    for block in &mut function.blocks {
+        for instruction in &mut block.instructions {
+            for operand in instruction.operands_mut() {
+                if let Operand::Register(register) = operand {
+                    *register = registers[register];
+                }
+            }
+
+            match &mut instruction.kind {
+                InstructionKind::Phi { incoming } => {
+                    incoming.retain(|(_, block)| blocks.contains_key(block));
+
+                    for (_, block) in incoming {
+                        *block = blocks[block];
+                    }
+                }
+
+                InstructionKind::Jump(target) => *target = blocks[target],
+
+                InstructionKind::Branch { then_block, else_block, .. } => {
+                    *then_block = blocks[then_block];
+                    *else_block = blocks[else_block];
+                }
+
+                InstructionKind::Switch { default, cases, .. } => {
+                    *default = blocks[default];
+
+                    for (_, target) in cases {
+                        *target = blocks[target];
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    function.next_register = registers.len() as u32;
+    lowering_state.module.functions.push(function);
+}
+
+
+fn inline_hint(attributes: &[Attribute], inline: bool) -> InlineHint {
+    if attributes.iter().any(|attribute| attribute.is("noinline")) {
+        InlineHint::Never
+    } else if attributes.iter().any(|attribute| attribute.is("always_inline")) {
+        InlineHint::Always
+    } else if inline {
+        InlineHint::Hint
+    } else {
+        InlineHint::None
+    }
+}
+
+
+fn renumber(registers: &mut HashMap<Register, Register>, register: Register) -> Register {
+    let next = Register(registers.len() as u32);
+    *registers.entry(register).or_insert(next)
+}
+
+
+fn declare_externals(lowering_state: &mut LoweringState) {
+    let mut externals: Vec<(String, CType)> = lowering_state.externals.drain().collect();
+    externals.sort_by(|left, right| left.0.cmp(&right.0));
+
+    for (name, ctype) in externals {
+        let module = &lowering_state.module;
+
+        if module.functions.iter().any(|function| function.name == name) || module.globals.iter().any(|global| global.name == name) {
+            continue;
+        }
+
+        let CType::Function { result, parameters, variadic, prototyped } = ctype.unqualified() else {
+            let layout = lowering_state.types.layout(&ctype, lowering_state.target);
+
+            lowering_state.module.globals.push(Global {
+                name,
+                internal: false,
+                constant: false,
+                external: true,
+                size: layout.map_or(0, |layout| layout.size),
+                align: layout.map_or(1, |layout| layout.align),
+                data: Vec::new(),
+                relocations: Vec::new(),
+            });
+
+            continue;
+        };
+
+        let returns_record = is_aggregate(result);
+        let types = returns_record.then_some(IrType::Ptr).into_iter().chain(parameters.iter().map(ir_type));
+
+        lowering_state.module.functions.push(IrFunction {
+            name,
+            internal: false,
+            inline_hint: InlineHint::None,
+            return_type: if returns_record { IrType::Void } else { ir_type(result) },
+            parameters: types.enumerate().map(|(index, ty)| (ty, Register(index as u32))).collect(),
+            variadic: *variadic || !prototyped,
+            blocks: Vec::new(),
+            next_register: 0,
+        });
+    }
+}
+
+
+/* * * * * * * * * * */
+/*  - STATEMENTS - */
+/* * * * * * * * * * */
+fn lower_statement(lowering_state: &mut LoweringState, statement: NodeId) {
+    let ast = lowering_state.ast;
+
+    match ast.kind(statement) {
+        NodeKind::Compound(items) => {
+            for &item in items {
+                lower_statement(lowering_state, item);
+            }
+        }
+
+        NodeKind::Declaration { .. } => lower_local_declaration(lowering_state, statement),
+        NodeKind::Attributed { statement, .. } => lower_statement(lowering_state, *statement),
+
+        NodeKind::ExpressionStatement(Some(expression)) => {
+            lower_value(lowering_state, *expression);
+        }
+
+        NodeKind::If { condition, then_branch, else_branch } => {
+            let then_block = lowering_state.new_block();
+            let join = lowering_state.new_block();
+            let else_block = match else_branch {
+                Some(_) => lowering_state.new_block(),
+                None => join,
+            };
+
+            lower_branch(lowering_state, *condition, then_block, else_block);
+
+            lowering_state.current = then_block;
+            lower_statement(lowering_state, *then_branch);
+            jump(lowering_state, join);
+
+            if let Some(else_branch) = else_branch {
+                lowering_state.current = else_block;
+                lower_statement(lowering_state, *else_branch);
+                jump(lowering_state, join);
+            }
+
+            lowering_state.current = join;
+        }
+
+        NodeKind::While { condition, body } => {
+            let header = lowering_state.new_block();
+            let body_block = lowering_state.new_block();
+            let exit = lowering_state.new_block();
+
+            jump(lowering_state, header);
+            lowering_state.current = header;
+            lower_branch(lowering_state, *condition, body_block, exit);
+
+            lowering_state.current = body_block;
+            lower_loop_body(lowering_state, *body, exit, header);
+            jump(lowering_state, header);
+
+            lowering_state.current = exit;
+        }
+
+        NodeKind::DoWhile { body, condition } => {
+            let body_block = lowering_state.new_block();
+            let latch = lowering_state.new_block();
+            let exit = lowering_state.new_block();
+
+            jump(lowering_state, body_block);
+            lowering_state.current = body_block;
+            lower_loop_body(lowering_state, *body, exit, latch);
+            jump(lowering_state, latch);
+
+            lowering_state.current = latch;
+            lower_branch(lowering_state, *condition, body_block, exit);
+
+            lowering_state.current = exit;
+        }
+
+        NodeKind::For { init, condition, step, body } => {
+            if let Some(init) = init {
+                lower_statement(lowering_state, *init);
+            }
+
+            let header = lowering_state.new_block();
+            let body_block = lowering_state.new_block();
+            let latch = lowering_state.new_block();
+            let exit = lowering_state.new_block();
+
+            jump(lowering_state, header);
+            lowering_state.current = header;
+
+            match condition {
+                Some(condition) => lower_branch(lowering_state, *condition, body_block, exit),
+                None => jump(lowering_state, body_block),
+            }
+
+            lowering_state.current = body_block;
+            lower_loop_body(lowering_state, *body, exit, latch);
+            jump(lowering_state, latch);
+
+            lowering_state.current = latch;
+
+            if let Some(step) = step {
+                lower_value(lowering_state, *step);
+            }
+
+            jump(lowering_state, header);
+            lowering_state.current = exit;
+        }
+
+        NodeKind::Switch { condition, body } => lower_switch(lowering_state, *condition, *body),
+
+        NodeKind::Case { value, body } => {
+            let block = lowering_state.new_block();
+            jump(lowering_state, block);
+            lowering_state.current = block;
+
+            let value = match constant_of(lowering_state, *value) {
+                Some(ConstantValue::Integer(value, _)) => value as i64,
+                _ => {
+                    report(lowering_state, statement, "case label does not reduce to an integer constant".to_string());
+                    0
+                }
+            };
+
+            if let Some(switch) = lowering_state.switches.last_mut() {
+                switch.cases.push((value, block));
+            }
+
+            lower_statement(lowering_state, *body);
+        }
+
+        NodeKind::Default(body) => {
+            let block = lowering_state.new_block();
+            jump(lowering_state, block);
+            lowering_state.current = block;
+
+            if let Some(switch) = lowering_state.switches.last_mut() {
+                switch.default = Some(block);
+            }
+
+            lower_statement(lowering_state, *body);
+        }
+
+        NodeKind::Label { name, body } => {
+            let block = label_block(lowering_state, *name);
+            jump(lowering_state, block);
+            lowering_state.current = block;
+            lower_statement(lowering_state, *body);
+        }
+
+        NodeKind::Goto(name) => {
+            let block = label_block(lowering_state, *name);
+            lowering_state.terminate(InstructionKind::Jump(block));
+        }
+
+        NodeKind::Break => {
+            if let Some(&target) = lowering_state.breaks.last() {
+                lowering_state.terminate(InstructionKind::Jump(target));
+            }
+        }
+
+        NodeKind::Continue => {
+            if let Some(&target) = lowering_state.continues.last() {
+                lowering_state.terminate(InstructionKind::Jump(target));
+            }
+        }
+
+        NodeKind::Return(value) => lower_return(lowering_state, *value),
+        _ => {}
+    }
+}
+
+
+fn lower_loop_body(lowering_state: &mut LoweringState, body: NodeId, exit: BlockId, latch: BlockId) {
+    lowering_state.breaks.push(exit);
+    lowering_state.continues.push(latch);
+
+    lower_statement(lowering_state, body);
+
+    lowering_state.breaks.pop();
+    lowering_state.continues.pop();
+}
+
+
+fn lower_switch(lowering_state: &mut LoweringState, condition: NodeId, body: NodeId) {
+    let ty = ir_type(&expression_type(lowering_state, condition));
+    let value = lower_value(lowering_state, condition);
+
+    if lowering_state.is_terminated() {
+        lowering_state.current = lowering_state.new_block();
+    }
+
+    let head = lowering_state.current;
+    let exit = lowering_state.new_block();
+
+    lowering_state.switches.push(SwitchTargets::default());
+    lowering_state.breaks.push(exit);
+    lowering_state.current = lowering_state.new_block();
+
+    lower_statement(lowering_state, body);
+    jump(lowering_state, exit);
+
+    lowering_state.breaks.pop();
+    let SwitchTargets { cases, default } = lowering_state.switches.pop().unwrap_or_default();
+    let cases = cases.into_iter().map(|(value, block)| (wrap(value as i128, ty), block)).collect();
+    let kind = InstructionKind::Switch { value, default: default.unwrap_or(exit), cases };
+
+    lowering_state.function().blocks[head.0 as usize].instructions.push(Instruction { result: None, ty, kind });
+    lowering_state.current = exit;
+}
+
+
+fn lower_return(lowering_state: &mut LoweringState, value: Option<NodeId>) {
+    if let Some(slot) = lowering_state.return_slot.clone() {
+        if let Some(value) = value {
+            let size = size_of(lowering_state, &lowering_state.return_type.clone());
+            let source = lower_value(lowering_state, value);
+            lowering_state.emit(IrType::Void, InstructionKind::Copy { destination: slot, source, size });
+        }
+
+        return lowering_state.terminate(InstructionKind::Return(None));
+    }
+
+    let return_type = lowering_state.return_type.clone();
+    let ty = ir_type(&return_type);
+
+    let result = match value {
+        Some(value) if ty != IrType::Void => {
+            let source = expression_type(lowering_state, value);
+            let result = lower_value(lowering_state, value);
+            Some(convert(lowering_state, result, &source, &return_type))
+        }
+
+        Some(value) => {
+            lower_value(lowering_state, value);
+            None
+        }
+
+        None if ty != IrType::Void => Some(Operand::Undef),
+        None => None,
+    };
+
+    lowering_state.emit(ty, InstructionKind::Return(result));
+}
+
+
+fn jump(lowering_state: &mut LoweringState, target: BlockId) {
+    if !lowering_state.is_terminated() {
+        lowering_state.terminate(InstructionKind::Jump(target));
+    }
+}
+
+
+fn label_block(lowering_state: &mut LoweringState, name: Symbol) -> BlockId {
+    if let Some(&block) = lowering_state.labels.get(&name) {
+        return block;
+    }
+
+    let block = lowering_state.new_block();
+    lowering_state.labels.insert(name, block);
+
+    block
+}
+
+
+/* * * * * * * * * * * */
+/*  - DECLARATIONS -  */
+/* * * * * * * * * * * */
+fn lower_global_declaration(lowering_state: &mut LoweringState, declaration: NodeId) {
+    let ast = lowering_state.ast;
+
+    let NodeKind::Declaration { specifiers, declarators, .. } = ast.kind(declaration) else {
+        return;
+    };
+
+    if specifiers.storage.contains(&Keyword::Typedef) {
+        return;
+    }
+
+    for init_declarator in declarators {
+        let Some(name) = init_declarator.declarator.name else {
+            continue;
+        };
+
+        let Some(ctype) = declared_type(lowering_state, declaration, name) else {
+            continue;
+        };
+
+        if matches!(ctype.unqualified(), CType::Function { .. }) || specifiers.storage.contains(&Keyword::Extern) && init_declarator.initializer.is_none() {
+            continue;
+        }
+
+        let spelling = lowering_state.name(name).to_string();
+        let internal = specifiers.storage.contains(&Keyword::Static);
+        let align = lowering_state.types.alignment(declaration);
+        define_global(lowering_state, spelling, internal, &ctype, align, init_declarator.initializer);
+    }
+}
+
+
+fn lower_local_declaration(lowering_state: &mut LoweringState, declaration: NodeId) {
+    let ast = lowering_state.ast;
+
+    let NodeKind::Declaration { specifiers, declarators, .. } = ast.kind(declaration) else {
+        return;
+    };
+
+    if specifiers.storage.contains(&Keyword::Typedef) || specifiers.storage.contains(&Keyword::Extern) {
+        return;
+    }
+
+    for init_declarator in declarators {
+        let Some(name) = init_declarator.declarator.name else {
+            continue;
+        };
+
+        let Some(ctype) = declared_type(lowering_state, declaration, name) else {
+            continue;
+        };
+
+        let Some(&entity) = lowering_state.declared.get(&(declaration, name)) else {
+            continue;
+        };
+
+        if matches!(ctype.unqualified(), CType::Function { .. }) {
+            continue;
+        }
+
+        if specifiers.storage.contains(&Keyword::Static) {
+            let spelling = format!("{}.{}", lowering_state.name(name), lowering_state.statics);
+            lowering_state.statics += 1;
+            lowering_state.locals.insert(entity, Operand::Global(spelling.clone()));
+            define_global(lowering_state, spelling, true, &ctype, lowering_state.types.alignment(declaration), init_declarator.initializer);
+            continue;
+        }
+
+        if matches!(ctype.unqualified(), CType::Array(element, _) if has_variable_length(element)) {
+            report(lowering_state, declaration, "IR lowering does not support multidimensional variable length arrays".to_string());
+            continue;
+        }
+
+        if let CType::Array(element, ArraySize::Variable) = ctype.unqualified() {
+            let Some(DerivedDeclarator::Array(Some(length))) = init_declarator.declarator.derived.last() else {
+                continue;
+            };
+
+            let Some(layout) = lowering_state.types.layout(element, lowering_state.target) else {
+                continue;
+            };
+
+            let length_type = expression_type(lowering_state, *length);
+            let length = lower_value(lowering_state, *length);
+            let length = convert(lowering_state, length, &length_type, &CType::UnsignedLong);
+            let size = binary(lowering_state, ArithmeticOperator::Mul, IrType::I64, length, Operand::Integer(layout.size as i64));
+            let align = layout.align.max(lowering_state.types.alignment(declaration));
+            let address = lowering_state.emit(IrType::Ptr, InstructionKind::Alloca { size: size.clone(), align });
+
+            lowering_state.variable_sizes.insert(entity, size);
+            lowering_state.locals.insert(entity, address);
+            continue;
+        }
+
+        let slot = stack_slot(lowering_state, &ctype, lowering_state.types.alignment(declaration));
+        lowering_state.locals.insert(entity, slot.clone());
+
+        if let Some(initializer) = init_declarator.initializer {
+            lower_local_initializer(lowering_state, slot, &ctype, initializer);
+        }
+    }
+}
+
+
+fn has_variable_length(ctype: &CType) -> bool {
+    match ctype.unqualified() {
+        CType::Array(_, ArraySize::Variable) => true,
+        CType::Array(element, _) => has_variable_length(element),
+        _ => false,
+    }
+}
+
+
+fn define_global(lowering_state: &mut LoweringState, name: String, internal: bool, ctype: &CType, align: u64, initializer: Option<NodeId>) {
+    let ctype = &complete_array(lowering_state, ctype, initializer);
+    let layout = lowering_state.types.layout(ctype, lowering_state.target);
+    let size = layout.map_or(0, |layout| layout.size);
+    let mut global = Global {
+        name,
+        internal,
+        constant: ctype.qualifiers().is_const,
+        external: false,
+        size,
+        align: layout.map_or(1, |layout| layout.align).max(align),
+        data: Vec::new(),
+        relocations: Vec::new(),
+    };
+
+    if let Some(initializer) = initializer {
+        let mut data = vec![0; size as usize];
+        let mut elements = Vec::new();
+        flatten_initializer(lowering_state, ctype, initializer, 0, &mut elements);
+
+        for element in &elements {
+            encode_element(lowering_state, &mut data, &mut global.relocations, element);
+        }
+
+        if data.iter().any(|&byte| byte != 0) {
+            global.data = data;
+        }
+    }
+
+    let existing = lowering_state.module.globals.iter().position(|existing| existing.name == global.name);
+
+    match existing {
+        Some(index) if initializer.is_some() => lowering_state.module.globals[index] = global,
+        Some(_) => {}
+        None => lowering_state.module.globals.push(global),
+    }
+}
+
+
+fn encode_element(lowering_state: &mut LoweringState, data: &mut [u8], relocations: &mut Vec<Relocation>, element: &InitializerElement) {
+    let ast = lowering_state.ast;
+    let offset = element.offset as usize;
+    let size = (size_of(lowering_state, &element.ctype) as usize).min(data.len().saturating_sub(offset));
+
+    if let (NodeKind::StringLiteral(text), CType::Array(..)) = (ast.kind(strip_casts(ast, element.value)), element.ctype.unqualified()) {
+        let bytes = lowering_state.name(*text).as_bytes();
+        let length = bytes.len().min(size);
+        data[offset..offset + length].copy_from_slice(&bytes[..length]);
+        return;
+    }
+
+    let Some(constant) = constant_of(lowering_state, element.value) else {
+        return report(lowering_state, element.value, "initializer element is not constant".to_string());
+    };
+
+    let integer = match constant {
+        ConstantValue::Address { base, offset: addend } => {
+            let symbol = address_symbol(lowering_state, base);
+            relocations.push(Relocation { offset: element.offset, symbol, addend });
+            return;
+        }
+
+        ConstantValue::Floating(value, _) if element.ctype.is_floating() => return write_floating(data, offset, size, value),
+        ConstantValue::Integer(value, _) if element.ctype.is_floating() => return write_floating(data, offset, size, value as f64),
+        ConstantValue::Floating(value, _) => value as i128,
+        ConstantValue::Integer(value, _) => value,
+    };
+
+    let integer = match (element.ctype.unqualified(), integer) {
+        (CType::Bool, value) => (value != 0) as i128,
+        (_, value) => value,
+    };
+
+    match element.bit_field {
+        Some((field, width)) => {
+            let mut unit = read_integer(data, offset, size);
+            let mask = ((1u128 << width) - 1) << field.bit_offset.unwrap_or(0);
+            unit = unit & !mask | (integer as u128) << field.bit_offset.unwrap_or(0) & mask;
+            data[offset..offset + size].copy_from_slice(&unit.to_le_bytes()[..size]);
+        }
+
+        None => data[offset..offset + size].copy_from_slice(&integer.to_le_bytes()[..size]),
+    }
+}
+
+
+fn read_integer(data: &[u8], offset: usize, size: usize) -> u128 {
+    let mut bytes = [0; 16];
+    bytes[..size].copy_from_slice(&data[offset..offset + size]);
+    u128::from_le_bytes(bytes)
+}
+
+
+fn write_floating(data: &mut [u8], offset: usize, size: usize, value: f64) {
+    match size {
+        4 => data[offset..offset + 4].copy_from_slice(&(value as f32).to_le_bytes()),
+        size if size >= 8 => data[offset..offset + 8].copy_from_slice(&value.to_le_bytes()),
+        _ => {}
+    }
+}
+
+
+fn address_symbol(lowering_state: &mut LoweringState, base: NodeId) -> String {
+    let ast = lowering_state.ast;
+
+    match ast.kind(base) {
+        NodeKind::StringLiteral(_) => string_global(lowering_state, base),
+
+        NodeKind::CompoundLiteral { initializer, .. } => {
+            if let Some(name) = lowering_state.strings.get(&base) {
+                return name.clone();
+            }
+
+            let name = format!(".compound.{}", lowering_state.statics);
+            lowering_state.statics += 1;
+            lowering_state.strings.insert(base, name.clone());
+
+            let ctype = expression_type(lowering_state, base);
+            define_global(lowering_state, name.clone(), true, &ctype, 1, Some(*initializer));
+
+            name
+        }
+
+        _ => match lower_identifier(lowering_state, base) {
+            Operand::Global(name) => name,
+            _ => {
+                report(lowering_state, base, "initializer element is not constant".to_string());
+                String::new()
+            }
+        },
+    }
+}
+
+
+fn string_global(lowering_state: &mut LoweringState, literal: NodeId) -> String {
+    if let Some(name) = lowering_state.strings.get(&literal) {
+        return name.clone();
+    }
+
+    let NodeKind::StringLiteral(text) = lowering_state.ast.kind(literal) else {
+        return String::new();
+    };
+
+    let name = format!(".str.{}", lowering_state.strings.values().filter(|name| name.starts_with(".str.")).count());
+    let mut data = lowering_state.name(*text).as_bytes().to_vec();
+    data.push(0);
+
+    lowering_state.strings.insert(literal, name.clone());
+    lowering_state.module.globals.push(Global {
+        name: name.clone(),
+        internal: true,
+        constant: true,
+        external: false,
+        size: data.len() as u64,
+        align: 1,
+        data,
+        relocations: Vec::new(),
+    });
+
+    name
+}
+
+
+fn function_name_global(lowering_state: &mut LoweringState) -> String {
+    let function = lowering_state.function().name.clone();
+    let name = format!("__func__.{}", function);
+
+    if !lowering_state.module.globals.iter().any(|global| global.name == name) {
+        let mut data = function.into_bytes();
+        data.push(0);
+
+        lowering_state.module.globals.push(Global {
+            name: name.clone(),
+            internal: true,
+            constant: true,
+            external: false,
+            size: data.len() as u64,
+            align: 1,
+            data,
+            relocations: Vec::new(),
+        });
+    }
+
+    name
+}
+
+
+/* * * * * * * * * * * */
+/*  - INITIALIZERS -  */
+/* * * * * * * * * * * */
+fn lower_local_initializer(lowering_state: &mut LoweringState, address: Operand, ctype: &CType, initializer: NodeId) {
+    let ast = lowering_state.ast;
+    let size = size_of(lowering_state, ctype);
+
+    let is_partial = match (ast.kind(initializer), ctype.unqualified()) {
+        (NodeKind::InitializerList(_), _) => true,
+        (NodeKind::StringLiteral(text), CType::Array(..)) => lowering_state.name(*text).len() as u64 + 1 < size,
+        _ => false,
+    };
+
+    if is_partial {
+        lowering_state.emit(IrType::Void, InstructionKind::Zero { address: address.clone(), size });
+    }
+
+    let mut elements = Vec::new();
+    flatten_initializer(lowering_state, ctype, initializer, 0, &mut elements);
+
+    for element in elements {
+        store_element(lowering_state, &address, &element);
+    }
+}
+
+
+fn store_element(lowering_state: &mut LoweringState, base: &Operand, element: &InitializerElement) {
+    let ast = lowering_state.ast;
+    let address = offset_address(lowering_state, base.clone(), element.offset);
+    let size = size_of(lowering_state, &element.ctype);
+
+    if let (NodeKind::StringLiteral(text), CType::Array(..)) = (ast.kind(strip_casts(ast, element.value)), element.ctype.unqualified()) {
+        let length = (lowering_state.name(*text).len() as u64 + 1).min(size);
+        let source = Operand::Global(string_global(lowering_state, strip_casts(ast, element.value)));
+        lowering_state.emit(IrType::Void, InstructionKind::Copy { destination: address, source, size: length });
+        return;
+    }
+
+    let source = expression_type(lowering_state, element.value);
+    let value = lower_value(lowering_state, element.value);
+
+    if is_aggregate(&element.ctype) {
+        lowering_state.emit(IrType::Void, InstructionKind::Copy { destination: address, source: value, size });
+        return;
+    }
+
+    let value = convert(lowering_state, value, &source, &element.ctype);
+
+    match element.bit_field {
+        Some((field, width)) => store_bit_field(lowering_state, address, (field, width), &element.ctype, value),
+        None => {
+            lowering_state.emit(ir_type(&element.ctype), InstructionKind::Store { value, address, volatile: element.ctype.qualifiers().is_volatile });
+        }
+    }
+}
+
+
+fn complete_array(lowering_state: &mut LoweringState, ctype: &CType, initializer: Option<NodeId>) -> CType {
+    let (CType::Array(element, ArraySize::Incomplete), Some(initializer)) = (ctype.unqualified(), initializer) else {
+        return ctype.clone();
+    };
+
+    let element_size = size_of(lowering_state, element).max(1);
+    let mut elements = Vec::new();
+    flatten_initializer(lowering_state, ctype, initializer, 0, &mut elements);
+
+    let end = elements.iter().map(|item| item.offset + size_of(lowering_state, &item.ctype).max(string_length(lowering_state, item))).max().unwrap_or(0);
+    CType::Array(element.clone(), ArraySize::Fixed(end.div_ceil(element_size))).qualified(ctype.qualifiers())
+}
+
+
+fn string_length(lowering_state: &LoweringState, element: &InitializerElement) -> u64 {
+    match lowering_state.ast.kind(strip_casts(lowering_state.ast, element.value)) {
+        NodeKind::StringLiteral(text) if matches!(element.ctype.unqualified(), CType::Array(..)) => lowering_state.name(*text).len() as u64 + 1,
+        _ => 0,
+    }
+}
+
+
+fn flatten_initializer(lowering_state: &mut LoweringState, ctype: &CType, initializer: NodeId, offset: u64, elements: &mut Vec<InitializerElement>) {
+    let ast = lowering_state.ast;
+
+    match (ast.kind(initializer), ctype.unqualified()) {
+        (NodeKind::InitializerList(items), CType::Array(element, _))
+            if matches!(items.as_slice(), [item] if item.designators.is_empty() && matches!(ast.kind(item.value), NodeKind::StringLiteral(_)))
+                && element.is_integer() && element.integer_rank() == 1 => {
+            elements.push(InitializerElement { offset, ctype: ctype.clone(), bit_field: None, value: items[0].value });
+        }
+
+        (NodeKind::InitializerList(items), CType::Array(..) | CType::Record { .. }) => flatten_list(lowering_state, ctype, items, offset, elements),
+
+        (NodeKind::InitializerList(items), _) => {
+            if let Some(item) = items.first() {
+                flatten_initializer(lowering_state, ctype, item.value, offset, elements);
+            }
+        }
+
+        _ => elements.push(InitializerElement { offset, ctype: ctype.clone(), bit_field: None, value: initializer }),
+    }
+}
+
+
+fn flatten_list(lowering_state: &mut LoweringState, ctype: &CType, items: &[Initializer], offset: u64, elements: &mut Vec<InitializerElement>) {
+    let ast = lowering_state.ast;
+    let mut stack = vec![(ctype.clone(), offset, 0)];
+
+    'items: for item in items {
+        if !item.designators.is_empty() {
+            stack.truncate(1);
+
+            for (index, designator) in item.designators.iter().enumerate() {
+                let (current, base, _) = stack.last().cloned().unwrap_or((ctype.clone(), offset, 0));
+
+                let position = match designator {
+                    Designator::Index(index) => match constant_of(lowering_state, *index) {
+                        Some(ConstantValue::Integer(value, _)) => value as u64,
+                        _ => continue 'items,
+                    },
+
+                    Designator::Member(name) => match member_position(lowering_state, &current, *name) {
+                        Some(position) => position,
+                        None => continue 'items,
+                    },
+                };
+
+                if let Some(top) = stack.last_mut() {
+                    top.2 = position;
+                }
+
+                if index + 1 < item.designators.len() {
+                    let Some((subobject, relative, _)) = subobject(lowering_state, &current, position) else {
+                        continue 'items;
+                    };
+
+                    stack.push((subobject, base + relative, 0));
+                }
+            }
+        }
+
+        let mut designated = !item.designators.is_empty();
+
+        loop {
+            while !designated && stack.len() > 1 && stack.last().is_some_and(|(current, _, position)| *position >= subobject_count(lowering_state, current)) {
+                stack.pop();
+
+                if let Some(top) = stack.last_mut() {
+                    top.2 += 1;
+                }
+            }
+
+            let Some((current, base, position)) = stack.last().cloned() else {
+                break 'items;
+            };
+
+            let Some((subobject, relative, bit_field)) = subobject(lowering_state, &current, position) else {
+                break 'items;
+            };
+
+            designated = false;
+
+            let is_list = matches!(ast.kind(item.value), NodeKind::InitializerList(_));
+            let value_type = expression_type(lowering_state, strip_casts(ast, item.value));
+            let is_string = matches!(ast.kind(strip_casts(ast, item.value)), NodeKind::StringLiteral(_));
+
+            let matches = match subobject.unqualified() {
+                CType::Array(element, _) => is_string && element.is_integer() && element.integer_rank() == 1,
+                CType::Record { .. } => matches!(value_type.unqualified(), CType::Record { .. }),
+                _ => true,
+            };
+
+            if !is_list && !matches {
+                stack.push((subobject, base + relative, 0));
+                continue;
+            }
+
+            if is_list {
+                flatten_initializer(lowering_state, &subobject, item.value, base + relative, elements);
+            } else {
+                elements.push(InitializerElement { offset: base + relative, ctype: subobject, bit_field, value: item.value });
+            }
+
+            if let Some(top) = stack.last_mut() {
+                top.2 += 1;
+            }
+
+            break;
+        }
+    }
+}
+
+
+fn subobject_count(lowering_state: &LoweringState, ctype: &CType) -> u64 {
+    match ctype.unqualified() {
+        CType::Array(_, ArraySize::Fixed(length)) => *length,
+        CType::Array(_, ArraySize::Incomplete) => u64::MAX,
+        CType::Record { kind: Keyword::Union, .. } => 1,
+        CType::Record { tag, .. } => record_members(lowering_state, tag).len() as u64,
+        _ => 0,
+    }
+}
+
+
+fn subobject(lowering_state: &LoweringState, ctype: &CType, position: u64) -> Option<(CType, u64, Option<BitField>)> {
+    match ctype.unqualified() {
+        CType::Array(element, size) => {
+            let in_bounds = match size {
+                ArraySize::Fixed(length) => position < *length,
+                _ => true,
+            };
+
+            in_bounds.then(|| (element.as_ref().clone(), position * size_of(lowering_state, element), None))
+        }
+
+        CType::Record { kind, tag } => {
+            let index = *record_members(lowering_state, tag).get(position as usize)?;
+            let member = lowering_state.types.records.get(tag)?.get(index)?;
+            let field = *lowering_state.types.record_layout(*kind, tag, lowering_state.target)?.fields.get(index)?;
+            let bit_field = member.bit_width.filter(|_| field.bit_offset.is_some()).map(|width| (field, width));
+
+            Some((member.ctype.clone(), field.offset, bit_field))
+        }
+
+        _ => None,
+    }
+}
+
+
+fn record_members(lowering_state: &LoweringState, tag: &str) -> Vec<usize> {
+    lowering_state.types.records.get(tag).into_iter().flatten().enumerate()
+        .filter(|(_, member)| member.name.is_some() || member.bit_width.is_none())
+        .map(|(index, _)| index)
+        .collect()
+}
+
+
+fn member_position(lowering_state: &LoweringState, ctype: &CType, name: Symbol) -> Option<u64> {
+    let CType::Record { tag, .. } = ctype.unqualified() else {
+        return None;
+    };
+
+    let members = lowering_state.types.records.get(tag)?;
+    record_members(lowering_state, tag).iter().position(|&index| members[index].name == Some(name)).map(|position| position as u64)
+}
+
+
+/* * * * * * * * * * * */
+/*  - EXPRESSIONS -  */
+/* * * * * * * * * * * */
+fn lower_value(lowering_state: &mut LoweringState, expression: NodeId) -> Operand {
+    let ast = lowering_state.ast;
+
+    match ast.kind(expression) {
+        NodeKind::ImplicitCast { kind, operand } => lower_implicit_cast(lowering_state, expression, *kind, *operand),
+
+        NodeKind::Cast { operand, .. } => {
+            let target = expression_type(lowering_state, expression);
+            let source = expression_type(lowering_state, *operand);
+            let value = lower_value(lowering_state, *operand);
+
+            convert(lowering_state, value, &source, &target)
+        }
+
+        NodeKind::Constant(constant) => {
+            let ty = ir_type(&expression_type(lowering_state, expression));
+
+            match *constant {
+                Constant::Integer { value, .. } => Operand::Integer(wrap(value as i128, ty)),
+                Constant::Floating { value, .. } if ty == IrType::F32 => Operand::Floating(value as f32 as f64),
+                Constant::Floating { value, .. } => Operand::Floating(value),
+                Constant::Character(value) => Operand::Integer(wrap(value as i128, ty)),
+                Constant::Bool(value) => Operand::Integer(value as i64),
+            }
+        }
+
+        NodeKind::Identifier(_) => match lowering_state.types.constants.get(&expression) {
+            Some(ConstantValue::Integer(value, _)) => Operand::Integer(wrap(*value, IrType::I32)),
+            _ => load_lvalue(lowering_state, expression),
+        },
+
+        NodeKind::BinaryOperation { left, operator, right } => lower_binary(lowering_state, expression, *left, *operator, *right),
+        NodeKind::Assignment { left, operator, right } => lower_assignment(lowering_state, *left, *operator, *right),
+        NodeKind::UnaryOperation { operator, operand } => lower_unary(lowering_state, expression, *operator, *operand),
+
+        NodeKind::PostfixOperation { operator, operand } => {
+            let delta = if *operator == PostfixOperator::Increment { 1 } else { -1 };
+            lower_increment(lowering_state, *operand, delta, false)
+        }
+
+        NodeKind::Conditional { condition, then_value, else_value } => lower_conditional(lowering_state, expression, *condition, *then_value, *else_value),
+        NodeKind::Call { function, arguments } => lower_call(lowering_state, expression, *function, arguments),
+
+        NodeKind::SizeofType(_) | NodeKind::AlignofType(_) => match lowering_state.types.constants.get(&expression) {
+            Some(ConstantValue::Integer(value, _)) => Operand::Integer(wrap(*value, IrType::I64)),
+            _ => {
+                report(lowering_state, expression, "IR lowering does not support 'sizeof' of a variable length array type".to_string());
+                Operand::Undef
+            }
+        },
+
+        NodeKind::GenericSelection { associations, .. } => match lowering_state.types.selections.get(&expression) {
+            Some(&index) => lower_value(lowering_state, associations[index].value),
+            None => Operand::Undef,
+        },
+
+        NodeKind::StringLiteral(_) | NodeKind::CompoundLiteral { .. } => lower_address(lowering_state, expression),
+        NodeKind::Index { .. } | NodeKind::Member { .. } => load_lvalue(lowering_state, expression),
+        _ => Operand::Undef,
+    }
+}
+
+
+fn load_lvalue(lowering_state: &mut LoweringState, expression: NodeId) -> Operand {
+    let ctype = expression_type(lowering_state, expression);
+    let bit_field = bit_field(lowering_state, expression);
+    let address = lower_address(lowering_state, expression);
+
+    match bit_field {
+        _ if is_aggregate(&ctype) || matches!(ctype.unqualified(), CType::Function { .. }) => address,
+        Some(bit_field) => load_bit_field(lowering_state, address, bit_field, &ctype),
+        None => lowering_state.emit(ir_type(&ctype), InstructionKind::Load { address, volatile: ctype.qualifiers().is_volatile }),
+    }
+}
+
+
+fn lower_address(lowering_state: &mut LoweringState, expression: NodeId) -> Operand {
+    let ast = lowering_state.ast;
+
+    match ast.kind(expression) {
+        NodeKind::Identifier(_) => lower_identifier(lowering_state, expression),
+        NodeKind::StringLiteral(_) => Operand::Global(string_global(lowering_state, expression)),
+        NodeKind::UnaryOperation { operator: UnaryOperator::Dereference, operand } => lower_value(lowering_state, *operand),
+
+        NodeKind::Index { array, index } => {
+            let (pointer, index) = match expression_type(lowering_state, *array).is_pointer() {
+                true => (*array, *index),
+                false => (*index, *array),
+            };
+
+            let element = expression_type(lowering_state, expression);
+            let index_type = expression_type(lowering_state, index);
+            let base = lower_value(lowering_state, pointer);
+            let index = lower_value(lowering_state, index);
+            let element_size = size_of(lowering_state, &element);
+            let offset = scale(lowering_state, index, &index_type, element_size, false);
+
+            pointer_add(lowering_state, base, offset)
+        }
+
+        NodeKind::Member { object, member, arrow } => {
+            let object_type = expression_type(lowering_state, *object);
+
+            let (base, record) = match arrow {
+                true => (lower_value(lowering_state, *object), object_type.pointee().cloned().unwrap_or(CType::Void)),
+                false => (lower_address(lowering_state, *object), object_type),
+            };
+
+            let offset = match record.unqualified() {
+                CType::Record { kind, tag } => lowering_state.types.member_offset(*kind, tag, *member, lowering_state.target).unwrap_or(0),
+                _ => 0,
+            };
+
+            offset_address(lowering_state, base, offset)
+        }
+
+        NodeKind::CompoundLiteral { initializer, .. } => {
+            let ctype = complete_array(lowering_state, &expression_type(lowering_state, expression), Some(*initializer));
+
+            if lowering_state.function.is_none() {
+                return Operand::Global(address_symbol(lowering_state, expression));
+            }
+
+            let slot = stack_slot(lowering_state, &ctype, 1);
+            lower_local_initializer(lowering_state, slot.clone(), &ctype, *initializer);
+
+            slot
+        }
+
+        NodeKind::GenericSelection { associations, .. } => match lowering_state.types.selections.get(&expression) {
+            Some(&index) => lower_address(lowering_state, associations[index].value),
+            None => Operand::Undef,
+        },
+
+        NodeKind::ImplicitCast { kind: CastKind::LValueToRValue, operand } => lower_address(lowering_state, *operand),
+
+        NodeKind::ImplicitCast { .. } | NodeKind::Cast { .. } | NodeKind::Call { .. } | NodeKind::Assignment { .. } | NodeKind::Conditional { .. }
+            | NodeKind::BinaryOperation { .. } | NodeKind::UnaryOperation { .. } | NodeKind::PostfixOperation { .. } => lower_value(lowering_state, expression),
+
+        _ => Operand::Undef,
+    }
+}
+
+
+fn lower_identifier(lowering_state: &mut LoweringState, expression: NodeId) -> Operand {
+    let ast = lowering_state.ast;
+
+    let NodeKind::Identifier(name) = ast.kind(expression) else {
+        return Operand::Undef;
+    };
+
+    if let Some(entity) = lowering_state.table.resolve(expression) {
+        if let Some(local) = lowering_state.locals.get(&entity) {
+            return local.clone();
+        }
+
+        let entity = &lowering_state.table.entities[entity.0 as usize];
+
+        if entity.kind == EntityKind::Object && matches!(ast.kind(entity.node), NodeKind::FunctionDefinition { .. }) {
+            return Operand::Global(function_name_global(lowering_state));
+        }
+    }
+
+    let spelling = lowering_state.name(*name).to_string();
+    let ctype = lowering_state.types.expressions.get(&expression).cloned().unwrap_or(CType::Function {
+        result: Box::new(CType::Int),
+        parameters: Vec::new(),
+        variadic: false,
+        prototyped: false,
+    });
+
+    lowering_state.externals.entry(spelling.clone()).or_insert(ctype);
+    Operand::Global(spelling)
+}
+
+
+fn lower_implicit_cast(lowering_state: &mut LoweringState, expression: NodeId, kind: CastKind, operand: NodeId) -> Operand {
+    match kind {
+        CastKind::LValueToRValue => load_lvalue(lowering_state, operand),
+
+        CastKind::ArrayToPointer | CastKind::FunctionToPointer => lower_address(lowering_state, operand),
+
+        CastKind::NullToPointer => {
+            lower_value(lowering_state, operand);
+            Operand::Null
+        }
+
+        _ => {
+            let target = expression_type(lowering_state, expression);
+            let source = expression_type(lowering_state, operand);
+            let value = lower_value(lowering_state, operand);
+
+            convert(lowering_state, value, &source, &target)
+        }
+    }
+}
+
+
+fn lower_binary(lowering_state: &mut LoweringState, expression: NodeId, left: NodeId, operator: BinaryOperator, right: NodeId) -> Operand {
+    match operator {
+        BinaryOperator::Comma => {
+            lower_value(lowering_state, left);
+            return lower_value(lowering_state, right);
+        }
+
+        BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => return lower_logical(lowering_state, expression),
+
+        _ if operator.is_comparison() => {
+            let condition = lower_compare(lowering_state, left, operator, right);
+            return convert_condition(lowering_state, condition, IrType::I32);
+        }
+
+        _ => {}
+    }
+
+    let left_type = expression_type(lowering_state, left);
+    let right_type = expression_type(lowering_state, right);
+    let result_type = expression_type(lowering_state, expression);
+    let left_value = lower_value(lowering_state, left);
+    let right_value = lower_value(lowering_state, right);
+
+    match (operator, left_type.is_pointer(), right_type.is_pointer()) {
+        (BinaryOperator::Subtract, true, true) => {
+            let element_size = pointee_size(lowering_state, &left_type);
+            let left_value = lowering_state.emit(IrType::I64, InstructionKind::Convert { operator: ConversionOperator::PtrToInt, value: left_value, from: IrType::Ptr });
+            let right_value = lowering_state.emit(IrType::I64, InstructionKind::Convert { operator: ConversionOperator::PtrToInt, value: right_value, from: IrType::Ptr });
+            let difference = binary(lowering_state, ArithmeticOperator::Sub, IrType::I64, left_value, right_value);
+
+            let difference = match element_size {
+                1 => difference,
+                size => binary(lowering_state, ArithmeticOperator::SDiv, IrType::I64, difference, Operand::Integer(size as i64)),
+            };
+
+            convert(lowering_state, difference, &CType::Long, &result_type)
+        }
+
+        (BinaryOperator::Add | BinaryOperator::Subtract, true, false) => {
+            let element_size = pointee_size(lowering_state, &left_type);
+            let offset = scale(lowering_state, right_value, &right_type, element_size, operator == BinaryOperator::Subtract);
+            pointer_add(lowering_state, left_value, offset)
+        }
+
+        (BinaryOperator::Add, false, true) => {
+            let element_size = pointee_size(lowering_state, &right_type);
+            let offset = scale(lowering_state, left_value, &left_type, element_size, false);
+            pointer_add(lowering_state, right_value, offset)
+        }
+
+        _ => {
+            let left_value = convert(lowering_state, left_value, &left_type, &result_type);
+            let right_value = convert(lowering_state, right_value, &right_type, &result_type);
+            arithmetic(lowering_state, arithmetic_operator(operator, &result_type), &result_type, left_value, right_value)
+        }
+    }
+}
+
+
+fn lower_compare(lowering_state: &mut LoweringState, left: NodeId, operator: BinaryOperator, right: NodeId) -> Operand {
+    let left_type = expression_type(lowering_state, left);
+    let right_type = expression_type(lowering_state, right);
+    let left_value = lower_value(lowering_state, left);
+    let right_value = lower_value(lowering_state, right);
+
+    let common = match left_type.is_arithmetic() && right_type.is_arithmetic() {
+        true => CType::usual_arithmetic(&left_type, &right_type),
+        false if left_type.is_pointer() => left_type.decay(),
+        false => right_type.decay(),
+    };
+
+    let left_value = convert(lowering_state, left_value, &left_type, &common);
+    let right_value = convert(lowering_state, right_value, &right_type, &common);
+    let predicate = compare_predicate(operator, &common);
+
+    lowering_state.emit(ir_type(&common), InstructionKind::Compare { predicate, left: left_value, right: right_value })
+}
+
+
+fn lower_logical(lowering_state: &mut LoweringState, expression: NodeId) -> Operand {
+    let true_block = lowering_state.new_block();
+    let false_block = lowering_state.new_block();
+    let join = lowering_state.new_block();
+
+    lower_branch(lowering_state, expression, true_block, false_block);
+
+    lowering_state.current = true_block;
+    jump(lowering_state, join);
+    lowering_state.current = false_block;
+    jump(lowering_state, join);
+    lowering_state.current = join;
+
+    let incoming = vec![(Operand::Integer(1), true_block), (Operand::Integer(0), false_block)];
+    lowering_state.emit(IrType::I32, InstructionKind::Phi { incoming })
+}
+
+
+fn lower_branch(lowering_state: &mut LoweringState, expression: NodeId, then_block: BlockId, else_block: BlockId) {
+    let ast = lowering_state.ast;
+
+    match ast.kind(expression) {
+        NodeKind::BinaryOperation { left, operator: BinaryOperator::LogicalAnd, right } => {
+            let middle = lowering_state.new_block();
+            lower_branch(lowering_state, *left, middle, else_block);
+            lowering_state.current = middle;
+            lower_branch(lowering_state, *right, then_block, else_block);
+        }
+
+        NodeKind::BinaryOperation { left, operator: BinaryOperator::LogicalOr, right } => {
+            let middle = lowering_state.new_block();
+            lower_branch(lowering_state, *left, then_block, middle);
+            lowering_state.current = middle;
+            lower_branch(lowering_state, *right, then_block, else_block);
+        }
+
+        NodeKind::UnaryOperation { operator: UnaryOperator::Not, operand } => lower_branch(lowering_state, *operand, else_block, then_block),
+        NodeKind::ImplicitCast { kind: CastKind::ToBoolean, operand } => lower_branch(lowering_state, *operand, then_block, else_block),
+
+        NodeKind::BinaryOperation { left, operator, right } if operator.is_comparison() => {
+            let condition = lower_compare(lowering_state, *left, *operator, *right);
+            lowering_state.terminate(InstructionKind::Branch { condition, then_block, else_block });
+        }
+
+        _ => {
+            let ty = ir_type(&expression_type(lowering_state, expression).decay());
+
+            match lower_value(lowering_state, expression) {
+                Operand::Integer(value) => lowering_state.terminate(InstructionKind::Jump(if value != 0 { then_block } else { else_block })),
+                Operand::Null => lowering_state.terminate(InstructionKind::Jump(else_block)),
+                Operand::Global(_) => lowering_state.terminate(InstructionKind::Jump(then_block)),
+
+                value => {
+                    let condition = compare_zero(lowering_state, value, ty, false);
+                    lowering_state.terminate(InstructionKind::Branch { condition, then_block, else_block });
+                }
+            }
+        }
+    }
+}
+
+
+fn lower_assignment(lowering_state: &mut LoweringState, left: NodeId, operator: AssignmentOperator, right: NodeId) -> Operand {
+    let qualified_type = expression_type(lowering_state, left);
+    let left_type = qualified_type.strip_qualifiers();
+    let right_type = expression_type(lowering_state, right);
+    let ty = ir_type(&left_type);
+    let volatile = qualified_type.qualifiers().is_volatile;
+
+    let AssignmentOperator::Compound(operator) = operator else {
+        if is_aggregate(&left_type) {
+            let size = size_of(lowering_state, &left_type);
+            let destination = lower_address(lowering_state, left);
+            let source = lower_value(lowering_state, right);
+            lowering_state.emit(IrType::Void, InstructionKind::Copy { destination: destination.clone(), source, size });
+
+            return destination;
+        }
+
+        let bit_field = bit_field(lowering_state, left);
+        let value = lower_value(lowering_state, right);
+        let value = convert(lowering_state, value, &right_type, &left_type);
+        let address = lower_address(lowering_state, left);
+
+        match bit_field {
+            Some(bit_field) => store_bit_field(lowering_state, address, bit_field, &left_type, value.clone()),
+            None => {
+                lowering_state.emit(ty, InstructionKind::Store { value: value.clone(), address, volatile });
+            }
+        }
+
+        return value;
+    };
+
+    let bit_field = bit_field(lowering_state, left);
+    let address = lower_address(lowering_state, left);
+
+    let old = match bit_field {
+        Some(bit_field) => load_bit_field(lowering_state, address.clone(), bit_field, &left_type),
+        None => lowering_state.emit(ty, InstructionKind::Load { address: address.clone(), volatile }),
+    };
+
+    let value = lower_value(lowering_state, right);
+
+    let result = if left_type.is_pointer() {
+        let element_size = pointee_size(lowering_state, &left_type);
+        let offset = scale(lowering_state, value, &right_type, element_size, operator == BinaryOperator::Subtract);
+        pointer_add(lowering_state, old, offset)
+    } else {
+        let common = match operator {
+            BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => left_type.promote(),
+            _ => CType::usual_arithmetic(&left_type, &right_type),
+        };
+
+        let old = convert(lowering_state, old, &left_type, &common);
+        let value = convert(lowering_state, value, &right_type, &common);
+        let result = arithmetic(lowering_state, arithmetic_operator(operator, &common), &common, old, value);
+
+        convert(lowering_state, result, &common, &left_type)
+    };
+
+    match bit_field {
+        Some(bit_field) => store_bit_field(lowering_state, address, bit_field, &left_type, result.clone()),
+        None => {
+            lowering_state.emit(ty, InstructionKind::Store { value: result.clone(), address, volatile });
+        }
+    }
+
+    result
+}
+
+
+fn lower_unary(lowering_state: &mut LoweringState, expression: NodeId, operator: UnaryOperator, operand: NodeId) -> Operand {
+    let result_type = expression_type(lowering_state, expression);
+    let ty = ir_type(&result_type);
+
+    match operator {
+        UnaryOperator::Address => lower_address(lowering_state, operand),
+        UnaryOperator::Dereference if matches!(result_type.unqualified(), CType::Function { .. }) => lower_value(lowering_state, operand),
+        UnaryOperator::Dereference => load_lvalue(lowering_state, expression),
+        UnaryOperator::Plus => lower_value(lowering_state, operand),
+
+        UnaryOperator::Minus => match lower_value(lowering_state, operand) {
+            Operand::Integer(value) => Operand::Integer(wrap(-(value as i128), ty)),
+            Operand::Floating(value) => Operand::Floating(-value),
+            value if ty.is_floating() => binary(lowering_state, ArithmeticOperator::FSub, ty, Operand::Floating(-0.0), value),
+            value => arithmetic(lowering_state, ArithmeticOperator::Sub, &result_type, Operand::Integer(0), value),
+        },
+
+        UnaryOperator::BitNot => match lower_value(lowering_state, operand) {
+            Operand::Integer(value) => Operand::Integer(wrap(!value as i128, ty)),
+            value => binary(lowering_state, ArithmeticOperator::Xor, ty, value, Operand::Integer(-1)),
+        },
+
+        UnaryOperator::Not => {
+            let operand_type = ir_type(&expression_type(lowering_state, operand).decay());
+            let value = lower_value(lowering_state, operand);
+            let condition = compare_zero(lowering_state, value, operand_type, true);
+
+            convert_condition(lowering_state, condition, ty)
+        }
+
+        UnaryOperator::PreIncrement => lower_increment(lowering_state, operand, 1, true),
+        UnaryOperator::PreDecrement => lower_increment(lowering_state, operand, -1, true),
+
+        UnaryOperator::Sizeof => {
+            if let Some(ConstantValue::Integer(value, _)) = lowering_state.types.constants.get(&expression) {
+                return Operand::Integer(wrap(*value, ty));
+            }
+
+            let size = lowering_state.table.resolve(strip_casts(lowering_state.ast, operand))
+                .and_then(|entity| lowering_state.variable_sizes.get(&entity))
+                .cloned();
+
+            size.unwrap_or_else(|| {
+                report(lowering_state, expression, "IR lowering does not support 'sizeof' of this variable length array".to_string());
+                Operand::Undef
+            })
+        }
+    }
+}
+
+
+fn lower_increment(lowering_state: &mut LoweringState, operand: NodeId, delta: i64, prefix: bool) -> Operand {
+    let qualified_type = expression_type(lowering_state, operand);
+    let ctype = qualified_type.strip_qualifiers();
+    let ty = ir_type(&ctype);
+    let volatile = qualified_type.qualifiers().is_volatile;
+    let bit_field = bit_field(lowering_state, operand);
+    let address = lower_address(lowering_state, operand);
+
+    let old = match bit_field {
+        Some(bit_field) => load_bit_field(lowering_state, address.clone(), bit_field, &ctype),
+        None => lowering_state.emit(ty, InstructionKind::Load { address: address.clone(), volatile }),
+    };
+
+    let new = match ctype.unqualified() {
+        CType::Pointer(_) => {
+            let element_size = pointee_size(lowering_state, &ctype);
+            pointer_add(lowering_state, old.clone(), Operand::Integer(delta * element_size as i64))
+        }
+
+        CType::Bool => {
+            let promoted = convert(lowering_state, old.clone(), &ctype, &CType::Int);
+            let result = binary(lowering_state, ArithmeticOperator::Add, IrType::I32, promoted, Operand::Integer(delta));
+            convert(lowering_state, result, &CType::Int, &ctype)
+        }
+
+        _ if ty.is_floating() => binary(lowering_state, ArithmeticOperator::FAdd, ty, old.clone(), Operand::Floating(delta as f64)),
+        _ => arithmetic(lowering_state, ArithmeticOperator::Add, &ctype, old.clone(), Operand::Integer(delta)),
+    };
+
+    match bit_field {
+        Some(bit_field) => store_bit_field(lowering_state, address, bit_field, &ctype, new.clone()),
+        None => {
+            lowering_state.emit(ty, InstructionKind::Store { value: new.clone(), address, volatile });
+        }
+    }
+
+    if prefix { new } else { old }
+}
+
+
+fn lower_conditional(lowering_state: &mut LoweringState, expression: NodeId, condition: NodeId, then_value: NodeId, else_value: NodeId) -> Operand {
+    let result_type = expression_type(lowering_state, expression);
+    let then_block = lowering_state.new_block();
+    let else_block = lowering_state.new_block();
+    let join = lowering_state.new_block();
+
+    lower_branch(lowering_state, condition, then_block, else_block);
+
+    let mut incoming = Vec::new();
+
+    for (block, value) in [(then_block, then_value), (else_block, else_value)] {
+        lowering_state.current = block;
+        let source = expression_type(lowering_state, value);
+        let operand = lower_value(lowering_state, value);
+        let operand = convert(lowering_state, operand, &source, &result_type);
+
+        incoming.push((operand, lowering_state.current));
+        jump(lowering_state, join);
+    }
+
+    lowering_state.current = join;
+
+    match ir_type(&result_type) {
+        IrType::Void => Operand::Undef,
+        ty => lowering_state.emit(ty, InstructionKind::Phi { incoming }),
+    }
+}
+
+
+fn lower_call(lowering_state: &mut LoweringState, expression: NodeId, function: NodeId, arguments: &[NodeId]) -> Operand {
+    let ast = lowering_state.ast;
+
+    if let NodeKind::Identifier(name) = ast.kind(strip_casts(ast, function)) && lowering_state.name(*name) == "__builtin_unreachable" {
+        lowering_state.terminate(InstructionKind::Unreachable);
+        return Operand::Undef;
+    }
+
+    let result_type = expression_type(lowering_state, expression);
+    let target = strip_casts(ast, function);
+
+    let callee = match ast.kind(target) {
+        NodeKind::Identifier(_) if !lowering_state.types.expressions.contains_key(&target) => lower_address(lowering_state, target),
+        _ => lower_value(lowering_state, function),
+    };
+    let mut values = Vec::new();
+
+    let slot = is_aggregate(&result_type).then(|| stack_slot(lowering_state, &result_type, 1));
+
+    if let Some(slot) = &slot {
+        values.push((IrType::Ptr, slot.clone()));
+    }
+
+    for &argument in arguments {
+        let ctype = expression_type(lowering_state, argument);
+        let value = lower_value(lowering_state, argument);
+
+        if is_aggregate(&ctype) {
+            let size = size_of(lowering_state, &ctype);
+            let copy = stack_slot(lowering_state, &ctype, 1);
+            lowering_state.emit(IrType::Void, InstructionKind::Copy { destination: copy.clone(), source: value, size });
+            values.push((IrType::Ptr, copy));
+            continue;
+        }
+
+        values.push((ir_type(&ctype.decay()), value));
+    }
+
+    let ty = if slot.is_some() { IrType::Void } else { ir_type(&result_type) };
+    let result = lowering_state.emit(ty, InstructionKind::Call { callee, arguments: values });
+
+    slot.unwrap_or(result)
+}
+
+
+/* * * * * * * * * * */
+/*  - BIT-FIELDS - */
+/* * * * * * * * * * */
+fn bit_field(lowering_state: &LoweringState, expression: NodeId) -> Option<BitField> {
+    let NodeKind::Member { object, member, arrow } = lowering_state.ast.kind(expression) else {
+        return None;
+    };
+
+    let object_type = expression_type(lowering_state, *object);
+    let record = if *arrow { object_type.pointee()?.clone() } else { object_type };
+
+    let CType::Record { kind, tag } = record.unqualified() else {
+        return None;
+    };
+
+    let members = lowering_state.types.records.get(tag)?;
+    let index = members.iter().position(|candidate| candidate.name == Some(*member))?;
+    let field = *lowering_state.types.record_layout(*kind, tag, lowering_state.target)?.fields.get(index)?;
+
+    field.bit_offset?;
+    members[index].bit_width.map(|width| (field, width))
+}
+
+
+fn load_bit_field(lowering_state: &mut LoweringState, address: Operand, (field, width): BitField, ctype: &CType) -> Operand {
+    let ty = ir_type(ctype);
+    let bits = ty.bits() as u64;
+    let offset = field.bit_offset.unwrap_or(0);
+    let unit = lowering_state.emit(ty, InstructionKind::Load { address, volatile: ctype.qualifiers().is_volatile });
+
+    if ctype.is_unsigned() {
+        let shifted = match offset {
+            0 => unit,
+            offset => binary(lowering_state, ArithmeticOperator::LShr, ty, unit, Operand::Integer(offset as i64)),
+        };
+
+        return match width {
+            _ if width >= bits => shifted,
+            width => binary(lowering_state, ArithmeticOperator::And, ty, shifted, Operand::Integer(((1i128 << width) - 1) as i64)),
+        };
+    }
+
+    let shifted = match bits - offset - width {
+        0 => unit,
+        shift => binary(lowering_state, ArithmeticOperator::Shl, ty, unit, Operand::Integer(shift as i64)),
+    };
+
+    match bits - width {
+        0 => shifted,
+        shift => binary(lowering_state, ArithmeticOperator::AShr, ty, shifted, Operand::Integer(shift as i64)),
+    }
+}
+
+
+fn store_bit_field(lowering_state: &mut LoweringState, address: Operand, (field, width): BitField, ctype: &CType, value: Operand) {
+    let ty = ir_type(ctype);
+    let offset = field.bit_offset.unwrap_or(0);
+    let mask = ((1i128 << width) - 1) << offset;
+
+    let volatile = ctype.qualifiers().is_volatile;
+    let old = lowering_state.emit(ty, InstructionKind::Load { address: address.clone(), volatile });
+    let cleared = binary(lowering_state, ArithmeticOperator::And, ty, old, Operand::Integer(wrap(!mask, ty)));
+
+    let shifted = match offset {
+        0 => value,
+        offset => binary(lowering_state, ArithmeticOperator::Shl, ty, value, Operand::Integer(offset as i64)),
+    };
+
+    let masked = binary(lowering_state, ArithmeticOperator::And, ty, shifted, Operand::Integer(wrap(mask, ty)));
+    let combined = binary(lowering_state, ArithmeticOperator::Or, ty, cleared, masked);
+
+    lowering_state.emit(ty, InstructionKind::Store { value: combined, address, volatile });
+}
+
+
+/* * * * * * * * * * */
+/*  - CONVERSIONS - */
+/* * * * * * * * * * */
+fn convert(lowering_state: &mut LoweringState, value: Operand, from: &CType, to: &CType) -> Operand {
+    let from = from.decay();
+    let source = ir_type(&from);
+    let target = ir_type(to);
+
+    if target == IrType::Void || source == IrType::Void {
+        return Operand::Undef;
+    }
+
+    if matches!(to.unqualified(), CType::Bool) && !matches!(from, CType::Bool) {
+        match value {
+            Operand::Integer(integer) => return Operand::Integer((integer != 0) as i64),
+            Operand::Floating(floating) => return Operand::Integer((floating != 0.0) as i64),
+            Operand::Null => return Operand::Integer(0),
+            Operand::Global(_) => return Operand::Integer(1),
+            _ => {}
+        }
+
+        let condition = compare_zero(lowering_state, value, source, false);
+        return convert_condition(lowering_state, condition, IrType::I8);
+    }
+
+    if source == target {
+        return value;
+    }
+
+    match (&value, source.is_floating(), target.is_floating()) {
+        (Operand::Integer(integer), false, false) if target != IrType::Ptr && source != IrType::Ptr => {
+            let integer = if from.is_unsigned() { *integer as i128 & ((1i128 << source.bits()) - 1) } else { *integer as i128 };
+            return Operand::Integer(wrap(integer, target));
+        }
+
+        (Operand::Integer(0), false, false) if target == IrType::Ptr => return Operand::Null,
+
+        (Operand::Integer(integer), false, true) => {
+            let integer = if from.is_unsigned() { (*integer as u64 & (u64::MAX >> (64 - source.bits()))) as f64 } else { *integer as f64 };
+            return Operand::Floating(round_floating(integer, target));
+        }
+
+        (Operand::Floating(floating), true, true) => return Operand::Floating(round_floating(*floating, target)),
+        (Operand::Floating(floating), true, false) if target != IrType::Ptr => return Operand::Integer(wrap(*floating as i128, target)),
+        _ => {}
+    }
+
+    let operator = match (source, target) {
+        (IrType::Ptr, _) => {
+            let integer = lowering_state.emit(IrType::I64, InstructionKind::Convert { operator: ConversionOperator::PtrToInt, value, from: IrType::Ptr });
+            return convert(lowering_state, integer, &CType::UnsignedLong, to);
+        }
+
+        (_, IrType::Ptr) => {
+            let extended = convert(lowering_state, value, &from, &CType::Long);
+            return lowering_state.emit(IrType::Ptr, InstructionKind::Convert { operator: ConversionOperator::IntToPtr, value: extended, from: IrType::I64 });
+        }
+
+        _ if source.is_floating() && target.is_floating() => if target.bits() < source.bits() { ConversionOperator::FpTrunc } else { ConversionOperator::FpExt },
+        _ if source.is_floating() => if to.is_unsigned() { ConversionOperator::FpToUi } else { ConversionOperator::FpToSi },
+        _ if target.is_floating() => if from.is_unsigned() { ConversionOperator::UiToFp } else { ConversionOperator::SiToFp },
+        _ if target.bits() < source.bits() => ConversionOperator::Trunc,
+        _ if from.is_unsigned() => ConversionOperator::ZExt,
+        _ => ConversionOperator::SExt,
+    };
+
+    lowering_state.emit(target, InstructionKind::Convert { operator, value, from: source })
+}
+
+
+fn convert_condition(lowering_state: &mut LoweringState, condition: Operand, ty: IrType) -> Operand {
+    lowering_state.emit(ty, InstructionKind::Convert { operator: ConversionOperator::ZExt, value: condition, from: IrType::I1 })
+}
+
+
+fn compare_zero(lowering_state: &mut LoweringState, value: Operand, ty: IrType, equal: bool) -> Operand {
+    let (predicate, zero) = match ty {
+        IrType::Ptr => (if equal { ComparePredicate::Eq } else { ComparePredicate::Ne }, Operand::Null),
+        _ if ty.is_floating() => (if equal { ComparePredicate::FEq } else { ComparePredicate::FNe }, Operand::Floating(0.0)),
+        _ => (if equal { ComparePredicate::Eq } else { ComparePredicate::Ne }, Operand::Integer(0)),
+    };
+
+    lowering_state.emit(ty, InstructionKind::Compare { predicate, left: value, right: zero })
+}
+
+
+fn scale(lowering_state: &mut LoweringState, value: Operand, ctype: &CType, element_size: u64, negate: bool) -> Operand {
+    let value = convert(lowering_state, value, ctype, &CType::Long);
+
+    let value = match (value, element_size) {
+        (Operand::Integer(integer), size) => Operand::Integer(integer.wrapping_mul(size as i64)),
+        (value, 1) => value,
+        (value, size) => binary(lowering_state, ArithmeticOperator::Mul, IrType::I64, value, Operand::Integer(size as i64)),
+    };
+
+    match (value, negate) {
+        (Operand::Integer(integer), true) => Operand::Integer(integer.wrapping_neg()),
+        (value, true) => binary(lowering_state, ArithmeticOperator::Sub, IrType::I64, Operand::Integer(0), value),
+        (value, false) => value,
+    }
+}
+
+
+fn pointer_add(lowering_state: &mut LoweringState, base: Operand, offset: Operand) -> Operand {
+    match offset {
+        Operand::Integer(0) => base,
+        offset => lowering_state.emit(IrType::Ptr, InstructionKind::PtrAdd { base, offset }),
+    }
+}
+
+
+fn offset_address(lowering_state: &mut LoweringState, base: Operand, offset: u64) -> Operand {
+    pointer_add(lowering_state, base, Operand::Integer(offset as i64))
+}
+
+
+fn binary(lowering_state: &mut LoweringState, operator: ArithmeticOperator, ty: IrType, left: Operand, right: Operand) -> Operand {
+    lowering_state.emit(ty, InstructionKind::Binary { operator, left, right, no_signed_wrap: false })
+}
+
+
+fn arithmetic(lowering_state: &mut LoweringState, operator: ArithmeticOperator, ctype: &CType, left: Operand, right: Operand) -> Operand {
+    let signed = ctype.is_integer() && !ctype.is_unsigned() && ir_type(ctype).size() >= IrType::I32.size();
+    let no_signed_wrap = signed && matches!(operator, ArithmeticOperator::Add | ArithmeticOperator::Sub | ArithmeticOperator::Mul);
+
+    lowering_state.emit(ir_type(ctype), InstructionKind::Binary { operator, left, right, no_signed_wrap })
+}
+
+
+fn stack_slot(lowering_state: &mut LoweringState, ctype: &CType, align: u64) -> Operand {
+    let layout = lowering_state.types.layout(ctype, lowering_state.target);
+    let size = Operand::Integer(layout.map_or(0, |layout| layout.size) as i64);
+    let align = layout.map_or(1, |layout| layout.align).max(align);
+
+    let function = lowering_state.function();
+    let register = function.new_register();
+    let entry = &mut function.blocks[0].instructions;
+    let position = entry.iter().take_while(|instruction| matches!(instruction.kind, InstructionKind::Alloca { .. })).count();
+
+    entry.insert(position, Instruction { result: Some(register), ty: IrType::Ptr, kind: InstructionKind::Alloca { size, align } });
+    Operand::Register(register)
+}
+
+
+fn arithmetic_operator(operator: BinaryOperator, ctype: &CType) -> ArithmeticOperator {
+    let floating = ctype.is_floating();
+    let unsigned = ctype.is_unsigned();
+
+    match operator {
+        BinaryOperator::Add if floating => ArithmeticOperator::FAdd,
+        BinaryOperator::Subtract if floating => ArithmeticOperator::FSub,
+        BinaryOperator::Multiply if floating => ArithmeticOperator::FMul,
+        BinaryOperator::Divide if floating => ArithmeticOperator::FDiv,
+        BinaryOperator::Add => ArithmeticOperator::Add,
+        BinaryOperator::Subtract => ArithmeticOperator::Sub,
+        BinaryOperator::Multiply => ArithmeticOperator::Mul,
+        BinaryOperator::Divide if unsigned => ArithmeticOperator::UDiv,
+        BinaryOperator::Divide => ArithmeticOperator::SDiv,
+        BinaryOperator::Remainder if unsigned => ArithmeticOperator::URem,
+        BinaryOperator::Remainder => ArithmeticOperator::SRem,
+        BinaryOperator::BitAnd => ArithmeticOperator::And,
+        BinaryOperator::BitOr => ArithmeticOperator::Or,
+        BinaryOperator::BitXor => ArithmeticOperator::Xor,
+        BinaryOperator::ShiftLeft => ArithmeticOperator::Shl,
+        BinaryOperator::ShiftRight if unsigned => ArithmeticOperator::LShr,
+        _ => ArithmeticOperator::AShr,
+    }
+}
+
+
+fn compare_predicate(operator: BinaryOperator, ctype: &CType) -> ComparePredicate {
+    let floating = ctype.is_floating();
+    let unsigned = ctype.is_unsigned() || ctype.is_pointer();
+
+    match operator {
+        BinaryOperator::Equal if floating => ComparePredicate::FEq,
+        BinaryOperator::NotEqual if floating => ComparePredicate::FNe,
+        BinaryOperator::Less if floating => ComparePredicate::FLt,
+        BinaryOperator::LessEqual if floating => ComparePredicate::FLe,
+        BinaryOperator::Greater if floating => ComparePredicate::FGt,
+        BinaryOperator::GreaterEqual if floating => ComparePredicate::FGe,
+        BinaryOperator::Equal => ComparePredicate::Eq,
+        BinaryOperator::NotEqual => ComparePredicate::Ne,
+        BinaryOperator::Less if unsigned => ComparePredicate::Ult,
+        BinaryOperator::LessEqual if unsigned => ComparePredicate::Ule,
+        BinaryOperator::Greater if unsigned => ComparePredicate::Ugt,
+        BinaryOperator::GreaterEqual if unsigned => ComparePredicate::Uge,
+        BinaryOperator::Less => ComparePredicate::Slt,
+        BinaryOperator::LessEqual => ComparePredicate::Sle,
+        BinaryOperator::Greater => ComparePredicate::Sgt,
+        _ => ComparePredicate::Sge,
+    }
+}
+
+
+/* * * * * * * * * * */
+/*  - CONSTANTS -  */
+/* * * * * * * * * * */
+impl ConstantContext for LoweringState<'_> {
+    fn ast(&self) -> &Ast {
+        self.ast
+    }
+
+
+    fn enumerator(&self, identifier: NodeId) -> Option<i128> {
+        match self.ast.kind(identifier) {
+            NodeKind::Identifier(name) => self.enumerators.get(name).copied(),
+            _ => None,
+        }
+    }
+
+
+    fn is_static(&self, _identifier: NodeId) -> bool {
+        true
+    }
+
+
+    fn type_of(&self, expression: NodeId) -> Option<CType> {
+        self.types.expressions.get(&expression).cloned()
+    }
+
+
+    fn type_name(&self, node: NodeId, _type_name: &TypeName) -> CType {
+        self.type_names.get(&node).cloned().unwrap_or(CType::Int)
+    }
+
+
+    fn layout(&self, ctype: &CType) -> Option<TypeLayout> {
+        self.types.layout(ctype, self.target)
+    }
+
+
+    fn member_offset(&self, record: &CType, member: Symbol) -> Option<u64> {
+        match record.unqualified() {
+            CType::Record { kind, tag } => self.types.member_offset(*kind, tag, member, self.target),
+            _ => None,
+        }
+    }
+}
+
+
+/* * * * * * * * * * */
+/*  - HELPERS -  */
+/* * * * * * * * * * */
+fn ir_type(ctype: &CType) -> IrType {
+    match ctype.unqualified() {
+        CType::Void => IrType::Void,
+        CType::Bool | CType::Char | CType::SignedChar | CType::UnsignedChar => IrType::I8,
+        CType::Short | CType::UnsignedShort => IrType::I16,
+        CType::Int | CType::UnsignedInt | CType::Enum(_) => IrType::I32,
+        CType::Long | CType::UnsignedLong | CType::LongLong | CType::UnsignedLongLong => IrType::I64,
+        CType::Float => IrType::F32,
+        CType::Double | CType::LongDouble => IrType::F64,
+        CType::Pointer(_) | CType::Array(..) | CType::Function { .. } | CType::Record { .. } => IrType::Ptr,
+        CType::Qualified(_, inner) => ir_type(inner),
+    }
+}
+
+
+fn is_aggregate(ctype: &CType) -> bool {
+    matches!(ctype.unqualified(), CType::Array(..) | CType::Record { .. })
+}
+
+
+fn wrap(value: i128, ty: IrType) -> i64 {
+    match ty.bits() {
+        bits if bits >= 64 => value as i64,
+        bits => (value as i64) << (64 - bits) >> (64 - bits),
+    }
+}
+
+
+fn round_floating(value: f64, ty: IrType) -> f64 {
+    match ty {
+        IrType::F32 => value as f32 as f64,
+        _ => value,
+    }
+}
+
+
+fn expression_type(lowering_state: &LoweringState, expression: NodeId) -> CType {
+    lowering_state.types.expressions.get(&expression).cloned().unwrap_or(CType::Int)
+}
+
+
+fn declared_type(lowering_state: &LoweringState, declaration: NodeId, name: Symbol) -> Option<CType> {
+    lowering_state.types.declarations.get(&declaration)?.iter().find(|(declared, _)| *declared == name).map(|(_, ctype)| ctype.clone())
+}
+
+
+fn size_of(lowering_state: &LoweringState, ctype: &CType) -> u64 {
+    lowering_state.types.layout(ctype, lowering_state.target).map_or(0, |layout| layout.size)
+}
+
+
+fn pointee_size(lowering_state: &LoweringState, ctype: &CType) -> u64 {
+    match ctype.pointee().map(|pointee| size_of(lowering_state, pointee)) {
+        Some(0) | None => 1,
+        Some(size) => size,
+    }
+}
+
+
+fn strip_casts(ast: &Ast, mut expression: NodeId) -> NodeId {
+    while let NodeKind::ImplicitCast { operand, .. } = ast.kind(expression) {
+        expression = *operand;
+    }
+
+    expression
+}
+
+
+fn constant_of(lowering_state: &LoweringState, mut expression: NodeId) -> Option<ConstantValue> {
+    let original = expression;
+
+    loop {
+        if let Some(constant) = lowering_state.types.constants.get(&expression) {
+            return Some(constant.clone());
+        }
+
+        match lowering_state.ast.kind(expression) {
+            NodeKind::ImplicitCast { operand, .. } => expression = *operand,
+            _ => return Evaluator::new(lowering_state).evaluate(original).ok(),
+        }
+    }
+}
+
+
+fn report(lowering_state: &mut LoweringState, node: NodeId, message: String) {
+    let line = lowering_state.ast[node].span.start;
+    lowering_state.diagnostics.push(Diagnostic::new(Severity::Error, format!("{}: {}", line, message)));
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{ Diagnostic, Module, Target };
+    use crate::logic::{ interpreter_start, ir_start, lexer_start, parser_start, semantic_start, sym_table_start };
+
+
+    fn lower(source: &str) -> (Module, Vec<Diagnostic>) {
+        let tokens = lexer_start(source).expect("source should lex");
+        let (mut ast, errors) = parser_start(&tokens);
+        assert!(errors.is_empty(), "parse errors: {:?}", errors);
+
+        let target = Target::x86_64_sysv();
+        let (table, _) = sym_table_start(&ast);
+        let (types, _) = semantic_start(&mut ast, &table, &target);
+
+        ir_start(&ast, &table, &types, &target)
+    }
+
+
+    #[test]
+    fn sizes_variable_outer_dimension() {
+        let (module, diagnostics) = lower("int main(void) { int n = 5; int v[n][3]; v[4][2] = 7; return sizeof v + v[4][2]; }");
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>());
+
+        let code = interpreter_start(&module, "vla", &mut Vec::new()).unwrap_or_else(|diagnostic| panic!("{}", diagnostic));
+        assert_eq!(code, 67);
+    }
+
+
+    #[test]
+    fn rejects_variable_inner_dimension() {
+        let (_, diagnostics) = lower("int main(void) { int n = 5; int v[3][n]; v[2][4] = 7; return v[2][4]; }");
+
+        assert!(diagnostics.iter().any(|diagnostic| diagnostic.to_string().contains("multidimensional variable length arrays")));
+    }
+
+
+    #[test]
+    fn sizes_arrays_through_brace_elision() {
+        let (module, diagnostics) = lower("struct P { int x, y; }; struct P ps[] = { 1, 2, 3, 4 }; int mm[][2] = { 1, 2, 3, 4, 5 }; \
+            union U { int i; char c[4]; }; union U us[] = { [1].c = \"xyz\", 3 }; \
+            int main(void) { return sizeof ps + sizeof mm + sizeof us + mm[2][0] + us[1].c[1] + us[2].i; }");
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>());
+
+        let code = interpreter_start(&module, "elision", &mut Vec::new()).unwrap_or_else(|diagnostic| panic!("{}", diagnostic));
+        assert_eq!(code, 16 + 24 + 12 + 5 + 'y' as i32 + 3);
+    }
+
+
+    #[test]
+    fn initializes_char_arrays_from_braced_strings() {
+        let (module, diagnostics) = lower("struct E { char s[3]; int n; }; char s[] = { \"xy\" }; struct E es[] = { \"ab\", 1, \"cd\", 2 }; \
+            int main(void) { char t[] = { \"pqr\" }; return sizeof s + sizeof es + sizeof t + es[1].s[1] + es[1].n + s[1] + t[2]; }");
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>());
+
+        let code = interpreter_start(&module, "strings", &mut Vec::new()).unwrap_or_else(|diagnostic| panic!("{}", diagnostic));
+        assert_eq!(code, 3 + 16 + 4 + 'd' as i32 + 2 + 'y' as i32 + 'r' as i32);
+    }
+
+
+    #[test]
+    fn resolves_shadowed_tags_and_typedefs() {
+        let (module, diagnostics) = lower("typedef struct A { int x; } A; int f(A a, struct A b) { return a.x + b.x; } typedef int T; \
+            int main(void) { struct A { long y, z; } inner; A outer = { 4 }; T t = sizeof(struct A) + sizeof(A); \
+            { typedef char T; T c = 1; t += sizeof(T) + c; } inner.y = 1; return f(outer, outer) + t + (int)inner.y; }");
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>());
+
+        let code = interpreter_start(&module, "shadow", &mut Vec::new()).unwrap_or_else(|diagnostic| panic!("{}", diagnostic));
+        assert_eq!(code, 8 + 16 + 4 + 2 + 1);
+    }
+}
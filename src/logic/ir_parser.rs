@@ -0,0 +1,920 @@
+use std::collections::HashMap;
+
+use crate::data::{ Module, ParseError };
+use crate::data::maps::{ ARITHMETIC_OPERATOR_MAP, COMPARE_PREDICATE_MAP, CONVERSION_OPERATOR_MAP, IR_TYPE_MAP };
+use crate::data::structures::{ Block, FunctionSource, Global, Instruction, IrFunction, IrParserState, Relocation, SourceMap };
+use crate::data::types::{ BlockId, InlineHint, InstructionKind, IrToken, IrType, Operand, Register };
+
+
+type ParseResult<T> = Result<T, ParseError>;
+
+
+pub fn ir_parser_start(source: &str) -> (Module, SourceMap, Vec<ParseError>) {
+    let (tokens, mut errors) = tokenize(source);
+    let mut ir_parser_state = IrParserState::new(tokens);
+    ir_parser_state.errors.append(&mut errors);
+
+    while let Some(token) = ir_parser_state.peek() {
+        if *token == IrToken::Newline {
+            ir_parser_state.advance();
+            continue;
+        }
+
+        if let Err(e) = process_item(&mut ir_parser_state) {
+            ir_parser_state.errors.push(e);
+            synchronize(&mut ir_parser_state);
+        }
+    }
+
+    ir_parser_state.source_map.lines = source.lines().map(String::from).collect();
+
+    (ir_parser_state.module, ir_parser_state.source_map, ir_parser_state.errors)
+}
+
+
+/* * * * * * * * * */
+/*  - TOKENIZER -  */
+/* * * * * * * * * */
+fn tokenize(source: &str) -> (Vec<(IrToken, u16)>, Vec<ParseError>) {
+    let mut tokens = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, text) in source.lines().enumerate() {
+        let line = (index + 1) as u16;
+        let chars: Vec<char> = text.chars().collect();
+        let mut position = 0;
+
+        while position < chars.len() {
+            let current = chars[position];
+
+            match current {
+                ';' => break,
+                ' ' | '\t' | '\r' => position += 1,
+
+                '%' | '@' => {
+                    position += 1;
+                    let name = take_name(&chars, &mut position);
+                    tokens.push((if current == '%' { IrToken::Register(name) } else { IrToken::Global(name) }, line));
+                }
+
+                '"' => {
+                    position += 1;
+                    let mut bytes = Vec::new();
+
+                    while position < chars.len() && chars[position] != '"' {
+                        if chars[position] == '\\' {
+                            let escape: String = chars.iter().skip(position + 1).take(2).collect();
+
+                            match u8::from_str_radix(&escape, 16) {
+                                Ok(byte) => bytes.push(byte),
+                                Err(_) => errors.push(ParseError::new(line, "two hexadecimal digits after `\\`", &format!("`{}`", escape))),
+                            }
+
+                            position += 3;
+                        } else {
+                            let mut buffer = [0; 4];
+                            bytes.extend_from_slice(chars[position].encode_utf8(&mut buffer).as_bytes());
+                            position += 1;
+                        }
+                    }
+
+                    if position >= chars.len() {
+                        errors.push(ParseError::new(line, "`\"`", "end of line"));
+                    }
+
+                    position += 1;
+                    tokens.push((IrToken::String(bytes), line));
+                }
+
+                '.' if chars.get(position..position + 3) == Some(&['.', '.', '.']) => {
+                    position += 3;
+                    tokens.push((IrToken::Ellipsis, line));
+                }
+
+                '=' | ',' | '(' | ')' | '[' | ']' | '{' | '}' | ':' | '+' => {
+                    position += 1;
+                    tokens.push((IrToken::Punctuation(current), line));
+                }
+
+                _ if current.is_ascii_alphanumeric() || current == '_' || current == '-' || current == '.' => {
+                    let start = position;
+                    position += 1;
+
+                    while let Some(&next) = chars.get(position) {
+                        let exponent_sign = (next == '-' || next == '+') && matches!(chars[position - 1], 'e' | 'E')
+                            && (chars[start].is_ascii_digit() || chars[start] == '-');
+
+                        if !(next.is_ascii_alphanumeric() || next == '_' || next == '.' || exponent_sign) {
+                            break;
+                        }
+
+                        position += 1;
+                    }
+
+                    tokens.push((IrToken::Word(chars[start..position].iter().collect()), line));
+                }
+
+                _ => {
+                    errors.push(ParseError::new(line, "a token", &format!("`{}`", current)));
+                    position += 1;
+                }
+            }
+        }
+
+        tokens.push((IrToken::Newline, line));
+    }
+
+    (tokens, errors)
+}
+
+
+fn take_name(chars: &[char], position: &mut usize) -> String {
+    let start = *position;
+
+    while chars.get(*position).is_some_and(|next| next.is_ascii_alphanumeric() || matches!(next, '_' | '.' | '$')) {
+        *position += 1;
+    }
+
+    chars[start..*position].iter().collect()
+}
+
+
+/* * * * * * * * */
+/*  - GLOBALS -  */
+/* * * * * * * * */
+fn process_item(ir_parser_state: &mut IrParserState) -> ParseResult<()> {
+    if ir_parser_state.is_word("define") {
+        return process_function(ir_parser_state);
+    }
+
+    if ir_parser_state.is_word("declare") {
+        return process_declaration(ir_parser_state);
+    }
+
+    if matches!(ir_parser_state.peek(), Some(IrToken::Global(_))) {
+        return process_global(ir_parser_state);
+    }
+
+    Err(unexpected(ir_parser_state, "`define`, `declare` or a global"))
+}
+
+
+fn process_global(ir_parser_state: &mut IrParserState) -> ParseResult<()> {
+    let name = expect_global(ir_parser_state)?;
+    expect_punctuation(ir_parser_state, '=')?;
+
+    let mut global = Global {
+        name,
+        internal: false,
+        constant: false,
+        external: false,
+        size: 0,
+        align: 1,
+        data: Vec::new(),
+        relocations: Vec::new(),
+    };
+
+    if ir_parser_state.is_word("external") {
+        ir_parser_state.advance();
+        expect_word(ir_parser_state, "global")?;
+        global.external = true;
+    } else {
+        global.internal = accept_word(ir_parser_state, "internal");
+        global.constant = accept_word(ir_parser_state, "constant");
+
+        if !global.constant {
+            expect_word(ir_parser_state, "global")?;
+        }
+
+        global.size = expect_integer(ir_parser_state)? as u64;
+        expect_word(ir_parser_state, "align")?;
+        global.align = expect_integer(ir_parser_state)? as u64;
+
+        if let Some(IrToken::String(bytes)) = ir_parser_state.peek() {
+            global.data = bytes.clone();
+            ir_parser_state.advance();
+        }
+
+        if accept_punctuation(ir_parser_state, '[') {
+            loop {
+                let offset = expect_integer(ir_parser_state)? as u64;
+                expect_punctuation(ir_parser_state, ':')?;
+                let symbol = expect_global(ir_parser_state)?;
+                expect_punctuation(ir_parser_state, '+')?;
+                let addend = expect_integer(ir_parser_state)?;
+                global.relocations.push(Relocation { offset, symbol, addend });
+
+                if !accept_punctuation(ir_parser_state, ',') {
+                    break;
+                }
+            }
+
+            expect_punctuation(ir_parser_state, ']')?;
+        }
+    }
+
+    expect_newline(ir_parser_state)?;
+    ir_parser_state.module.globals.push(global);
+
+    Ok(())
+}
+
+
+fn process_declaration(ir_parser_state: &mut IrParserState) -> ParseResult<()> {
+    ir_parser_state.advance();
+    let return_type = expect_type(ir_parser_state)?;
+    let name = expect_global(ir_parser_state)?;
+    let mut parameters = Vec::new();
+    let mut variadic = false;
+
+    expect_punctuation(ir_parser_state, '(')?;
+
+    while !accept_punctuation(ir_parser_state, ')') {
+        if !parameters.is_empty() || variadic {
+            expect_punctuation(ir_parser_state, ',')?;
+        }
+
+        if ir_parser_state.peek() == Some(&IrToken::Ellipsis) {
+            ir_parser_state.advance();
+            variadic = true;
+            continue;
+        }
+
+        parameters.push((expect_type(ir_parser_state)?, Register(parameters.len() as u32)));
+    }
+
+    expect_newline(ir_parser_state)?;
+
+    let next_register = parameters.len() as u32;
+    ir_parser_state.module.functions.push(IrFunction { name, internal: false, inline_hint: InlineHint::None, return_type, parameters, variadic, blocks: Vec::new(), next_register });
+
+    Ok(())
+}
+
+
+/* * * * * * * * * * */
+/*  - FUNCTIONS -  */
+/* * * * * * * * * * */
+fn process_function(ir_parser_state: &mut IrParserState) -> ParseResult<()> {
+    ir_parser_state.advance();
+    ir_parser_state.registers.clear();
+    ir_parser_state.blocks.clear();
+
+    let internal = accept_word(ir_parser_state, "internal");
+    let return_type = expect_type(ir_parser_state)?;
+    let name = expect_global(ir_parser_state)?;
+    let mut parameters = Vec::new();
+    let mut variadic = false;
+
+    expect_punctuation(ir_parser_state, '(')?;
+
+    while !accept_punctuation(ir_parser_state, ')') {
+        if !parameters.is_empty() || variadic {
+            expect_punctuation(ir_parser_state, ',')?;
+        }
+
+        if ir_parser_state.peek() == Some(&IrToken::Ellipsis) {
+            ir_parser_state.advance();
+            variadic = true;
+            continue;
+        }
+
+        let ty = expect_type(ir_parser_state)?;
+        parameters.push((ty, expect_register(ir_parser_state)?));
+    }
+
+    let inline_hint = InlineHint::KEYWORDS.into_iter()
+        .find(|hint| hint.keyword().is_some_and(|keyword| accept_word(ir_parser_state, keyword)))
+        .unwrap_or_default();
+
+    expect_punctuation(ir_parser_state, '{')?;
+    expect_newline(ir_parser_state)?;
+
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut lines: Vec<Vec<u16>> = Vec::new();
+
+    loop {
+        let is_label = ir_parser_state.tokens.get(ir_parser_state.iterator + 1).is_some_and(|(token, _)| *token == IrToken::Punctuation(':'));
+
+        match ir_parser_state.peek().cloned() {
+            None => return Err(unexpected(ir_parser_state, "`}`")),
+            Some(IrToken::Newline) => {
+                ir_parser_state.advance();
+            }
+
+            Some(IrToken::Punctuation('}')) => {
+                ir_parser_state.advance();
+                break;
+            }
+
+            Some(IrToken::Word(label)) if is_label => {
+                let line = ir_parser_state.line();
+                let id = ir_parser_state.block(label);
+                ir_parser_state.advance();
+                ir_parser_state.advance();
+
+                if let Err(e) = expect_newline(ir_parser_state) {
+                    ir_parser_state.errors.push(e);
+                    skip_line(ir_parser_state);
+                }
+
+                if blocks.iter().any(|block| block.id == id) {
+                    ir_parser_state.errors.push(ParseError::new(line, "a new block label", "a redefinition"));
+                    continue;
+                }
+
+                blocks.push(Block { id, instructions: Vec::new() });
+                lines.push(Vec::new());
+            }
+
+            Some(_) => {
+                let start = ir_parser_state.iterator;
+                let line = ir_parser_state.line();
+
+                match process_instruction(ir_parser_state) {
+                    Ok(instruction) => {
+                        if blocks.is_empty() {
+                            let id = ir_parser_state.block(String::new());
+                            blocks.push(Block { id, instructions: Vec::new() });
+                            lines.push(Vec::new());
+                        }
+
+                        blocks.last_mut().unwrap().instructions.push(instruction);
+                        lines.last_mut().unwrap().push(line);
+                    }
+
+                    Err(e) => {
+                        ir_parser_state.errors.push(e);
+                        ir_parser_state.iterator = start;
+                        skip_line(ir_parser_state);
+                    }
+                }
+            }
+        }
+    }
+
+    expect_newline(ir_parser_state)?;
+
+    let mut function = IrFunction { name, internal, inline_hint, return_type, parameters, variadic, blocks, next_register: 0 };
+    let blocks = number_blocks(ir_parser_state, &mut function);
+    let registers = number_registers(ir_parser_state, &mut function);
+
+    let instructions = function.blocks.iter().zip(lines)
+        .flat_map(|(block, lines)| lines.into_iter().enumerate().map(move |(index, line)| ((block.id, index), line)))
+        .collect();
+
+    ir_parser_state.source_map.functions.insert(function.name.clone(), FunctionSource { blocks, registers, instructions });
+    ir_parser_state.module.functions.push(function);
+
+    Ok(())
+}
+
+
+fn number_blocks(ir_parser_state: &mut IrParserState, function: &mut IrFunction) -> HashMap<BlockId, String> {
+    let mut numbers: HashMap<BlockId, BlockId> = function.blocks.iter().enumerate()
+        .map(|(index, block)| (block.id, BlockId(index as u32)))
+        .collect();
+
+    let mut labels: Vec<(&String, &(BlockId, u16))> = ir_parser_state.blocks.iter().collect();
+    labels.sort_by_key(|(_, (id, _))| *id);
+
+    for &(label, (id, line)) in &labels {
+        if !numbers.contains_key(id) {
+            ir_parser_state.errors.push(ParseError::new(*line, &format!("a definition of block `{}`", label), "none"));
+            numbers.insert(*id, BlockId(numbers.len() as u32));
+        }
+    }
+
+    let names = labels.iter()
+        .filter(|(label, _)| !label.is_empty())
+        .map(|(label, (id, _))| (numbers[id], label.to_string()))
+        .collect();
+
+    for block in &mut function.blocks {
+        block.id = numbers[&block.id];
+
+        for instruction in &mut block.instructions {
+            match &mut instruction.kind {
+                InstructionKind::Jump(target) => *target = numbers[target],
+
+                InstructionKind::Branch { then_block, else_block, .. } => {
+                    *then_block = numbers[then_block];
+                    *else_block = numbers[else_block];
+                }
+
+                InstructionKind::Switch { default, cases, .. } => {
+                    *default = numbers[default];
+
+                    for (_, target) in cases {
+                        *target = numbers[target];
+                    }
+                }
+
+                InstructionKind::Phi { incoming } => {
+                    for (_, block) in incoming {
+                        *block = numbers[block];
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    names
+}
+
+
+fn number_registers(ir_parser_state: &mut IrParserState, function: &mut IrFunction) -> HashMap<Register, String> {
+    let mut numbers: HashMap<Register, Register> = HashMap::new();
+    let definitions = function.parameters.iter().map(|(_, register)| *register)
+        .chain(function.blocks.iter().flat_map(|block| block.instructions.iter().filter_map(|instruction| instruction.result)))
+        .collect::<Vec<_>>();
+
+    for register in definitions {
+        let next = Register(numbers.len() as u32);
+        numbers.entry(register).or_insert(next);
+    }
+
+    let mut names: Vec<(&String, &(Register, u16))> = ir_parser_state.registers.iter().collect();
+    names.sort_by_key(|(_, (register, _))| *register);
+
+    for &(name, (register, line)) in &names {
+        if !numbers.contains_key(register) {
+            ir_parser_state.errors.push(ParseError::new(*line, &format!("a definition of register `%{}`", name), "none"));
+            numbers.insert(*register, Register(numbers.len() as u32));
+        }
+    }
+
+    for (_, register) in &mut function.parameters {
+        *register = numbers[register];
+    }
+
+    for block in &mut function.blocks {
+        for instruction in &mut block.instructions {
+            instruction.result = instruction.result.map(|result| numbers[&result]);
+
+            for operand in instruction.operands_mut() {
+                if let Operand::Register(register) = operand {
+                    *register = numbers[register];
+                }
+            }
+        }
+    }
+
+    function.next_register = numbers.len() as u32;
+
+    names.iter().map(|(name, (register, _))| (numbers[register], name.to_string())).collect()
+}
+
+
+/* * * * * * * * * * * * */
+/*  - INSTRUCTIONS -  */
+/* * * * * * * * * * * * */
+fn process_instruction(ir_parser_state: &mut IrParserState) -> ParseResult<Instruction> {
+    let line = ir_parser_state.line();
+    let mut result = None;
+
+    if let Some(IrToken::Register(_)) = ir_parser_state.peek() {
+        result = Some(expect_register(ir_parser_state)?);
+        expect_punctuation(ir_parser_state, '=')?;
+    }
+
+    let opcode = match ir_parser_state.advance() {
+        Some(IrToken::Word(opcode)) => opcode,
+        _ => {
+            ir_parser_state.iterator -= 1;
+            return Err(unexpected(ir_parser_state, "an instruction"));
+        }
+    };
+
+    let (ty, kind) = process_operation(ir_parser_state, &opcode)?;
+    expect_newline(ir_parser_state)?;
+
+    match (result.is_some(), Instruction::defines_value(ty, &kind)) {
+        (true, false) => Err(ParseError::new(line, &format!("`{}` without a result register", opcode), "a result register")),
+        (false, true) => Err(ParseError::new(line, &format!("a result register for `{}`", opcode), "none")),
+        _ => Ok(Instruction { result, ty, kind }),
+    }
+}
+
+
+fn process_operation(ir_parser_state: &mut IrParserState, opcode: &str) -> ParseResult<(IrType, InstructionKind)> {
+    if let Some(&operator) = ARITHMETIC_OPERATOR_MAP.get(opcode) {
+        let no_signed_wrap = accept_word(ir_parser_state, "nsw");
+        let ty = expect_type(ir_parser_state)?;
+        let (left, right) = process_operand_pair(ir_parser_state)?;
+
+        return Ok((ty, InstructionKind::Binary { operator, left, right, no_signed_wrap }));
+    }
+
+    if let Some(&operator) = CONVERSION_OPERATOR_MAP.get(opcode) {
+        let from = expect_type(ir_parser_state)?;
+        let value = process_operand(ir_parser_state)?;
+        expect_word(ir_parser_state, "to")?;
+
+        return Ok((expect_type(ir_parser_state)?, InstructionKind::Convert { operator, value, from }));
+    }
+
+    match opcode {
+        "icmp" | "fcmp" => {
+            let predicate = match ir_parser_state.advance() {
+                Some(IrToken::Word(name)) if COMPARE_PREDICATE_MAP.get(name.as_str()).is_some_and(|predicate| predicate.is_floating() == (opcode == "fcmp")) => COMPARE_PREDICATE_MAP[name.as_str()],
+                _ => {
+                    ir_parser_state.iterator -= 1;
+                    return Err(unexpected(ir_parser_state, &format!("a `{}` predicate", opcode)));
+                }
+            };
+
+            let ty = expect_type(ir_parser_state)?;
+            let (left, right) = process_operand_pair(ir_parser_state)?;
+
+            Ok((ty, InstructionKind::Compare { predicate, left, right }))
+        }
+
+        "alloca" => {
+            let size = process_operand(ir_parser_state)?;
+            expect_word(ir_parser_state, "align")?;
+
+            Ok((IrType::Ptr, InstructionKind::Alloca { size, align: expect_integer(ir_parser_state)? as u64 }))
+        }
+
+        "load" => {
+            let volatile = accept_word(ir_parser_state, "volatile");
+            let ty = expect_type(ir_parser_state)?;
+
+            Ok((ty, InstructionKind::Load { address: process_operand(ir_parser_state)?, volatile }))
+        }
+
+        "store" => {
+            let volatile = accept_word(ir_parser_state, "volatile");
+            let ty = expect_type(ir_parser_state)?;
+            let (value, address) = process_operand_pair(ir_parser_state)?;
+
+            Ok((ty, InstructionKind::Store { value, address, volatile }))
+        }
+
+        "ptradd" => {
+            let (base, offset) = process_operand_pair(ir_parser_state)?;
+            Ok((IrType::Ptr, InstructionKind::PtrAdd { base, offset }))
+        }
+
+        "copy" => {
+            let (destination, source) = process_operand_pair(ir_parser_state)?;
+            expect_punctuation(ir_parser_state, ',')?;
+
+            Ok((IrType::Void, InstructionKind::Copy { destination, source, size: expect_integer(ir_parser_state)? as u64 }))
+        }
+
+        "zero" => {
+            let address = process_operand(ir_parser_state)?;
+            expect_punctuation(ir_parser_state, ',')?;
+
+            Ok((IrType::Void, InstructionKind::Zero { address, size: expect_integer(ir_parser_state)? as u64 }))
+        }
+
+        "call" => {
+            let ty = expect_type(ir_parser_state)?;
+            let callee = process_operand(ir_parser_state)?;
+            let mut arguments = Vec::new();
+
+            expect_punctuation(ir_parser_state, '(')?;
+
+            while !accept_punctuation(ir_parser_state, ')') {
+                if !arguments.is_empty() {
+                    expect_punctuation(ir_parser_state, ',')?;
+                }
+
+                let ty = expect_type(ir_parser_state)?;
+                arguments.push((ty, process_operand(ir_parser_state)?));
+            }
+
+            Ok((ty, InstructionKind::Call { callee, arguments }))
+        }
+
+        "phi" => {
+            let ty = expect_type(ir_parser_state)?;
+            let mut incoming = Vec::new();
+
+            loop {
+                expect_punctuation(ir_parser_state, '[')?;
+                let value = process_operand(ir_parser_state)?;
+                expect_punctuation(ir_parser_state, ',')?;
+                incoming.push((value, expect_block(ir_parser_state)?));
+                expect_punctuation(ir_parser_state, ']')?;
+
+                if !accept_punctuation(ir_parser_state, ',') {
+                    break;
+                }
+            }
+
+            Ok((ty, InstructionKind::Phi { incoming }))
+        }
+
+        "jmp" => Ok((IrType::Void, InstructionKind::Jump(expect_block(ir_parser_state)?))),
+
+        "br" => {
+            let condition = process_operand(ir_parser_state)?;
+            expect_punctuation(ir_parser_state, ',')?;
+            let then_block = expect_block(ir_parser_state)?;
+            expect_punctuation(ir_parser_state, ',')?;
+            let else_block = expect_block(ir_parser_state)?;
+
+            Ok((IrType::Void, InstructionKind::Branch { condition, then_block, else_block }))
+        }
+
+        "switch" => {
+            let ty = expect_type(ir_parser_state)?;
+            let value = process_operand(ir_parser_state)?;
+            expect_punctuation(ir_parser_state, ',')?;
+            let default = expect_block(ir_parser_state)?;
+            let mut cases = Vec::new();
+
+            expect_punctuation(ir_parser_state, '[')?;
+
+            while !accept_punctuation(ir_parser_state, ']') {
+                if !cases.is_empty() {
+                    expect_punctuation(ir_parser_state, ',')?;
+                }
+
+                let case = expect_integer(ir_parser_state)?;
+                expect_punctuation(ir_parser_state, ':')?;
+                cases.push((case, expect_block(ir_parser_state)?));
+            }
+
+            Ok((ty, InstructionKind::Switch { value, default, cases }))
+        }
+
+        "ret" => {
+            let ty = expect_type(ir_parser_state)?;
+
+            if ty == IrType::Void {
+                return Ok((ty, InstructionKind::Return(None)));
+            }
+
+            Ok((ty, InstructionKind::Return(Some(process_operand(ir_parser_state)?))))
+        }
+
+        "unreachable" => Ok((IrType::Void, InstructionKind::Unreachable)),
+
+        _ => {
+            ir_parser_state.iterator -= 1;
+            Err(unexpected(ir_parser_state, "an instruction"))
+        }
+    }
+}
+
+
+fn process_operand_pair(ir_parser_state: &mut IrParserState) -> ParseResult<(Operand, Operand)> {
+    let left = process_operand(ir_parser_state)?;
+    expect_punctuation(ir_parser_state, ',')?;
+
+    Ok((left, process_operand(ir_parser_state)?))
+}
+
+
+fn process_operand(ir_parser_state: &mut IrParserState) -> ParseResult<Operand> {
+    match ir_parser_state.peek().cloned() {
+        Some(IrToken::Register(name)) => {
+            let register = ir_parser_state.register(name);
+            ir_parser_state.advance();
+
+            Ok(Operand::Register(register))
+        }
+
+        Some(IrToken::Global(name)) => {
+            ir_parser_state.advance();
+            Ok(Operand::Global(name))
+        }
+
+        Some(IrToken::Word(word)) => {
+            let operand = match word.as_str() {
+                "null" => Operand::Null,
+                "undef" => Operand::Undef,
+                _ => match (word.parse::<i64>(), word.parse::<f64>()) {
+                    (Ok(value), _) => Operand::Integer(value),
+                    (_, Ok(value)) => Operand::Floating(value),
+                    _ => return Err(unexpected(ir_parser_state, "an operand")),
+                },
+            };
+
+            ir_parser_state.advance();
+            Ok(operand)
+        }
+
+        _ => Err(unexpected(ir_parser_state, "an operand")),
+    }
+}
+
+
+/* * * * * * * * */
+/*  - HELPERS -  */
+/* * * * * * * * */
+fn expect_type(ir_parser_state: &mut IrParserState) -> ParseResult<IrType> {
+    if let Some(IrToken::Word(name)) = ir_parser_state.peek() && let Some(&ty) = IR_TYPE_MAP.get(name.as_str()) {
+        ir_parser_state.advance();
+        return Ok(ty);
+    }
+
+    Err(unexpected(ir_parser_state, "a type"))
+}
+
+
+fn expect_integer(ir_parser_state: &mut IrParserState) -> ParseResult<i64> {
+    if let Some(IrToken::Word(word)) = ir_parser_state.peek() && let Ok(value) = word.parse::<i64>() {
+        ir_parser_state.advance();
+        return Ok(value);
+    }
+
+    Err(unexpected(ir_parser_state, "an integer"))
+}
+
+
+fn expect_global(ir_parser_state: &mut IrParserState) -> ParseResult<String> {
+    if let Some(IrToken::Global(name)) = ir_parser_state.peek().cloned() {
+        ir_parser_state.advance();
+        return Ok(name);
+    }
+
+    Err(unexpected(ir_parser_state, "a global name"))
+}
+
+
+fn expect_register(ir_parser_state: &mut IrParserState) -> ParseResult<Register> {
+    if let Some(IrToken::Register(name)) = ir_parser_state.peek().cloned() {
+        let register = ir_parser_state.register(name);
+        ir_parser_state.advance();
+
+        return Ok(register);
+    }
+
+    Err(unexpected(ir_parser_state, "a register"))
+}
+
+
+fn expect_block(ir_parser_state: &mut IrParserState) -> ParseResult<BlockId> {
+    if let Some(IrToken::Word(label)) = ir_parser_state.peek().cloned() {
+        let block = ir_parser_state.block(label);
+        ir_parser_state.advance();
+
+        return Ok(block);
+    }
+
+    Err(unexpected(ir_parser_state, "a block label"))
+}
+
+
+fn expect_word(ir_parser_state: &mut IrParserState, word: &str) -> ParseResult<()> {
+    if !accept_word(ir_parser_state, word) {
+        return Err(unexpected(ir_parser_state, &format!("`{}`", word)));
+    }
+
+    Ok(())
+}
+
+
+fn expect_punctuation(ir_parser_state: &mut IrParserState, punctuation: char) -> ParseResult<()> {
+    if !accept_punctuation(ir_parser_state, punctuation) {
+        return Err(unexpected(ir_parser_state, &format!("`{}`", punctuation)));
+    }
+
+    Ok(())
+}
+
+
+fn expect_newline(ir_parser_state: &mut IrParserState) -> ParseResult<()> {
+    match ir_parser_state.peek() {
+        Some(IrToken::Newline) => {
+            ir_parser_state.advance();
+            Ok(())
+        }
+
+        None => Ok(()),
+        Some(_) => Err(unexpected(ir_parser_state, "end of line")),
+    }
+}
+
+
+fn accept_word(ir_parser_state: &mut IrParserState, word: &str) -> bool {
+    let found = ir_parser_state.is_word(word);
+
+    if found {
+        ir_parser_state.advance();
+    }
+
+    found
+}
+
+
+fn accept_punctuation(ir_parser_state: &mut IrParserState, punctuation: char) -> bool {
+    let found = ir_parser_state.is_punctuation(punctuation);
+
+    if found {
+        ir_parser_state.advance();
+    }
+
+    found
+}
+
+
+fn unexpected(ir_parser_state: &IrParserState, expected: &str) -> ParseError {
+    let found = match ir_parser_state.peek() {
+        Some(token) => token.to_string(),
+        None => "end of input".to_string(),
+    };
+
+    ParseError::new(ir_parser_state.line(), expected, &found)
+}
+
+
+fn skip_line(ir_parser_state: &mut IrParserState) {
+    while let Some(token) = ir_parser_state.advance() {
+        if token == IrToken::Newline {
+            break;
+        }
+    }
+}
+
+
+fn synchronize(ir_parser_state: &mut IrParserState) {
+    let mut depth = 0;
+
+    while let Some(token) = ir_parser_state.advance() {
+        match token {
+            IrToken::Punctuation('{') => depth += 1,
+            IrToken::Punctuation('}') => depth -= 1,
+            IrToken::Newline if depth <= 0 => break,
+            _ => {}
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::data::Module;
+    use crate::logic::{ interpreter_start, ir_parser_start, verifier_start_with_source };
+
+
+    fn parse_fixture(name: &str) -> (Module, Vec<String>) {
+        let path = format!("{}/examples/ir/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let source = fs::read_to_string(&path).expect("fixture should be readable");
+        let (module, source_map, errors) = ir_parser_start(&source);
+        assert!(errors.is_empty(), "parse errors in {}: {:?}", name, errors.iter().map(ToString::to_string).collect::<Vec<_>>());
+
+        let diagnostics = verifier_start_with_source(&module, &source_map).iter().map(ToString::to_string).collect();
+        (module, diagnostics)
+    }
+
+
+    fn assert_rejected(name: &str, expected: &str) {
+        let (_, diagnostics) = parse_fixture(name);
+        assert!(diagnostics.iter().any(|diagnostic| diagnostic.contains(expected)), "{} should report '{}', got {:?}", name, expected, diagnostics);
+    }
+
+
+    #[test]
+    fn parses_and_runs_named_blocks() {
+        let (module, diagnostics) = parse_fixture("sum.ir");
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+        let code = interpreter_start(&module, "sum", &mut Vec::new()).unwrap_or_else(|diagnostic| panic!("{}", diagnostic));
+        assert_eq!(code, 55);
+    }
+
+
+    #[test]
+    fn rejects_missing_terminator() {
+        assert_rejected("missing_terminator.ir", "7: in `@main`: join does not end with a terminator");
+    }
+
+
+    #[test]
+    fn rejects_type_mismatch() {
+        assert_rejected("type_mismatch.ir", "7: in `@main`: join: `%4 = add i32 %wide, 1`: operand `%wide` has type i64, expected i32");
+    }
+
+
+    #[test]
+    fn rejects_use_not_dominated_by_definition() {
+        assert_rejected("dominance.ir", "11: in `@main`: join: `%w = add i32 %v, 1`: %v does not dominate this use");
+    }
+
+
+    #[test]
+    fn rejects_wrong_phi_arity() {
+        assert_rejected("phi_arity.ir", "10: in `@main`: join: `%v = phi i32 [1, left]`: phi has 1 incoming values but join has 2 predecessors");
+    }
+
+
+    #[test]
+    fn reports_undefined_labels_and_registers() {
+        let (_, _, errors) = ir_parser_start("define i32 @main() {\nentry:\n  %x = add i32 %y, 1\n  jmp nowhere\n}\n");
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+
+        assert!(errors.iter().any(|error| error.contains("a definition of block `nowhere`")), "{:?}", errors);
+        assert!(errors.iter().any(|error| error.contains("a definition of register `%y`")), "{:?}", errors);
+    }
+}
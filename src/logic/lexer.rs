@@ -37,7 +37,7 @@ pub fn lexer_start(source: &str) -> Result<Vec<Token>, Box<dyn std::error::Error
             }
             
             buffer.clear();
-        } else if character.is_ascii_digit() || (character == '.' && chars.peek().map_or(false, |c| c.is_ascii_digit())) {
+        } else if character.is_ascii_digit() || (character == '.' && chars.peek().is_some_and(|c| c.is_ascii_digit())) {
             match (character, chars.peek()) {
                 ('0', Some(&next_char)) if is_octal(next_char) => {
                     let mut oct_digits = String::new();
@@ -136,10 +136,8 @@ pub fn lexer_start(source: &str) -> Result<Vec<Token>, Box<dyn std::error::Error
                         suffix.push(chars.next().unwrap());
                     }
 
-                    if let Some(&next) = chars.peek() {
-                        if !next.is_whitespace() && !SINGLE_OPERATOR_MAP.contains_key(&next) {
-                            return Err(format!("{}, {}: Invalid number suffix", filename, line).into());
-                        }
+                    if let Some(&next) = chars.peek() && !next.is_whitespace() && !SINGLE_OPERATOR_MAP.contains_key(&next) {
+                        return Err(format!("{}, {}: Invalid number suffix", filename, line).into());
                     }
 
                     if !matches!(suffix.to_ascii_lowercase().as_str(), "u" | "l" | "f" | "ul" | "lu" | "ll" | "ull" | "llu" | "lf") {
@@ -180,13 +178,13 @@ pub fn lexer_start(source: &str) -> Result<Vec<Token>, Box<dyn std::error::Error
 
                     let char_literal = process_escape_sequence(&mut chars, line, &filename)?;
 
-                    token.push(Token::new(TokenType::Literal(char_literal), line));
+                    token.push(Token::new(TokenType::Literal(format!("'{}'", char_literal)), line));
                 } else {    
-                    token.push(Token::new(TokenType::Literal(character.to_string()), line));
+                    token.push(Token::new(TokenType::Literal(format!("'{}'", character)), line));
                 }
 
                 if chars.next() != Some('\'') {
-                    while let Some(next_char) = chars.next() {
+                    for next_char in chars.by_ref() {
                         if next_char == '\n' {
                             return Err(format!("{}, {}: Unterminated character literal", filename, line).into());
                         } else if next_char == '\'' {
@@ -214,7 +212,7 @@ pub fn lexer_start(source: &str) -> Result<Vec<Token>, Box<dyn std::error::Error
                             }
 
                             if lookahead.peek() != Some(&'"') {
-                                token.push(Token::new(TokenType::Literal(string_lit), line));
+                                token.push(Token::new(TokenType::Literal(format!("\"{}\"", string_lit)), line));
                                 ok = true;
 
                                 break;
@@ -241,7 +239,7 @@ pub fn lexer_start(source: &str) -> Result<Vec<Token>, Box<dyn std::error::Error
                                 continue;
                             }
 
-                            process_escape_sequence(&mut chars, line, &filename)?;
+                            string_lit.push_str(&process_escape_sequence(&mut chars, line, &filename)?);
                         }
 
                         '\n' => return Err(format!("{}, {}: Unterminated string literal", filename, line).into()),
@@ -290,13 +288,13 @@ pub fn lexer_start(source: &str) -> Result<Vec<Token>, Box<dyn std::error::Error
                 if c.is_numeric() {
                     pp_line_num.push(c);
 
-                    if !chars.peek().map_or(false, |c| c.is_ascii_digit()) {
+                    if !chars.peek().is_some_and(|c| c.is_ascii_digit()) {
                         line = pp_line_num.parse::<u16>().unwrap_or(0);
                     }
                 } else if c == '"' {
                     let mut pp_filename = String::new();
 
-                    while let Some(fc) = chars.next() {
+                    for fc in chars.by_ref() {
                         if fc == '"' {
                             break;
                         }
@@ -304,7 +302,7 @@ pub fn lexer_start(source: &str) -> Result<Vec<Token>, Box<dyn std::error::Error
                         pp_filename.push(fc);
                     }
 
-                    filename = pp_filename.split('/').last().unwrap().to_string();
+                    filename = pp_filename.split('/').next_back().unwrap().to_string();
                 } else if c == '\n' || c == '\r' {
                     break;
                 }
@@ -331,7 +329,7 @@ pub fn lexer_start(source: &str) -> Result<Vec<Token>, Box<dyn std::error::Error
 
 
 fn is_octal(c: char) -> bool {
-    c >= '0' && c <= '7'
+    ('0'..='7').contains(&c)
 }
 
 
@@ -428,7 +426,7 @@ fn process_escape_sequence(chars: &mut Peekable<Chars<'_>>, line: u16, filename:
             Ok(string_lit)
         }
         
-        _ => return Err(format!("{}, {}: Invalid escape sequence: \\{}", filename, line, escape_char).into()),
+        _ => Err(format!("{}, {}: Invalid escape sequence: \\{}", filename, line, escape_char).into()),
     }
 }
 
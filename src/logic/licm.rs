@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use crate::data::structures::{ Analyses, Instruction, IrFunction, Loop };
+use crate::data::types::{ ArithmeticOperator, InstructionKind, Operand, Register };
+
+
+pub fn licm(function: &mut IrFunction, analyses: &mut Analyses) -> bool {
+    let (loops, mut changed) = analyses.canonical_loops(function);
+
+    for natural in loops.iter().rev() {
+        changed |= hoist(function, natural);
+    }
+
+    changed
+}
+
+
+/* * * * * * * * * */
+/*  - HOISTING -  */
+/* * * * * * * * * */
+fn hoist(function: &mut IrFunction, natural: &Loop) -> bool {
+    let Some(preheader) = function.preheader(natural) else {
+        return false;
+    };
+
+    let mut defined: HashSet<Register> = natural.blocks.iter()
+        .flat_map(|block| function.blocks[block.0 as usize].instructions.iter().filter_map(|instruction| instruction.result))
+        .collect();
+
+    let writes_memory = natural.blocks.iter()
+        .flat_map(|block| &function.blocks[block.0 as usize].instructions)
+        .any(|instruction| matches!(instruction.kind, InstructionKind::Store { .. } | InstructionKind::Copy { .. } | InstructionKind::Zero { .. } | InstructionKind::Call { .. }));
+
+    let order: Vec<_> = function.reverse_postorder().into_iter().filter(|block| natural.blocks.contains(block)).collect();
+    let mut hoisted = Vec::new();
+
+    for block in order {
+        let instructions = std::mem::take(&mut function.blocks[block.0 as usize].instructions);
+        let mut kept = Vec::with_capacity(instructions.len());
+
+        for instruction in instructions {
+            let movable = match &instruction.kind {
+                InstructionKind::Load { volatile: false, .. } => !writes_memory && block == natural.header,
+                _ => speculatable(&instruction),
+            };
+
+            let invariant = instruction.clone().operands_mut().into_iter()
+                .all(|operand| !matches!(operand, Operand::Register(register) if defined.contains(register)));
+
+            match instruction.result {
+                Some(result) if movable && invariant => {
+                    defined.remove(&result);
+                    hoisted.push(instruction);
+                }
+
+                _ => kept.push(instruction),
+            }
+        }
+
+        function.blocks[block.0 as usize].instructions = kept;
+    }
+
+    if hoisted.is_empty() {
+        return false;
+    }
+
+    let instructions = &mut function.blocks[preheader.0 as usize].instructions;
+    let terminator = instructions.len() - 1;
+    instructions.splice(terminator..terminator, hoisted);
+
+    true
+}
+
+
+fn speculatable(instruction: &Instruction) -> bool {
+    match &instruction.kind {
+        InstructionKind::Binary { operator: ArithmeticOperator::SDiv | ArithmeticOperator::SRem, right, .. } => {
+            matches!(right, Operand::Integer(divisor) if *divisor != 0 && *divisor != -1)
+        }
+
+        InstructionKind::Binary { operator: ArithmeticOperator::UDiv | ArithmeticOperator::URem, right, .. } => {
+            matches!(right, Operand::Integer(divisor) if *divisor != 0)
+        }
+
+        InstructionKind::Binary { .. } | InstructionKind::Compare { .. } | InstructionKind::Convert { .. } | InstructionKind::PtrAdd { .. } => true,
+        _ => false,
+    }
+}
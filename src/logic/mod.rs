@@ -2,4 +2,57 @@ pub mod lexer;
 pub use lexer::lexer_start;
 
 pub mod parser;
-pub use parser::parser_start;
\ No newline at end of file
+pub use parser::parser_start;
+
+pub mod sym_table;
+pub use sym_table::sym_table_start;
+
+pub mod constant;
+
+pub mod semantic;
+pub use semantic::semantic_start;
+
+pub mod dataflow;
+pub use dataflow::dataflow_start;
+
+pub mod ir;
+pub use ir::ir_start;
+
+pub mod ir_parser;
+pub use ir_parser::ir_parser_start;
+
+pub mod verifier;
+pub use verifier::verifier_start;
+pub use verifier::verifier_start_with_source;
+
+pub mod ssa;
+
+pub mod fold;
+
+pub mod sccp;
+
+pub mod gvn;
+
+pub mod licm;
+
+pub mod induction;
+
+pub mod unroll;
+
+pub mod inline;
+
+pub mod adce;
+
+pub mod simplify_cfg;
+
+pub mod pass_manager;
+pub use pass_manager::pass_manager_start;
+
+pub mod interpreter;
+pub use interpreter::interpreter_start;
+
+pub mod dump;
+pub use dump::dump_start;
+
+pub mod printer;
+pub use printer::printer_start;
@@ -1,69 +1,1373 @@
-use std::fmt::Error;
+use std::str::FromStr;
 
-use crate::{Token, TokenType, data::ParserState};
+use crate::Token;
+use crate::data::{ Ast, Keyword, NodeId, NodeKind, ParseError, ParserState, Symbol, TokenType };
+use crate::data::maps::{ ASSIGNMENT_OPERATOR_MAP, BINARY_OPERATOR_MAP, UNARY_OPERATOR_MAP };
+use crate::data::types::{ Attribute, BinaryOperator, Constant, DeclSpecifiers, Declarator, DerivedDeclarator, Designator, Enumerator, GenericAssociation, InitDeclarator, Initializer, Parameter, PostfixOperator, TypeName, TypeSpecifier, TypeofArgument, UnaryOperator };
 
 
-pub fn parser_start(tokens: &Vec<Token>) -> Result<(), Box<Error>> {
-    let mut parser_state = ParserState { tokens, iterator: 0 };
+type ParseResult<T> = Result<T, ParseError>;
 
-    process_switch(&mut parser_state)?;
+
+pub fn parser_start(tokens: &[Token]) -> (Ast, Vec<ParseError>) {
+    let mut parser_state = ParserState::new(tokens);
+    let mut items = Vec::new();
+    let start = parser_state.line();
+
+    while parser_state.peek().is_some() {
+        if parser_state.is_operator(";") {
+            parser_state.advance();
+            continue;
+        }
+
+        let item_start = parser_state.iterator;
+        let line = parser_state.line();
+
+        match process_declaration(&mut parser_state, true) {
+            Ok(item) => items.push(item),
+            Err(e) => {
+                parser_state.errors.push(e);
+                synchronize(&mut parser_state, item_start);
+                items.push(parser_state.push_node(NodeKind::Error, line));
+            }
+        }
+    }
+
+    parser_state.ast.root = parser_state.push_node(NodeKind::TranslationUnit(items), start);
+
+    (parser_state.ast, parser_state.errors)
+}
+
+
+/* * * * * * * * * * */
+/*  - DECLARATIONS - */
+/* * * * * * * * * * */
+fn process_declaration(parser_state: &mut ParserState, allow_definition: bool) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+    process_attributes(parser_state)?;
+
+    if parser_state.keyword() == Some(Keyword::StaticAssert) {
+        parser_state.attributes.clear();
+        return process_static_assert(parser_state);
+    }
+
+    let specifiers = process_decl_specifiers(parser_state)?;
+    let attributes = std::mem::take(&mut parser_state.attributes);
+
+    if parser_state.is_operator(";") {
+        parser_state.advance();
+        return Ok(parser_state.push_node(NodeKind::Declaration { attributes, specifiers, declarators: Vec::new() }, start));
+    }
+
+    let declarator = process_declarator(parser_state, false)?;
+    process_attributes(parser_state)?;
+
+    if allow_definition && parser_state.is_operator("{") && declarator.function_parameters().is_some() {
+        let mut attributes = attributes;
+        attributes.append(&mut parser_state.attributes);
+
+        return process_function_definition(parser_state, start, attributes, specifiers, declarator);
+    }
+
+    process_init_declarators(parser_state, start, attributes, specifiers, declarator)
+}
+
+
+fn process_function_definition(parser_state: &mut ParserState, start: u16, attributes: Vec<Attribute>, specifiers: DeclSpecifiers, declarator: Declarator) -> ParseResult<NodeId> {
+    if let Some(name) = declarator.name {
+        parser_state.declare(name, false);
+    }
+
+    parser_state.push_scope();
+
+    for parameter in declarator.function_parameters().into_iter().flatten() {
+        if let Some(name) = parameter.declarator.name {
+            parser_state.declare(name, false);
+        }
+    }
+
+    let body = process_compound(parser_state, false);
+    parser_state.pop_scope();
+
+    Ok(parser_state.push_node(NodeKind::FunctionDefinition { attributes, specifiers, declarator, body: body? }, start))
+}
+
+
+fn process_init_declarators(parser_state: &mut ParserState, start: u16, attributes: Vec<Attribute>, specifiers: DeclSpecifiers, first: Declarator) -> ParseResult<NodeId> {
+    let is_typedef = specifiers.storage.contains(&Keyword::Typedef);
+    let mut declarators = Vec::new();
+    let mut declarator = first;
+
+    loop {
+        if let Some(name) = declarator.name {
+            parser_state.declare(name, is_typedef);
+        }
+
+        let declarator_attributes = std::mem::take(&mut parser_state.attributes);
+
+        let initializer = if parser_state.is_operator("=") {
+            parser_state.advance();
+            Some(process_initializer(parser_state)?)
+        } else {
+            None
+        };
+
+        declarators.push(InitDeclarator { attributes: declarator_attributes, declarator, initializer, bit_width: None });
+
+        if !parser_state.is_operator(",") {
+            break;
+        }
+
+        parser_state.advance();
+        declarator = process_declarator(parser_state, false)?;
+        process_attributes(parser_state)?;
+    }
+
+    expect_operator(parser_state, ";", "after declaration")?;
+
+    Ok(parser_state.push_node(NodeKind::Declaration { attributes, specifiers, declarators }, start))
+}
+
+
+fn process_static_assert(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+    parser_state.advance();
+    expect_operator(parser_state, "(", "after static_assert")?;
+
+    let condition = process_conditional(parser_state)?;
+
+    let message = if parser_state.is_operator(",") {
+        parser_state.advance();
+
+        match parser_state.peek().map(|token| &token.token_type) {
+            Some(TokenType::Literal(value)) if value.starts_with('"') => {
+                parser_state.advance();
+                Some(parser_state.intern(&value[1..value.len() - 1]))
+            } _ => return Err(unexpected(parser_state, "string literal in static_assert")),
+        }
+    } else {
+        None
+    };
+
+    expect_operator(parser_state, ")", "to close static_assert")?;
+    expect_operator(parser_state, ";", "after static_assert")?;
+
+    Ok(parser_state.push_node(NodeKind::StaticAssert { condition, message }, start))
+}
+
+
+fn process_initializer(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    if parser_state.is_operator("{") {
+        return process_initializer_list(parser_state);
+    }
+
+    process_assignment(parser_state)
+}
+
+
+fn process_initializer_list(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+    expect_operator(parser_state, "{", "to open initializer list")?;
+    let mut initializers = Vec::new();
+
+    while !parser_state.is_operator("}") {
+        let mut designators = Vec::new();
+
+        loop {
+            if parser_state.is_operator("[") {
+                parser_state.advance();
+                let index = process_conditional(parser_state)?;
+                expect_operator(parser_state, "]", "after array designator")?;
+
+                designators.push(Designator::Index(index));
+            } else if parser_state.is_operator(".") {
+                parser_state.advance();
+
+                let Some(member) = process_optional_identifier(parser_state) else {
+                    return Err(unexpected(parser_state, "member name in designator"));
+                };
+
+                designators.push(Designator::Member(member));
+            } else {
+                break;
+            }
+        }
+
+        if !designators.is_empty() {
+            expect_operator(parser_state, "=", "after designator")?;
+        }
+
+        let value = process_initializer(parser_state)?;
+        initializers.push(Initializer { designators, value });
+
+        if !parser_state.is_operator(",") {
+            break;
+        }
+
+        parser_state.advance();
+    }
+
+    expect_operator(parser_state, "}", "to close initializer list")?;
+
+    Ok(parser_state.push_node(NodeKind::InitializerList(initializers), start))
+}
+
+
+fn process_decl_specifiers(parser_state: &mut ParserState) -> ParseResult<DeclSpecifiers> {
+    let mut decl_specifiers = DeclSpecifiers::default();
+
+    loop {
+        process_attributes(parser_state)?;
+
+        if let Some(keyword) = parser_state.keyword() {
+            match keyword {
+                Keyword::Typedef | Keyword::Extern | Keyword::Static | Keyword::Auto | Keyword::Register => {
+                    decl_specifiers.storage.push(keyword);
+                } Keyword::Const | Keyword::Volatile | Keyword::Restrict => {
+                    decl_specifiers.qualifiers.push(keyword);
+                } Keyword::Inline => {
+                    decl_specifiers.function.push(keyword);
+                } Keyword::Void | Keyword::Char | Keyword::Short | Keyword::Int | Keyword::Long
+                | Keyword::Float | Keyword::Double | Keyword::Signed | Keyword::Unsigned | Keyword::Bool => {
+                    decl_specifiers.specifiers.push(TypeSpecifier::Basic(keyword));
+                } Keyword::Struct | Keyword::Union => {
+                    let record = process_record_specifier(parser_state)?;
+                    decl_specifiers.specifiers.push(record);
+                    continue;
+                } Keyword::Enum => {
+                    let enumeration = process_enum_specifier(parser_state)?;
+                    decl_specifiers.specifiers.push(enumeration);
+                    continue;
+                } Keyword::Typeof | Keyword::TypeofUnqual => {
+                    let typeof_specifier = process_typeof_specifier(parser_state)?;
+                    decl_specifiers.specifiers.push(typeof_specifier);
+                    continue;
+                } Keyword::Alignas => {
+                    let alignment = process_alignment_specifier(parser_state)?;
+                    decl_specifiers.alignment.push(alignment);
+                    continue;
+                } _ => break,
+            }
+
+            parser_state.advance();
+        } else if let Some(name) = parser_state.identifier_at(0) {
+            if !decl_specifiers.specifiers.is_empty() || !parser_state.is_typedef_name(name) {
+                break;
+            }
+
+            let name = parser_state.intern(name);
+            decl_specifiers.specifiers.push(TypeSpecifier::TypedefName(name));
+            parser_state.advance();
+        } else {
+            break;
+        }
+    }
+
+    if decl_specifiers.storage.is_empty() && decl_specifiers.qualifiers.is_empty() && decl_specifiers.function.is_empty()
+        && decl_specifiers.alignment.is_empty() && decl_specifiers.specifiers.is_empty() {
+        return Err(unexpected(parser_state, "declaration specifiers"));
+    }
+
+    Ok(decl_specifiers)
+}
+
+
+fn process_record_specifier(parser_state: &mut ParserState) -> ParseResult<TypeSpecifier> {
+    let kind = parser_state.keyword().unwrap_or(Keyword::Struct);
+    parser_state.advance();
+    process_attributes(parser_state)?;
+
+    let tag = process_optional_identifier(parser_state);
+
+    if !parser_state.is_operator("{") {
+        if tag.is_none() {
+            return Err(unexpected(parser_state, "tag or `{` after struct"));
+        }
+
+        return Ok(TypeSpecifier::Record { kind, tag, members: None });
+    }
+
+    parser_state.advance();
+    let outer_attributes = std::mem::take(&mut parser_state.attributes);
+    let mut members = Vec::new();
+
+    while !parser_state.is_operator("}") {
+        if parser_state.peek().is_none() {
+            return Err(unexpected(parser_state, "`}` to close struct declaration"));
+        }
+
+        members.push(process_member_declaration(parser_state)?);
+    }
+
+    parser_state.attributes = outer_attributes;
+    parser_state.advance();
+    process_attributes(parser_state)?;
+
+    Ok(TypeSpecifier::Record { kind, tag, members: Some(members) })
+}
+
+
+fn process_member_declaration(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+    process_attributes(parser_state)?;
+
+    if parser_state.keyword() == Some(Keyword::StaticAssert) {
+        parser_state.attributes.clear();
+        return process_static_assert(parser_state);
+    }
+
+    let specifiers = process_decl_specifiers(parser_state)?;
+    let attributes = std::mem::take(&mut parser_state.attributes);
+    let mut declarators = Vec::new();
+
+    while !parser_state.is_operator(";") {
+        let declarator = if parser_state.is_operator(":") {
+            Declarator::default()
+        } else {
+            process_declarator(parser_state, false)?
+        };
+
+        let bit_width = if parser_state.is_operator(":") {
+            parser_state.advance();
+            Some(process_conditional(parser_state)?)
+        } else {
+            None
+        };
+
+        process_attributes(parser_state)?;
+
+        let declarator_attributes = std::mem::take(&mut parser_state.attributes);
+        declarators.push(InitDeclarator { attributes: declarator_attributes, declarator, initializer: None, bit_width });
+
+        if !parser_state.is_operator(",") {
+            break;
+        }
+
+        parser_state.advance();
+    }
+
+    expect_operator(parser_state, ";", "after struct member")?;
+
+    Ok(parser_state.push_node(NodeKind::Declaration { attributes, specifiers, declarators }, start))
+}
+
+
+fn process_enum_specifier(parser_state: &mut ParserState) -> ParseResult<TypeSpecifier> {
+    parser_state.advance();
+    process_attributes(parser_state)?;
+
+    let tag = process_optional_identifier(parser_state);
+
+    if !parser_state.is_operator("{") {
+        if tag.is_none() {
+            return Err(unexpected(parser_state, "tag or `{` after enum"));
+        }
+
+        return Ok(TypeSpecifier::Enum { tag, enumerators: None });
+    }
+
+    parser_state.advance();
+    let outer_attributes = std::mem::take(&mut parser_state.attributes);
+    let mut enumerators = Vec::new();
+
+    while !parser_state.is_operator("}") {
+        let Some(name) = process_optional_identifier(parser_state) else {
+            return Err(unexpected(parser_state, "enumerator name"));
+        };
+
+        process_attributes(parser_state)?;
+
+        let value = if parser_state.is_operator("=") {
+            parser_state.advance();
+            Some(process_conditional(parser_state)?)
+        } else {
+            None
+        };
+
+        parser_state.attributes.clear();
+        parser_state.declare(name, false);
+        enumerators.push(Enumerator { name, value });
+
+        if !parser_state.is_operator(",") {
+            break;
+        }
+
+        parser_state.advance();
+    }
+
+    parser_state.attributes = outer_attributes;
+    expect_operator(parser_state, "}", "to close enum")?;
+
+    Ok(TypeSpecifier::Enum { tag, enumerators: Some(enumerators) })
+}
+
+
+fn process_typeof_specifier(parser_state: &mut ParserState) -> ParseResult<TypeSpecifier> {
+    let unqualified = parser_state.keyword() == Some(Keyword::TypeofUnqual);
+    parser_state.advance();
+    expect_operator(parser_state, "(", "after typeof")?;
+
+    let argument = if starts_type_name(parser_state, 0) {
+        TypeofArgument::Type(process_type_name(parser_state)?)
+    } else {
+        TypeofArgument::Expression(process_expression(parser_state)?)
+    };
+
+    expect_operator(parser_state, ")", "to close typeof")?;
+
+    Ok(TypeSpecifier::Typeof { unqualified, argument })
+}
+
+
+fn process_alignment_specifier(parser_state: &mut ParserState) -> ParseResult<TypeofArgument> {
+    parser_state.advance();
+    expect_operator(parser_state, "(", "after alignas")?;
+
+    let argument = if starts_type_name(parser_state, 0) {
+        TypeofArgument::Type(process_type_name(parser_state)?)
+    } else {
+        TypeofArgument::Expression(process_conditional(parser_state)?)
+    };
+
+    expect_operator(parser_state, ")", "to close alignas")?;
+
+    Ok(argument)
+}
+
+
+fn process_declarator(parser_state: &mut ParserState, is_abstract: bool) -> ParseResult<Declarator> {
+    let mut pointers = Vec::new();
+
+    while parser_state.is_operator("*") {
+        parser_state.advance();
+        let mut qualifiers = Vec::new();
+
+        loop {
+            process_attributes(parser_state)?;
+
+            match parser_state.keyword() {
+                Some(keyword @ (Keyword::Const | Keyword::Volatile | Keyword::Restrict)) => {
+                    qualifiers.push(keyword);
+                    parser_state.advance();
+                } _ => break,
+            }
+        }
+
+        pointers.push(DerivedDeclarator::Pointer(qualifiers));
+    }
+
+    let nested = if let Some(name) = parser_state.identifier_at(0) {
+        parser_state.advance();
+        Declarator { name: Some(parser_state.intern(name)), derived: Vec::new() }
+    } else if parser_state.is_operator("(") && starts_nested_declarator(parser_state, is_abstract) {
+        parser_state.advance();
+        let inner = process_declarator(parser_state, is_abstract)?;
+        expect_operator(parser_state, ")", "to close declarator")?;
+
+        inner
+    } else if is_abstract {
+        Declarator::default()
+    } else {
+        return Err(unexpected(parser_state, "identifier"));
+    };
+
+    let mut suffixes = Vec::new();
+
+    loop {
+        if parser_state.is_operator("[") && !parser_state.is_operator_at(1, "[") {
+            parser_state.advance();
+
+            while matches!(parser_state.keyword(), Some(Keyword::Static | Keyword::Const | Keyword::Volatile | Keyword::Restrict)) {
+                parser_state.advance();
+            }
+
+            let size = if parser_state.is_operator("]") {
+                None
+            } else {
+                Some(process_assignment(parser_state)?)
+            };
+
+            expect_operator(parser_state, "]", "to close array declarator")?;
+            suffixes.push(DerivedDeclarator::Array(size));
+        } else if parser_state.is_operator("(") {
+            parser_state.advance();
+
+            let (parameters, variadic) = process_parameter_list(parser_state)?;
+            suffixes.push(DerivedDeclarator::Function { parameters, variadic });
+        } else {
+            break;
+        }
+    }
+
+    let mut derived = pointers;
+    derived.extend(suffixes.into_iter().rev());
+    derived.extend(nested.derived);
+
+    Ok(Declarator { name: nested.name, derived })
+}
+
+
+fn starts_nested_declarator(parser_state: &ParserState, is_abstract: bool) -> bool {
+    if !is_abstract {
+        return true;
+    }
+
+    if parser_state.is_operator_at(1, "*") || parser_state.is_operator_at(1, "(") || parser_state.is_operator_at(1, "[") {
+        return true;
+    }
+
+    parser_state.identifier_at(1).is_some_and(|name| !parser_state.is_typedef_name(name))
+}
+
+
+fn process_parameter_list(parser_state: &mut ParserState) -> ParseResult<(Vec<Parameter>, bool)> {
+    let mut parameters = Vec::new();
+
+    if parser_state.is_operator(")") {
+        parser_state.advance();
+        return Ok((parameters, false));
+    }
+
+    let outer_attributes = std::mem::take(&mut parser_state.attributes);
+    parser_state.push_scope();
+
+    let result = process_parameters(parser_state, &mut parameters);
+    parser_state.pop_scope();
+    parser_state.attributes = outer_attributes;
+
+    let variadic = result?;
+    expect_operator(parser_state, ")", "to close parameter list")?;
+
+    Ok((parameters, variadic))
+}
+
+
+fn process_parameters(parser_state: &mut ParserState, parameters: &mut Vec<Parameter>) -> ParseResult<bool> {
+    loop {
+        if is_ellipsis(parser_state) {
+            for _ in 0..3 {
+                parser_state.advance();
+            }
+
+            return Ok(true);
+        }
+
+        let specifiers = process_decl_specifiers(parser_state)?;
+        let declarator = process_declarator(parser_state, true)?;
+        process_attributes(parser_state)?;
+
+        if let Some(name) = declarator.name {
+            parser_state.declare(name, false);
+        }
+
+        let attributes = std::mem::take(&mut parser_state.attributes);
+        parameters.push(Parameter { attributes, specifiers, declarator });
+
+        if !parser_state.is_operator(",") {
+            return Ok(false);
+        }
+
+        parser_state.advance();
+    }
+}
+
+
+fn process_type_name(parser_state: &mut ParserState) -> ParseResult<TypeName> {
+    let outer_attributes = std::mem::take(&mut parser_state.attributes);
+    let specifiers = process_decl_specifiers(parser_state)?;
+
+    if !specifiers.storage.is_empty() {
+        return Err(ParseError::new(parser_state.line(), "type name", "storage class specifier"));
+    }
+
+    let declarator = process_declarator(parser_state, true)?;
+    parser_state.attributes = outer_attributes;
+
+    if let Some(name) = declarator.name {
+        let name = parser_state.ast.symbols.resolve(name).to_string();
+        return Err(ParseError::new(parser_state.line(), "type name", &format!("identifier `{}`", name)));
+    }
+
+    Ok(TypeName { specifiers, declarator })
+}
+
+
+/* * * * * * * * * */
+/*  - STATEMENTS - */
+/* * * * * * * * * */
+fn process_compound(parser_state: &mut ParserState, new_scope: bool) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+    expect_operator(parser_state, "{", "to open block")?;
+
+    if new_scope {
+        parser_state.push_scope();
+    }
+
+    let mut items = Vec::new();
+
+    while !parser_state.is_operator("}") {
+        if parser_state.peek().is_none() {
+            let error = unexpected(parser_state, "`}` to close block");
+            parser_state.errors.push(error);
+            break;
+        }
+
+        let item_start = parser_state.iterator;
+        let line = parser_state.line();
+
+        match process_block_item(parser_state) {
+            Ok(item) => items.push(item),
+            Err(e) => {
+                parser_state.errors.push(e);
+                synchronize(parser_state, item_start);
+                items.push(parser_state.push_node(NodeKind::Error, line));
+            }
+        }
+    }
+
+    if new_scope {
+        parser_state.pop_scope();
+    }
+
+    parser_state.advance();
+
+    Ok(parser_state.push_node(NodeKind::Compound(items), start))
+}
+
+
+fn process_block_item(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    process_attributes(parser_state)?;
+
+    if starts_declaration(parser_state) {
+        process_declaration(parser_state, false)
+    } else {
+        process_statement(parser_state)
+    }
+}
+
+
+fn process_statement(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+    process_attributes(parser_state)?;
+
+    if !parser_state.attributes.is_empty() {
+        let attributes = std::mem::take(&mut parser_state.attributes);
+        let statement = process_statement(parser_state)?;
+
+        return Ok(parser_state.push_node(NodeKind::Attributed { attributes, statement }, start));
+    }
+
+    if parser_state.is_operator("{") {
+        return process_compound(parser_state, true);
+    }
+
+    if parser_state.is_operator(";") {
+        parser_state.advance();
+        return Ok(parser_state.push_node(NodeKind::ExpressionStatement(None), start));
+    }
+
+    if let Some(name) = parser_state.identifier_at(0) && parser_state.is_operator_at(1, ":") {
+        let name = parser_state.intern(name);
+        parser_state.advance();
+        parser_state.advance();
+
+        let body = process_statement(parser_state)?;
+
+        return Ok(parser_state.push_node(NodeKind::Label { name, body }, start));
+    }
+
+    match parser_state.keyword() {
+        Some(Keyword::If) => {
+            parser_state.advance();
+            let condition = process_parenthesized(parser_state)?;
+            let then_branch = process_statement(parser_state)?;
+
+            let else_branch = if parser_state.keyword() == Some(Keyword::Else) {
+                parser_state.advance();
+                Some(process_statement(parser_state)?)
+            } else {
+                None
+            };
+
+            Ok(parser_state.push_node(NodeKind::If { condition, then_branch, else_branch }, start))
+        } Some(Keyword::While) => {
+            parser_state.advance();
+            let condition = process_parenthesized(parser_state)?;
+            let body = process_statement(parser_state)?;
+
+            Ok(parser_state.push_node(NodeKind::While { condition, body }, start))
+        } Some(Keyword::Do) => {
+            parser_state.advance();
+            let body = process_statement(parser_state)?;
+
+            if parser_state.keyword() != Some(Keyword::While) {
+                return Err(unexpected(parser_state, "`while` after do body"));
+            }
+
+            parser_state.advance();
+            let condition = process_parenthesized(parser_state)?;
+            expect_operator(parser_state, ";", "after do-while statement")?;
+
+            Ok(parser_state.push_node(NodeKind::DoWhile { body, condition }, start))
+        } Some(Keyword::For) => {
+            parser_state.advance();
+            parser_state.push_scope();
+
+            let result = process_for(parser_state, start);
+            parser_state.pop_scope();
+
+            result
+        } Some(Keyword::Switch) => {
+            parser_state.advance();
+            let condition = process_parenthesized(parser_state)?;
+            let body = process_statement(parser_state)?;
+
+            Ok(parser_state.push_node(NodeKind::Switch { condition, body }, start))
+        } Some(Keyword::Case) => {
+            parser_state.advance();
+            let value = process_conditional(parser_state)?;
+            expect_operator(parser_state, ":", "after case label")?;
+            let body = process_statement(parser_state)?;
+
+            Ok(parser_state.push_node(NodeKind::Case { value, body }, start))
+        } Some(Keyword::Default) => {
+            parser_state.advance();
+            expect_operator(parser_state, ":", "after default")?;
+            let body = process_statement(parser_state)?;
+
+            Ok(parser_state.push_node(NodeKind::Default(body), start))
+        } Some(Keyword::Break) => {
+            parser_state.advance();
+            expect_operator(parser_state, ";", "after break")?;
+
+            Ok(parser_state.push_node(NodeKind::Break, start))
+        } Some(Keyword::Continue) => {
+            parser_state.advance();
+            expect_operator(parser_state, ";", "after continue")?;
+
+            Ok(parser_state.push_node(NodeKind::Continue, start))
+        } Some(Keyword::Return) => {
+            parser_state.advance();
+
+            let value = if parser_state.is_operator(";") {
+                None
+            } else {
+                Some(process_expression(parser_state)?)
+            };
+
+            expect_operator(parser_state, ";", "after return statement")?;
+
+            Ok(parser_state.push_node(NodeKind::Return(value), start))
+        } Some(Keyword::Goto) => {
+            parser_state.advance();
+
+            let Some(label) = process_optional_identifier(parser_state) else {
+                return Err(unexpected(parser_state, "label after goto"));
+            };
+
+            expect_operator(parser_state, ";", "after goto statement")?;
+
+            Ok(parser_state.push_node(NodeKind::Goto(label), start))
+        } _ => {
+            let expression = process_expression(parser_state)?;
+            expect_operator(parser_state, ";", "after expression")?;
+
+            Ok(parser_state.push_node(NodeKind::ExpressionStatement(Some(expression)), start))
+        }
+    }
+}
+
+
+fn process_for(parser_state: &mut ParserState, start: u16) -> ParseResult<NodeId> {
+    expect_operator(parser_state, "(", "after for")?;
+
+    let init = if parser_state.is_operator(";") {
+        parser_state.advance();
+        None
+    } else if starts_declaration(parser_state) {
+        Some(process_declaration(parser_state, false)?)
+    } else {
+        let init_start = parser_state.line();
+        let expression = process_expression(parser_state)?;
+        expect_operator(parser_state, ";", "after for initializer")?;
+
+        Some(parser_state.push_node(NodeKind::ExpressionStatement(Some(expression)), init_start))
+    };
+
+    let condition = if parser_state.is_operator(";") {
+        None
+    } else {
+        Some(process_expression(parser_state)?)
+    };
+
+    expect_operator(parser_state, ";", "after for condition")?;
+
+    let step = if parser_state.is_operator(")") {
+        None
+    } else {
+        Some(process_expression(parser_state)?)
+    };
+
+    expect_operator(parser_state, ")", "to close for clauses")?;
+    let body = process_statement(parser_state)?;
+
+    Ok(parser_state.push_node(NodeKind::For { init, condition, step, body }, start))
+}
+
+
+fn process_parenthesized(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    expect_operator(parser_state, "(", "before condition")?;
+    let expression = process_expression(parser_state)?;
+    expect_operator(parser_state, ")", "after condition")?;
+
+    Ok(expression)
+}
+
+
+/* * * * * * * * * * */
+/*  - EXPRESSIONS -  */
+/* * * * * * * * * * */
+fn process_expression(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+    let mut left = process_assignment(parser_state)?;
+
+    while parser_state.is_operator(",") {
+        parser_state.advance();
+        let right = process_assignment(parser_state)?;
+
+        left = parser_state.push_node(NodeKind::BinaryOperation { left, operator: BinaryOperator::Comma, right }, start);
+    }
+
+    Ok(left)
+}
+
+
+fn process_assignment(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let left = process_conditional(parser_state)?;
+
+    let Some(&operator) = operator_token(parser_state).and_then(|operator| ASSIGNMENT_OPERATOR_MAP.get(operator)) else {
+        return Ok(left);
+    };
+
+    parser_state.advance();
+    let right = process_assignment(parser_state)?;
+    let start = parser_state.start_of(left);
+
+    Ok(parser_state.push_node(NodeKind::Assignment { left, operator, right }, start))
+}
+
+
+fn process_conditional(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let condition = process_binary(parser_state, 1)?;
+
+    if !parser_state.is_operator("?") {
+        return Ok(condition);
+    }
+
+    parser_state.advance();
+    let then_value = process_expression(parser_state)?;
+    expect_operator(parser_state, ":", "in conditional expression")?;
+    let else_value = process_conditional(parser_state)?;
+
+    let start = parser_state.start_of(condition);
+
+    Ok(parser_state.push_node(NodeKind::Conditional { condition, then_value, else_value }, start))
+}
+
+
+fn process_binary(parser_state: &mut ParserState, min_precedence: u8) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+    let mut left = process_cast(parser_state)?;
+
+    while let Some(&operator) = operator_token(parser_state).and_then(|operator| BINARY_OPERATOR_MAP.get(operator)) {
+        if operator.precedence() < min_precedence {
+            break;
+        }
+
+        parser_state.advance();
+        let right = process_binary(parser_state, operator.precedence() + 1)?;
+
+        left = parser_state.push_node(NodeKind::BinaryOperation { left, operator, right }, start);
+    }
+
+    Ok(left)
+}
+
+
+fn process_cast(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+
+    if parser_state.is_operator("(") && starts_type_name(parser_state, 1) {
+        parser_state.advance();
+        let type_name = process_type_name(parser_state)?;
+        expect_operator(parser_state, ")", "to close cast")?;
+
+        if parser_state.is_operator("{") {
+            let literal = process_compound_literal(parser_state, type_name, start)?;
+            return process_postfix_tail(parser_state, literal);
+        }
+
+        let operand = process_cast(parser_state)?;
+
+        return Ok(parser_state.push_node(NodeKind::Cast { type_name, operand }, start));
+    }
+
+    process_unary(parser_state)
+}
+
+
+fn process_unary(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+
+    if parser_state.identifier_at(0) == Some("__extension__") {
+        parser_state.advance();
+        return process_cast(parser_state);
+    }
+
+    if parser_state.keyword() == Some(Keyword::Sizeof) {
+        parser_state.advance();
+
+        if parser_state.is_operator("(") && starts_type_name(parser_state, 1) {
+            parser_state.advance();
+            let type_name = process_type_name(parser_state)?;
+            expect_operator(parser_state, ")", "to close sizeof type")?;
+
+            if parser_state.is_operator("{") {
+                let literal = process_compound_literal(parser_state, type_name, start)?;
+                let operand = process_postfix_tail(parser_state, literal)?;
+
+                return Ok(parser_state.push_node(NodeKind::UnaryOperation { operator: UnaryOperator::Sizeof, operand }, start));
+            }
+
+            return Ok(parser_state.push_node(NodeKind::SizeofType(type_name), start));
+        }
+
+        let operand = process_unary(parser_state)?;
+
+        return Ok(parser_state.push_node(NodeKind::UnaryOperation { operator: UnaryOperator::Sizeof, operand }, start));
+    }
+
+    if parser_state.keyword() == Some(Keyword::Alignof) {
+        parser_state.advance();
+        expect_operator(parser_state, "(", "after alignof")?;
+        let type_name = process_type_name(parser_state)?;
+        expect_operator(parser_state, ")", "to close alignof type")?;
+
+        return Ok(parser_state.push_node(NodeKind::AlignofType(type_name), start));
+    }
+
+    let Some(&operator) = operator_token(parser_state).and_then(|operator| UNARY_OPERATOR_MAP.get(operator)) else {
+        return process_postfix(parser_state);
+    };
+
+    parser_state.advance();
+
+    let operand = match operator {
+        UnaryOperator::PreIncrement | UnaryOperator::PreDecrement => process_unary(parser_state)?,
+        _ => process_cast(parser_state)?,
+    };
+
+    Ok(parser_state.push_node(NodeKind::UnaryOperation { operator, operand }, start))
+}
+
+
+fn process_compound_literal(parser_state: &mut ParserState, type_name: TypeName, start: u16) -> ParseResult<NodeId> {
+    let initializer = process_initializer_list(parser_state)?;
+
+    Ok(parser_state.push_node(NodeKind::CompoundLiteral { type_name, initializer }, start))
+}
+
+
+fn process_postfix(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let expression = process_primary(parser_state)?;
+
+    process_postfix_tail(parser_state, expression)
+}
+
+
+fn process_postfix_tail(parser_state: &mut ParserState, mut expression: NodeId) -> ParseResult<NodeId> {
+    let start = parser_state.start_of(expression);
+
+    loop {
+        if parser_state.is_operator("[") {
+            parser_state.advance();
+            let index = process_expression(parser_state)?;
+            expect_operator(parser_state, "]", "to close subscript")?;
+
+            expression = parser_state.push_node(NodeKind::Index { array: expression, index }, start);
+        } else if parser_state.is_operator("(") {
+            parser_state.advance();
+            let mut arguments = Vec::new();
+
+            if !parser_state.is_operator(")") {
+                loop {
+                    arguments.push(process_assignment(parser_state)?);
+
+                    if !parser_state.is_operator(",") {
+                        break;
+                    }
+
+                    parser_state.advance();
+                }
+            }
+
+            expect_operator(parser_state, ")", "to close argument list")?;
+            expression = parser_state.push_node(NodeKind::Call { function: expression, arguments }, start);
+        } else if parser_state.is_operator(".") || parser_state.is_operator("->") {
+            let arrow = parser_state.is_operator("->");
+            parser_state.advance();
+
+            let Some(member) = process_optional_identifier(parser_state) else {
+                return Err(unexpected(parser_state, "member name"));
+            };
+
+            expression = parser_state.push_node(NodeKind::Member { object: expression, member, arrow }, start);
+        } else if parser_state.is_operator("++") || parser_state.is_operator("--") {
+            let operator = if parser_state.is_operator("++") { PostfixOperator::Increment } else { PostfixOperator::Decrement };
+            parser_state.advance();
+
+            expression = parser_state.push_node(NodeKind::PostfixOperation { operator, operand: expression }, start);
+        } else {
+            break;
+        }
+    }
+
+    Ok(expression)
+}
+
+
+fn process_primary(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+
+    let Some(token) = parser_state.peek() else {
+        return Err(unexpected(parser_state, "expression"));
+    };
+
+    let kind = match &token.token_type {
+        TokenType::Identifier(name) => NodeKind::Identifier(parser_state.intern(name)),
+        TokenType::Literal(value) if value.starts_with('"') => NodeKind::StringLiteral(parser_state.intern(&value[1..value.len() - 1])),
+        TokenType::Literal(value) => NodeKind::Constant(process_constant(parser_state, value)?),
+        TokenType::Keyword(value) if matches!(parser_state.keyword(), Some(Keyword::True | Keyword::False)) => NodeKind::Constant(process_constant(parser_state, value)?),
+        TokenType::Operator(value) if value == "(" => {
+            let expression = process_parenthesized(parser_state)?;
+            parser_state.ast.parenthesized.insert(expression);
+
+            return Ok(expression);
+        }
+
+        TokenType::Keyword(_) if parser_state.keyword() == Some(Keyword::Generic) => return process_generic_selection(parser_state),
+        _ => return Err(unexpected(parser_state, "expression")),
+    };
+
+    parser_state.advance();
+
+    Ok(parser_state.push_node(kind, start))
+}
+
+
+fn process_constant(parser_state: &ParserState, value: &str) -> ParseResult<Constant> {
+    Constant::from_str(value).map_err(|_| unexpected(parser_state, "constant"))
+}
+
+
+fn process_generic_selection(parser_state: &mut ParserState) -> ParseResult<NodeId> {
+    let start = parser_state.line();
+    parser_state.advance();
+    expect_operator(parser_state, "(", "after _Generic")?;
+
+    let controlling = process_assignment(parser_state)?;
+    let mut associations = Vec::new();
+
+    while parser_state.is_operator(",") {
+        parser_state.advance();
+
+        let type_name = if parser_state.keyword() == Some(Keyword::Default) {
+            parser_state.advance();
+            None
+        } else {
+            Some(process_type_name(parser_state)?)
+        };
+
+        expect_operator(parser_state, ":", "after generic association type")?;
+        let value = process_assignment(parser_state)?;
+
+        associations.push(GenericAssociation { type_name, value });
+    }
+
+    expect_operator(parser_state, ")", "to close _Generic")?;
+
+    if associations.is_empty() {
+        return Err(unexpected(parser_state, "at least one generic association"));
+    }
+
+    Ok(parser_state.push_node(NodeKind::GenericSelection { controlling, associations }, start))
+}
+
+
+/* * * * * * * */
+/*  - UTILS -  */
+/* * * * * * * */
+fn starts_declaration(parser_state: &ParserState) -> bool {
+    if let Some(name) = parser_state.identifier_at(0) {
+        if name == "__extension__" || name == "__attribute__" {
+            return true;
+        }
+
+        return parser_state.is_typedef_name(name) && !parser_state.is_operator_at(1, ":");
+    }
+
+    matches!(parser_state.keyword(), Some(Keyword::Typedef | Keyword::Extern | Keyword::Static | Keyword::Auto | Keyword::Register | Keyword::Inline | Keyword::Alignas | Keyword::StaticAssert))
+        || starts_type_name(parser_state, 0)
+}
+
+
+fn starts_type_name(parser_state: &ParserState, offset: usize) -> bool {
+    if let Some(name) = parser_state.identifier_at(offset) {
+        return parser_state.is_typedef_name(name);
+    }
+
+    matches!(parser_state.keyword_at(offset), Some(
+        Keyword::Void | Keyword::Char | Keyword::Short | Keyword::Int | Keyword::Long | Keyword::Float | Keyword::Double
+        | Keyword::Signed | Keyword::Unsigned | Keyword::Bool | Keyword::Struct | Keyword::Union | Keyword::Enum
+        | Keyword::Const | Keyword::Volatile | Keyword::Restrict | Keyword::Typeof | Keyword::TypeofUnqual
+    ))
+}
+
+
+fn is_ellipsis(parser_state: &ParserState) -> bool {
+    (0..3).all(|offset| parser_state.is_operator_at(offset, "."))
+}
+
+
+fn process_optional_identifier(parser_state: &mut ParserState) -> Option<Symbol> {
+    let name = parser_state.identifier_at(0)?;
+    parser_state.advance();
+
+    Some(parser_state.intern(name))
+}
+
+
+fn operator_token<'a>(parser_state: &ParserState<'a>) -> Option<&'a str> {
+    match parser_state.peek().map(|token| &token.token_type) {
+        Some(TokenType::Operator(operator)) => Some(operator.as_str()),
+        _ => None,
+    }
+}
+
+
+fn expect_operator(parser_state: &mut ParserState, operator: &str, context: &str) -> ParseResult<()> {
+    if !parser_state.is_operator(operator) {
+        return Err(unexpected(parser_state, &format!("`{}` {}", operator, context)));
+    }
+
+    parser_state.advance();
 
     Ok(())
 }
 
-fn process_switch(parser_state: &mut ParserState) -> Result<(), Box<Error>> {
-    let tokens = parser_state.tokens;
-    let mut i = parser_state.iterator;
 
-    while i < tokens.len() {
-        match &tokens[i].token_type {
-            TokenType::Keyword(_) => {
+fn unexpected(parser_state: &ParserState, expected: &str) -> ParseError {
+    let found = match parser_state.peek() {
+        Some(token) => format!("`{}`", token.token_type.value()),
+        None => "end of input".to_string(),
+    };
+
+    ParseError::new(parser_state.line(), expected, &found)
+}
+
+
+fn synchronize(parser_state: &mut ParserState, start: usize) {
+    parser_state.attributes.clear();
+
+    if parser_state.iterator == start {
+        parser_state.advance();
+    }
+
+    let mut brace_depth = 0;
+    let mut paren_depth = 0;
+
+    for token in &parser_state.tokens[start..parser_state.iterator.min(parser_state.tokens.len())] {
+        match token.token_type.value() {
+            "{" => brace_depth += 1,
+            "}" => brace_depth = 0.max(brace_depth - 1),
+            "(" | "[" => paren_depth += 1,
+            ")" | "]" => paren_depth = 0.max(paren_depth - 1),
+            _ => {}
+        }
+    }
+
+    while parser_state.peek().is_some() {
+        if parser_state.is_operator("{") {
+            brace_depth += 1;
+        } else if parser_state.is_operator("}") {
+            if brace_depth == 0 {
+                return;
+            }
 
-            } TokenType::Identifier(_) => {
+            brace_depth -= 1;
 
-            } TokenType::Literal(_) => {
-                process_expression(parser_state)?;
-            } TokenType::Operator(_) => {
-                process_expression(parser_state)?;
+            if brace_depth == 0 {
+                parser_state.advance();
+                return;
             }
+        } else if parser_state.is_operator("(") || parser_state.is_operator("[") {
+            paren_depth += 1;
+        } else if parser_state.is_operator(")") || parser_state.is_operator("]") {
+            paren_depth = 0.max(paren_depth - 1);
+        } else if brace_depth == 0 && parser_state.is_operator(";") {
+            parser_state.advance();
+            return;
+        } else if brace_depth == 0 && paren_depth == 0 && starts_declaration(parser_state) {
+            return;
+        }
+
+        parser_state.advance();
+    }
+}
+
+
+fn process_attributes(parser_state: &mut ParserState) -> ParseResult<()> {
+    loop {
+        if parser_state.is_operator("[") && parser_state.is_operator_at(1, "[") {
+            parser_state.advance();
+            parser_state.advance();
+            process_attribute_list(parser_state, None, "]")?;
+            expect_operator(parser_state, "]", "to close attribute")?;
+            expect_operator(parser_state, "]", "to close attribute")?;
+            continue;
         }
 
-        i += 1;
+        match parser_state.identifier_at(0) {
+            Some("__extension__") => {
+                parser_state.advance();
+            } Some("__attribute__") => {
+                parser_state.advance();
+                expect_operator(parser_state, "(", "after __attribute__")?;
+                expect_operator(parser_state, "(", "after __attribute__")?;
+                process_attribute_list(parser_state, Some("gnu"), ")")?;
+                expect_operator(parser_state, ")", "to close attribute")?;
+                expect_operator(parser_state, ")", "to close attribute")?;
+            } Some("__asm__" | "__asm") => {
+                parser_state.advance();
+                skip_balanced_parentheses(parser_state)?;
+            } _ => break,
+        }
+    }
+
+    Ok(())
+}
+
+
+fn process_attribute_list(parser_state: &mut ParserState, implicit_prefix: Option<&str>, closing: &str) -> ParseResult<()> {
+    while !parser_state.is_operator(closing) {
+        if parser_state.is_operator(",") {
+            parser_state.advance();
+            continue;
+        }
+
+        let Some(mut name) = attribute_token(parser_state) else {
+            return Err(unexpected(parser_state, "attribute name"));
+        };
+
+        let mut prefix = implicit_prefix.map(str::to_string);
+
+        if parser_state.is_operator(":") && parser_state.is_operator_at(1, ":") {
+            parser_state.advance();
+            parser_state.advance();
+
+            let Some(scoped_name) = attribute_token(parser_state) else {
+                return Err(unexpected(parser_state, "attribute name after `::`"));
+            };
+
+            prefix = Some(name);
+            name = scoped_name;
+        }
+
+        let arguments = if parser_state.is_operator("(") {
+            process_attribute_arguments(parser_state)?
+        } else {
+            Vec::new()
+        };
+
+        let prefix = prefix.map(|prefix| prefix.trim_matches('_').to_string());
+        let name = name.trim_matches('_').to_string();
+
+        parser_state.attributes.push(Attribute { prefix, name, arguments });
     }
 
-    parser_state.iterator = i;
     Ok(())
 }
 
-fn process_expression(parser_state: &mut ParserState) -> Result<(), Box<Error>> {
-    let tokens = &parser_state.tokens;
-    let i = parser_state.iterator;
-    let string = tokens[i].token_type.value();
-    
-    if string == "(" {
-        // if !stack.empty() {
-        //  return Err();
-        // }
-
-        parser_state.iterator = i + 1;
-        process_expression(parser_state)?;
-    } else if matches!(tokens[i].token_type, TokenType::Literal(_)) {
-        // stack.push(tokens[i]);
-        
-        parser_state.iterator = i + 1;
-        process_expression(parser_state)?;
-    } else if matches!(tokens[i].token_type, TokenType::Operator(_)) {
-        // ast.type = BinaryOperation;
-        // ast.left = stack.pop();
-        // ast.operator = tokens[i];
-
-        parser_state.iterator = i + 1;
-        process_expression(parser_state)?;
-    } else if string == ")" {
-        // ast.type = Literal;
-        // ast.value = tokens[i];
-        // return ast;
-    }
-
-    Ok(())  // Replace with return Ast
+
+fn attribute_token(parser_state: &mut ParserState) -> Option<String> {
+    let token = parser_state.peek()?;
+
+    if !matches!(token.token_type, TokenType::Identifier(_) | TokenType::Keyword(_)) {
+        return None;
+    }
+
+    parser_state.advance();
+
+    Some(token.token_type.value().to_string())
+}
+
+
+fn process_attribute_arguments(parser_state: &mut ParserState) -> ParseResult<Vec<String>> {
+    expect_operator(parser_state, "(", "to open attribute arguments")?;
+
+    let mut arguments = Vec::new();
+    let mut argument: Vec<&str> = Vec::new();
+    let mut depth = 0;
+
+    loop {
+        let Some(token) = parser_state.peek() else {
+            return Err(unexpected(parser_state, "`)` to close attribute arguments"));
+        };
+
+        let value = token.token_type.value();
+        parser_state.advance();
+
+        if matches!(token.token_type, TokenType::Operator(_)) {
+            match value {
+                "(" | "[" | "{" => depth += 1,
+                ")" if depth == 0 => break,
+                ")" | "]" | "}" => depth -= 1,
+                "," if depth == 0 => {
+                    arguments.push(argument.join(" "));
+                    argument.clear();
+                    continue;
+                } _ => {}
+            }
+        }
+
+        argument.push(value);
+    }
+
+    if !argument.is_empty() {
+        arguments.push(argument.join(" "));
+    }
+
+    Ok(arguments)
+}
+
+
+fn skip_balanced_parentheses(parser_state: &mut ParserState) -> ParseResult<()> {
+    expect_operator(parser_state, "(", "after attribute")?;
+    let mut depth = 1;
+
+    while depth > 0 {
+        if parser_state.peek().is_none() {
+            return Err(unexpected(parser_state, "`)` to close attribute"));
+        }
+
+        if parser_state.is_operator("(") {
+            depth += 1;
+        } else if parser_state.is_operator(")") {
+            depth -= 1;
+        }
+
+        parser_state.advance();
+    }
+
+    Ok(())
 }
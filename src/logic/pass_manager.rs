@@ -0,0 +1,112 @@
+use std::time::{ Duration, Instant };
+
+use crate::data::{ Diagnostic, Module, PassOptions };
+use crate::data::structures::{ Analyses, IrFunction };
+use crate::data::types::PassName;
+use crate::logic::adce::adce;
+use crate::logic::gvn::gvn;
+use crate::logic::induction::indvars;
+use crate::logic::inline::inline;
+use crate::logic::licm::licm;
+use crate::logic::sccp::sccp;
+use crate::logic::simplify_cfg::simplify_cfg;
+use crate::logic::ssa::{ mem2reg, out_of_ssa };
+use crate::logic::unroll::loop_unroll;
+use crate::logic::verifier_start;
+
+
+pub fn pass_manager_start(module: &mut Module, options: &PassOptions) -> Vec<Diagnostic> {
+    let mut analyses: Vec<Analyses> = module.functions.iter().map(|_| Analyses::default()).collect();
+    let mut timings: Vec<(PassName, Duration)> = Vec::new();
+
+    for pass in options.pipeline() {
+        let start = Instant::now();
+
+        if pass.is_module_pass() {
+            if run_module_pass(pass, module) {
+                analyses = module.functions.iter().map(|_| Analyses::default()).collect();
+            }
+        } else {
+            for (function, analyses) in module.functions.iter_mut().zip(&mut analyses) {
+                if !function.is_declaration() && run_pass(pass, function, analyses) {
+                    analyses.invalidate(pass.preserves());
+                }
+            }
+        }
+
+        record_timing(&mut timings, pass, start.elapsed());
+
+        if options.print_after.contains(&pass) {
+            eprint!("; *** IR after {} ***\n{}", pass.name(), module);
+        }
+
+        if cfg!(debug_assertions) {
+            let diagnostics = verifier_start(module);
+
+            if !diagnostics.is_empty() {
+                return diagnostics.into_iter()
+                    .map(|diagnostic| Diagnostic::new(diagnostic.severity, format!("after pass `{}`: {}", pass.name(), diagnostic.message)))
+                    .collect();
+            }
+        }
+    }
+
+    if options.time_passes {
+        print_timings(&timings);
+    }
+
+    Vec::new()
+}
+
+
+/* * * * * * * * */
+/*  - PASSES -  */
+/* * * * * * * * */
+fn run_pass(pass: PassName, function: &mut IrFunction, analyses: &mut Analyses) -> bool {
+    match pass {
+        PassName::Mem2Reg => mem2reg(function, analyses),
+        PassName::Sccp => sccp(function, analyses),
+        PassName::Gvn => gvn(function, analyses),
+        PassName::Licm => licm(function, analyses),
+        PassName::IndVars => indvars(function, analyses),
+        PassName::LoopUnroll => loop_unroll(function, analyses),
+        PassName::Inline => false,
+        PassName::Adce => adce(function, analyses),
+        PassName::SimplifyCfg => simplify_cfg(function, analyses),
+        PassName::OutOfSsa => out_of_ssa(function, analyses),
+    }
+}
+
+
+fn run_module_pass(pass: PassName, module: &mut Module) -> bool {
+    match pass {
+        PassName::Inline => inline(module),
+        _ => false,
+    }
+}
+
+
+/* * * * * * * * */
+/*  - TIMING -  */
+/* * * * * * * * */
+fn record_timing(timings: &mut Vec<(PassName, Duration)>, pass: PassName, elapsed: Duration) {
+    match timings.iter_mut().find(|(name, _)| *name == pass) {
+        Some((_, total)) => *total += elapsed,
+        None => timings.push((pass, elapsed)),
+    }
+}
+
+
+fn print_timings(timings: &[(PassName, Duration)]) {
+    let total: Duration = timings.iter().map(|(_, elapsed)| *elapsed).sum();
+
+    eprintln!("===== pass execution timing =====");
+    eprintln!("{:>12}  {:>6}  pass", "time (ms)", "share");
+
+    for (pass, elapsed) in timings {
+        let share = if total.is_zero() { 0.0 } else { elapsed.as_secs_f64() / total.as_secs_f64() * 100.0 };
+        eprintln!("{:>12.3}  {:>5.1}%  {}", elapsed.as_secs_f64() * 1000.0, share, pass.name());
+    }
+
+    eprintln!("{:>12.3}  {:>5.1}%  total", total.as_secs_f64() * 1000.0, 100.0);
+}
@@ -0,0 +1,734 @@
+use crate::data::{ Ast, Keyword, NodeId, NodeKind, escape_c };
+use crate::data::types::{ Attribute, DeclSpecifiers, Declarator, DerivedDeclarator, Designator, InitDeclarator, Initializer, Parameter, TypeName, TypeSpecifier, TypeofArgument, UnaryOperator };
+
+
+const INDENT: &str = "    ";
+
+const COMMA: u8 = 0;
+const ASSIGNMENT: u8 = 1;
+const CONDITIONAL: u8 = 2;
+const CAST: u8 = 14;
+const UNARY: u8 = 15;
+const POSTFIX: u8 = 16;
+const PRIMARY: u8 = 17;
+
+
+pub fn printer_start(ast: &Ast) -> String {
+    let mut output = String::new();
+
+    let NodeKind::TranslationUnit(items) = ast.kind(ast.root) else {
+        return output;
+    };
+
+    for (index, &item) in items.iter().enumerate() {
+        let is_function = matches!(ast.kind(item), NodeKind::FunctionDefinition { .. });
+
+        if index > 0 && (is_function || matches!(ast.kind(items[index - 1]), NodeKind::FunctionDefinition { .. })) {
+            output.push('\n');
+        }
+
+        print_statement(ast, item, 0, &mut output);
+        output.push('\n');
+    }
+
+    output
+}
+
+
+/* * * * * * * * * * */
+/*  - DECLARATIONS - */
+/* * * * * * * * * * */
+fn print_declaration(ast: &Ast, attributes: &[Attribute], specifiers: &DeclSpecifiers, declarators: &[InitDeclarator], indent: usize) -> String {
+    let mut declaration = print_attributes(attributes);
+    declaration.push_str(&print_specifiers(ast, specifiers, indent));
+
+    let declarators: Vec<String> = declarators.iter().map(|declarator| print_init_declarator(ast, declarator, indent)).collect();
+
+    if !declarators.is_empty() {
+        declaration.push(' ');
+        declaration.push_str(&declarators.join(", "));
+    }
+
+    declaration.push(';');
+    declaration
+}
+
+
+fn print_init_declarator(ast: &Ast, init_declarator: &InitDeclarator, indent: usize) -> String {
+    let mut declarator = print_declarator(ast, &init_declarator.declarator, indent);
+
+    if let Some(bit_width) = init_declarator.bit_width {
+        if !declarator.is_empty() {
+            declarator.push(' ');
+        }
+
+        declarator.push_str(&format!(": {}", print_expression(ast, bit_width, CONDITIONAL, indent)));
+    }
+
+    if !init_declarator.attributes.is_empty() {
+        declarator.push(' ');
+        declarator.push_str(print_attributes(&init_declarator.attributes).trim_end());
+    }
+
+    if let Some(initializer) = init_declarator.initializer {
+        declarator.push_str(&format!(" = {}", print_expression(ast, initializer, ASSIGNMENT, indent)));
+    }
+
+    declarator
+}
+
+
+fn print_specifiers(ast: &Ast, specifiers: &DeclSpecifiers, indent: usize) -> String {
+    let mut words: Vec<String> = specifiers.storage.iter()
+        .chain(&specifiers.function)
+        .chain(&specifiers.qualifiers)
+        .map(|keyword| keyword.to_string())
+        .collect();
+
+    for alignment in &specifiers.alignment {
+        let operand = match alignment {
+            TypeofArgument::Expression(expression) => print_expression(ast, *expression, COMMA, indent),
+            TypeofArgument::Type(type_name) => print_type_name(ast, type_name, indent),
+        };
+
+        words.push(format!("alignas({})", operand));
+    }
+
+    for specifier in &specifiers.specifiers {
+        words.push(print_type_specifier(ast, specifier, indent));
+    }
+
+    words.join(" ")
+}
+
+
+fn print_type_specifier(ast: &Ast, specifier: &TypeSpecifier, indent: usize) -> String {
+    match specifier {
+        TypeSpecifier::Basic(keyword) => keyword.to_string(),
+        TypeSpecifier::TypedefName(name) => ast.symbols.resolve(*name).to_string(),
+
+        TypeSpecifier::Record { kind, tag, members } => {
+            let mut record = if *kind == Keyword::Union { "union".to_string() } else { "struct".to_string() };
+
+            if let Some(tag) = tag {
+                record.push(' ');
+                record.push_str(ast.symbols.resolve(*tag));
+            }
+
+            if let Some(members) = members {
+                record.push_str(" {");
+
+                for &member in members {
+                    record.push('\n');
+                    print_statement(ast, member, indent + 1, &mut record);
+                }
+
+                if !members.is_empty() {
+                    record.push('\n');
+                    record.push_str(&INDENT.repeat(indent));
+                }
+
+                record.push('}');
+            }
+
+            record
+        }
+
+        TypeSpecifier::Enum { tag, enumerators } => {
+            let mut enumeration = "enum".to_string();
+
+            if let Some(tag) = tag {
+                enumeration.push(' ');
+                enumeration.push_str(ast.symbols.resolve(*tag));
+            }
+
+            if let Some(enumerators) = enumerators {
+                let enumerators: Vec<String> = enumerators.iter()
+                    .map(|enumerator| {
+                        let mut line = format!("{}{}", INDENT.repeat(indent + 1), ast.symbols.resolve(enumerator.name));
+
+                        if let Some(value) = enumerator.value {
+                            line.push_str(&format!(" = {}", print_expression(ast, value, CONDITIONAL, indent + 1)));
+                        }
+
+                        line
+                    })
+                    .collect();
+
+                if enumerators.is_empty() {
+                    enumeration.push_str(" {}");
+                } else {
+                    enumeration.push_str(&format!(" {{\n{}\n{}}}", enumerators.join(",\n"), INDENT.repeat(indent)));
+                }
+            }
+
+            enumeration
+        }
+
+        TypeSpecifier::Typeof { unqualified, argument } => {
+            let keyword = if *unqualified { Keyword::TypeofUnqual } else { Keyword::Typeof };
+
+            let argument = match argument {
+                TypeofArgument::Expression(expression) => print_expression(ast, *expression, COMMA, indent),
+                TypeofArgument::Type(type_name) => print_type_name(ast, type_name, indent),
+            };
+
+            format!("{}({})", keyword, argument)
+        }
+    }
+}
+
+
+fn print_declarator(ast: &Ast, declarator: &Declarator, indent: usize) -> String {
+    let mut output = declarator.name.map_or(String::new(), |name| ast.symbols.resolve(name).to_string());
+    let mut after_pointer = false;
+
+    for derived in declarator.derived.iter().rev() {
+        match derived {
+            DerivedDeclarator::Pointer(qualifiers) => {
+                let qualifiers: Vec<String> = qualifiers.iter().map(|keyword| keyword.to_string()).collect();
+                let mut pointer = format!("*{}", qualifiers.join(" "));
+
+                if !qualifiers.is_empty() && !output.is_empty() {
+                    pointer.push(' ');
+                }
+
+                output = pointer + &output;
+                after_pointer = true;
+            }
+
+            DerivedDeclarator::Array(size) => {
+                if std::mem::take(&mut after_pointer) {
+                    output = format!("({})", output);
+                }
+
+                let size = size.map_or(String::new(), |size| print_expression(ast, size, ASSIGNMENT, indent));
+                output.push_str(&format!("[{}]", size));
+            }
+
+            DerivedDeclarator::Function { parameters, variadic } => {
+                if std::mem::take(&mut after_pointer) {
+                    output = format!("({})", output);
+                }
+
+                let mut parameters: Vec<String> = parameters.iter().map(|parameter| print_parameter(ast, parameter, indent)).collect();
+
+                if *variadic {
+                    parameters.push("...".to_string());
+                }
+
+                output.push_str(&format!("({})", parameters.join(", ")));
+            }
+        }
+    }
+
+    output
+}
+
+
+fn print_parameter(ast: &Ast, parameter: &Parameter, indent: usize) -> String {
+    let specifiers = print_specifiers(ast, &parameter.specifiers, indent);
+    let declarator = print_declarator(ast, &parameter.declarator, indent);
+
+    print_attributes(&parameter.attributes) + &join_declarator(specifiers, declarator)
+}
+
+
+fn print_type_name(ast: &Ast, type_name: &TypeName, indent: usize) -> String {
+    let specifiers = print_specifiers(ast, &type_name.specifiers, indent);
+    let declarator = print_declarator(ast, &type_name.declarator, indent);
+
+    join_declarator(specifiers, declarator)
+}
+
+
+fn join_declarator(specifiers: String, declarator: String) -> String {
+    if declarator.is_empty() || declarator.starts_with('[') {
+        specifiers + &declarator
+    } else {
+        format!("{} {}", specifiers, declarator)
+    }
+}
+
+
+fn print_attributes(attributes: &[Attribute]) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+
+    let attributes: Vec<String> = attributes.iter().map(|attribute| attribute.to_string()).collect();
+
+    format!("[[{}]] ", attributes.join(", "))
+}
+
+
+/* * * * * * * * * */
+/*  - STATEMENTS - */
+/* * * * * * * * * */
+fn print_statement(ast: &Ast, id: NodeId, indent: usize, output: &mut String) {
+    let outdent = matches!(ast.kind(id), NodeKind::Case { .. } | NodeKind::Default(_) | NodeKind::Label { .. });
+
+    output.push_str(&INDENT.repeat(if outdent { indent.saturating_sub(1) } else { indent }));
+    print_unindented(ast, id, indent, output);
+}
+
+
+fn print_unindented(ast: &Ast, id: NodeId, indent: usize, output: &mut String) {
+    match ast.kind(id) {
+        NodeKind::FunctionDefinition { attributes, specifiers, declarator, body } => {
+            output.push_str(&print_attributes(attributes));
+            output.push_str(&print_specifiers(ast, specifiers, indent));
+            output.push(' ');
+            output.push_str(&print_declarator(ast, declarator, indent));
+            output.push(' ');
+            print_block(ast, *body, indent, output);
+        }
+
+        NodeKind::Declaration { attributes, specifiers, declarators } => {
+            output.push_str(&print_declaration(ast, attributes, specifiers, declarators, indent));
+        }
+
+        NodeKind::StaticAssert { condition, message } => {
+            output.push_str(&format!("static_assert({}", print_expression(ast, *condition, CONDITIONAL, indent)));
+
+            if let Some(message) = message {
+                output.push_str(&format!(", {}", string_literal(ast.symbols.resolve(*message))));
+            }
+
+            output.push_str(");");
+        }
+
+        NodeKind::Attributed { attributes, statement } => {
+            output.push_str(print_attributes(attributes).trim_end());
+
+            if !matches!(ast.kind(*statement), NodeKind::ExpressionStatement(None)) {
+                output.push(' ');
+            }
+
+            print_unindented(ast, *statement, indent, output);
+        }
+
+        NodeKind::Compound(_) => print_block(ast, id, indent, output),
+        NodeKind::ExpressionStatement(None) => output.push(';'),
+        NodeKind::ExpressionStatement(Some(expression)) => output.push_str(&format!("{};", print_expression(ast, *expression, COMMA, indent))),
+
+        NodeKind::If { condition, then_branch, else_branch } => {
+            output.push_str(&format!("if ({})", print_expression(ast, *condition, COMMA, indent)));
+
+            let Some(else_branch) = else_branch else {
+                print_body(ast, *then_branch, indent, output);
+                return;
+            };
+
+            let braced = ends_with_open_if(ast, *then_branch);
+
+            if braced {
+                output.push_str(" {\n");
+                print_statement(ast, *then_branch, indent + 1, output);
+                output.push('\n');
+                output.push_str(&INDENT.repeat(indent));
+                output.push('}');
+            } else {
+                print_body(ast, *then_branch, indent, output);
+            }
+
+            if braced || matches!(ast.kind(*then_branch), NodeKind::Compound(_)) {
+                output.push(' ');
+            } else {
+                output.push('\n');
+                output.push_str(&INDENT.repeat(indent));
+            }
+
+            output.push_str("else");
+
+            if matches!(ast.kind(*else_branch), NodeKind::If { .. }) {
+                output.push(' ');
+                print_unindented(ast, *else_branch, indent, output);
+            } else {
+                print_body(ast, *else_branch, indent, output);
+            }
+        }
+
+        NodeKind::While { condition, body } => {
+            output.push_str(&format!("while ({})", print_expression(ast, *condition, COMMA, indent)));
+            print_body(ast, *body, indent, output);
+        }
+
+        NodeKind::DoWhile { body, condition } => {
+            output.push_str("do");
+            print_body(ast, *body, indent, output);
+
+            if matches!(ast.kind(*body), NodeKind::Compound(_)) {
+                output.push(' ');
+            } else {
+                output.push('\n');
+                output.push_str(&INDENT.repeat(indent));
+            }
+
+            output.push_str(&format!("while ({});", print_expression(ast, *condition, COMMA, indent)));
+        }
+
+        NodeKind::For { init, condition, step, body } => {
+            output.push_str("for (");
+
+            match init {
+                Some(init) => print_unindented(ast, *init, indent, output),
+                None => output.push(';'),
+            }
+
+            if let Some(condition) = condition {
+                output.push_str(&format!(" {}", print_expression(ast, *condition, COMMA, indent)));
+            }
+
+            output.push(';');
+
+            if let Some(step) = step {
+                output.push_str(&format!(" {}", print_expression(ast, *step, COMMA, indent)));
+            }
+
+            output.push(')');
+            print_body(ast, *body, indent, output);
+        }
+
+        NodeKind::Switch { condition, body } => {
+            output.push_str(&format!("switch ({})", print_expression(ast, *condition, COMMA, indent)));
+            print_body(ast, *body, indent, output);
+        }
+
+        NodeKind::Case { value, body } => {
+            output.push_str(&format!("case {}:\n", print_expression(ast, *value, CONDITIONAL, indent)));
+            print_statement(ast, *body, indent, output);
+        }
+
+        NodeKind::Default(body) => {
+            output.push_str("default:\n");
+            print_statement(ast, *body, indent, output);
+        }
+
+        NodeKind::Label { name, body } => {
+            output.push_str(&format!("{}:\n", ast.symbols.resolve(*name)));
+            print_statement(ast, *body, indent, output);
+        }
+
+        NodeKind::Goto(label) => output.push_str(&format!("goto {};", ast.symbols.resolve(*label))),
+        NodeKind::Break => output.push_str("break;"),
+        NodeKind::Continue => output.push_str("continue;"),
+        NodeKind::Return(None) => output.push_str("return;"),
+        NodeKind::Return(Some(value)) => output.push_str(&format!("return {};", print_expression(ast, *value, COMMA, indent))),
+        NodeKind::Error => output.push_str("/* parse error */;"),
+        _ => output.push_str(&format!("{};", print_expression(ast, id, COMMA, indent))),
+    }
+}
+
+
+fn print_block(ast: &Ast, id: NodeId, indent: usize, output: &mut String) {
+    let NodeKind::Compound(items) = ast.kind(id) else {
+        print_statement(ast, id, indent, output);
+        return;
+    };
+
+    if items.is_empty() {
+        output.push_str("{}");
+        return;
+    }
+
+    let has_cases = items.iter().any(|&item| matches!(ast.kind(item), NodeKind::Case { .. } | NodeKind::Default(_)));
+    let inner = if has_cases { indent + 2 } else { indent + 1 };
+
+    output.push_str("{\n");
+
+    for &item in items {
+        print_statement(ast, item, inner, output);
+        output.push('\n');
+    }
+
+    output.push_str(&INDENT.repeat(indent));
+    output.push('}');
+}
+
+
+fn print_body(ast: &Ast, id: NodeId, indent: usize, output: &mut String) {
+    if matches!(ast.kind(id), NodeKind::Compound(_)) {
+        output.push(' ');
+        print_block(ast, id, indent, output);
+    } else {
+        output.push('\n');
+        print_statement(ast, id, indent + 1, output);
+    }
+}
+
+
+fn ends_with_open_if(ast: &Ast, id: NodeId) -> bool {
+    match ast.kind(id) {
+        NodeKind::If { else_branch: None, .. } => true,
+        NodeKind::If { else_branch: Some(body), .. }
+        | NodeKind::While { body, .. }
+        | NodeKind::For { body, .. }
+        | NodeKind::Switch { body, .. }
+        | NodeKind::Case { body, .. }
+        | NodeKind::Default(body)
+        | NodeKind::Label { body, .. }
+        | NodeKind::Attributed { statement: body, .. } => ends_with_open_if(ast, *body),
+        _ => false,
+    }
+}
+
+
+/* * * * * * * * * * */
+/*  - EXPRESSIONS -  */
+/* * * * * * * * * * */
+fn precedence(ast: &Ast, id: NodeId) -> u8 {
+    match ast.kind(id) {
+        NodeKind::ImplicitCast { operand, .. } => precedence(ast, *operand),
+        NodeKind::BinaryOperation { operator, .. } if operator.precedence() == 0 => COMMA,
+        NodeKind::BinaryOperation { operator, .. } => CONDITIONAL + operator.precedence(),
+        NodeKind::Assignment { .. } => ASSIGNMENT,
+        NodeKind::Conditional { .. } => CONDITIONAL,
+        NodeKind::Cast { .. } => CAST,
+        NodeKind::UnaryOperation { .. } | NodeKind::SizeofType(_) | NodeKind::AlignofType(_) => UNARY,
+        NodeKind::PostfixOperation { .. } | NodeKind::Call { .. } | NodeKind::Index { .. } | NodeKind::Member { .. } | NodeKind::CompoundLiteral { .. } => POSTFIX,
+        _ => PRIMARY,
+    }
+}
+
+
+fn print_expression(ast: &Ast, id: NodeId, min_precedence: u8, indent: usize) -> String {
+    let expression = match ast.kind(id) {
+        NodeKind::ImplicitCast { operand, .. } => return print_expression(ast, *operand, min_precedence, indent),
+
+        NodeKind::BinaryOperation { left, operator, right } => {
+            let precedence = precedence(ast, id);
+            let left = print_expression(ast, *left, precedence, indent);
+            let right = print_expression(ast, *right, precedence + 1, indent);
+
+            if precedence == COMMA {
+                format!("{}, {}", left, right)
+            } else {
+                format!("{} {} {}", left, operator.symbol(), right)
+            }
+        }
+
+        NodeKind::Assignment { left, operator, right } => {
+            format!("{} {} {}", print_expression(ast, *left, UNARY, indent), operator.symbol(), print_expression(ast, *right, ASSIGNMENT, indent))
+        }
+
+        NodeKind::Conditional { condition, then_value, else_value } => {
+            format!("{} ? {} : {}",
+                print_expression(ast, *condition, CONDITIONAL + 1, indent),
+                print_expression(ast, *then_value, COMMA, indent),
+                print_expression(ast, *else_value, CONDITIONAL, indent))
+        }
+
+        NodeKind::Cast { type_name, operand } => {
+            format!("({}){}", print_type_name(ast, type_name, indent), print_expression(ast, *operand, CAST, indent))
+        }
+
+        NodeKind::UnaryOperation { operator: UnaryOperator::Sizeof, operand } => {
+            let operand = print_expression(ast, *operand, UNARY, indent);
+
+            if operand.starts_with('(') {
+                format!("sizeof{}", operand)
+            } else {
+                format!("sizeof {}", operand)
+            }
+        }
+
+        NodeKind::UnaryOperation { operator, operand } => {
+            let operand_precedence = if matches!(operator, UnaryOperator::PreIncrement | UnaryOperator::PreDecrement) { UNARY } else { CAST };
+            let operand = print_expression(ast, *operand, operand_precedence, indent);
+            let symbol = operator.symbol();
+
+            if symbol.ends_with(|character| operand.starts_with(character)) {
+                format!("{} {}", symbol, operand)
+            } else {
+                format!("{}{}", symbol, operand)
+            }
+        }
+
+        NodeKind::PostfixOperation { operator, operand } => format!("{}{}", print_expression(ast, *operand, POSTFIX, indent), operator.symbol()),
+
+        NodeKind::Call { function, arguments } => {
+            let arguments: Vec<String> = arguments.iter().map(|&argument| print_expression(ast, argument, ASSIGNMENT, indent)).collect();
+
+            format!("{}({})", print_expression(ast, *function, POSTFIX, indent), arguments.join(", "))
+        }
+
+        NodeKind::Index { array, index } => {
+            format!("{}[{}]", print_expression(ast, *array, POSTFIX, indent), print_expression(ast, *index, COMMA, indent))
+        }
+
+        NodeKind::Member { object, member, arrow } => {
+            format!("{}{}{}", print_expression(ast, *object, POSTFIX, indent), if *arrow { "->" } else { "." }, ast.symbols.resolve(*member))
+        }
+
+        NodeKind::CompoundLiteral { type_name, initializer } => {
+            format!("({}){}", print_type_name(ast, type_name, indent), print_expression(ast, *initializer, PRIMARY, indent))
+        }
+
+        NodeKind::GenericSelection { controlling, associations } => {
+            let mut parts = vec![print_expression(ast, *controlling, ASSIGNMENT, indent)];
+
+            for association in associations {
+                let type_name = association.type_name.as_ref().map_or("default".to_string(), |type_name| print_type_name(ast, type_name, indent));
+                parts.push(format!("{}: {}", type_name, print_expression(ast, association.value, ASSIGNMENT, indent)));
+            }
+
+            format!("_Generic({})", parts.join(", "))
+        }
+
+        NodeKind::InitializerList(initializers) => {
+            let initializers: Vec<String> = initializers.iter().map(|initializer| print_initializer(ast, initializer, indent)).collect();
+
+            if initializers.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", initializers.join(", "))
+            }
+        }
+
+        NodeKind::SizeofType(type_name) => format!("sizeof({})", print_type_name(ast, type_name, indent)),
+        NodeKind::AlignofType(type_name) => format!("alignof({})", print_type_name(ast, type_name, indent)),
+        NodeKind::Identifier(name) => ast.symbols.resolve(*name).to_string(),
+        NodeKind::StringLiteral(value) => string_literal(ast.symbols.resolve(*value)),
+        NodeKind::Constant(constant) => constant.to_string(),
+        NodeKind::Error => "/* parse error */".to_string(),
+        _ => String::new(),
+    };
+
+    if precedence(ast, id) < min_precedence {
+        format!("({})", expression)
+    } else {
+        expression
+    }
+}
+
+
+pub fn print_designators(ast: &Ast, designators: &[Designator]) -> String {
+    let mut output = String::new();
+
+    for designator in designators {
+        match designator {
+            Designator::Index(index) => output.push_str(&format!("[{}]", print_expression(ast, *index, CONDITIONAL, 0))),
+            Designator::Member(member) => output.push_str(&format!(".{}", ast.symbols.resolve(*member))),
+        }
+    }
+
+    output
+}
+
+
+fn print_initializer(ast: &Ast, initializer: &Initializer, indent: usize) -> String {
+    let mut output = print_designators(ast, &initializer.designators);
+
+    if !output.is_empty() {
+        output.push_str(" = ");
+    }
+
+    output.push_str(&print_expression(ast, initializer.value, ASSIGNMENT, indent));
+    output
+}
+
+
+fn string_literal(value: &str) -> String {
+    let mut literal = String::from("\"");
+    let mut after_numeric_escape = false;
+
+    for character in value.chars() {
+        if after_numeric_escape && character.is_ascii_hexdigit() {
+            literal.push_str("\" \"");
+        }
+
+        let escaped = escape_c(&character.to_string(), '"');
+        after_numeric_escape = escaped.starts_with("\\x") || escaped == "\\0";
+        literal.push_str(&escaped);
+    }
+
+    literal.push('"');
+    literal
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use crate::data::{ Ast, NodeId, Symbol };
+    use crate::logic::{ lexer_start, parser_start, printer_start };
+
+
+    fn parse(source: &str) -> Ast {
+        let tokens = lexer_start(source).expect("source should lex");
+        let (ast, errors) = parser_start(&tokens);
+        assert!(errors.is_empty(), "parse errors in {:?}: {:?}", source, errors);
+
+        ast
+    }
+
+
+    fn shape(ast: &Ast, id: NodeId) -> String {
+        let debug = format!("{:?}", ast.kind(id));
+        let mut output = String::new();
+        let mut rest = debug.as_str();
+
+        while let Some(start) = [rest.find("NodeId("), rest.find("Symbol(")].into_iter().flatten().min() {
+            let (before, after) = rest.split_at(start);
+            let open = after.find('(').unwrap_or(0) + 1;
+            let close = after.find(')').unwrap_or(after.len());
+            let value: u32 = after[open..close].parse().expect("id should be numeric");
+
+            output.push_str(before);
+
+            match after.starts_with("NodeId(") {
+                true => output.push_str(&shape(ast, NodeId(value))),
+                false => output.push_str(&format!("{:?}", ast.symbols.resolve(Symbol(value)))),
+            }
+
+            rest = &after[close + 1..];
+        }
+
+        output.push_str(rest);
+        output
+    }
+
+
+    fn assert_round_trips(source: &str, name: &str) {
+        let ast = parse(source);
+        let printed = printer_start(&ast);
+        let reparsed = parse(&printed);
+
+        assert_eq!(shape(&ast, ast.root), shape(&reparsed, reparsed.root), "{} does not round-trip:\n{}", name, printed);
+        assert_eq!(printed, printer_start(&reparsed), "{} does not print stably", name);
+    }
+
+
+    #[test]
+    fn round_trips_examples() {
+        for example in ["oddEven.c", "oddEven2.c", "primeNumbers.c", "reverseString.c"] {
+            let path = format!("{}/examples/{}", env!("CARGO_MANIFEST_DIR"), example);
+            let output = Command::new("cpp").arg(&path).output().expect("cpp should run");
+            let source = String::from_utf8(output.stdout).expect("preprocessed source should be UTF-8");
+
+            assert_round_trips(&source, example);
+        }
+    }
+
+
+    #[test]
+    fn preserves_precedence() {
+        for source in [
+            "int f(int a, int b, int c) { return (a + b) * c; }",
+            "int f(int a, int b, int c) { return a - (b - c); }",
+            "int f(int a, int b, int c) { return a - b - c; }",
+            "int f(int a, int b, int c) { return a << (b + c) & 1; }",
+            "int f(int a, int b, int c) { return (a ? b : c) ? a : (b, c); }",
+            "int f(int a, int b, int c) { a = b = c; return (a = b) + c; }",
+            "int f(int *p, int a) { return -(*p)++ + ~-a + !(a && *p || a); }",
+            "int f(int **p) { return *p[0] + (*p)[0] + (int)sizeof *p + sizeof(int *); }",
+            "struct s { int x; } *g(void); int f(void) { return g()->x + (&*g())->x + (int)(long)g(); }",
+            "int (*f(int (*h)(int)))(int) { return h; }",
+            "int a[3][4]; int (*f(void))[4] { return &a[1]; }",
+        ] {
+            assert_round_trips(source, source);
+        }
+    }
+}
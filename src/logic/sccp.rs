@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use crate::data::structures::{ Analyses, Instruction, IrFunction, SccpState };
+use crate::data::types::{ BlockId, InstructionKind, Lattice, Operand, Register };
+use crate::logic::fold::{ fold_binary, fold_compare, fold_convert, unsigned };
+
+
+pub fn sccp(function: &mut IrFunction, _analyses: &mut Analyses) -> bool {
+    let mut sccp_state = SccpState::default();
+
+    for (_, register) in &function.parameters {
+        sccp_state.values.insert(*register, Lattice::Bottom);
+    }
+
+    for block in &function.blocks {
+        for (index, instruction) in block.instructions.iter().enumerate() {
+            let mut instruction = instruction.clone();
+
+            for operand in instruction.operands_mut() {
+                if let Operand::Register(register) = operand {
+                    sccp_state.uses.entry(*register).or_default().push((block.id, index));
+                }
+            }
+        }
+    }
+
+    solve(&mut sccp_state, function);
+    rewrite(&sccp_state, function)
+}
+
+
+/* * * * * * * * */
+/*  - SOLVER -  */
+/* * * * * * * * */
+fn solve(sccp_state: &mut SccpState, function: &IrFunction) {
+    sccp_state.executable.insert(BlockId(0));
+    visit_block(sccp_state, function, BlockId(0), false);
+
+    loop {
+        if let Some((_, target)) = sccp_state.flow_worklist.pop() {
+            let first = sccp_state.executable.insert(target);
+            visit_block(sccp_state, function, target, !first);
+        } else if let Some(register) = sccp_state.value_worklist.pop() {
+            for (block, index) in sccp_state.uses.get(&register).cloned().unwrap_or_default() {
+                if sccp_state.executable.contains(&block) {
+                    visit(sccp_state, function, block, &function.blocks[block.0 as usize].instructions[index]);
+                }
+            }
+        } else {
+            return;
+        }
+    }
+}
+
+
+fn visit_block(sccp_state: &mut SccpState, function: &IrFunction, block: BlockId, phis_only: bool) {
+    for instruction in &function.blocks[block.0 as usize].instructions {
+        if phis_only && !matches!(instruction.kind, InstructionKind::Phi { .. }) {
+            return;
+        }
+
+        visit(sccp_state, function, block, instruction);
+    }
+}
+
+
+fn visit(sccp_state: &mut SccpState, function: &IrFunction, block: BlockId, instruction: &Instruction) {
+    let ty = instruction.ty;
+
+    let value = match &instruction.kind {
+        InstructionKind::Binary { operator, left, right, no_signed_wrap } => {
+            evaluate(sccp_state, &[left, right], |operands| fold_binary(*operator, ty, *no_signed_wrap, &operands[0], &operands[1]))
+        }
+
+        InstructionKind::Compare { predicate, left, right } => {
+            evaluate(sccp_state, &[left, right], |operands| fold_compare(*predicate, ty, &operands[0], &operands[1]))
+        }
+
+        InstructionKind::Convert { operator, value, from } => {
+            evaluate(sccp_state, &[value], |operands| fold_convert(*operator, *from, ty, &operands[0]))
+        }
+
+        InstructionKind::Phi { incoming } => {
+            incoming.iter()
+                .filter(|(_, predecessor)| sccp_state.edges.contains(&(*predecessor, block)))
+                .fold(Lattice::Top, |value, (operand, _)| meet(value, lattice(sccp_state, operand)))
+        }
+
+        InstructionKind::Jump(target) => return mark_edge(sccp_state, block, *target),
+
+        InstructionKind::Branch { condition, then_block, else_block } => {
+            return match lattice(sccp_state, condition) {
+                Lattice::Top => {}
+                Lattice::Constant(Operand::Integer(value)) => mark_edge(sccp_state, block, if value != 0 { *then_block } else { *else_block }),
+                _ => mark_successors(sccp_state, function, block),
+            };
+        }
+
+        InstructionKind::Switch { value, default, cases } => {
+            return match lattice(sccp_state, value) {
+                Lattice::Top => {}
+
+                Lattice::Constant(Operand::Integer(value)) => {
+                    let target = cases.iter().find(|(case, _)| unsigned(*case, ty) == unsigned(value, ty)).map_or(*default, |(_, target)| *target);
+                    mark_edge(sccp_state, block, target);
+                }
+
+                _ => mark_successors(sccp_state, function, block),
+            };
+        }
+
+        _ => Lattice::Bottom,
+    };
+
+    if let Some(result) = instruction.result {
+        update(sccp_state, result, value);
+    }
+}
+
+
+fn evaluate(sccp_state: &SccpState, operands: &[&Operand], fold: impl Fn(&[Operand]) -> Option<Operand>) -> Lattice {
+    let mut constants = Vec::with_capacity(operands.len());
+
+    for operand in operands {
+        match lattice(sccp_state, operand) {
+            Lattice::Bottom => return Lattice::Bottom,
+            Lattice::Top => return Lattice::Top,
+            Lattice::Constant(constant) => constants.push(constant),
+        }
+    }
+
+    fold(&constants).map_or(Lattice::Bottom, Lattice::Constant)
+}
+
+
+fn lattice(sccp_state: &SccpState, operand: &Operand) -> Lattice {
+    match operand {
+        Operand::Register(register) => sccp_state.values.get(register).cloned().unwrap_or(Lattice::Top),
+        Operand::Undef => Lattice::Bottom,
+        constant => Lattice::Constant(constant.clone()),
+    }
+}
+
+
+fn meet(left: Lattice, right: Lattice) -> Lattice {
+    match (left, right) {
+        (Lattice::Top, value) | (value, Lattice::Top) => value,
+        (Lattice::Constant(left), Lattice::Constant(right)) if left == right => Lattice::Constant(left),
+        _ => Lattice::Bottom,
+    }
+}
+
+
+fn update(sccp_state: &mut SccpState, register: Register, value: Lattice) {
+    let current = sccp_state.values.get(&register).cloned().unwrap_or(Lattice::Top);
+    let value = meet(current.clone(), value);
+
+    if value != current {
+        sccp_state.values.insert(register, value);
+        sccp_state.value_worklist.push(register);
+    }
+}
+
+
+fn mark_edge(sccp_state: &mut SccpState, from: BlockId, to: BlockId) {
+    if sccp_state.edges.insert((from, to)) {
+        sccp_state.flow_worklist.push((from, to));
+    }
+}
+
+
+fn mark_successors(sccp_state: &mut SccpState, function: &IrFunction, block: BlockId) {
+    for successor in function.blocks[block.0 as usize].successors() {
+        mark_edge(sccp_state, block, successor);
+    }
+}
+
+
+/* * * * * * * * * */
+/*  - REWRITING -  */
+/* * * * * * * * * */
+fn rewrite(sccp_state: &SccpState, function: &mut IrFunction) -> bool {
+    let replacements: HashMap<Register, Operand> = sccp_state.values.iter()
+        .filter_map(|(register, value)| match value {
+            Lattice::Constant(constant) => Some((*register, constant.clone())),
+            _ => None,
+        })
+        .collect();
+
+    let mut changed = !replacements.is_empty();
+
+    for block in &mut function.blocks {
+        block.instructions.retain(|instruction| instruction.result.is_none_or(|result| !replacements.contains_key(&result)));
+    }
+
+    function.replace_operands(&replacements);
+
+    for index in 0..function.blocks.len() {
+        let block = BlockId(index as u32);
+        let successors = function.blocks[index].successors();
+
+        if !sccp_state.executable.contains(&block) || successors.len() < 2 {
+            continue;
+        }
+
+        let taken: Vec<BlockId> = successors.iter().copied().filter(|successor| sccp_state.edges.contains(&(block, *successor))).collect();
+
+        let [target] = taken[..] else {
+            continue;
+        };
+
+        if let Some(terminator) = function.blocks[index].instructions.last_mut() {
+            terminator.kind = InstructionKind::Jump(target);
+        }
+
+        for successor in successors.into_iter().filter(|successor| *successor != target) {
+            function.remove_incoming(successor, block);
+        }
+
+        changed = true;
+    }
+
+    changed
+}
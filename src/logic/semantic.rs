@@ -0,0 +1,2382 @@
+use crate::data::{ Ast, Binding, CType, Diagnostic, EntityId, Keyword, Member, NodeId, NodeKind, SemanticState, Symbol, SymbolTable, Target, TypeTable };
+use crate::data::maps::FORMAT_FUNCTION_MAP;
+use crate::data::structures::{ FormatArgument, SwitchLabels, TypeLayout };
+use crate::data::visit::{ Folder, Visitor, children, walk_fold_node, walk_generic_selection, walk_node };
+use crate::logic::constant::{ ConstantContext, Evaluator };
+use crate::logic::sym_table::PREDEFINED;
+use crate::data::types::{ ArraySize, AssignmentOperator, Attribute, BinaryOperator, CastKind, Constant, ConstantValue, DeclSpecifiers, Declarator, DerivedDeclarator, Designator, EvaluationError, FormatKind, GenericAssociation, Initializer, PostfixOperator, Qualifiers, ScopeKind, Severity, TypeName, TypeofArgument, TypeSpecifier, UnaryOperator, ValueCategory };
+use std::collections::{ HashMap, HashSet };
+
+
+pub fn semantic_start(ast: &mut Ast, table: &SymbolTable, target: &Target) -> (TypeTable, Vec<Diagnostic>) {
+    let mut semantic_state = SemanticState::new(ast, table, target);
+
+    if let NodeKind::TranslationUnit(items) = ast.kind(ast.root) {
+        for &item in items {
+            process_external(&mut semantic_state, item);
+        }
+    }
+
+    let SemanticState { types, casts, diagnostics, .. } = semantic_state;
+    let mut cast_inserter = CastInserter { types, casts };
+    let root = ast.root;
+    cast_inserter.fold_node(ast, root);
+
+    (cast_inserter.types, diagnostics)
+}
+
+
+struct CastInserter {
+    types: TypeTable,
+    casts: HashMap<NodeId, Vec<(CastKind, CType)>>,
+}
+
+
+impl Folder for CastInserter {
+    fn fold_node(&mut self, ast: &mut Ast, id: NodeId) -> NodeId {
+        let id = walk_fold_node(self, ast, id);
+
+        self.casts.remove(&id).into_iter().flatten().fold(id, |operand, (kind, ctype)| {
+            let cast = ast.push(NodeKind::ImplicitCast { kind, operand }, ast[operand].span);
+            self.types.expressions.insert(cast, ctype);
+            self.types.categories.insert(cast, ValueCategory::RValue);
+
+            cast
+        })
+    }
+}
+
+
+fn process_external(semantic_state: &mut SemanticState, item: NodeId) {
+    let ast = semantic_state.ast;
+
+    match ast.kind(item) {
+        NodeKind::FunctionDefinition { attributes, specifiers, declarator, body } => {
+            define_specifiers(semantic_state, item, specifiers);
+
+            let Some(name) = declarator.name else {
+                return;
+            };
+
+            let ctype = resolve_declarator(semantic_state, item, declarator, resolve_specifiers(semantic_state, item, specifiers));
+            semantic_state.types.declarations.insert(item, vec![(name, ctype.clone())]);
+            let entity = semantic_state.entity(item, name, false);
+            declare_symbol(semantic_state, item, entity, ctype.clone(), (false, true), attributes.clone());
+
+            semantic_state.function = semantic_state.name(name).to_string();
+            semantic_state.function_noreturn = has_attribute(semantic_state, entity, "noreturn");
+            semantic_state.return_type = match ctype.unqualified() {
+                CType::Function { result, .. } => Some(result.strip_qualifiers()),
+                _ => None,
+            };
+            semantic_state.push_scope();
+
+            for parameter in declarator.function_parameters().into_iter().flatten() {
+                define_specifiers(semantic_state, item, &parameter.specifiers);
+
+                if let Some(parameter_name) = parameter.declarator.name {
+                    let ctype = resolve_declarator(semantic_state, item, &parameter.declarator, resolve_specifiers(semantic_state, item, &parameter.specifiers));
+                    let entity = semantic_state.entity(item, parameter_name, true);
+                    declare_symbol(semantic_state, item, entity, adjust_parameter(ctype), (false, false), parameter.attributes.clone());
+                }
+            }
+
+            let function_name = CType::Array(Box::new(CType::Char.qualified(Qualifiers { is_const: true, ..Qualifiers::default() })), ArraySize::Fixed(semantic_state.function.len() as u64 + 1));
+
+            for predefined in PREDEFINED.iter().filter_map(|predefined| ast.symbols.get(predefined)) {
+                let entity = semantic_state.entity(item, predefined, false);
+                declare_symbol(semantic_state, item, entity, function_name.clone(), (false, true), Vec::new());
+            }
+
+            process_statement(semantic_state, *body);
+            check_gotos(semantic_state);
+
+            if semantic_state.function_noreturn && can_fall_through(semantic_state, *body) {
+                semantic_state.report(Severity::Warning, item, "function declared 'noreturn' can return".to_string());
+            }
+
+            let returns_value = semantic_state.return_type.as_ref().is_some_and(|ctype| !matches!(ctype, CType::Void));
+
+            if returns_value && semantic_state.function != "main" && can_fall_through(semantic_state, *body) {
+                semantic_state.report(Severity::Warning, item, "control reaches end of non-void function".to_string());
+            }
+
+            semantic_state.pop_scope();
+            semantic_state.function.clear();
+            semantic_state.function_noreturn = false;
+            semantic_state.return_type = None;
+        }
+
+        _ => process_statement(semantic_state, item),
+    }
+}
+
+
+fn process_declaration(semantic_state: &mut SemanticState, declaration: NodeId) {
+    let NodeKind::Declaration { attributes, specifiers, declarators } = semantic_state.ast.kind(declaration) else {
+        return;
+    };
+
+    define_specifiers(semantic_state, declaration, specifiers);
+
+    let base = resolve_specifiers(semantic_state, declaration, specifiers);
+    let is_typedef = specifiers.storage.contains(&Keyword::Typedef);
+
+    for init_declarator in declarators {
+        if let Some(name) = init_declarator.declarator.name {
+            let mut declared_attributes = attributes.clone();
+            declared_attributes.extend(init_declarator.attributes.iter().cloned());
+
+            let mut ctype = resolve_declarator(semantic_state, declaration, &init_declarator.declarator, base.clone());
+
+            if let Some(initializer) = init_declarator.initializer {
+                ctype = complete_array(semantic_state, ctype, initializer);
+            }
+
+            semantic_state.types.declarations.entry(declaration).or_default().push((name, ctype.clone()));
+            check_declared_alignment(semantic_state, declaration, name, &ctype);
+
+            let entity = semantic_state.entity(declaration, name, false);
+            let file_scope = entity.is_none_or(|entity| semantic_state.table[entity].scope == ScopeKind::File);
+
+            if !is_typedef && init_declarator.initializer.is_none() && !file_scope
+                && !specifiers.storage.contains(&Keyword::Extern) && is_incomplete(semantic_state, &ctype) {
+                let message = match ctype.unqualified() {
+                    CType::Void => format!("variable or field '{}' declared void", semantic_state.name(name)),
+                    _ => format!("storage size of '{}' isn't known", semantic_state.name(name)),
+                };
+
+                semantic_state.report(Severity::Error, declaration, message);
+            }
+
+            let is_static = file_scope || specifiers.storage.iter().any(|keyword| matches!(keyword, Keyword::Static | Keyword::Extern));
+            declare_symbol(semantic_state, declaration, entity, ctype.clone(), (is_typedef, is_static), declared_attributes);
+            check_array_sizes(semantic_state, &init_declarator.declarator, name, file_scope);
+
+            if !file_scope && is_variably_modified(&ctype) {
+                let depth = semantic_state.depth;
+                semantic_state.variably_modified.push((depth, declaration));
+            }
+
+            if let Some(initializer) = init_declarator.initializer {
+                process_expression(semantic_state, initializer);
+                check_initializer(semantic_state, &ctype, initializer, &format!("initialization of '{}'", ctype.strip_qualifiers()));
+
+                if is_static && !is_typedef {
+                    check_static_initializer(semantic_state, initializer);
+                }
+            }
+        } else if let Some(initializer) = init_declarator.initializer {
+            process_expression(semantic_state, initializer);
+        }
+    }
+}
+
+
+fn declare_symbol(semantic_state: &mut SemanticState, node: NodeId, entity: Option<EntityId>, ctype: CType, (is_typedef, is_static): (bool, bool), attributes: Vec<Attribute>) {
+    if let Some(entity) = entity {
+        semantic_state.declare(entity, node, Binding { ctype, is_typedef, is_static, constant: None, attributes });
+    }
+}
+
+
+fn define_specifiers(semantic_state: &mut SemanticState, node: NodeId, specifiers: &DeclSpecifiers) {
+    let ast = semantic_state.ast;
+
+    for argument in &specifiers.alignment {
+        let align = match argument {
+            TypeofArgument::Type(type_name) => {
+                define_specifiers(semantic_state, node, &type_name.specifiers);
+                let ctype = resolve_type_name(semantic_state, node, type_name);
+                semantic_state.types.layout(&ctype, semantic_state.target).map(|layout| layout.align)
+            }
+
+            TypeofArgument::Expression(expression) => {
+                process_expression(semantic_state, *expression);
+                check_alignment(semantic_state, *expression)
+            }
+        };
+
+        if let Some(align) = align {
+            let current = semantic_state.types.alignment(node);
+            semantic_state.types.alignments.insert(node, current.max(align));
+        }
+    }
+
+    for specifier in &specifiers.specifiers {
+        match specifier {
+            TypeSpecifier::TypedefName(name) => {
+                let entity = semantic_state.table.typedef_names.get(&(node, *name)).copied();
+                check_deprecated(semantic_state, node, entity);
+            }
+
+            TypeSpecifier::Record { tag, members: Some(members), .. } => {
+                let mut fields = Vec::new();
+
+                for &member in members {
+                    let NodeKind::Declaration { specifiers, declarators, .. } = ast.kind(member) else {
+                        continue;
+                    };
+
+                    define_specifiers(semantic_state, member, specifiers);
+                    let base = resolve_specifiers(semantic_state, member, specifiers);
+
+                    if declarators.is_empty() {
+                        fields.push(Member { name: None, ctype: base, bit_width: None, align: None });
+                        continue;
+                    }
+
+                    for init_declarator in declarators {
+                        let ctype = resolve_declarator(semantic_state, member, &init_declarator.declarator, base.clone());
+
+                        if let Some(name) = init_declarator.declarator.name {
+                            semantic_state.types.declarations.entry(member).or_default().push((name, ctype.clone()));
+                        }
+
+                        let bit_width = init_declarator.bit_width.and_then(|bit_width| {
+                            process_expression(semantic_state, bit_width);
+                            check_bit_width(semantic_state, bit_width, init_declarator.declarator.name, &ctype)
+                        });
+
+                        let align = semantic_state.types.alignments.get(&member).copied();
+                        fields.push(Member { name: init_declarator.declarator.name, ctype, bit_width, align });
+                    }
+                }
+
+                let key = tag_key(semantic_state, node, *tag, members.first().map(|member| member.0));
+                semantic_state.types.records.insert(key, fields);
+            }
+
+            TypeSpecifier::Enum { enumerators: Some(enumerators), .. } => {
+                let mut next = 0;
+
+                for enumerator in enumerators {
+                    if let Some(value) = enumerator.value {
+                        process_expression(semantic_state, value);
+
+                        match evaluate_constant(semantic_state, value) {
+                            Ok(ConstantValue::Integer(value, ctype)) if !ctype.is_pointer() => next = value,
+                            _ => {
+                                let message = format!("enumerator value for '{}' is not an integer constant", semantic_state.name(enumerator.name));
+                                semantic_state.report(Severity::Error, value, message);
+                            }
+                        }
+                    }
+
+                    let binding = Binding { ctype: CType::Int, is_typedef: false, is_static: true, constant: Some(next), attributes: Vec::new() };
+                    if let Some(entity) = semantic_state.entity(node, enumerator.name, false) {
+                        semantic_state.declare(entity, node, binding);
+                    }
+                    next += 1;
+                }
+            }
+
+            TypeSpecifier::Typeof { argument, .. } => match argument {
+                TypeofArgument::Type(type_name) => define_specifiers(semantic_state, node, &type_name.specifiers),
+                TypeofArgument::Expression(expression) => {
+                    process_expression(semantic_state, *expression);
+
+                    if type_of(semantic_state, *expression).is_none() {
+                        semantic_state.report(Severity::Error, *expression, "cannot determine type of typeof operand".to_string());
+                    }
+                }
+            },
+
+            _ => {}
+        }
+    }
+}
+
+
+/* * * * * * * * */
+/*  - TYPES -  */
+/* * * * * * * * */
+fn resolve_specifiers(semantic_state: &SemanticState, node: NodeId, specifiers: &DeclSpecifiers) -> CType {
+    let qualifiers = Qualifiers::from_keywords(&specifiers.qualifiers);
+    let mut keywords = Vec::new();
+
+    for specifier in &specifiers.specifiers {
+        let ctype = match specifier {
+            TypeSpecifier::Basic(keyword) => {
+                keywords.push(*keyword);
+                continue;
+            }
+
+            TypeSpecifier::TypedefName(name) => match semantic_state.lookup_typedef(node, *name) {
+                Some(binding) if binding.is_typedef => binding.ctype.clone(),
+                _ => CType::Int,
+            },
+
+            TypeSpecifier::Record { kind, tag, members } => CType::Record {
+                kind: *kind,
+                tag: tag_key(semantic_state, node, *tag, members.as_ref().and_then(|members| members.first()).map(|member| member.0)),
+            },
+
+            TypeSpecifier::Enum { tag, enumerators } => {
+                let first = enumerators.as_ref().and_then(|enumerators| enumerators.first());
+                CType::Enum(tag_key(semantic_state, node, *tag, first.and_then(|enumerator| semantic_state.entity(node, enumerator.name, false)).map(|entity| entity.0)))
+            }
+
+            TypeSpecifier::Typeof { unqualified, argument } => {
+                let ctype = match argument {
+                    TypeofArgument::Expression(expression) => type_of(semantic_state, *expression).unwrap_or(CType::Int),
+                    TypeofArgument::Type(type_name) => resolve_type_name(semantic_state, node, type_name),
+                };
+
+                if *unqualified { ctype.strip_qualifiers() } else { ctype }
+            }
+        };
+
+        return ctype.qualified(qualifiers);
+    }
+
+    let count = |keyword: Keyword| keywords.iter().filter(|&&k| k == keyword).count();
+    let is_unsigned = count(Keyword::Unsigned) > 0;
+
+    let ctype = if count(Keyword::Void) > 0 {
+        CType::Void
+    } else if count(Keyword::Bool) > 0 {
+        CType::Bool
+    } else if count(Keyword::Char) > 0 {
+        if is_unsigned { CType::UnsignedChar } else if count(Keyword::Signed) > 0 { CType::SignedChar } else { CType::Char }
+    } else if count(Keyword::Float) > 0 {
+        CType::Float
+    } else if count(Keyword::Double) > 0 {
+        if count(Keyword::Long) > 0 { CType::LongDouble } else { CType::Double }
+    } else if count(Keyword::Short) > 0 {
+        if is_unsigned { CType::UnsignedShort } else { CType::Short }
+    } else if count(Keyword::Long) > 1 {
+        if is_unsigned { CType::UnsignedLongLong } else { CType::LongLong }
+    } else if count(Keyword::Long) > 0 {
+        if is_unsigned { CType::UnsignedLong } else { CType::Long }
+    } else if is_unsigned {
+        CType::UnsignedInt
+    } else {
+        CType::Int
+    };
+
+    ctype.qualified(qualifiers)
+}
+
+
+fn resolve_declarator(semantic_state: &SemanticState, node: NodeId, declarator: &Declarator, base: CType) -> CType {
+    declarator.derived.iter().fold(base, |ctype, derived| match derived {
+        DerivedDeclarator::Pointer(qualifiers) => CType::Pointer(Box::new(ctype)).qualified(Qualifiers::from_keywords(qualifiers)),
+
+        DerivedDeclarator::Array(size) => {
+            let size = match size {
+                None => ArraySize::Incomplete,
+                Some(size) => match Evaluator::new(semantic_state).evaluate_integer(*size) {
+                    Ok(length) if length >= 0 => ArraySize::Fixed(length as u64),
+                    _ => ArraySize::Variable,
+                },
+            };
+
+            CType::Array(Box::new(ctype), size)
+        }
+
+        DerivedDeclarator::Function { parameters, variadic } => {
+            let mut parameter_types: Vec<CType> = parameters.iter().map(|parameter| {
+                let base = resolve_specifiers(semantic_state, node, &parameter.specifiers);
+                adjust_parameter(resolve_declarator(semantic_state, node, &parameter.declarator, base))
+            }).collect();
+
+            if parameter_types == [CType::Void] && parameters[0].declarator.name.is_none() {
+                parameter_types.clear();
+            }
+
+            CType::Function {
+                result: Box::new(ctype),
+                prototyped: !parameters.is_empty() || *variadic,
+                parameters: parameter_types,
+                variadic: *variadic,
+            }
+        }
+    })
+}
+
+
+fn resolve_type_name(semantic_state: &SemanticState, node: NodeId, type_name: &TypeName) -> CType {
+    resolve_declarator(semantic_state, node, &type_name.declarator, resolve_specifiers(semantic_state, node, &type_name.specifiers))
+}
+
+
+fn adjust_parameter(ctype: CType) -> CType {
+    match ctype.unqualified() {
+        CType::Array(..) | CType::Function { .. } => ctype.decay(),
+        _ => ctype,
+    }
+}
+
+
+fn is_incomplete(semantic_state: &SemanticState, ctype: &CType) -> bool {
+    match ctype.unqualified() {
+        CType::Void | CType::Array(_, ArraySize::Incomplete) => true,
+        CType::Array(element, _) => is_incomplete(semantic_state, element),
+        CType::Record { tag, .. } => semantic_state.lookup_tag(tag).is_none(),
+        _ => false,
+    }
+}
+
+
+fn complete_array(semantic_state: &mut SemanticState, ctype: CType, initializer: NodeId) -> CType {
+    let CType::Array(element, ArraySize::Incomplete) = ctype.unqualified() else {
+        return ctype;
+    };
+
+    let length = match (string_initializer(semantic_state, &ctype, initializer), semantic_state.ast.kind(initializer)) {
+        (Some(value), _) | (None, NodeKind::StringLiteral(value)) => semantic_state.name(*value).len() as u64 + 1,
+        (None, NodeKind::InitializerList(items)) => walk_initializer_list(semantic_state, &ctype, items, None),
+        _ => return ctype,
+    };
+
+    CType::Array(element.clone(), ArraySize::Fixed(length)).qualified(ctype.qualifiers())
+}
+
+
+fn tag_key(semantic_state: &SemanticState, node: NodeId, tag: Option<Symbol>, anchor: Option<u32>) -> String {
+    let table = semantic_state.table;
+
+    match tag {
+        Some(tag) => match table.tags.get(&(node, tag)).map(|&entity| table.first_declaration(entity)) {
+            Some(entity) if table[entity].scope != ScopeKind::File => format!("{}#{}", semantic_state.name(tag), entity.0),
+            _ => semantic_state.name(tag).to_string(),
+        },
+
+        None => match anchor {
+            Some(anchor) => format!("<{}.{}>", node.0, anchor),
+            None => format!("<{}>", node.0),
+        },
+    }
+}
+
+
+fn type_of(semantic_state: &SemanticState, expression: NodeId) -> Option<CType> {
+    if semantic_state.erroneous.contains(&expression) {
+        return None;
+    }
+
+    if let Some(ctype) = semantic_state.types.expressions.get(&expression) {
+        return Some(ctype.clone());
+    }
+
+    match semantic_state.ast.kind(expression) {
+        NodeKind::Identifier(_) => semantic_state.lookup(expression).filter(|binding| !binding.is_typedef).map(|binding| binding.ctype.clone()),
+        NodeKind::Constant(constant) => Some(constant.ctype()),
+        NodeKind::StringLiteral(value) => Some(CType::Array(Box::new(CType::Char), ArraySize::Fixed(semantic_state.name(*value).len() as u64 + 1))),
+        NodeKind::SizeofType(_) | NodeKind::AlignofType(_) => Some(CType::UnsignedLong),
+        NodeKind::Cast { type_name, .. } => Some(resolve_type_name(semantic_state, expression, type_name).strip_qualifiers()),
+        NodeKind::CompoundLiteral { type_name, .. } => Some(resolve_type_name(semantic_state, expression, type_name)),
+        NodeKind::Assignment { left, .. } => Some(type_of(semantic_state, *left)?.strip_qualifiers()),
+        NodeKind::PostfixOperation { operand, .. } => Some(type_of(semantic_state, *operand)?.strip_qualifiers()),
+
+        NodeKind::BinaryOperation { left, operator, right } => {
+            if *operator == BinaryOperator::Comma {
+                return type_of(semantic_state, *right);
+            }
+
+            if operator.is_comparison() || matches!(operator, BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr) {
+                return Some(CType::Int);
+            }
+
+            let left = type_of(semantic_state, *left)?.decay();
+            let right = type_of(semantic_state, *right)?.decay();
+
+            match operator {
+                BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => Some(left.promote()),
+                BinaryOperator::Subtract if left.is_pointer() && right.is_pointer() => Some(CType::Long),
+                BinaryOperator::Add | BinaryOperator::Subtract if left.is_pointer() => Some(left),
+                BinaryOperator::Add if right.is_pointer() => Some(right),
+                _ if left.is_arithmetic() && right.is_arithmetic() => Some(CType::usual_arithmetic(&left, &right)),
+                _ => None,
+            }
+        }
+
+        NodeKind::UnaryOperation { operator, operand } => match operator {
+            UnaryOperator::Sizeof => Some(CType::UnsignedLong),
+            UnaryOperator::Not => Some(CType::Int),
+            UnaryOperator::Address => Some(CType::Pointer(Box::new(type_of(semantic_state, *operand)?))),
+            UnaryOperator::Dereference => type_of(semantic_state, *operand)?.decay().pointee().cloned(),
+            UnaryOperator::PreIncrement | UnaryOperator::PreDecrement => Some(type_of(semantic_state, *operand)?.strip_qualifiers()),
+            _ => Some(type_of(semantic_state, *operand)?.promote()),
+        },
+
+        NodeKind::Conditional { then_value, else_value, .. } => {
+            let then_type = type_of(semantic_state, *then_value)?.decay();
+            let else_type = type_of(semantic_state, *else_value)?.decay();
+
+            if then_type.is_arithmetic() && else_type.is_arithmetic() {
+                Some(CType::usual_arithmetic(&then_type, &else_type))
+            } else {
+                Some(then_type)
+            }
+        }
+
+        NodeKind::Call { function, .. } => match type_of(semantic_state, *function)?.decay().pointee()?.unqualified() {
+            CType::Function { result, .. } => Some(result.strip_qualifiers()),
+            _ => None,
+        },
+
+        NodeKind::Index { array, index } => {
+            let array = type_of(semantic_state, *array)?.decay();
+
+            match array.pointee() {
+                Some(element) => Some(element.clone()),
+                None => type_of(semantic_state, *index)?.decay().pointee().cloned(),
+            }
+        }
+
+        NodeKind::Member { object, member, arrow } => {
+            let object = type_of(semantic_state, *object)?;
+            let record = if *arrow { object.decay().pointee()?.clone() } else { object };
+
+            let CType::Record { tag, .. } = record.unqualified() else {
+                return None;
+            };
+
+            Some(semantic_state.find_member(tag, *member)?.qualified(record.qualifiers()))
+        }
+
+        NodeKind::GenericSelection { controlling, associations } => {
+            let selected = select_generic(semantic_state, expression, *controlling, associations).ok()?;
+            type_of(semantic_state, selected.value)
+        }
+
+        _ => None,
+    }
+}
+
+
+fn select_generic<'a>(semantic_state: &SemanticState, generic: NodeId, controlling: NodeId, associations: &'a [GenericAssociation]) -> Result<&'a GenericAssociation, String> {
+    let Some(controlling_type) = type_of(semantic_state, controlling).map(|ctype| ctype.decay()) else {
+        return Err("cannot determine type of _Generic controlling expression".to_string());
+    };
+
+    let mut default = None;
+    let mut selected = None;
+    let mut types: Vec<CType> = Vec::new();
+
+    for association in associations {
+        let Some(type_name) = &association.type_name else {
+            if default.replace(association).is_some() {
+                return Err("duplicate 'default' association in _Generic".to_string());
+            }
+
+            continue;
+        };
+
+        let ctype = resolve_type_name(semantic_state, generic, type_name);
+
+        if let Some(previous) = types.iter().find(|previous| previous.is_compatible(&ctype)) {
+            return Err(format!("_Generic specifies two compatible types '{}' and '{}'", previous, ctype));
+        }
+
+        if ctype.is_compatible(&controlling_type) {
+            selected = Some(association);
+        }
+
+        types.push(ctype);
+    }
+
+    selected.or(default).ok_or_else(|| format!("_Generic selector of type '{}' is not compatible with any association", controlling_type))
+}
+
+
+/* * * * * * * * * */
+/*  - STATEMENTS - */
+/* * * * * * * * * */
+fn process_statement(semantic_state: &mut SemanticState, statement: NodeId) {
+    let ast = semantic_state.ast;
+
+    match ast.kind(statement) {
+        NodeKind::Declaration { .. } => process_declaration(semantic_state, statement),
+        NodeKind::StaticAssert { condition, message } => {
+            process_expression(semantic_state, *condition);
+
+            match evaluate_constant(semantic_state, *condition) {
+                Ok(ConstantValue::Integer(0, _)) => {
+                    let message = match message {
+                        Some(message) => format!("static assertion failed: \"{}\"", semantic_state.name(*message)),
+                        None => "static assertion failed".to_string(),
+                    };
+
+                    semantic_state.report(Severity::Error, statement, message);
+                }
+
+                Ok(ConstantValue::Integer(..)) => {}
+                _ => semantic_state.report(Severity::Error, statement, "expression in static assertion is not an integer constant expression".to_string()),
+            }
+        }
+
+        NodeKind::Compound(items) => {
+            semantic_state.push_scope();
+
+            for &item in items {
+                process_statement(semantic_state, item);
+            }
+
+            semantic_state.pop_scope();
+        }
+
+        NodeKind::ExpressionStatement(Some(expression)) => {
+            check_discarded(semantic_state, *expression);
+            process_expression(semantic_state, *expression);
+        }
+
+        NodeKind::If { condition, then_branch, else_branch } => {
+            process_expression(semantic_state, *condition);
+            check_scalar(semantic_state, *condition);
+            process_statement(semantic_state, *then_branch);
+
+            if let Some(else_branch) = else_branch {
+                process_statement(semantic_state, *else_branch);
+            }
+        }
+
+        NodeKind::While { condition, body } | NodeKind::DoWhile { body, condition } => {
+            process_expression(semantic_state, *condition);
+            check_scalar(semantic_state, *condition);
+
+            semantic_state.loop_depth += 1;
+            process_statement(semantic_state, *body);
+            semantic_state.loop_depth -= 1;
+        }
+
+        NodeKind::For { init, condition, step, body } => {
+            semantic_state.push_scope();
+
+            if let Some(init) = init {
+                process_statement(semantic_state, *init);
+            }
+
+            for &expression in [condition, step].into_iter().flatten() {
+                process_expression(semantic_state, expression);
+            }
+
+            if let Some(condition) = condition {
+                check_scalar(semantic_state, *condition);
+            }
+
+            semantic_state.loop_depth += 1;
+            process_statement(semantic_state, *body);
+            semantic_state.loop_depth -= 1;
+            semantic_state.pop_scope();
+        }
+
+        NodeKind::Switch { condition, body } => {
+            process_expression(semantic_state, *condition);
+
+            let mut ctype = CType::Int;
+
+            if let Some(condition_type) = convert_value(semantic_state, *condition) {
+                if condition_type.is_integer() {
+                    ctype = condition_type.promote();
+                    implicit_cast(semantic_state, *condition, &condition_type, &ctype);
+                } else {
+                    semantic_state.report(Severity::Error, *condition, "switch quantity not an integer".to_string());
+                }
+            }
+
+            let variably_modified = semantic_state.variably_modified.len();
+            semantic_state.switches.push(SwitchLabels { ctype, cases: HashSet::new(), has_default: false, variably_modified });
+            check_switch_body(semantic_state, *body);
+            process_statement(semantic_state, *body);
+            semantic_state.switches.pop();
+        }
+
+        NodeKind::Case { value, body } => {
+            process_expression(semantic_state, *value);
+            convert_value(semantic_state, *value);
+            check_case(semantic_state, statement, Some(*value));
+            process_statement(semantic_state, *body);
+        }
+
+        NodeKind::Default(body) => {
+            check_case(semantic_state, statement, None);
+            process_statement(semantic_state, *body);
+        }
+
+        NodeKind::Label { name, body } => {
+            let in_scope = semantic_state.variably_modified.iter().map(|&(_, declaration)| declaration).collect();
+            semantic_state.labels.insert(*name, in_scope);
+            process_statement(semantic_state, *body);
+        }
+
+        NodeKind::Goto(name) => {
+            let in_scope = semantic_state.variably_modified.iter().map(|&(_, declaration)| declaration).collect();
+            semantic_state.gotos.push((statement, *name, in_scope));
+        }
+
+        NodeKind::Break if semantic_state.loop_depth == 0 && semantic_state.switches.is_empty() => {
+            semantic_state.report(Severity::Error, statement, "break statement not within loop or switch".to_string());
+        }
+
+        NodeKind::Continue if semantic_state.loop_depth == 0 => {
+            semantic_state.report(Severity::Error, statement, "continue statement not within a loop".to_string());
+        }
+
+        NodeKind::Return(value) => {
+            if let Some(value) = value {
+                process_expression(semantic_state, *value);
+            }
+
+            check_return(semantic_state, statement, *value);
+
+            if semantic_state.function_noreturn {
+                semantic_state.report(Severity::Warning, statement, "function declared 'noreturn' should not return".to_string());
+            }
+        }
+
+        NodeKind::Attributed { attributes, statement: inner } => {
+            if semantic_state.switches.is_empty() && attributes.iter().any(|attribute| attribute.is("fallthrough")) {
+                semantic_state.report(Severity::Error, statement, "fallthrough annotation outside switch statement".to_string());
+            }
+
+            process_statement(semantic_state, *inner);
+        }
+
+        _ => {}
+    }
+}
+
+
+fn check_discarded(semantic_state: &mut SemanticState, expression: NodeId) {
+    let ast = semantic_state.ast;
+
+    let NodeKind::Call { function, .. } = ast.kind(expression) else {
+        return;
+    };
+
+    let NodeKind::Identifier(name) = ast.kind(*function) else {
+        return;
+    };
+
+    let Some(attribute) = semantic_state.lookup(*function).and_then(|binding| {
+        binding.attributes.iter().find(|attribute| attribute.is("nodiscard") || attribute.is("warn_unused_result"))
+    }) else {
+        return;
+    };
+
+    let name = semantic_state.name(*name);
+
+    let message = match attribute.message() {
+        Some(reason) => format!("ignoring return value of '{}', declared with attribute nodiscard: {}", name, reason),
+        None => format!("ignoring return value of '{}', declared with attribute nodiscard", name),
+    };
+
+    semantic_state.report(Severity::Warning, expression, message);
+}
+
+
+fn check_switch_body(semantic_state: &mut SemanticState, body: NodeId) {
+    let ast = semantic_state.ast;
+
+    let NodeKind::Compound(items) = ast.kind(body) else {
+        return;
+    };
+
+    let mut labelled = false;
+
+    for (i, &item) in items.iter().enumerate() {
+        let next_is_label = items.get(i + 1).is_some_and(|&next| is_switch_label(ast, next));
+        let last = last_statement(ast, item);
+        labelled |= is_switch_label(ast, item);
+
+        if is_fallthrough(ast, last) {
+            if !next_is_label {
+                semantic_state.report(Severity::Error, last, "fallthrough annotation does not directly precede switch label".to_string());
+            }
+        } else if labelled && next_is_label && !is_empty(ast, last) && can_fall_through(semantic_state, item) {
+            semantic_state.report(Severity::Warning, last, "unannotated fall-through between switch labels".to_string());
+        }
+    }
+}
+
+
+fn is_switch_label(ast: &Ast, statement: NodeId) -> bool {
+    match ast.kind(statement) {
+        NodeKind::Case { .. } | NodeKind::Default(_) => true,
+        NodeKind::Attributed { statement, .. } => is_switch_label(ast, *statement),
+        _ => false,
+    }
+}
+
+
+fn is_fallthrough(ast: &Ast, statement: NodeId) -> bool {
+    matches!(ast.kind(statement), NodeKind::Attributed { attributes, statement }
+        if matches!(ast.kind(*statement), NodeKind::ExpressionStatement(None)) && attributes.iter().any(|attribute| attribute.is("fallthrough")))
+}
+
+
+fn is_empty(ast: &Ast, statement: NodeId) -> bool {
+    match ast.kind(statement) {
+        NodeKind::ExpressionStatement(None) => true,
+        NodeKind::Compound(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+
+fn last_statement(ast: &Ast, statement: NodeId) -> NodeId {
+    match ast.kind(statement) {
+        NodeKind::Case { body, .. } | NodeKind::Default(body) | NodeKind::Label { body, .. } => last_statement(ast, *body),
+        NodeKind::Compound(items) => items.last().map_or(statement, |&item| last_statement(ast, item)),
+        _ => statement,
+    }
+}
+
+
+fn can_fall_through(semantic_state: &SemanticState, statement: NodeId) -> bool {
+    let ast = semantic_state.ast;
+
+    match ast.kind(statement) {
+        NodeKind::Break | NodeKind::Continue | NodeKind::Goto(_) | NodeKind::Return(_) => false,
+        NodeKind::Case { body, .. } | NodeKind::Default(body) | NodeKind::Label { body, .. } => can_fall_through(semantic_state, *body),
+        NodeKind::Attributed { statement, .. } => can_fall_through(semantic_state, *statement),
+
+        NodeKind::Compound(items) => items.iter().fold(true, |reachable, &item| {
+            (reachable || is_labeled(ast, item)) && can_fall_through(semantic_state, item)
+        }),
+
+        NodeKind::If { then_branch, else_branch: Some(else_branch), .. } => {
+            can_fall_through(semantic_state, *then_branch) || can_fall_through(semantic_state, *else_branch)
+        }
+
+        NodeKind::While { condition, body } => !is_always_true(semantic_state, Some(*condition)) || contains_jump(ast, *body, &NodeKind::Break),
+        NodeKind::For { condition, body, .. } => !is_always_true(semantic_state, *condition) || contains_jump(ast, *body, &NodeKind::Break),
+
+        NodeKind::DoWhile { body, condition } => {
+            contains_jump(ast, *body, &NodeKind::Break) || !is_always_true(semantic_state, Some(*condition))
+                && (can_fall_through(semantic_state, *body) || contains_jump(ast, *body, &NodeKind::Continue))
+        }
+
+        NodeKind::Switch { body, .. } => {
+            !contains_default(ast, *body) || can_fall_through(semantic_state, *body) || contains_jump(ast, *body, &NodeKind::Break)
+        }
+
+        NodeKind::ExpressionStatement(Some(expression)) => match ast.kind(*expression) {
+            NodeKind::Call { function, .. } => !matches!(ast.kind(*function), NodeKind::Identifier(_) if has_attribute(semantic_state, semantic_state.table.resolve(*function), "noreturn")),
+            _ => true,
+        },
+
+        _ => true,
+    }
+}
+
+
+fn is_labeled(ast: &Ast, statement: NodeId) -> bool {
+    match ast.kind(statement) {
+        NodeKind::Case { .. } | NodeKind::Default(_) | NodeKind::Label { .. } => true,
+        NodeKind::Attributed { statement, .. } => is_labeled(ast, *statement),
+        _ => false,
+    }
+}
+
+
+fn is_always_true(semantic_state: &SemanticState, condition: Option<NodeId>) -> bool {
+    condition.is_none_or(|condition| Evaluator::new(semantic_state).evaluate_integer(condition).is_ok_and(|value| value != 0))
+}
+
+
+fn contains_jump(ast: &Ast, statement: NodeId, jump: &NodeKind) -> bool {
+    match ast.kind(statement) {
+        NodeKind::Break => matches!(jump, NodeKind::Break),
+        NodeKind::Continue => matches!(jump, NodeKind::Continue),
+        NodeKind::While { .. } | NodeKind::DoWhile { .. } | NodeKind::For { .. } => false,
+        NodeKind::Switch { body, .. } => matches!(jump, NodeKind::Continue) && contains_jump(ast, *body, jump),
+        NodeKind::Compound(items) => items.iter().any(|&item| contains_jump(ast, item, jump)),
+
+        NodeKind::If { then_branch, else_branch, .. } => {
+            contains_jump(ast, *then_branch, jump) || else_branch.is_some_and(|else_branch| contains_jump(ast, else_branch, jump))
+        }
+
+        NodeKind::Case { body, .. } | NodeKind::Default(body) | NodeKind::Label { body, .. }
+            | NodeKind::Attributed { statement: body, .. } => contains_jump(ast, *body, jump),
+
+        _ => false,
+    }
+}
+
+
+fn contains_default(ast: &Ast, statement: NodeId) -> bool {
+    match ast.kind(statement) {
+        NodeKind::Default(_) => true,
+        NodeKind::Switch { .. } => false,
+        _ => children(ast, statement).into_iter().any(|child| contains_default(ast, child)),
+    }
+}
+
+
+fn check_case(semantic_state: &mut SemanticState, label: NodeId, value: Option<NodeId>) {
+    let kind = if value.is_some() { "case label" } else { "'default' label" };
+
+    let Some(switch) = semantic_state.switches.last() else {
+        return semantic_state.report(Severity::Error, label, format!("{} not within a switch statement", kind));
+    };
+
+    let ctype = switch.ctype.clone();
+
+    if semantic_state.variably_modified.len() > switch.variably_modified {
+        semantic_state.report(Severity::Error, label, "switch jumps into scope of identifier with variably modified type".to_string());
+    }
+
+    let Some(value) = value else {
+        let has_default = std::mem::replace(&mut semantic_state.switches.last_mut().unwrap().has_default, true);
+
+        if has_default {
+            semantic_state.report(Severity::Error, label, "multiple default labels in one switch".to_string());
+        }
+
+        return;
+    };
+
+    let converted = evaluate_constant(semantic_state, value).and_then(|constant| Evaluator::new(&*semantic_state).convert(constant, &ctype));
+
+    match converted {
+        Ok(ConstantValue::Integer(value, ctype)) if !ctype.is_pointer() => {
+            if !semantic_state.switches.last_mut().unwrap().cases.insert(value) {
+                semantic_state.report(Severity::Error, label, format!("duplicate case value '{}'", value));
+            }
+        }
+
+        _ => semantic_state.report(Severity::Error, label, "case label does not reduce to an integer constant".to_string()),
+    }
+}
+
+
+fn check_gotos(semantic_state: &mut SemanticState) {
+    let gotos = std::mem::take(&mut semantic_state.gotos);
+    let labels = std::mem::take(&mut semantic_state.labels);
+
+    for (goto, name, in_scope) in gotos {
+        let Some(label_scope) = labels.get(&name) else {
+            continue;
+        };
+
+        if label_scope.iter().any(|declaration| !in_scope.contains(declaration)) {
+            let message = format!("jump into scope of identifier with variably modified type at label '{}'", semantic_state.name(name));
+            semantic_state.report(Severity::Error, goto, message);
+        }
+    }
+}
+
+
+fn is_variably_modified(ctype: &CType) -> bool {
+    match ctype.unqualified() {
+        CType::Array(_, ArraySize::Variable) => true,
+        CType::Array(inner, _) | CType::Pointer(inner) => is_variably_modified(inner),
+        _ => false,
+    }
+}
+
+
+/* * * * * * * * * * */
+/*  - EXPRESSIONS -  */
+/* * * * * * * * * * */
+fn process_expression(semantic_state: &mut SemanticState, expression: NodeId) {
+    let ast = semantic_state.ast;
+    semantic_state.visit_node(ast, expression);
+}
+
+
+impl<'a> Visitor<'a> for SemanticState<'a> {
+    fn visit_node(&mut self, ast: &'a Ast, id: NodeId) {
+        let previous_node = std::mem::replace(&mut self.node, id);
+        walk_node(self, ast, id);
+        self.node = previous_node;
+
+        if let Some(ctype) = type_of(self, id) {
+            self.types.expressions.insert(id, ctype);
+            self.types.categories.insert(id, category_of(self, id));
+        }
+
+        let errors = self.diagnostics.len();
+        check_expression(self, id);
+
+        if self.diagnostics[errors..].iter().any(|diagnostic| diagnostic.severity == Severity::Error) {
+            self.types.expressions.remove(&id);
+            self.types.categories.remove(&id);
+            self.erroneous.insert(id);
+        }
+    }
+
+
+    fn visit_identifier(&mut self, _ast: &'a Ast, id: NodeId, _name: Symbol) {
+        check_deprecated(self, id, self.table.resolve(id));
+
+        if let Some(value) = self.lookup(id).and_then(|binding| binding.constant) {
+            self.types.constants.insert(id, ConstantValue::Integer(value, CType::Int));
+        }
+    }
+
+
+    fn visit_sizeof_type(&mut self, ast: &'a Ast, id: NodeId, type_name: &'a TypeName) {
+        self.visit_type_name(ast, type_name);
+        check_sizeof(self, id, &resolve_type_name(self, id, type_name));
+        record_size(self, id);
+    }
+
+
+    fn visit_alignof_type(&mut self, ast: &'a Ast, id: NodeId, type_name: &'a TypeName) {
+        self.visit_type_name(ast, type_name);
+        let ctype = resolve_type_name(self, id, type_name);
+
+        if is_incomplete(self, &ctype) {
+            self.report(Severity::Error, id, format!("invalid application of 'alignof' to incomplete type '{}'", ctype));
+        }
+
+        record_size(self, id);
+    }
+
+
+    fn visit_unary_operation(&mut self, ast: &'a Ast, id: NodeId, operator: UnaryOperator, operand: NodeId) {
+        self.visit_node(ast, operand);
+
+        if operator != UnaryOperator::Sizeof {
+            return;
+        }
+
+        if let Some(ctype) = type_of(self, operand) {
+            check_sizeof(self, id, &ctype);
+        }
+
+        record_size(self, id);
+    }
+
+
+    fn visit_generic_selection(&mut self, ast: &'a Ast, id: NodeId, controlling: NodeId, associations: &'a [GenericAssociation]) {
+        walk_generic_selection(self, ast, controlling, associations);
+
+        match select_generic(self, id, controlling, associations) {
+            Ok(selected) => {
+                let index = associations.iter().position(|association| std::ptr::eq(association, selected)).unwrap_or_default();
+                self.types.selections.insert(id, index);
+            }
+
+            Err(message) => self.report(Severity::Error, id, message),
+        }
+    }
+
+
+    fn visit_decl_specifiers(&mut self, _ast: &'a Ast, specifiers: &'a DeclSpecifiers) {
+        define_specifiers(self, self.node, specifiers);
+    }
+}
+
+
+fn check_sizeof(semantic_state: &mut SemanticState, expression: NodeId, ctype: &CType) {
+    if !matches!(ctype.unqualified(), CType::Void) && is_incomplete(semantic_state, ctype) {
+        semantic_state.report(Severity::Error, expression, format!("invalid application of 'sizeof' to incomplete type '{}'", ctype));
+    }
+}
+
+
+fn record_size(semantic_state: &mut SemanticState, expression: NodeId) {
+    if let Ok(constant) = Evaluator::new(&*semantic_state).evaluate(expression) {
+        semantic_state.types.constants.insert(expression, constant);
+    }
+}
+
+
+/* * * * * * * * * * */
+/*  - CONSTANTS -  */
+/* * * * * * * * * * */
+impl ConstantContext for SemanticState<'_> {
+    fn ast(&self) -> &Ast {
+        self.ast
+    }
+
+
+    fn enumerator(&self, identifier: NodeId) -> Option<i128> {
+        self.lookup(identifier).and_then(|binding| binding.constant)
+    }
+
+
+    fn is_static(&self, identifier: NodeId) -> bool {
+        self.lookup(identifier).is_some_and(|binding| binding.is_static && !binding.is_typedef)
+    }
+
+
+    fn type_of(&self, expression: NodeId) -> Option<CType> {
+        type_of(self, expression)
+    }
+
+
+    fn type_name(&self, node: NodeId, type_name: &TypeName) -> CType {
+        resolve_type_name(self, node, type_name)
+    }
+
+
+    fn layout(&self, ctype: &CType) -> Option<TypeLayout> {
+        self.types.layout(ctype, self.target)
+    }
+
+
+    fn member_offset(&self, record: &CType, member: Symbol) -> Option<u64> {
+        match record.unqualified() {
+            CType::Record { kind, tag } => self.types.member_offset(*kind, tag, member, self.target),
+            _ => None,
+        }
+    }
+}
+
+
+fn evaluate_constant(semantic_state: &mut SemanticState, expression: NodeId) -> Result<ConstantValue, EvaluationError> {
+    let mut evaluator = Evaluator::new(&*semantic_state);
+    let value = evaluator.evaluate(expression);
+    let overflow = evaluator.overflow;
+
+    if overflow {
+        semantic_state.report(Severity::Warning, expression, "integer overflow in constant expression".to_string());
+    }
+
+    match &value {
+        Ok(constant) => {
+            semantic_state.types.constants.insert(expression, constant.clone());
+        }
+
+        Err(EvaluationError::DivisionByZero) => semantic_state.report(Severity::Warning, expression, "division by zero".to_string()),
+        Err(EvaluationError::NotConstant) => {}
+    }
+
+    value
+}
+
+
+fn check_bit_width(semantic_state: &mut SemanticState, bit_width: NodeId, name: Option<Symbol>, ctype: &CType) -> Option<u64> {
+    let name = name.map_or("<anonymous>", |name| semantic_state.name(name));
+
+    let message = match evaluate_constant(semantic_state, bit_width) {
+        Ok(ConstantValue::Integer(width, _)) if width < 0 => format!("negative width in bit-field '{}'", name),
+        Ok(ConstantValue::Integer(0, _)) if name != "<anonymous>" => format!("zero width for bit-field '{}'", name),
+
+        Ok(ConstantValue::Integer(width, _)) => match semantic_state.types.layout(ctype, semantic_state.target) {
+            Some(layout) if width as u64 > layout.size * 8 => format!("width of '{}' exceeds its type", name),
+            _ => return Some(width as u64),
+        },
+
+        _ => format!("bit-field '{}' width not an integer constant", name),
+    };
+
+    semantic_state.report(Severity::Error, bit_width, message);
+    None
+}
+
+
+fn check_alignment(semantic_state: &mut SemanticState, alignment: NodeId) -> Option<u64> {
+    let message = match evaluate_constant(semantic_state, alignment) {
+        Ok(ConstantValue::Integer(0, _)) => return None,
+        Ok(ConstantValue::Integer(value, _)) if u64::try_from(value).is_ok_and(u64::is_power_of_two) => return Some(value as u64),
+        Ok(ConstantValue::Integer(value, _)) => format!("requested alignment '{}' is not a positive power of 2", value),
+        _ => "requested alignment is not an integer constant".to_string(),
+    };
+
+    semantic_state.report(Severity::Error, alignment, message);
+    None
+}
+
+
+fn check_declared_alignment(semantic_state: &mut SemanticState, declaration: NodeId, name: Symbol, ctype: &CType) {
+    let Some(&align) = semantic_state.types.alignments.get(&declaration) else {
+        return;
+    };
+
+    if semantic_state.types.layout(ctype, semantic_state.target).is_some_and(|layout| layout.align > align) {
+        let message = format!("'alignas' specifiers cannot reduce alignment of '{}'", semantic_state.name(name));
+        semantic_state.report(Severity::Error, declaration, message);
+    }
+}
+
+
+fn check_array_sizes(semantic_state: &mut SemanticState, declarator: &Declarator, name: Symbol, file_scope: bool) {
+    for derived in &declarator.derived {
+        let DerivedDeclarator::Array(Some(size)) = derived else {
+            continue;
+        };
+
+        let message = match evaluate_constant(semantic_state, *size) {
+            Ok(ConstantValue::Integer(length, _)) if length < 0 => format!("size of array '{}' is negative", semantic_state.name(name)),
+            Ok(ConstantValue::Integer(..)) => continue,
+            _ if file_scope => format!("variably modified '{}' at file scope", semantic_state.name(name)),
+
+            _ => {
+                process_expression(semantic_state, *size);
+                convert_value(semantic_state, *size);
+                continue;
+            }
+        };
+
+        semantic_state.report(Severity::Error, *size, message);
+    }
+}
+
+
+fn check_static_initializer(semantic_state: &mut SemanticState, initializer: NodeId) {
+    let ast = semantic_state.ast;
+
+    if let NodeKind::InitializerList(items) = ast.kind(initializer) {
+        for item in items {
+            check_static_initializer(semantic_state, item.value);
+        }
+    } else if evaluate_constant(semantic_state, initializer).is_err() {
+        semantic_state.report(Severity::Error, initializer, "initializer element is not constant".to_string());
+    }
+}
+
+
+/* * * * * * * * * * * */
+/*  - CONVERSIONS -  */
+/* * * * * * * * * * * */
+fn category_of(semantic_state: &SemanticState, expression: NodeId) -> ValueCategory {
+    let ast = semantic_state.ast;
+
+    match ast.kind(expression) {
+        NodeKind::Identifier(_) => match semantic_state.lookup(expression) {
+            Some(binding) if binding.constant.is_some() => ValueCategory::RValue,
+            Some(binding) if matches!(binding.ctype.unqualified(), CType::Function { .. }) => ValueCategory::Function,
+            _ => ValueCategory::LValue,
+        },
+
+        NodeKind::UnaryOperation { operator: UnaryOperator::Dereference, .. } => match type_of(semantic_state, expression) {
+            Some(CType::Function { .. }) => ValueCategory::Function,
+            _ => ValueCategory::LValue,
+        },
+
+        NodeKind::Member { object, arrow: false, .. } => category_of(semantic_state, *object),
+
+        NodeKind::GenericSelection { controlling, associations } => match select_generic(semantic_state, expression, *controlling, associations) {
+            Ok(selected) => category_of(semantic_state, selected.value),
+            Err(_) => ValueCategory::RValue,
+        },
+
+        NodeKind::StringLiteral(_) | NodeKind::Index { .. } | NodeKind::Member { .. } | NodeKind::CompoundLiteral { .. } => ValueCategory::LValue,
+        _ => ValueCategory::RValue,
+    }
+}
+
+
+fn convert_value(semantic_state: &mut SemanticState, expression: NodeId) -> Option<CType> {
+    let ctype = type_of(semantic_state, expression)?;
+
+    let (kind, value) = match (category_of(semantic_state, expression), ctype.unqualified()) {
+        (ValueCategory::Function, _) => (CastKind::FunctionToPointer, ctype.decay()),
+        (_, CType::Array(..)) => (CastKind::ArrayToPointer, ctype.decay()),
+        (ValueCategory::LValue, _) => (CastKind::LValueToRValue, ctype.strip_qualifiers()),
+        (ValueCategory::RValue, _) => return Some(ctype),
+    };
+
+    semantic_state.casts.entry(expression).or_default().push((kind, value.clone()));
+    Some(value)
+}
+
+
+fn implicit_cast(semantic_state: &mut SemanticState, expression: NodeId, source: &CType, target: &CType) {
+    let kind = match (source.unqualified(), target.unqualified()) {
+        (source, target) if source == target => return,
+        (CType::Bool, _) if target.is_pointer() => CastKind::IntegralToPointer,
+        (_, CType::Bool) if source.is_arithmetic() || source.is_pointer() => CastKind::ToBoolean,
+        _ if source.is_integer() && target.is_integer() => CastKind::IntegralCast,
+        _ if source.is_integer() && target.is_floating() => CastKind::IntegralToFloating,
+        _ if source.is_floating() && target.is_integer() => CastKind::FloatingToIntegral,
+        _ if source.is_floating() && target.is_floating() => CastKind::FloatingCast,
+        _ if source.is_pointer() && target.is_pointer() => CastKind::PointerCast,
+        _ if source.is_pointer() && target.is_integer() => CastKind::PointerToIntegral,
+        _ if source.is_integer() && target.is_pointer() && is_null_pointer_constant(semantic_state, expression) => CastKind::NullToPointer,
+        _ if source.is_integer() && target.is_pointer() => CastKind::IntegralToPointer,
+        _ => return,
+    };
+
+    semantic_state.casts.entry(expression).or_default().push((kind, target.strip_qualifiers()));
+}
+
+
+fn is_null_pointer_constant(semantic_state: &SemanticState, expression: NodeId) -> bool {
+    match semantic_state.ast.kind(expression) {
+        NodeKind::Constant(Constant::Integer { value: 0, .. }) => true,
+
+        NodeKind::Cast { type_name, operand } => {
+            matches!(resolve_type_name(semantic_state, expression, type_name).pointee(), Some(CType::Void)) && is_null_pointer_constant(semantic_state, *operand)
+        }
+
+        _ => false,
+    }
+}
+
+
+fn is_scalar(ctype: &CType) -> bool {
+    ctype.is_arithmetic() || ctype.is_pointer()
+}
+
+
+fn check_scalar(semantic_state: &mut SemanticState, expression: NodeId) {
+    let Some(ctype) = convert_value(semantic_state, expression) else {
+        return;
+    };
+
+    match ctype.unqualified() {
+        CType::Void => semantic_state.report(Severity::Error, expression, "void value not ignored as it ought to be".to_string()),
+        CType::Record { kind: Keyword::Union, .. } => semantic_state.report(Severity::Error, expression, "used union type value where scalar is required".to_string()),
+        CType::Record { .. } => semantic_state.report(Severity::Error, expression, "used struct type value where scalar is required".to_string()),
+        _ => {}
+    }
+}
+
+
+fn check_assignable(semantic_state: &mut SemanticState, expression: NodeId, target: &CType, context: &str) {
+    let Some(source) = convert_value(semantic_state, expression) else {
+        return;
+    };
+
+    let target = target.strip_qualifiers();
+
+    let problem = match (target.unqualified(), source.unqualified()) {
+        _ if target.is_arithmetic() && source.is_arithmetic() => None,
+        (CType::Bool, _) if source.is_pointer() => None,
+        (CType::Record { .. }, CType::Record { .. }) if target.is_compatible(&source) => None,
+
+        (CType::Pointer(target_pointee), CType::Pointer(source_pointee)) => {
+            let is_void = matches!(target_pointee.unqualified(), CType::Void) || matches!(source_pointee.unqualified(), CType::Void);
+            let discarded = source_pointee.qualifiers().without(target_pointee.qualifiers());
+
+            if !is_void && !target_pointee.strip_qualifiers().is_compatible(&source_pointee.strip_qualifiers()) {
+                Some((Severity::Warning, format!("{} from incompatible pointer type '{}'", context, source)))
+            } else if discarded != Qualifiers::default() {
+                Some((Severity::Warning, format!("{} discards '{}' qualifier from pointer target type", context, discarded)))
+            } else {
+                None
+            }
+        }
+
+        (CType::Pointer(_), _) if source.is_integer() && !is_null_pointer_constant(semantic_state, expression) => {
+            Some((Severity::Warning, format!("{} makes pointer from integer without a cast", context)))
+        }
+
+        (CType::Pointer(_), _) if source.is_integer() => None,
+        _ if target.is_integer() && source.is_pointer() => Some((Severity::Warning, format!("{} makes integer from pointer without a cast", context))),
+        _ => Some((Severity::Error, format!("incompatible types in {} from type '{}'", context, source))),
+    };
+
+    match problem {
+        Some((Severity::Error, message)) => semantic_state.report(Severity::Error, expression, message),
+
+        Some((severity, message)) => {
+            semantic_state.report(severity, expression, message);
+            implicit_cast(semantic_state, expression, &source, &target);
+        }
+
+        None => implicit_cast(semantic_state, expression, &source, &target),
+    }
+}
+
+
+fn check_modifiable(semantic_state: &mut SemanticState, expression: NodeId, action: &str, operand: &str) -> bool {
+    let Some(ctype) = type_of(semantic_state, expression) else {
+        return false;
+    };
+
+    let message = if category_of(semantic_state, expression) != ValueCategory::LValue {
+        format!("lvalue required as {}", operand)
+    } else if matches!(ctype.unqualified(), CType::Array(..)) {
+        format!("{} to expression with array type", action)
+    } else if ctype.qualifiers().is_const {
+        match semantic_state.ast.kind(expression) {
+            NodeKind::Identifier(name) => format!("{} of read-only variable '{}'", action, semantic_state.name(*name)),
+            NodeKind::Member { member, .. } => format!("{} of read-only member '{}'", action, semantic_state.name(*member)),
+            _ => format!("{} of read-only location", action),
+        }
+    } else {
+        return true;
+    };
+
+    semantic_state.report(Severity::Error, expression, message);
+    false
+}
+
+
+fn check_initializer(semantic_state: &mut SemanticState, target: &CType, initializer: NodeId, context: &str) {
+    let ast = semantic_state.ast;
+
+    if let Some(value) = string_initializer(semantic_state, target, initializer) {
+        if let CType::Array(_, ArraySize::Fixed(length)) = target.unqualified()
+            && semantic_state.name(*value).len() as u64 > *length {
+            semantic_state.report(Severity::Warning, initializer, "initializer-string for array of 'char' is too long".to_string());
+        }
+
+        return;
+    }
+
+    match (ast.kind(initializer), target.unqualified()) {
+        (NodeKind::InitializerList(items), _) => check_initializer_list(semantic_state, target, items, context),
+        (NodeKind::StringLiteral(_), CType::Array(..)) => {}
+
+        (_, CType::Array(..)) => {
+            convert_value(semantic_state, initializer);
+            semantic_state.report(Severity::Error, initializer, "invalid initializer".to_string());
+        }
+
+        _ => check_assignable(semantic_state, initializer, target, context),
+    }
+}
+
+
+fn check_initializer_list(semantic_state: &mut SemanticState, target: &CType, items: &[Initializer], context: &str) {
+    walk_initializer_list(semantic_state, target, items, Some(context));
+}
+
+
+fn walk_initializer_list(semantic_state: &mut SemanticState, target: &CType, items: &[Initializer], context: Option<&str>) -> u64 {
+    let ast = semantic_state.ast;
+
+    if !matches!(target.unqualified(), CType::Array(..) | CType::Record { .. }) {
+        if let (Some(item), Some(context)) = (items.first(), context) {
+            check_initializer(semantic_state, target, item.value, context);
+
+            for item in &items[1..] {
+                discard_initializer(semantic_state, item.value);
+            }
+
+            if let Some(item) = items.get(1) {
+                semantic_state.report(Severity::Warning, item.value, "excess elements in scalar initializer".to_string());
+            }
+        }
+
+        return items.len().min(1) as u64;
+    }
+
+    let mut stack = vec![(target.clone(), 0)];
+    let mut length = 0;
+
+    'items: for item in items {
+        if !item.designators.is_empty() {
+            stack.truncate(1);
+
+            for (index, designator) in item.designators.iter().enumerate() {
+                let Some((current, _)) = stack.last().cloned() else {
+                    continue 'items;
+                };
+
+                let position = match designator_position(semantic_state, &current, designator, item.value) {
+                    Ok(position) => position,
+
+                    Err((node, message)) => {
+                        if context.is_some() {
+                            semantic_state.report(Severity::Error, node, message);
+                            discard_initializer(semantic_state, item.value);
+                        }
+
+                        continue 'items;
+                    }
+                };
+
+                if let Some(top) = stack.last_mut() {
+                    top.1 = position;
+                }
+
+                if index + 1 < item.designators.len() {
+                    let Some(subobject) = subobject(semantic_state, &current, position) else {
+                        continue 'items;
+                    };
+
+                    stack.push((subobject, 0));
+                }
+            }
+        }
+
+        let mut designated = !item.designators.is_empty();
+
+        loop {
+            while !designated && stack.len() > 1 && stack.last().is_some_and(|(current, position)| *position >= subobject_count(semantic_state, current)) {
+                stack.pop();
+
+                if let Some(top) = stack.last_mut() {
+                    top.1 += 1;
+                }
+            }
+
+            let Some((current, position)) = stack.last().cloned() else {
+                break 'items;
+            };
+
+            if !designated && position >= subobject_count(semantic_state, &current) {
+                if context.is_some() {
+                    let kind = match current.unqualified() {
+                        CType::Record { kind: Keyword::Union, .. } => "union",
+                        CType::Record { .. } => "struct",
+                        _ => "array",
+                    };
+
+                    semantic_state.report(Severity::Warning, item.value, format!("excess elements in {} initializer", kind));
+                    discard_initializer(semantic_state, item.value);
+                }
+
+                continue 'items;
+            }
+
+            let Some(subobject) = subobject(semantic_state, &current, position) else {
+                break 'items;
+            };
+
+            designated = false;
+
+            let is_list = matches!(ast.kind(item.value), NodeKind::InitializerList(_));
+            let is_string = matches!(ast.kind(item.value), NodeKind::StringLiteral(_));
+
+            let matches = match subobject.unqualified() {
+                CType::Array(..) => is_string && is_char_array(&subobject),
+                CType::Record { .. } => type_of(semantic_state, item.value).is_some_and(|ctype| matches!(ctype.unqualified(), CType::Record { .. })),
+                _ => true,
+            };
+
+            if !is_list && !matches {
+                stack.push((subobject, 0));
+                continue;
+            }
+
+            if let Some(context) = context {
+                check_initializer(semantic_state, &subobject, item.value, context);
+            }
+
+            if let Some(top) = stack.last_mut() {
+                top.1 += 1;
+            }
+
+            break;
+        }
+
+        length = length.max(stack[0].1 + (stack.len() > 1) as u64);
+    }
+
+    length
+}
+
+
+fn designator_position(semantic_state: &SemanticState, current: &CType, designator: &Designator, value: NodeId) -> Result<u64, (NodeId, String)> {
+    match (designator, current.unqualified()) {
+        (Designator::Index(index), CType::Array(_, size)) => match Evaluator::new(semantic_state).evaluate_integer(*index) {
+            Ok(position) if position >= 0 && !matches!(size, ArraySize::Fixed(length) if position as u64 >= *length) => Ok(position as u64),
+            Ok(_) => Err((*index, "array index in initializer exceeds array bounds".to_string())),
+            Err(_) => Err((*index, "nonconstant array index in initializer".to_string())),
+        },
+
+        (Designator::Member(name), CType::Record { tag, .. }) => match initializer_members(semantic_state, tag).iter().position(|member| member.name == Some(*name)) {
+            Some(position) => Ok(position as u64),
+            None => Err((value, format!("'{}' has no member named '{}'", current.strip_qualifiers(), semantic_state.name(*name)))),
+        },
+
+        (Designator::Index(index), _) => Err((*index, "array index in non-array initializer".to_string())),
+        (Designator::Member(_), _) => Err((value, "field name not in record or union initializer".to_string())),
+    }
+}
+
+
+fn discard_initializer(semantic_state: &mut SemanticState, initializer: NodeId) {
+    match semantic_state.ast.kind(initializer) {
+        NodeKind::InitializerList(items) => {
+            for item in items {
+                discard_initializer(semantic_state, item.value);
+            }
+        }
+
+        _ => {
+            convert_value(semantic_state, initializer);
+        }
+    }
+}
+
+
+fn string_initializer<'a>(semantic_state: &SemanticState<'a>, target: &CType, initializer: NodeId) -> Option<&'a Symbol> {
+    if !is_char_array(target) {
+        return None;
+    }
+
+    match semantic_state.ast.kind(initializer) {
+        NodeKind::StringLiteral(value) => Some(value),
+
+        NodeKind::InitializerList(items) => match items.as_slice() {
+            [item] if item.designators.is_empty() => match semantic_state.ast.kind(item.value) {
+                NodeKind::StringLiteral(value) => Some(value),
+                _ => None,
+            },
+
+            _ => None,
+        },
+
+        _ => None,
+    }
+}
+
+
+fn is_char_array(ctype: &CType) -> bool {
+    matches!(ctype.unqualified(), CType::Array(element, _) if element.is_integer() && element.integer_rank() == 1)
+}
+
+
+fn initializer_members(semantic_state: &SemanticState, tag: &str) -> Vec<Member> {
+    semantic_state.lookup_tag(tag).into_iter().flatten()
+        .filter(|member| member.name.is_some() || member.bit_width.is_none())
+        .cloned()
+        .collect()
+}
+
+
+fn subobject_count(semantic_state: &SemanticState, ctype: &CType) -> u64 {
+    match ctype.unqualified() {
+        CType::Array(_, ArraySize::Fixed(length)) => *length,
+        CType::Array(..) => u64::MAX,
+        CType::Record { kind: Keyword::Union, .. } => 1,
+        CType::Record { tag, .. } => initializer_members(semantic_state, tag).len() as u64,
+        _ => 0,
+    }
+}
+
+
+fn subobject(semantic_state: &SemanticState, ctype: &CType, position: u64) -> Option<CType> {
+    match ctype.unqualified() {
+        CType::Array(element, ArraySize::Fixed(length)) => (position < *length).then(|| element.as_ref().clone()),
+        CType::Array(element, _) => Some(element.as_ref().clone()),
+        CType::Record { tag, .. } => initializer_members(semantic_state, tag).get(position as usize).map(|member| member.ctype.clone()),
+        _ => None,
+    }
+}
+
+
+fn check_return(semantic_state: &mut SemanticState, statement: NodeId, value: Option<NodeId>) {
+    let Some(return_type) = semantic_state.return_type.clone() else {
+        return;
+    };
+
+    let is_void = matches!(return_type, CType::Void);
+
+    match value {
+        Some(value) if is_void && !matches!(type_of(semantic_state, value), Some(CType::Void)) => {
+            semantic_state.report(Severity::Warning, value, "'return' with a value, in function returning void".to_string());
+        }
+
+        Some(_) if is_void => {}
+
+        Some(value) => {
+            let context = format!("return value of '{}'", semantic_state.function);
+            check_assignable(semantic_state, value, &return_type, &context);
+        }
+
+        None if !is_void => semantic_state.report(Severity::Warning, statement, "'return' with no value, in function returning non-void".to_string()),
+        None => {}
+    }
+}
+
+
+fn check_expression(semantic_state: &mut SemanticState, expression: NodeId) {
+    let ast = semantic_state.ast;
+
+    match ast.kind(expression) {
+        NodeKind::BinaryOperation { operator: BinaryOperator::Comma, right, .. } => {
+            convert_value(semantic_state, *right);
+        }
+
+        NodeKind::BinaryOperation { left, operator: BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr, right } => {
+            check_scalar(semantic_state, *left);
+            check_scalar(semantic_state, *right);
+        }
+
+        NodeKind::BinaryOperation { left, operator, right } => {
+            let (Some(left_type), Some(right_type)) = (convert_value(semantic_state, *left), convert_value(semantic_state, *right)) else {
+                return;
+            };
+
+            check_binary(semantic_state, *operator, (*left, &left_type), (*right, &right_type));
+        }
+
+        NodeKind::Assignment { left, operator, right } => {
+            if !check_modifiable(semantic_state, *left, "assignment", "left operand of assignment") {
+                convert_value(semantic_state, *right);
+                return;
+            }
+
+            let Some(left_type) = type_of(semantic_state, *left) else {
+                convert_value(semantic_state, *right);
+                return;
+            };
+
+            match operator {
+                AssignmentOperator::Assign => check_assignable(semantic_state, *right, &left_type, &format!("assignment to '{}'", left_type.strip_qualifiers())),
+
+                AssignmentOperator::Compound(operator) => {
+                    let Some(right_type) = convert_value(semantic_state, *right) else {
+                        return;
+                    };
+
+                    let left_type = left_type.strip_qualifiers();
+
+                    if !operands_valid(*operator, &left_type, &right_type) {
+                        let message = format!("invalid operands to binary {} (have '{}' and '{}')", operator.symbol(), left_type, right_type);
+                        semantic_state.report(Severity::Error, expression, message);
+                    }
+                }
+            }
+        }
+
+        NodeKind::UnaryOperation { operator, operand } => check_unary(semantic_state, *operator, *operand),
+
+        NodeKind::PostfixOperation { operator, operand } => {
+            let (action, operand_name) = match operator {
+                PostfixOperator::Increment => ("increment", "increment operand"),
+                PostfixOperator::Decrement => ("decrement", "decrement operand"),
+            };
+
+            check_step(semantic_state, *operand, action, operand_name);
+        }
+
+        NodeKind::Conditional { condition, then_value, else_value } => {
+            check_scalar(semantic_state, *condition);
+            check_conditional(semantic_state, *then_value, *else_value);
+        }
+
+        NodeKind::Call { function, arguments } => check_call(semantic_state, *function, arguments),
+
+        NodeKind::Index { array, index } => {
+            let (Some(array_type), Some(index_type)) = (convert_value(semantic_state, *array), convert_value(semantic_state, *index)) else {
+                return;
+            };
+
+            if !(array_type.is_pointer() && index_type.is_integer() || index_type.is_pointer() && array_type.is_integer()) {
+                let message = if array_type.is_pointer() || index_type.is_pointer() {
+                    "array subscript is not an integer"
+                } else {
+                    "subscripted value is neither array nor pointer"
+                };
+
+                semantic_state.report(Severity::Error, expression, message.to_string());
+            }
+        }
+
+        NodeKind::Member { object, member, arrow } => check_member(semantic_state, *object, *member, *arrow),
+
+        NodeKind::Cast { type_name, operand } => {
+            let target = resolve_type_name(semantic_state, expression, type_name);
+
+            if matches!(target.unqualified(), CType::Void) {
+                return;
+            }
+
+            let Some(source) = convert_value(semantic_state, *operand) else {
+                return;
+            };
+
+            let message = if !is_scalar(&target) && !target.strip_qualifiers().is_compatible(&source) {
+                "conversion to non-scalar type requested"
+            } else if is_scalar(&target) && !is_scalar(&source) {
+                "aggregate value used where a scalar was expected"
+            } else if target.is_pointer() && source.is_floating() || target.is_floating() && source.is_pointer() {
+                "pointer value used where a floating-point was expected"
+            } else {
+                return;
+            };
+
+            semantic_state.report(Severity::Error, expression, message.to_string());
+        }
+
+        _ => {}
+    }
+}
+
+
+fn operands_valid(operator: BinaryOperator, left: &CType, right: &CType) -> bool {
+    match operator {
+        BinaryOperator::Add => left.is_arithmetic() && right.is_arithmetic() || left.is_pointer() && right.is_integer() || left.is_integer() && right.is_pointer(),
+
+        BinaryOperator::Subtract => {
+            left.is_arithmetic() && right.is_arithmetic() || left.is_pointer() && right.is_integer()
+                || left.is_pointer() && right.is_pointer() && left.pointee().zip(right.pointee()).is_some_and(|(left, right)| left.strip_qualifiers().is_compatible(&right.strip_qualifiers()))
+        }
+
+        BinaryOperator::Multiply | BinaryOperator::Divide => left.is_arithmetic() && right.is_arithmetic(),
+
+        BinaryOperator::Remainder | BinaryOperator::BitAnd | BinaryOperator::BitOr | BinaryOperator::BitXor
+            | BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => left.is_integer() && right.is_integer(),
+
+        _ if operator.is_comparison() => is_scalar(left) && is_scalar(right),
+        _ => true,
+    }
+}
+
+
+fn check_binary(semantic_state: &mut SemanticState, operator: BinaryOperator, (left, left_type): (NodeId, &CType), (right, right_type): (NodeId, &CType)) {
+    if !operands_valid(operator, left_type, right_type) {
+        let message = format!("invalid operands to binary {} (have '{}' and '{}')", operator.symbol(), left_type, right_type);
+        semantic_state.report(Severity::Error, left, message);
+        return;
+    }
+
+    if left_type.is_arithmetic() && right_type.is_arithmetic() {
+        if matches!(operator, BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight) {
+            implicit_cast(semantic_state, left, left_type, &left_type.promote());
+            implicit_cast(semantic_state, right, right_type, &right_type.promote());
+        } else {
+            let common = CType::usual_arithmetic(left_type, right_type);
+            implicit_cast(semantic_state, left, left_type, &common);
+            implicit_cast(semantic_state, right, right_type, &common);
+        }
+
+        return;
+    }
+
+    if !operator.is_comparison() {
+        return;
+    }
+
+    let is_equality = matches!(operator, BinaryOperator::Equal | BinaryOperator::NotEqual);
+
+    match (left_type.pointee(), right_type.pointee()) {
+        (Some(left_pointee), Some(right_pointee)) => {
+            let is_void = matches!(left_pointee.unqualified(), CType::Void) || matches!(right_pointee.unqualified(), CType::Void);
+
+            if !(is_equality && is_void || left_pointee.strip_qualifiers().is_compatible(&right_pointee.strip_qualifiers())) {
+                semantic_state.report(Severity::Warning, left, "comparison of distinct pointer types lacks a cast".to_string());
+            }
+        }
+
+        (Some(_), None) if is_equality && is_null_pointer_constant(semantic_state, right) => implicit_cast(semantic_state, right, right_type, left_type),
+        (None, Some(_)) if is_equality && is_null_pointer_constant(semantic_state, left) => implicit_cast(semantic_state, left, left_type, right_type),
+        _ => semantic_state.report(Severity::Warning, left, "comparison between pointer and integer".to_string()),
+    }
+}
+
+
+fn check_unary(semantic_state: &mut SemanticState, operator: UnaryOperator, operand: NodeId) {
+    let message = match operator {
+        UnaryOperator::Sizeof => return,
+
+        UnaryOperator::Address => {
+            if category_of(semantic_state, operand) == ValueCategory::RValue && type_of(semantic_state, operand).is_some() {
+                semantic_state.report(Severity::Error, operand, "lvalue required as unary '&' operand".to_string());
+            }
+
+            return;
+        }
+
+        UnaryOperator::PreIncrement => return check_step(semantic_state, operand, "increment", "increment operand"),
+        UnaryOperator::PreDecrement => return check_step(semantic_state, operand, "decrement", "decrement operand"),
+        UnaryOperator::Not => return check_scalar(semantic_state, operand),
+
+        UnaryOperator::Dereference => {
+            let Some(ctype) = convert_value(semantic_state, operand) else {
+                return;
+            };
+
+            match ctype.pointee() {
+                Some(CType::Void) => semantic_state.report(Severity::Warning, operand, format!("dereferencing '{}' pointer", ctype)),
+                Some(_) => {}
+                None => semantic_state.report(Severity::Error, operand, format!("invalid type argument of unary '*' (have '{}')", ctype)),
+            }
+
+            return;
+        }
+
+        UnaryOperator::Plus => "wrong type argument to unary plus",
+        UnaryOperator::Minus => "wrong type argument to unary minus",
+        UnaryOperator::BitNot => "wrong type argument to bit-complement",
+    };
+
+    let Some(ctype) = convert_value(semantic_state, operand) else {
+        return;
+    };
+
+    let valid = if operator == UnaryOperator::BitNot { ctype.is_integer() } else { ctype.is_arithmetic() };
+
+    if valid {
+        implicit_cast(semantic_state, operand, &ctype, &ctype.promote());
+    } else {
+        semantic_state.report(Severity::Error, operand, message.to_string());
+    }
+}
+
+
+fn check_step(semantic_state: &mut SemanticState, operand: NodeId, action: &str, operand_name: &str) {
+    if !check_modifiable(semantic_state, operand, action, operand_name) {
+        return;
+    }
+
+    if type_of(semantic_state, operand).is_some_and(|ctype| !is_scalar(&ctype)) {
+        semantic_state.report(Severity::Error, operand, format!("wrong type argument to {}", action));
+    }
+}
+
+
+fn check_conditional(semantic_state: &mut SemanticState, then_value: NodeId, else_value: NodeId) {
+    let (Some(then_type), Some(else_type)) = (convert_value(semantic_state, then_value), convert_value(semantic_state, else_value)) else {
+        return;
+    };
+
+    let message = match (then_type.unqualified(), else_type.unqualified()) {
+        _ if then_type.is_arithmetic() && else_type.is_arithmetic() => {
+            let common = CType::usual_arithmetic(&then_type, &else_type);
+            implicit_cast(semantic_state, then_value, &then_type, &common);
+            implicit_cast(semantic_state, else_value, &else_type, &common);
+            return;
+        }
+
+        (CType::Void, CType::Void) => return,
+        (CType::Record { .. }, CType::Record { .. }) if then_type.is_compatible(&else_type) => return,
+
+        (CType::Pointer(then_pointee), CType::Pointer(else_pointee)) => {
+            let is_void = matches!(then_pointee.unqualified(), CType::Void) || matches!(else_pointee.unqualified(), CType::Void);
+
+            if is_void || then_pointee.strip_qualifiers().is_compatible(&else_pointee.strip_qualifiers()) {
+                return;
+            }
+
+            (Severity::Warning, "pointer type mismatch in conditional expression")
+        }
+
+        (CType::Pointer(_), _) if is_null_pointer_constant(semantic_state, else_value) => return implicit_cast(semantic_state, else_value, &else_type, &then_type),
+        (_, CType::Pointer(_)) if is_null_pointer_constant(semantic_state, then_value) => return implicit_cast(semantic_state, then_value, &then_type, &else_type),
+        _ if then_type.is_pointer() && else_type.is_integer() || then_type.is_integer() && else_type.is_pointer() => (Severity::Warning, "pointer/integer type mismatch in conditional expression"),
+        _ => (Severity::Error, "type mismatch in conditional expression"),
+    };
+
+    semantic_state.report(message.0, then_value, message.1.to_string());
+}
+
+
+fn check_call(semantic_state: &mut SemanticState, function: NodeId, arguments: &[NodeId]) {
+    let ast = semantic_state.ast;
+
+    let Some(callee) = convert_value(semantic_state, function) else {
+        for &argument in arguments {
+            convert_value(semantic_state, argument);
+        }
+
+        return;
+    };
+
+    let Some(CType::Function { parameters, variadic, prototyped, .. }) = callee.pointee().map(CType::unqualified).cloned() else {
+        semantic_state.report(Severity::Error, function, "called object is not a function or function pointer".to_string());
+        return;
+    };
+
+    let name = match ast.kind(function) {
+        NodeKind::Identifier(name) => semantic_state.name(*name),
+        _ => "function",
+    };
+
+    if prototyped && arguments.len() < parameters.len() {
+        semantic_state.report(Severity::Error, function, format!("too few arguments to function '{}'", name));
+    } else if prototyped && !variadic && arguments.len() > parameters.len() {
+        semantic_state.report(Severity::Error, function, format!("too many arguments to function '{}'", name));
+    }
+
+    for (index, &argument) in arguments.iter().enumerate() {
+        match parameters.get(index).filter(|_| prototyped) {
+            Some(parameter) => check_assignable(semantic_state, argument, parameter, &format!("passing argument {} of '{}'", index + 1, name)),
+
+            None => {
+                let Some(ctype) = convert_value(semantic_state, argument) else {
+                    continue;
+                };
+
+                let promoted = if matches!(ctype.unqualified(), CType::Float) { CType::Double } else { ctype.promote() };
+                implicit_cast(semantic_state, argument, &ctype, &promoted);
+            }
+        }
+    }
+
+    check_format(semantic_state, function, arguments);
+}
+
+
+fn check_member(semantic_state: &mut SemanticState, object: NodeId, member: Symbol, arrow: bool) {
+    let object_type = if arrow { convert_value(semantic_state, object) } else { type_of(semantic_state, object) };
+
+    let Some(object_type) = object_type else {
+        return;
+    };
+
+    let record = if arrow {
+        match object_type.pointee() {
+            Some(record) => record.clone(),
+            None => return semantic_state.report(Severity::Error, object, format!("invalid type argument of '->' (have '{}')", object_type)),
+        }
+    } else {
+        object_type
+    };
+
+    let CType::Record { tag, .. } = record.unqualified() else {
+        let message = format!("request for member '{}' in something not a structure or union", semantic_state.name(member));
+        return semantic_state.report(Severity::Error, object, message);
+    };
+
+    if semantic_state.lookup_tag(tag).is_none() {
+        semantic_state.report(Severity::Error, object, format!("invalid use of incomplete type '{}'", record.strip_qualifiers()));
+    } else if semantic_state.find_member(tag, member).is_none() {
+        let message = format!("'{}' has no member named '{}'", record.strip_qualifiers(), semantic_state.name(member));
+        semantic_state.report(Severity::Error, object, message);
+    }
+}
+
+
+fn check_deprecated(semantic_state: &mut SemanticState, node: NodeId, entity: Option<EntityId>) {
+    let Some(entity) = entity else {
+        return;
+    };
+
+    let Some(attribute) = semantic_state.binding(entity).and_then(|binding| binding.attributes.iter().find(|attribute| attribute.is("deprecated"))) else {
+        return;
+    };
+
+    let name = semantic_state.name(semantic_state.table[entity].name);
+
+    let message = match attribute.message() {
+        Some(reason) => format!("'{}' is deprecated: {}", name, reason),
+        None => format!("'{}' is deprecated", name),
+    };
+
+    semantic_state.report(Severity::Warning, node, message);
+}
+
+
+fn has_attribute(semantic_state: &SemanticState, entity: Option<EntityId>, attribute_name: &str) -> bool {
+    entity.and_then(|entity| semantic_state.binding(entity)).is_some_and(|binding| binding.attributes.iter().any(|attribute| attribute.is(attribute_name)))
+}
+
+
+/* * * * * * * * */
+/*  - FORMATS -  */
+/* * * * * * * * */
+const INTEGER_LENGTHS: &[&str] = &["", "hh", "h", "l", "ll", "j", "z", "t"];
+const FLOATING_LENGTHS: &[&str] = &["", "l", "L"];
+const CHARACTER_LENGTHS: &[&str] = &["", "l"];
+
+
+fn check_format(semantic_state: &mut SemanticState, function: NodeId, arguments: &[NodeId]) {
+    let ast = semantic_state.ast;
+
+    let NodeKind::Identifier(name) = ast.kind(function) else {
+        return;
+    };
+
+    let declared = semantic_state.lookup(function)
+        .and_then(|binding| binding.attributes.iter().find(|attribute| attribute.is("format") || attribute.is("__format__")))
+        .and_then(format_attribute);
+
+    let Some((kind, format_index, first_argument)) = declared.or_else(|| FORMAT_FUNCTION_MAP.get(semantic_state.name(*name)).copied()) else {
+        return;
+    };
+
+    let Some(&format) = format_index.checked_sub(1).and_then(|index| arguments.get(index)) else {
+        return;
+    };
+
+    let variadic = first_argument.checked_sub(1).map(|first| arguments.get(first..).unwrap_or_default());
+
+    let NodeKind::StringLiteral(text) = ast.kind(format) else {
+        match variadic {
+            Some([]) => semantic_state.report(Severity::Warning, format, "format not a string literal and no format arguments".to_string()),
+            Some(_) => semantic_state.report(Severity::Warning, format, "format not a string literal, argument types not checked".to_string()),
+            None => {}
+        }
+
+        return;
+    };
+
+    let expected = match kind {
+        FormatKind::Printf => parse_printf_format(semantic_state.name(*text)),
+        FormatKind::Scanf => parse_scanf_format(semantic_state.name(*text)),
+    };
+
+    let expected = match expected {
+        Ok(expected) => expected,
+        Err(message) => return semantic_state.report(Severity::Warning, format, message),
+    };
+
+    let Some(variadic) = variadic else {
+        return;
+    };
+
+    for (index, argument) in expected.iter().enumerate() {
+        let Some(&node) = variadic.get(index) else {
+            semantic_state.report(Severity::Warning, format, format!("{} expects a matching '{}' argument", argument.description, argument.expected));
+            continue;
+        };
+
+        let Some(actual) = type_of(semantic_state, node).map(|ctype| ctype.decay()) else {
+            continue;
+        };
+
+        if !format_matches(&argument.expected, &actual) {
+            let message = format!("{} expects argument of type '{}', but argument {} has type '{}'", argument.description, argument.expected, first_argument + index, actual);
+            semantic_state.report(Severity::Warning, node, message);
+        }
+    }
+
+    if variadic.len() > expected.len() {
+        semantic_state.report(Severity::Warning, variadic[expected.len()], "too many arguments for format".to_string());
+    }
+}
+
+
+fn format_attribute(attribute: &Attribute) -> Option<(FormatKind, usize, usize)> {
+    let [archetype, format_index, first_argument] = attribute.arguments.as_slice() else {
+        return None;
+    };
+
+    let kind = match archetype.trim_matches('_') {
+        "printf" | "gnu_printf" => FormatKind::Printf,
+        "scanf" | "gnu_scanf" => FormatKind::Scanf,
+        _ => return None,
+    };
+
+    Some((kind, format_index.parse().ok()?, first_argument.parse().ok()?))
+}
+
+
+fn parse_printf_format(format: &str) -> Result<Vec<FormatArgument>, String> {
+    let characters: Vec<char> = format.chars().collect();
+    let mut arguments = Vec::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        let start = index;
+        index += 1;
+
+        if characters[start] != '%' {
+            continue;
+        }
+
+        if characters.get(index) == Some(&'%') {
+            index += 1;
+            continue;
+        }
+
+        if index == characters.len() {
+            return Err("spurious trailing '%' in format".to_string());
+        }
+
+        let mut flags = Vec::new();
+
+        while let Some(&flag) = characters.get(index).filter(|character| "-+ #0'".contains(**character)) {
+            flags.push(flag);
+            index += 1;
+        }
+
+        if characters.get(index) == Some(&'*') {
+            arguments.push(FormatArgument { description: "field width specifier '*'".to_string(), expected: CType::Int });
+            index += 1;
+        }
+
+        skip_digits(&characters, &mut index);
+
+        if characters.get(index) == Some(&'.') {
+            index += 1;
+
+            if characters.get(index) == Some(&'*') {
+                arguments.push(FormatArgument { description: "field precision specifier '.*'".to_string(), expected: CType::Int });
+                index += 1;
+            }
+
+            skip_digits(&characters, &mut index);
+        }
+
+        let length = format_length(&characters, &mut index);
+
+        let Some(&conversion) = characters.get(index) else {
+            return Err("conversion lacks type at end of format".to_string());
+        };
+
+        index += 1;
+
+        let (expected, lengths) = match conversion {
+            'd' | 'i' => (format_integer(length, false).promote(), INTEGER_LENGTHS),
+            'o' | 'u' | 'x' | 'X' => (format_integer(length, true).promote(), INTEGER_LENGTHS),
+            'n' => (CType::Pointer(Box::new(format_integer(length, false))), INTEGER_LENGTHS),
+            'c' => (if length == "l" { CType::UnsignedInt } else { CType::Int }, CHARACTER_LENGTHS),
+            's' => (CType::Pointer(Box::new(if length == "l" { CType::Int } else { CType::Char })), CHARACTER_LENGTHS),
+            'p' => (CType::Pointer(Box::new(CType::Void)), &[""][..]),
+            'f' | 'F' | 'e' | 'E' | 'g' | 'G' | 'a' | 'A' => (if length == "L" { CType::LongDouble } else { CType::Double }, FLOATING_LENGTHS),
+            _ => return Err(format!("unknown conversion type character '{}' in format", conversion)),
+        };
+
+        if !lengths.contains(&length) {
+            return Err(format!("use of '{}' length modifier with '{}' type character", length, conversion));
+        }
+
+        if flags.contains(&'#') && "dicspnu".contains(conversion) {
+            return Err(format!("'#' flag used with '%{}' printf format", conversion));
+        }
+
+        if flags.contains(&'0') && "cspn".contains(conversion) {
+            return Err(format!("'0' flag used with '%{}' printf format", conversion));
+        }
+
+        let specification: String = characters[start..index].iter().collect();
+        arguments.push(FormatArgument { description: format!("format '{}'", specification), expected });
+    }
+
+    Ok(arguments)
+}
+
+
+fn parse_scanf_format(format: &str) -> Result<Vec<FormatArgument>, String> {
+    let characters: Vec<char> = format.chars().collect();
+    let mut arguments = Vec::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        let start = index;
+        index += 1;
+
+        if characters[start] != '%' {
+            continue;
+        }
+
+        if characters.get(index) == Some(&'%') {
+            index += 1;
+            continue;
+        }
+
+        if index == characters.len() {
+            return Err("spurious trailing '%' in format".to_string());
+        }
+
+        let suppressed = characters.get(index) == Some(&'*');
+        index += usize::from(suppressed);
+
+        skip_digits(&characters, &mut index);
+        let length = format_length(&characters, &mut index);
+
+        let Some(&conversion) = characters.get(index) else {
+            return Err("conversion lacks type at end of format".to_string());
+        };
+
+        index += 1;
+
+        let specification: String = characters[start..index].iter().collect();
+
+        let (pointee, lengths) = match conversion {
+            'd' | 'i' | 'n' => (format_integer(length, false), INTEGER_LENGTHS),
+            'o' | 'u' | 'x' | 'X' => (format_integer(length, true), INTEGER_LENGTHS),
+            's' | 'c' | '[' => (if length == "l" { CType::Int } else { CType::Char }, CHARACTER_LENGTHS),
+            'p' => (CType::Pointer(Box::new(CType::Void)), &[""][..]),
+
+            'f' | 'F' | 'e' | 'E' | 'g' | 'G' | 'a' | 'A' => {
+                let ctype = match length {
+                    "l" => CType::Double,
+                    "L" => CType::LongDouble,
+                    _ => CType::Float,
+                };
+
+                (ctype, FLOATING_LENGTHS)
+            }
+
+            _ => return Err(format!("unknown conversion type character '{}' in format", conversion)),
+        };
+
+        if !lengths.contains(&length) {
+            return Err(format!("use of '{}' length modifier with '{}' type character", length, conversion));
+        }
+
+        if conversion == '[' {
+            index += usize::from(characters.get(index) == Some(&'^'));
+            index += usize::from(characters.get(index) == Some(&']'));
+
+            match characters[index.min(characters.len())..].iter().position(|&character| character == ']') {
+                Some(offset) => index += offset + 1,
+                None => return Err("no closing ']' for '%[' format".to_string()),
+            }
+        }
+
+        if !suppressed {
+            arguments.push(FormatArgument { description: format!("format '{}'", specification), expected: CType::Pointer(Box::new(pointee)) });
+        }
+    }
+
+    Ok(arguments)
+}
+
+
+fn skip_digits(characters: &[char], index: &mut usize) {
+    while characters.get(*index).is_some_and(char::is_ascii_digit) {
+        *index += 1;
+    }
+}
+
+
+fn format_length(characters: &[char], index: &mut usize) -> &'static str {
+    let length = match (characters.get(*index), characters.get(*index + 1)) {
+        (Some('h'), Some('h')) => "hh",
+        (Some('l'), Some('l')) => "ll",
+        (Some('h'), _) => "h",
+        (Some('l'), _) => "l",
+        (Some('j'), _) => "j",
+        (Some('z'), _) => "z",
+        (Some('t'), _) => "t",
+        (Some('L'), _) => "L",
+        _ => "",
+    };
+
+    *index += length.len();
+    length
+}
+
+
+fn format_integer(length: &str, unsigned: bool) -> CType {
+    let ctype = match length {
+        "hh" => CType::SignedChar,
+        "h" => CType::Short,
+        "l" | "j" | "z" | "t" => CType::Long,
+        "ll" => CType::LongLong,
+        _ => CType::Int,
+    };
+
+    if unsigned { ctype.to_unsigned() } else { ctype }
+}
+
+
+fn format_matches(expected: &CType, actual: &CType) -> bool {
+    match (expected.unqualified(), actual.unqualified()) {
+        (CType::Pointer(expected), CType::Pointer(actual)) => match (expected.unqualified(), actual.unqualified()) {
+            (CType::Void, _) => true,
+            (CType::Char, actual) => matches!(actual, CType::Char | CType::SignedChar | CType::UnsignedChar),
+            (CType::Pointer(_), actual) => actual.is_pointer(),
+            (expected, actual) if expected.is_integer() => actual.is_integer() && !matches!(actual, CType::Bool) && actual.integer_rank() == expected.integer_rank(),
+            (expected, actual) => expected == actual,
+        },
+
+        (expected, actual) if expected.is_integer() => actual.is_integer() && actual.promote().integer_rank() == expected.integer_rank(),
+        (CType::Double, actual) => matches!(actual, CType::Float | CType::Double),
+        (expected, actual) => expected == actual,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::data::Target;
+    use crate::logic::{ lexer_start, parser_start, semantic_start, sym_table_start };
+
+
+    fn check(source: &str) -> Vec<String> {
+        let tokens = lexer_start(source).expect("source should lex");
+        let (mut ast, errors) = parser_start(&tokens);
+        assert!(errors.is_empty(), "parse errors: {:?}", errors);
+
+        let target = Target::x86_64_sysv();
+        let (table, _) = sym_table_start(&ast);
+        let (_, diagnostics) = semantic_start(&mut ast, &table, &target);
+
+        diagnostics.iter().map(ToString::to_string).collect()
+    }
+
+
+    #[test]
+    fn validates_initializer_lists() {
+        let diagnostics = check("struct P { int x, y; }; struct P p = { .z = 1, 2 }; int g[2] = { [5] = 1 }; \
+            int h[2] = { 1, 2, 3 }; char c[2] = \"toolong\"; char d[2] = \"ok\"; int s = { 1, 2 };");
+
+        for expected in ["'struct P' has no member named 'z'", "array index in initializer exceeds array bounds",
+            "excess elements in array initializer", "initializer-string for array of 'char' is too long", "excess elements in scalar initializer"] {
+            assert!(diagnostics.iter().any(|diagnostic| diagnostic.contains(expected)), "missing '{}' in {:?}", expected, diagnostics);
+        }
+
+        assert_eq!(diagnostics.len(), 5, "{:?}", diagnostics);
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::{ HashMap, HashSet };
+
+use crate::data::structures::{ Analyses, Instruction, IrFunction };
+use crate::data::types::{ BlockId, InstructionKind, IrType, Operand };
+use crate::logic::fold::unsigned;
+
+
+pub fn simplify_cfg(function: &mut IrFunction, _analyses: &mut Analyses) -> bool {
+    let mut changed = false;
+
+    loop {
+        let progress = fold_branches(function)
+            | remove_unreachable_blocks(function)
+            | function.remove_trivial_phis()
+            | merge_blocks(function)
+            | skip_empty_blocks(function);
+
+        if !progress {
+            return changed;
+        }
+
+        changed = true;
+    }
+}
+
+
+/* * * * * * * * * */
+/*  - BRANCHES -  */
+/* * * * * * * * * */
+fn fold_branches(function: &mut IrFunction) -> bool {
+    let mut changed = false;
+
+    for index in 0..function.blocks.len() {
+        let Some(terminator) = function.blocks[index].instructions.last() else {
+            continue;
+        };
+
+        let target = match &terminator.kind {
+            InstructionKind::Branch { then_block, else_block, .. } if then_block == else_block => *then_block,
+            InstructionKind::Branch { condition: Operand::Integer(value), then_block, else_block } => if *value != 0 { *then_block } else { *else_block },
+            InstructionKind::Switch { default, cases, .. } if cases.iter().all(|(_, target)| target == default) => *default,
+
+            InstructionKind::Switch { value: Operand::Integer(value), default, cases } => {
+                let ty = terminator.ty;
+                cases.iter().find(|(case, _)| unsigned(*case, ty) == unsigned(*value, ty)).map_or(*default, |(_, target)| *target)
+            }
+
+            _ => continue,
+        };
+
+        let block = BlockId(index as u32);
+        let successors = function.blocks[index].successors();
+        replace_terminator(function, block, InstructionKind::Jump(target));
+
+        for successor in successors.into_iter().filter(|successor| *successor != target) {
+            function.remove_incoming(successor, block);
+        }
+
+        changed = true;
+    }
+
+    changed
+}
+
+
+/* * * * * * * * */
+/*  - BLOCKS -  */
+/* * * * * * * * */
+pub fn remove_unreachable_blocks(function: &mut IrFunction) -> bool {
+    let reachable: HashSet<BlockId> = function.reverse_postorder().into_iter().collect();
+
+    if reachable.len() == function.blocks.len() {
+        return false;
+    }
+
+    let mut renumbered = HashMap::new();
+
+    for block in &function.blocks {
+        if reachable.contains(&block.id) {
+            renumbered.insert(block.id, BlockId(renumbered.len() as u32));
+        }
+    }
+
+    function.blocks.retain(|block| reachable.contains(&block.id));
+
+    for block in &mut function.blocks {
+        block.id = renumbered[&block.id];
+
+        for instruction in &mut block.instructions {
+            match &mut instruction.kind {
+                InstructionKind::Phi { incoming } => {
+                    incoming.retain(|(_, predecessor)| renumbered.contains_key(predecessor));
+
+                    for (_, predecessor) in incoming.iter_mut() {
+                        *predecessor = renumbered[predecessor];
+                    }
+                }
+
+                InstructionKind::Jump(target) => *target = renumbered[target],
+
+                InstructionKind::Branch { then_block, else_block, .. } => {
+                    *then_block = renumbered[then_block];
+                    *else_block = renumbered[else_block];
+                }
+
+                InstructionKind::Switch { default, cases, .. } => {
+                    *default = renumbered[default];
+
+                    for (_, target) in cases.iter_mut() {
+                        *target = renumbered[target];
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    true
+}
+
+
+fn merge_blocks(function: &mut IrFunction) -> bool {
+    let predecessors = function.predecessors();
+
+    for index in 0..function.blocks.len() {
+        let block = BlockId(index as u32);
+
+        let Some(InstructionKind::Jump(successor)) = function.blocks[index].instructions.last().map(|instruction| &instruction.kind) else {
+            continue;
+        };
+
+        let successor = *successor;
+
+        if successor == block || successor == BlockId(0) || predecessors[successor.0 as usize] != [block] {
+            continue;
+        }
+
+        let mut replacements = HashMap::new();
+        let instructions = std::mem::replace(&mut function.blocks[successor.0 as usize].instructions, vec![unreachable()]);
+
+        let body: Vec<Instruction> = instructions.into_iter()
+            .filter(|instruction| match (&instruction.kind, instruction.result) {
+                (InstructionKind::Phi { incoming }, Some(result)) => {
+                    replacements.insert(result, incoming.first().map_or(Operand::Undef, |(value, _)| value.clone()));
+                    false
+                }
+
+                _ => true,
+            })
+            .collect();
+
+        for target in body.last().map(Instruction::successors).unwrap_or_default() {
+            for instruction in &mut function.blocks[target.0 as usize].instructions {
+                if let InstructionKind::Phi { incoming } = &mut instruction.kind {
+                    for (_, predecessor) in incoming.iter_mut().filter(|(_, predecessor)| *predecessor == successor) {
+                        *predecessor = block;
+                    }
+                }
+            }
+        }
+
+        let instructions = &mut function.blocks[index].instructions;
+        instructions.pop();
+        instructions.extend(body);
+        function.replace_operands(&replacements);
+
+        return true;
+    }
+
+    false
+}
+
+
+fn skip_empty_blocks(function: &mut IrFunction) -> bool {
+    let predecessors = function.predecessors();
+
+    for (index, sources) in predecessors.iter().enumerate().skip(1) {
+        let block = BlockId(index as u32);
+
+        let [Instruction { kind: InstructionKind::Jump(successor), .. }] = function.blocks[index].instructions[..] else {
+            continue;
+        };
+
+        if successor == block || sources.is_empty() || sources.contains(&block) {
+            continue;
+        }
+
+        let phis: Vec<&Instruction> = function.blocks[successor.0 as usize].instructions.iter()
+            .filter(|instruction| matches!(instruction.kind, InstructionKind::Phi { .. }))
+            .collect();
+
+        let compatible = phis.iter().all(|phi| {
+            let value = incoming_value(phi, block);
+            sources.iter().all(|source| incoming_value(phi, *source).is_none_or(|existing| Some(existing) == value))
+        });
+
+        if !compatible {
+            continue;
+        }
+
+        for source in sources {
+            if let Some(terminator) = function.blocks[source.0 as usize].instructions.last_mut() {
+                terminator.retarget(block, successor);
+            }
+        }
+
+        for instruction in &mut function.blocks[successor.0 as usize].instructions {
+            if let InstructionKind::Phi { incoming } = &mut instruction.kind {
+                let value = incoming.iter().find(|(_, predecessor)| *predecessor == block).map_or(Operand::Undef, |(value, _)| value.clone());
+                incoming.retain(|(_, predecessor)| *predecessor != block);
+
+                for source in sources {
+                    if !incoming.iter().any(|(_, predecessor)| predecessor == source) {
+                        incoming.push((value.clone(), *source));
+                    }
+                }
+            }
+        }
+
+        return true;
+    }
+
+    false
+}
+
+
+/* * * * * * * * */
+/*  - HELPERS -  */
+/* * * * * * * * */
+fn replace_terminator(function: &mut IrFunction, block: BlockId, kind: InstructionKind) {
+    if let Some(terminator) = function.blocks[block.0 as usize].instructions.last_mut() {
+        *terminator = Instruction { result: None, ty: IrType::Void, kind };
+    }
+}
+
+
+fn incoming_value(phi: &Instruction, predecessor: BlockId) -> Option<&Operand> {
+    match &phi.kind {
+        InstructionKind::Phi { incoming } => incoming.iter().find(|(_, source)| *source == predecessor).map(|(value, _)| value),
+        _ => None,
+    }
+}
+
+
+fn unreachable() -> Instruction {
+    Instruction { result: None, ty: IrType::Void, kind: InstructionKind::Unreachable }
+}
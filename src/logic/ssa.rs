@@ -0,0 +1,334 @@
+use std::collections::{ HashMap, HashSet };
+
+use crate::data::structures::{ Analyses, Instruction, IrFunction };
+use crate::data::types::{ BlockId, InstructionKind, IrType, Operand, Register };
+
+
+pub fn mem2reg(function: &mut IrFunction, analyses: &mut Analyses) -> bool {
+    let slots = promotable_slots(function);
+
+    if slots.is_empty() {
+        return false;
+    }
+
+    let dominators = analyses.dominators(function).to_vec();
+    promote_slots(function, &slots, &dominators);
+    true
+}
+
+
+pub fn out_of_ssa(function: &mut IrFunction, _analyses: &mut Analyses) -> bool {
+    demote_phis(function)
+}
+
+
+/* * * * * * * * * */
+/*  - PROMOTION -  */
+/* * * * * * * * * */
+fn promote_slots(function: &mut IrFunction, slots: &HashMap<Register, IrType>, dominators: &[Option<BlockId>]) {
+    let predecessors = function.predecessors();
+    let frontiers = IrFunction::dominance_frontiers(dominators, &predecessors);
+    let children = IrFunction::dominator_tree(dominators);
+
+    let phis = place_phis(function, slots, &frontiers, &predecessors);
+    let mut replacements = HashMap::new();
+    let incoming = rename_slots(function, slots, &phis, &children, &mut replacements);
+
+    remove_unreachable_accesses(function, slots, dominators, &mut replacements);
+    insert_phis(function, slots, &phis, incoming, &predecessors);
+    function.replace_operands(&replacements);
+    function.remove_trivial_phis();
+}
+
+
+fn promotable_slots(function: &IrFunction) -> HashMap<Register, IrType> {
+    let mut candidates: HashMap<Register, (u64, Option<IrType>)> = HashMap::new();
+
+    for instruction in &function.blocks[0].instructions {
+        if let (Some(result), InstructionKind::Alloca { size: Operand::Integer(size), .. }) = (instruction.result, &instruction.kind) {
+            candidates.insert(result, (*size as u64, None));
+        }
+    }
+
+    let mut rejected = HashSet::new();
+
+    for instruction in function.blocks.iter().flat_map(|block| &block.instructions) {
+        match &instruction.kind {
+            InstructionKind::Load { address: Operand::Register(address), volatile } if candidates.contains_key(address) => {
+                record_access(&mut candidates, &mut rejected, *address, instruction.ty, *volatile);
+            }
+
+            InstructionKind::Store { value, address: Operand::Register(address), volatile } if candidates.contains_key(address) => {
+                record_access(&mut candidates, &mut rejected, *address, instruction.ty, *volatile);
+
+                if let Operand::Register(value) = value {
+                    rejected.insert(*value);
+                }
+            }
+
+            InstructionKind::Alloca { .. } => {}
+
+            _ => {
+                let mut instruction = instruction.clone();
+
+                for operand in instruction.operands_mut() {
+                    if let Operand::Register(register) = operand {
+                        rejected.insert(*register);
+                    }
+                }
+            }
+        }
+    }
+
+    candidates.into_iter()
+        .filter(|(register, _)| !rejected.contains(register))
+        .filter_map(|(register, (size, ty))| ty.filter(|ty| ty.size() == size).map(|ty| (register, ty)))
+        .collect()
+}
+
+
+fn record_access(candidates: &mut HashMap<Register, (u64, Option<IrType>)>, rejected: &mut HashSet<Register>, slot: Register, ty: IrType, volatile: bool) {
+    let Some((_, slot_type)) = candidates.get_mut(&slot) else {
+        return;
+    };
+
+    match slot_type {
+        _ if volatile => {
+            rejected.insert(slot);
+        }
+
+        Some(existing) if *existing != ty => {
+            rejected.insert(slot);
+        }
+
+        _ => *slot_type = Some(ty),
+    }
+}
+
+
+fn place_phis(function: &mut IrFunction, slots: &HashMap<Register, IrType>, frontiers: &[Vec<BlockId>], predecessors: &[Vec<BlockId>]) -> HashMap<BlockId, Vec<(Register, Register)>> {
+    let mut phis: HashMap<BlockId, Vec<(Register, Register)>> = HashMap::new();
+    let mut ordered: Vec<Register> = slots.keys().copied().collect();
+    ordered.sort_by_key(|register| register.0);
+
+    for slot in ordered {
+        let definitions: Vec<BlockId> = function.blocks.iter()
+            .filter(|block| block.instructions.iter().any(|instruction| is_store_to(instruction, slot)))
+            .map(|block| block.id)
+            .collect();
+
+        let live_in = live_in_blocks(function, slot, &definitions, predecessors);
+        let mut worklist = definitions;
+        let mut placed = HashSet::new();
+
+        while let Some(block) = worklist.pop() {
+            for &frontier in &frontiers[block.0 as usize] {
+                if !live_in.contains(&frontier) || !placed.insert(frontier) {
+                    continue;
+                }
+
+                let phi = function.new_register();
+                phis.entry(frontier).or_default().push((slot, phi));
+                worklist.push(frontier);
+            }
+        }
+    }
+
+    phis
+}
+
+
+fn live_in_blocks(function: &IrFunction, slot: Register, definitions: &[BlockId], predecessors: &[Vec<BlockId>]) -> HashSet<BlockId> {
+    let mut worklist = Vec::new();
+
+    for block in &function.blocks {
+        for instruction in &block.instructions {
+            if is_store_to(instruction, slot) {
+                break;
+            }
+
+            if is_load_from(instruction, slot) {
+                worklist.push(block.id);
+                break;
+            }
+        }
+    }
+
+    let mut live_in = HashSet::new();
+
+    while let Some(block) = worklist.pop() {
+        if !live_in.insert(block) {
+            continue;
+        }
+
+        for &predecessor in &predecessors[block.0 as usize] {
+            if !definitions.contains(&predecessor) {
+                worklist.push(predecessor);
+            }
+        }
+    }
+
+    live_in
+}
+
+
+fn rename_slots(function: &mut IrFunction, slots: &HashMap<Register, IrType>, phis: &HashMap<BlockId, Vec<(Register, Register)>>, children: &[Vec<BlockId>], replacements: &mut HashMap<Register, Operand>) -> HashMap<Register, Vec<(Operand, BlockId)>> {
+    let mut values: HashMap<Register, Vec<Operand>> = slots.keys().map(|slot| (*slot, vec![Operand::Undef])).collect();
+    let mut incoming: HashMap<Register, Vec<(Operand, BlockId)>> = HashMap::new();
+    let mut pushed: HashMap<BlockId, Vec<Register>> = HashMap::new();
+    let mut stack = vec![(BlockId(0), false)];
+
+    while let Some((block, leaving)) = stack.pop() {
+        if leaving {
+            for slot in pushed.remove(&block).unwrap_or_default() {
+                values.get_mut(&slot).map(Vec::pop);
+            }
+
+            continue;
+        }
+
+        let mut defined = Vec::new();
+
+        for (slot, phi) in phis.get(&block).into_iter().flatten() {
+            values.entry(*slot).or_default().push(Operand::Register(*phi));
+            defined.push(*slot);
+        }
+
+        let instructions = std::mem::take(&mut function.blocks[block.0 as usize].instructions);
+        let mut kept = Vec::with_capacity(instructions.len());
+
+        for mut instruction in instructions {
+            for operand in instruction.operands_mut() {
+                operand.substitute(replacements);
+            }
+
+            match (&instruction.kind, instruction.result) {
+                (InstructionKind::Load { address: Operand::Register(address), .. }, Some(result)) if slots.contains_key(address) => {
+                    let value = values[address].last().cloned().unwrap_or(Operand::Undef);
+                    replacements.insert(result, value);
+                }
+
+                (InstructionKind::Store { value, address: Operand::Register(address), .. }, _) if slots.contains_key(address) => {
+                    values.entry(*address).or_default().push(value.clone());
+                    defined.push(*address);
+                }
+
+                (InstructionKind::Alloca { .. }, Some(result)) if slots.contains_key(&result) => {}
+
+                _ => kept.push(instruction),
+            }
+        }
+
+        function.blocks[block.0 as usize].instructions = kept;
+
+        for successor in function.blocks[block.0 as usize].successors() {
+            for (slot, phi) in phis.get(&successor).into_iter().flatten() {
+                let value = values[slot].last().cloned().unwrap_or(Operand::Undef);
+                incoming.entry(*phi).or_default().push((value, block));
+            }
+        }
+
+        pushed.insert(block, defined);
+        stack.push((block, true));
+
+        for child in children[block.0 as usize].iter().rev() {
+            stack.push((*child, false));
+        }
+    }
+
+    incoming
+}
+
+
+fn remove_unreachable_accesses(function: &mut IrFunction, slots: &HashMap<Register, IrType>, dominators: &[Option<BlockId>], replacements: &mut HashMap<Register, Operand>) {
+    for (index, block) in function.blocks.iter_mut().enumerate() {
+        if dominators[index].is_some() {
+            continue;
+        }
+
+        block.instructions.retain(|instruction| match (&instruction.kind, instruction.result) {
+            (InstructionKind::Load { address: Operand::Register(address), .. }, Some(result)) if slots.contains_key(address) => {
+                replacements.insert(result, Operand::Undef);
+                false
+            }
+
+            (InstructionKind::Store { address: Operand::Register(address), .. }, _) => !slots.contains_key(address),
+            _ => true,
+        });
+    }
+}
+
+
+fn insert_phis(function: &mut IrFunction, slots: &HashMap<Register, IrType>, phis: &HashMap<BlockId, Vec<(Register, Register)>>, mut incoming: HashMap<Register, Vec<(Operand, BlockId)>>, predecessors: &[Vec<BlockId>]) {
+    for (block, block_phis) in phis {
+        let instructions = &mut function.blocks[block.0 as usize].instructions;
+
+        for (position, (slot, phi)) in block_phis.iter().enumerate() {
+            let mut values = incoming.remove(phi).unwrap_or_default();
+
+            for &predecessor in &predecessors[block.0 as usize] {
+                if !values.iter().any(|(_, source)| *source == predecessor) {
+                    values.push((Operand::Undef, predecessor));
+                }
+            }
+
+            values.sort_by_key(|(_, source)| predecessors[block.0 as usize].iter().position(|predecessor| predecessor == source));
+            instructions.insert(position, Instruction { result: Some(*phi), ty: slots[slot], kind: InstructionKind::Phi { incoming: values } });
+        }
+    }
+}
+
+
+/* * * * * * * * * */
+/*  - DEMOTION -  */
+/* * * * * * * * * */
+fn demote_phis(function: &mut IrFunction) -> bool {
+    let mut slots = Vec::new();
+    let mut stores: Vec<(BlockId, Instruction)> = Vec::new();
+
+    for index in 0..function.blocks.len() {
+        let mut position = 0;
+
+        while let Some(instruction) = function.blocks[index].instructions.get(position) {
+            let (Some(result), InstructionKind::Phi { incoming }) = (instruction.result, &instruction.kind) else {
+                position += 1;
+                continue;
+            };
+
+            let ty = instruction.ty;
+            let incoming = incoming.clone();
+            let slot = function.new_register();
+
+            for (value, predecessor) in incoming {
+                stores.push((predecessor, Instruction { result: None, ty, kind: InstructionKind::Store { value, address: Operand::Register(slot), volatile: false } }));
+            }
+
+            function.blocks[index].instructions[position] = Instruction { result: Some(result), ty, kind: InstructionKind::Load { address: Operand::Register(slot), volatile: false } };
+            slots.push(Instruction { result: Some(slot), ty: IrType::Ptr, kind: InstructionKind::Alloca { size: Operand::Integer(ty.size() as i64), align: ty.size() } });
+            position += 1;
+        }
+    }
+
+    for (predecessor, store) in stores {
+        let instructions = &mut function.blocks[predecessor.0 as usize].instructions;
+        let position = instructions.len() - usize::from(instructions.last().is_some_and(Instruction::is_terminator));
+        instructions.insert(position, store);
+    }
+
+    let changed = !slots.is_empty();
+    function.blocks[0].instructions.splice(0..0, slots);
+    changed
+}
+
+
+/* * * * * * * * */
+/*  - HELPERS -  */
+/* * * * * * * * */
+fn is_store_to(instruction: &Instruction, slot: Register) -> bool {
+    matches!(&instruction.kind, InstructionKind::Store { address: Operand::Register(address), .. } if *address == slot)
+}
+
+
+fn is_load_from(instruction: &Instruction, slot: Register) -> bool {
+    matches!(&instruction.kind, InstructionKind::Load { address: Operand::Register(address), .. } if *address == slot)
+}
@@ -0,0 +1,546 @@
+use std::collections::HashSet;
+
+use crate::data::{ Ast, Diagnostic, Entity, EntityId, Keyword, NodeId, NodeKind, Symbol, SymbolTable, SymbolTableState };
+use crate::data::visit::{ Visitor, walk_for, walk_items, walk_node, walk_type_specifier };
+use crate::data::types::{ Attribute, DeclSpecifiers, Declarator, Definition, DerivedDeclarator, EntityKind, Enumerator, InitDeclarator, Linkage, Parameter, ScopeKind, Severity, StorageDuration, TypeSpecifier };
+
+
+pub const PREDEFINED: [&str; 3] = ["__func__", "__FUNCTION__", "__PRETTY_FUNCTION__"];
+
+
+pub fn sym_table_start(ast: &Ast) -> (SymbolTable, Vec<Diagnostic>) {
+    let mut symbol_state = SymbolTableState::new(ast);
+    symbol_state.visit_node(ast, ast.root);
+
+    (symbol_state.table, symbol_state.diagnostics)
+}
+
+
+/* * * * * * * * * * */
+/*  - DECLARATIONS - */
+/* * * * * * * * * * */
+fn process_function_definition<'a>(symbol_state: &mut SymbolTableState<'a>, specifiers: &'a DeclSpecifiers, declarator: &'a Declarator, body: NodeId) {
+    let ast = symbol_state.ast;
+    symbol_state.visit_decl_specifiers(ast, specifiers);
+
+    let Some((DerivedDeclarator::Function { parameters, .. }, outer)) = declarator.derived.split_last() else {
+        return;
+    };
+
+    process_derived(symbol_state, outer);
+
+    if let Some(name) = declarator.name {
+        declare(symbol_state, name, EntityKind::Function, &specifiers.storage, Definition::Defined);
+    }
+
+    symbol_state.push_scope(ScopeKind::Block);
+    declare_parameters(symbol_state, parameters);
+
+    for name in PREDEFINED.iter().filter_map(|name| ast.symbols.get(name)) {
+        let entity = Entity {
+            name,
+            kind: EntityKind::Object,
+            scope: ScopeKind::Block,
+            linkage: Linkage::None,
+            storage: Some(StorageDuration::Static),
+            definition: Definition::Defined,
+            node: symbol_state.node,
+            previous: None,
+        };
+
+        let id = symbol_state.push_entity(entity);
+
+        if let Some(scope) = symbol_state.scopes.last_mut() {
+            scope.ordinary.insert(name, id);
+        }
+    }
+
+    if let NodeKind::Compound(items) = ast.kind(body) {
+        for &item in items {
+            symbol_state.visit_node(ast, item);
+        }
+    }
+
+    symbol_state.pop_scope();
+    resolve_gotos(symbol_state);
+    symbol_state.undeclared.clear();
+}
+
+
+fn process_declaration<'a>(symbol_state: &mut SymbolTableState<'a>, specifiers: &'a DeclSpecifiers, declarators: &'a [InitDeclarator]) {
+    let ast = symbol_state.ast;
+
+    match specifiers.specifiers.as_slice() {
+        [TypeSpecifier::Record { kind, tag: Some(tag), members: None }] if declarators.is_empty() => {
+            let entity = declare_tag(symbol_state, *tag, *kind, Definition::Declared);
+            symbol_state.table.tags.insert((symbol_state.node, *tag), entity);
+        }
+
+        _ => symbol_state.visit_decl_specifiers(ast, specifiers),
+    }
+
+    let is_typedef = specifiers.storage.contains(&Keyword::Typedef);
+    let is_extern = specifiers.storage.contains(&Keyword::Extern);
+    let function_typedef = specifiers.specifiers.iter().any(|specifier| matches!(specifier, TypeSpecifier::TypedefName(name)
+        if symbol_state.lookup(*name).is_some_and(|id| symbol_state.function_typedefs.contains(&id))));
+
+    for init_declarator in declarators {
+        let declarator = &init_declarator.declarator;
+        symbol_state.visit_declarator(ast, declarator);
+
+        if let Some(name) = declarator.name {
+            let is_function = declarator.function_parameters().is_some() || (function_typedef && declarator.derived.is_empty());
+
+            let (kind, definition) = if is_typedef {
+                (EntityKind::Typedef, Definition::Defined)
+            } else if is_function {
+                (EntityKind::Function, Definition::Declared)
+            } else if init_declarator.initializer.is_some() {
+                (EntityKind::Object, Definition::Defined)
+            } else if is_extern {
+                (EntityKind::Object, Definition::Declared)
+            } else if symbol_state.scope_kind() == ScopeKind::File {
+                (EntityKind::Object, Definition::Tentative)
+            } else {
+                (EntityKind::Object, Definition::Defined)
+            };
+
+            let id = declare(symbol_state, name, kind, &specifiers.storage, definition);
+
+            if is_typedef && is_function {
+                symbol_state.function_typedefs.insert(id);
+            }
+
+            if is_extern && init_declarator.initializer.is_some() && symbol_state.scope_kind() != ScopeKind::File {
+                let line = symbol_state.ast[symbol_state.node].span.start;
+                symbol_state.report(Severity::Error, line, format!("'{}' has both 'extern' and initializer", symbol_state.name(name)));
+            }
+        }
+
+        for &child in [&init_declarator.bit_width, &init_declarator.initializer].into_iter().flatten() {
+            symbol_state.visit_node(ast, child);
+        }
+    }
+}
+
+
+fn process_derived<'a>(symbol_state: &mut SymbolTableState<'a>, derived: &'a [DerivedDeclarator]) {
+    let ast = symbol_state.ast;
+
+    for derived in derived {
+        match derived {
+            DerivedDeclarator::Array(Some(size)) => symbol_state.visit_node(ast, *size),
+
+            DerivedDeclarator::Function { parameters, .. } => {
+                symbol_state.push_scope(ScopeKind::Prototype);
+                declare_parameters(symbol_state, parameters);
+                symbol_state.pop_scope();
+            }
+
+            DerivedDeclarator::Pointer(_) | DerivedDeclarator::Array(None) => {}
+        }
+    }
+}
+
+
+fn declare_parameters<'a>(symbol_state: &mut SymbolTableState<'a>, parameters: &'a [Parameter]) {
+    let ast = symbol_state.ast;
+
+    for parameter in parameters {
+        symbol_state.visit_decl_specifiers(ast, &parameter.specifiers);
+        symbol_state.visit_declarator(ast, &parameter.declarator);
+
+        if let Some(name) = parameter.declarator.name {
+            declare(symbol_state, name, EntityKind::Parameter, &parameter.specifiers.storage, Definition::Defined);
+        }
+    }
+}
+
+
+fn declare(symbol_state: &mut SymbolTableState, name: Symbol, kind: EntityKind, storage: &[Keyword], definition: Definition) -> EntityId {
+    let scope = symbol_state.scope_kind();
+    let line = symbol_state.ast[symbol_state.node].span.start;
+    let spelling = symbol_state.name(name);
+    let is_static = storage.contains(&Keyword::Static);
+    let is_extern = storage.contains(&Keyword::Extern);
+
+    let prior_linkage = symbol_state.lookup(name)
+        .map(|id| symbol_state.table[id].linkage)
+        .filter(|linkage| *linkage != Linkage::None);
+
+    let linkage = match kind {
+        EntityKind::Object | EntityKind::Function if scope == ScopeKind::File && is_static => Linkage::Internal,
+
+        EntityKind::Function => {
+            if is_static {
+                symbol_state.report(Severity::Error, line, format!("invalid storage class for function '{}'", spelling));
+            }
+
+            prior_linkage.unwrap_or(Linkage::External)
+        }
+
+        EntityKind::Object if is_extern => prior_linkage.unwrap_or(Linkage::External),
+        EntityKind::Object if scope == ScopeKind::File => Linkage::External,
+        _ => Linkage::None,
+    };
+
+    let storage = match kind {
+        EntityKind::Object if scope == ScopeKind::File || is_static || is_extern => Some(StorageDuration::Static),
+        EntityKind::Object | EntityKind::Parameter => Some(StorageDuration::Automatic),
+        _ => None,
+    };
+
+    let existing = symbol_state.scopes.last().and_then(|scope| scope.ordinary.get(&name).copied());
+    let linked = symbol_state.linked.get(&name).copied().filter(|_| linkage != Linkage::None);
+    let mut previous = existing.or(linked);
+
+    if let Some(existing) = existing {
+        let existing_kind = symbol_state.table[existing].kind;
+
+        let message = if existing_kind != kind {
+            Some(format!("'{}' redeclared as different kind of symbol", spelling))
+        } else if kind == EntityKind::EnumConstant {
+            Some(format!("redeclaration of enumerator '{}'", spelling))
+        } else if kind == EntityKind::Parameter {
+            Some(format!("redefinition of parameter '{}'", spelling))
+        } else if linkage == Linkage::None && kind != EntityKind::Typedef {
+            Some(format!("redeclaration of '{}' with no linkage", spelling))
+        } else {
+            None
+        };
+
+        if let Some(message) = message {
+            report_previous(symbol_state, line, message, existing, "declaration");
+            previous = None;
+        }
+    }
+
+    if let Some(linked) = linked {
+        let linked_linkage = symbol_state.table[linked].linkage;
+
+        if linkage == Linkage::Internal && linked_linkage == Linkage::External {
+            report_previous(symbol_state, line, format!("static declaration of '{}' follows non-static declaration", spelling), linked, "declaration");
+        } else if linkage == Linkage::External && linked_linkage == Linkage::Internal {
+            report_previous(symbol_state, line, format!("non-static declaration of '{}' follows static declaration", spelling), linked, "declaration");
+        }
+    }
+
+    if definition == Definition::Defined && matches!(kind, EntityKind::Object | EntityKind::Function)
+        && let Some(defined) = previous.and_then(|previous| symbol_state.table.definition(previous))
+        && symbol_state.table[defined].definition == Definition::Defined {
+        report_previous(symbol_state, line, format!("redefinition of '{}'", spelling), defined, "definition");
+    }
+
+    let entity = Entity { name, kind, scope, linkage, storage, definition, node: symbol_state.node, previous };
+    let id = symbol_state.push_entity(entity);
+
+    if let Some(scope) = symbol_state.scopes.last_mut() {
+        scope.ordinary.insert(name, id);
+    }
+
+    if linkage != Linkage::None {
+        symbol_state.linked.insert(name, id);
+    }
+
+    id
+}
+
+
+fn declare_tag(symbol_state: &mut SymbolTableState, name: Symbol, keyword: Keyword, definition: Definition) -> EntityId {
+    let line = symbol_state.ast[symbol_state.node].span.start;
+    let existing = symbol_state.scopes.last().and_then(|scope| scope.tags.get(&name).copied());
+
+    if let Some(existing) = existing {
+        let spelling = format!("{} {}", keyword, symbol_state.name(name));
+
+        if symbol_state.table[existing].kind != EntityKind::Tag(keyword) {
+            report_previous(symbol_state, line, format!("'{}' defined as wrong kind of tag", symbol_state.name(name)), existing, "declaration");
+        } else if definition == Definition::Declared {
+            return existing;
+        } else if symbol_state.table[existing].definition == Definition::Defined {
+            report_previous(symbol_state, line, format!("redefinition of '{}'", spelling), existing, "definition");
+        }
+    }
+
+    let entity = Entity {
+        name,
+        kind: EntityKind::Tag(keyword),
+        scope: symbol_state.scope_kind(),
+        linkage: Linkage::None,
+        storage: None,
+        definition,
+        node: symbol_state.node,
+        previous: existing,
+    };
+
+    let id = symbol_state.push_entity(entity);
+
+    if let Some(scope) = symbol_state.scopes.last_mut() {
+        scope.tags.insert(name, id);
+    }
+
+    id
+}
+
+
+fn reference_tag(symbol_state: &mut SymbolTableState, name: Symbol, keyword: Keyword) -> EntityId {
+    let Some(existing) = symbol_state.lookup_tag(name) else {
+        return declare_tag(symbol_state, name, keyword, Definition::Declared);
+    };
+
+    if symbol_state.table[existing].kind != EntityKind::Tag(keyword) {
+        let line = symbol_state.ast[symbol_state.node].span.start;
+        let message = format!("use of '{}' with tag type that does not match previous declaration", symbol_state.name(name));
+
+        report_previous(symbol_state, line, message, existing, "declaration");
+    }
+
+    existing
+}
+
+
+fn process_members<'a>(symbol_state: &mut SymbolTableState<'a>, members: &'a [NodeId]) -> Vec<EntityId> {
+    let ast = symbol_state.ast;
+    let mut declared: Vec<EntityId> = Vec::new();
+
+    for &member in members {
+        let NodeKind::Declaration { specifiers, declarators, .. } = ast.kind(member) else {
+            symbol_state.visit_node(ast, member);
+            continue;
+        };
+
+        let previous_node = std::mem::replace(&mut symbol_state.node, member);
+
+        let mut fields = match specifiers.specifiers.as_slice() {
+            [TypeSpecifier::Record { tag: None, members: Some(inner), .. }] if declarators.is_empty() => process_members(symbol_state, inner),
+
+            _ => {
+                symbol_state.visit_decl_specifiers(ast, specifiers);
+                Vec::new()
+            }
+        };
+
+        for init_declarator in declarators {
+            symbol_state.visit_declarator(ast, &init_declarator.declarator);
+
+            if let Some(bit_width) = init_declarator.bit_width {
+                symbol_state.visit_node(ast, bit_width);
+            }
+
+            if let Some(name) = init_declarator.declarator.name {
+                let entity = Entity {
+                    name,
+                    kind: EntityKind::Member,
+                    scope: symbol_state.scope_kind(),
+                    linkage: Linkage::None,
+                    storage: None,
+                    definition: Definition::Defined,
+                    node: member,
+                    previous: None,
+                };
+
+                fields.push(symbol_state.push_entity(entity));
+            }
+        }
+
+        for field in fields {
+            let name = symbol_state.table[field].name;
+
+            if let Some(&existing) = declared.iter().find(|&&existing| symbol_state.table[existing].name == name) {
+                let line = symbol_state.line(field);
+                report_previous(symbol_state, line, format!("duplicate member '{}'", symbol_state.name(name)), existing, "declaration");
+            }
+
+            declared.push(field);
+        }
+
+        symbol_state.node = previous_node;
+    }
+
+    declared
+}
+
+
+fn process_enumerators<'a>(symbol_state: &mut SymbolTableState<'a>, enumerators: &'a [Enumerator]) {
+    let ast = symbol_state.ast;
+
+    for enumerator in enumerators {
+        if let Some(value) = enumerator.value {
+            symbol_state.visit_node(ast, value);
+        }
+
+        declare(symbol_state, enumerator.name, EntityKind::EnumConstant, &[], Definition::Defined);
+    }
+}
+
+
+/* * * * * * * * * */
+/*  - STATEMENTS - */
+/* * * * * * * * * */
+fn declare_label(symbol_state: &mut SymbolTableState, name: Symbol, label: NodeId) {
+    let line = symbol_state.ast[label].span.start;
+
+    if let Some(&existing) = symbol_state.labels.get(&name) {
+        report_previous(symbol_state, line, format!("duplicate label '{}'", symbol_state.name(name)), existing, "definition");
+        return;
+    }
+
+    let entity = Entity {
+        name,
+        kind: EntityKind::Label,
+        scope: ScopeKind::Function,
+        linkage: Linkage::None,
+        storage: None,
+        definition: Definition::Defined,
+        node: label,
+        previous: None,
+    };
+
+    let id = symbol_state.push_entity(entity);
+    symbol_state.labels.insert(name, id);
+}
+
+
+fn resolve_gotos(symbol_state: &mut SymbolTableState) {
+    let mut reported = HashSet::new();
+
+    for (goto, name) in std::mem::take(&mut symbol_state.gotos) {
+        if let Some(&label) = symbol_state.labels.get(&name) {
+            symbol_state.table.references.insert(goto, label);
+        } else if reported.insert(name) {
+            let line = symbol_state.ast[goto].span.start;
+            symbol_state.report(Severity::Error, line, format!("label '{}' used but not defined", symbol_state.name(name)));
+        }
+    }
+
+    symbol_state.labels.clear();
+}
+
+
+fn resolve_identifier(symbol_state: &mut SymbolTableState, id: NodeId, name: Symbol) {
+    if let Some(entity) = symbol_state.lookup(name) {
+        symbol_state.table.references.insert(id, entity);
+        return;
+    }
+
+    let spelling = symbol_state.name(name);
+
+    if spelling.starts_with("__builtin_") || !symbol_state.undeclared.insert(name) {
+        return;
+    }
+
+    let line = symbol_state.ast[id].span.start;
+    symbol_state.report(Severity::Error, line, format!("use of undeclared identifier '{}'", spelling));
+}
+
+
+fn report_previous(symbol_state: &mut SymbolTableState, line: u16, message: String, previous: EntityId, what: &str) {
+    let note = format!("previous {} of '{}' is here", what, symbol_state.name(symbol_state.table[previous].name));
+    let previous_line = symbol_state.line(previous);
+
+    symbol_state.report(Severity::Error, line, message);
+    symbol_state.report(Severity::Note, previous_line, note);
+}
+
+
+impl<'a> Visitor<'a> for SymbolTableState<'a> {
+    fn visit_node(&mut self, ast: &'a Ast, id: NodeId) {
+        let previous_node = std::mem::replace(&mut self.node, id);
+        walk_node(self, ast, id);
+        self.node = previous_node;
+    }
+
+
+    fn visit_function_definition(&mut self, _ast: &'a Ast, _id: NodeId, _attributes: &'a [Attribute], specifiers: &'a DeclSpecifiers, declarator: &'a Declarator, body: NodeId) {
+        process_function_definition(self, specifiers, declarator, body);
+    }
+
+
+    fn visit_declaration(&mut self, _ast: &'a Ast, _id: NodeId, _attributes: &'a [Attribute], specifiers: &'a DeclSpecifiers, declarators: &'a [InitDeclarator]) {
+        process_declaration(self, specifiers, declarators);
+    }
+
+
+    fn visit_compound(&mut self, ast: &'a Ast, _id: NodeId, items: &'a [NodeId]) {
+        self.push_scope(ScopeKind::Block);
+        walk_items(self, ast, items);
+        self.pop_scope();
+    }
+
+
+    fn visit_for(&mut self, ast: &'a Ast, _id: NodeId, init: Option<NodeId>, condition: Option<NodeId>, step: Option<NodeId>, body: NodeId) {
+        self.push_scope(ScopeKind::Block);
+        walk_for(self, ast, init, condition, step, body);
+        self.pop_scope();
+    }
+
+
+    fn visit_label(&mut self, ast: &'a Ast, id: NodeId, name: Symbol, body: NodeId) {
+        declare_label(self, name, id);
+        self.visit_node(ast, body);
+    }
+
+
+    fn visit_goto(&mut self, _ast: &'a Ast, id: NodeId, name: Symbol) {
+        self.gotos.push((id, name));
+    }
+
+
+    fn visit_identifier(&mut self, _ast: &'a Ast, id: NodeId, name: Symbol) {
+        resolve_identifier(self, id, name);
+    }
+
+
+    fn visit_type_specifier(&mut self, ast: &'a Ast, specifier: &'a TypeSpecifier) {
+        match specifier {
+            TypeSpecifier::Record { kind, tag, members } => match (tag, members) {
+                (Some(tag), None) => {
+                    let entity = reference_tag(self, *tag, *kind);
+                    self.table.tags.insert((self.node, *tag), entity);
+                }
+
+                (None, None) => {}
+
+                (tag, Some(members)) => {
+                    if let Some(tag) = tag {
+                        let entity = declare_tag(self, *tag, *kind, Definition::Defined);
+                        self.table.tags.insert((self.node, *tag), entity);
+                    }
+
+                    process_members(self, members);
+                }
+            },
+
+            TypeSpecifier::Enum { tag, enumerators } => match (tag, enumerators) {
+                (Some(tag), None) => {
+                    let entity = reference_tag(self, *tag, Keyword::Enum);
+                    self.table.tags.insert((self.node, *tag), entity);
+                }
+
+                (None, None) => {}
+
+                (tag, Some(enumerators)) => {
+                    if let Some(tag) = tag {
+                        let entity = declare_tag(self, *tag, Keyword::Enum, Definition::Defined);
+                        self.table.tags.insert((self.node, *tag), entity);
+                    }
+
+                    process_enumerators(self, enumerators);
+                }
+            },
+
+            TypeSpecifier::TypedefName(name) => {
+                if let Some(entity) = self.lookup(*name) {
+                    self.table.typedef_names.insert((self.node, *name), entity);
+                }
+            }
+
+            _ => walk_type_specifier(self, ast, specifier),
+        }
+    }
+
+
+    fn visit_declarator(&mut self, _ast: &'a Ast, declarator: &'a Declarator) {
+        process_derived(self, &declarator.derived);
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use crate::data::structures::{ Analyses, Block, IrFunction, Loop };
+use crate::data::types::{ BlockId, InstructionKind, Operand, Register };
+use crate::logic::induction::trip_count;
+use crate::logic::simplify_cfg::remove_unreachable_blocks;
+
+const MAX_TRIP_COUNT: u64 = 8;
+const MAX_UNROLLED_SIZE: u64 = 128;
+
+
+pub fn loop_unroll(function: &mut IrFunction, analyses: &mut Analyses) -> bool {
+    let mut changed = false;
+
+    loop {
+        let (loops, inserted) = analyses.canonical_loops(function);
+        changed |= inserted;
+
+        let candidate = loops.iter().enumerate()
+            .filter(|(index, _)| !loops.iter().any(|inner| inner.parent == Some(*index)))
+            .find_map(|(_, natural)| plan(function, natural).map(|trip_count| (natural, trip_count)));
+
+        let Some((natural, trip_count)) = candidate else {
+            return changed;
+        };
+
+        unroll(function, natural, trip_count);
+        analyses.invalidate(&[]);
+        changed = true;
+    }
+}
+
+
+fn plan(function: &IrFunction, natural: &Loop) -> Option<u64> {
+    let trip_count = trip_count(function, natural)?;
+    let size: usize = natural.blocks.iter().map(|block| function.blocks[block.0 as usize].instructions.len()).sum();
+
+    (trip_count <= MAX_TRIP_COUNT && trip_count * size as u64 <= MAX_UNROLLED_SIZE).then_some(trip_count)
+}
+
+
+/* * * * * * * * * */
+/*  - UNROLLING -  */
+/* * * * * * * * * */
+fn unroll(function: &mut IrFunction, natural: &Loop, trip_count: u64) {
+    let Some(preheader) = function.preheader(natural) else {
+        return;
+    };
+
+    let header = natural.header;
+    let latch = natural.latches[0];
+    let order: Vec<BlockId> = function.reverse_postorder().into_iter().filter(|block| natural.blocks.contains(block)).collect();
+    let positions: HashMap<BlockId, u32> = order.iter().enumerate().map(|(position, block)| (*block, position as u32)).collect();
+    let first = function.blocks.len() as u32;
+    let copy = |iteration: u64, block: BlockId| BlockId(first + iteration as u32 * order.len() as u32 + positions[&block]);
+
+    let InstructionKind::Branch { then_block, else_block, .. } = function.blocks[header.0 as usize].instructions.last().map(|instruction| instruction.kind.clone()).unwrap_or(InstructionKind::Unreachable) else {
+        return;
+    };
+
+    let (inside, exit) = if natural.blocks.contains(&then_block) { (then_block, else_block) } else { (else_block, then_block) };
+    let mut values = incoming_values(function, header, preheader);
+    let mut mapping = HashMap::new();
+
+    for iteration in 0..=trip_count {
+        let last = iteration == trip_count;
+        let target = |block: BlockId| if block == header { copy(iteration + 1, header) } else { copy(iteration, block) };
+        mapping = values.clone();
+
+        for block in order.iter().take(if last { 1 } else { order.len() }) {
+            let mut instructions = Vec::new();
+
+            for mut instruction in function.blocks[block.0 as usize].instructions.clone() {
+                if *block == header && matches!(instruction.kind, InstructionKind::Phi { .. }) {
+                    continue;
+                }
+
+                for operand in instruction.operands_mut() {
+                    operand.substitute(&mapping);
+                }
+
+                if let Some(result) = instruction.result {
+                    let fresh = function.new_register();
+                    mapping.insert(result, Operand::Register(fresh));
+                    instruction.result = Some(fresh);
+                }
+
+                if let InstructionKind::Phi { incoming } = &mut instruction.kind {
+                    for (_, predecessor) in incoming.iter_mut() {
+                        *predecessor = copy(iteration, *predecessor);
+                    }
+                }
+
+                if *block == header && instruction.is_terminator() {
+                    instruction.kind = InstructionKind::Jump(if last { exit } else { target(inside) });
+                } else {
+                    for successor in instruction.successors() {
+                        instruction.retarget(successor, target(successor));
+                    }
+                }
+
+                instructions.push(instruction);
+            }
+
+            function.blocks.push(Block { id: copy(iteration, *block), instructions });
+        }
+
+        if !last {
+            values = incoming_values(function, header, latch).into_iter()
+                .map(|(register, mut value)| {
+                    value.substitute(&mapping);
+                    (register, value)
+                })
+                .collect();
+        }
+    }
+
+    if let Some(terminator) = function.blocks[preheader.0 as usize].instructions.last_mut() {
+        terminator.retarget(header, copy(0, header));
+    }
+
+    for instruction in &mut function.blocks[exit.0 as usize].instructions {
+        if let InstructionKind::Phi { incoming } = &mut instruction.kind {
+            for (value, predecessor) in incoming.iter_mut().filter(|(_, predecessor)| *predecessor == header) {
+                value.substitute(&mapping);
+                *predecessor = copy(trip_count, header);
+            }
+        }
+    }
+
+    remove_unreachable_blocks(function);
+    function.replace_operands(&mapping);
+}
+
+
+fn incoming_values(function: &IrFunction, header: BlockId, predecessor: BlockId) -> HashMap<Register, Operand> {
+    function.blocks[header.0 as usize].instructions.iter()
+        .filter_map(|instruction| match (&instruction.kind, instruction.result) {
+            (InstructionKind::Phi { incoming }, Some(result)) => {
+                incoming.iter().find(|(_, source)| *source == predecessor).map(|(value, _)| (result, value.clone()))
+            }
+
+            _ => None,
+        })
+        .collect()
+}
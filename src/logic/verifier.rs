@@ -0,0 +1,489 @@
+use std::collections::HashSet;
+
+use crate::data::{ Diagnostic, Module };
+use crate::data::structures::{ Global, Instruction, IrFunction, SourceMap, VerifierState };
+use crate::data::types::{ BlockId, ConversionOperator, InstructionKind, IrType, Operand, Severity };
+
+
+pub fn verifier_start(module: &Module) -> Vec<Diagnostic> {
+    verify_module(VerifierState::new(module, None), module)
+}
+
+
+pub fn verifier_start_with_source(module: &Module, source_map: &SourceMap) -> Vec<Diagnostic> {
+    verify_module(VerifierState::new(module, Some(source_map)), module)
+}
+
+
+fn verify_module<'a>(mut verifier_state: VerifierState<'a>, module: &'a Module) -> Vec<Diagnostic> {
+    let mut names = HashSet::new();
+
+    for name in module.globals.iter().map(|global| &global.name).chain(module.functions.iter().map(|function| &function.name)) {
+        if !names.insert(name) {
+            report(&mut verifier_state, format!("symbol `@{}` is defined more than once", name));
+        }
+    }
+
+    for global in &module.globals {
+        verify_global(&mut verifier_state, global);
+    }
+
+    for function in &module.functions {
+        verify_function(&mut verifier_state, function);
+    }
+
+    verifier_state.diagnostics
+}
+
+
+/* * * * * * * * */
+/*  - GLOBALS -  */
+/* * * * * * * * */
+fn verify_global(verifier_state: &mut VerifierState, global: &Global) {
+    if global.external {
+        return;
+    }
+
+    if !global.align.is_power_of_two() {
+        report(verifier_state, format!("global `@{}` has alignment {}, which is not a power of two", global.name, global.align));
+    }
+
+    if global.data.len() as u64 > global.size {
+        report(verifier_state, format!("global `@{}` has {} bytes of data but a size of {}", global.name, global.data.len(), global.size));
+    }
+
+    for relocation in &global.relocations {
+        if relocation.offset + IrType::Ptr.size() > global.size {
+            report(verifier_state, format!("relocation at offset {} lies outside global `@{}`", relocation.offset, global.name));
+        }
+
+        if !verifier_state.symbols.contains_key(relocation.symbol.as_str()) {
+            report(verifier_state, format!("global `@{}` refers to undefined symbol `@{}`", global.name, relocation.symbol));
+        }
+    }
+}
+
+
+/* * * * * * * * * */
+/*  - FUNCTIONS -  */
+/* * * * * * * * * */
+fn verify_function<'a>(verifier_state: &mut VerifierState<'a>, function: &'a IrFunction) {
+    verifier_state.function = Some(function);
+    verifier_state.source = verifier_state.source_map.and_then(|source_map| source_map.functions.get(&function.name));
+    verifier_state.register_types.clear();
+    verifier_state.definitions.clear();
+
+    for (ty, register) in &function.parameters {
+        if *ty == IrType::Void {
+            report(verifier_state, format!("parameter {} has type void", verifier_state.register_name(*register)));
+        }
+
+        if verifier_state.register_types.insert(*register, *ty).is_some() {
+            report(verifier_state, format!("register {} is defined more than once", verifier_state.register_name(*register)));
+        }
+    }
+
+    if function.is_declaration() {
+        return;
+    }
+
+    if !verify_structure(verifier_state, function) {
+        return;
+    }
+
+    for block in &function.blocks {
+        for (index, instruction) in block.instructions.iter().enumerate() {
+            let Some(result) = instruction.result else {
+                continue;
+            };
+
+            if verifier_state.register_types.insert(result, instruction.result_type()).is_some() {
+                report_at(verifier_state, block.id, instruction, format!("register {} is defined more than once", verifier_state.register_name(result)));
+            }
+
+            verifier_state.definitions.insert(result, (block.id, index));
+        }
+    }
+
+    for block in &function.blocks {
+        for instruction in &block.instructions {
+            verify_instruction(verifier_state, block.id, instruction);
+        }
+    }
+
+    verify_dominance(verifier_state, function);
+}
+
+
+fn verify_structure(verifier_state: &mut VerifierState, function: &IrFunction) -> bool {
+    let mut valid = true;
+
+    for (index, block) in function.blocks.iter().enumerate() {
+        if block.id != BlockId(index as u32) {
+            report(verifier_state, format!("block at position {} is labelled {}", index, verifier_state.block_name(block.id)));
+            return false;
+        }
+
+        if !block.instructions.last().is_some_and(Instruction::is_terminator) {
+            let line = block.instructions.len().checked_sub(1).and_then(|index| verifier_state.source_line(block.id, index)).map(|(line, _)| line);
+            report_line(verifier_state, line, format!("{} does not end with a terminator", verifier_state.block_name(block.id)));
+            valid = false;
+        }
+
+        let mut phis_allowed = true;
+
+        for (position, instruction) in block.instructions.iter().enumerate() {
+            if instruction.is_terminator() && position + 1 != block.instructions.len() {
+                report_at(verifier_state, block.id, instruction, "terminator in the middle of a block".to_string());
+                valid = false;
+            }
+
+            match instruction.kind {
+                InstructionKind::Phi { .. } if !phis_allowed => {
+                    report_at(verifier_state, block.id, instruction, "phi after a non-phi instruction".to_string());
+                }
+
+                InstructionKind::Phi { .. } => {}
+                _ => phis_allowed = false,
+            }
+
+            if Instruction::defines_value(instruction.ty, &instruction.kind) != instruction.result.is_some() {
+                let message = if instruction.result.is_some() { "instruction does not produce a value" } else { "instruction result is not named" };
+                report_at(verifier_state, block.id, instruction, message.to_string());
+            }
+
+            for successor in instruction.successors() {
+                if successor.0 as usize >= function.blocks.len() {
+                    report_at(verifier_state, block.id, instruction, format!("branch to nonexistent block {}", successor));
+                    valid = false;
+                }
+            }
+        }
+    }
+
+    valid
+}
+
+
+/* * * * * * * * * * * * */
+/*  - INSTRUCTIONS -  */
+/* * * * * * * * * * * * */
+fn verify_instruction(verifier_state: &mut VerifierState, block: BlockId, instruction: &Instruction) {
+    let ty = instruction.ty;
+
+    match &instruction.kind {
+        InstructionKind::Binary { operator, left, right, .. } => {
+            if ty.is_floating() != operator.is_floating() || matches!(ty, IrType::Void | IrType::Ptr) {
+                report_at(verifier_state, block, instruction, format!("`{}` cannot operate on {}", operator.name(), ty));
+            }
+
+            check_operand(verifier_state, block, instruction, left, ty);
+            check_operand(verifier_state, block, instruction, right, ty);
+        }
+
+        InstructionKind::Compare { predicate, left, right } => {
+            if predicate.is_floating() != ty.is_floating() || ty == IrType::Void {
+                report_at(verifier_state, block, instruction, format!("predicate `{}` cannot compare {}", predicate.name(), ty));
+            }
+
+            check_operand(verifier_state, block, instruction, left, ty);
+            check_operand(verifier_state, block, instruction, right, ty);
+        }
+
+        InstructionKind::Convert { operator, value, from } => {
+            if !is_valid_conversion(*operator, *from, ty) {
+                report_at(verifier_state, block, instruction, format!("`{}` cannot convert {} to {}", operator.name(), from, ty));
+            }
+
+            check_operand(verifier_state, block, instruction, value, *from);
+        }
+
+        InstructionKind::Alloca { size, align } => {
+            if !align.is_power_of_two() {
+                report_at(verifier_state, block, instruction, format!("alignment {} is not a power of two", align));
+            }
+
+            check_operand(verifier_state, block, instruction, size, IrType::I64);
+            check_type(verifier_state, block, instruction, IrType::Ptr);
+        }
+
+        InstructionKind::Load { address, .. } => {
+            check_value_type(verifier_state, block, instruction);
+            check_operand(verifier_state, block, instruction, address, IrType::Ptr);
+        }
+
+        InstructionKind::Store { value, address, .. } => {
+            check_value_type(verifier_state, block, instruction);
+            check_operand(verifier_state, block, instruction, value, ty);
+            check_operand(verifier_state, block, instruction, address, IrType::Ptr);
+        }
+
+        InstructionKind::PtrAdd { base, offset } => {
+            check_type(verifier_state, block, instruction, IrType::Ptr);
+            check_operand(verifier_state, block, instruction, base, IrType::Ptr);
+            check_operand(verifier_state, block, instruction, offset, IrType::I64);
+        }
+
+        InstructionKind::Copy { destination, source, .. } => {
+            check_operand(verifier_state, block, instruction, destination, IrType::Ptr);
+            check_operand(verifier_state, block, instruction, source, IrType::Ptr);
+        }
+
+        InstructionKind::Zero { address, .. } => check_operand(verifier_state, block, instruction, address, IrType::Ptr),
+
+        InstructionKind::Call { callee, arguments } => {
+            check_operand(verifier_state, block, instruction, callee, IrType::Ptr);
+
+            for (argument_type, argument) in arguments {
+                check_operand(verifier_state, block, instruction, argument, *argument_type);
+            }
+
+            verify_call(verifier_state, block, instruction, callee, arguments);
+        }
+
+        InstructionKind::Phi { incoming } => {
+            check_value_type(verifier_state, block, instruction);
+
+            for (value, _) in incoming {
+                check_operand(verifier_state, block, instruction, value, ty);
+            }
+        }
+
+        InstructionKind::Branch { condition, .. } => check_operand(verifier_state, block, instruction, condition, IrType::I1),
+
+        InstructionKind::Switch { value, cases, .. } => {
+            if !ty.is_integer() {
+                report_at(verifier_state, block, instruction, format!("cannot switch on {}", ty));
+            }
+
+            let mut seen = HashSet::new();
+
+            for (case, _) in cases {
+                if !seen.insert(case) {
+                    report_at(verifier_state, block, instruction, format!("duplicate case value {}", case));
+                }
+            }
+
+            check_operand(verifier_state, block, instruction, value, ty);
+        }
+
+        InstructionKind::Return(value) => {
+            let return_type = verifier_state.function.map_or(IrType::Void, |function| function.return_type);
+
+            match value {
+                Some(value) => {
+                    check_type(verifier_state, block, instruction, return_type);
+                    check_operand(verifier_state, block, instruction, value, return_type);
+                }
+
+                None if return_type != IrType::Void => {
+                    report_at(verifier_state, block, instruction, format!("missing return value of type {}", return_type));
+                }
+
+                None => {}
+            }
+        }
+
+        InstructionKind::Jump(_) | InstructionKind::Unreachable => {}
+    }
+}
+
+
+fn verify_call(verifier_state: &mut VerifierState, block: BlockId, instruction: &Instruction, callee: &Operand, arguments: &[(IrType, Operand)]) {
+    let Operand::Global(name) = callee else {
+        return;
+    };
+
+    let target = match verifier_state.symbols.get(name.as_str()) {
+        Some(Some(function)) => *function,
+        Some(None) => return report_at(verifier_state, block, instruction, format!("call to non-function `@{}`", name)),
+        None => return,
+    };
+
+    let count_matches = match target.variadic {
+        true => arguments.len() >= target.parameters.len(),
+        false => arguments.len() == target.parameters.len(),
+    };
+
+    if !count_matches {
+        report_at(verifier_state, block, instruction, format!("`@{}` takes {} arguments, {} given", name, target.parameters.len(), arguments.len()));
+    }
+
+    for ((argument_type, _), (parameter_type, _)) in arguments.iter().zip(&target.parameters) {
+        if argument_type != parameter_type {
+            report_at(verifier_state, block, instruction, format!("argument of type {} passed to parameter of type {}", argument_type, parameter_type));
+        }
+    }
+
+    if instruction.ty != target.return_type {
+        report_at(verifier_state, block, instruction, format!("`@{}` returns {}, not {}", name, target.return_type, instruction.ty));
+    }
+}
+
+
+/* * * * * * * * * * */
+/*  - DOMINANCE -  */
+/* * * * * * * * * * */
+fn verify_dominance(verifier_state: &mut VerifierState, function: &IrFunction) {
+    let dominators = function.dominators();
+    let predecessors = function.predecessors();
+
+    for block in &function.blocks {
+        if dominators[block.id.0 as usize].is_none() {
+            continue;
+        }
+
+        for (index, instruction) in block.instructions.iter().enumerate() {
+            if let InstructionKind::Phi { incoming } = &instruction.kind {
+                verify_phi(verifier_state, block.id, instruction, incoming, &predecessors[block.id.0 as usize]);
+
+                for (value, predecessor) in incoming {
+                    let Operand::Register(register) = value else {
+                        continue;
+                    };
+
+                    let reachable = dominators.get(predecessor.0 as usize).is_some_and(Option::is_some);
+
+                    if reachable && let Some(&(definition, _)) = verifier_state.definitions.get(register)
+                        && !IrFunction::dominates(&dominators, definition, *predecessor) {
+                        report_at(verifier_state, block.id, instruction, format!("{} does not dominate the end of {}", verifier_state.register_name(*register), verifier_state.block_name(*predecessor)));
+                    }
+                }
+
+                continue;
+            }
+
+            let mut used = instruction.clone();
+
+            for operand in used.operands_mut() {
+                let Operand::Register(register) = operand else {
+                    continue;
+                };
+
+                let Some(&(definition, position)) = verifier_state.definitions.get(register) else {
+                    continue;
+                };
+
+                let dominated = match definition == block.id {
+                    true => position < index,
+                    false => IrFunction::dominates(&dominators, definition, block.id),
+                };
+
+                if !dominated {
+                    report_at(verifier_state, block.id, instruction, format!("{} does not dominate this use", verifier_state.register_name(*register)));
+                }
+            }
+        }
+    }
+}
+
+
+fn verify_phi(verifier_state: &mut VerifierState, block: BlockId, instruction: &Instruction, incoming: &[(Operand, BlockId)], predecessors: &[BlockId]) {
+    if incoming.len() != predecessors.len() {
+        report_at(verifier_state, block, instruction, format!("phi has {} incoming values but {} has {} predecessors", incoming.len(), verifier_state.block_name(block), predecessors.len()));
+    }
+
+    let mut seen = HashSet::new();
+
+    for (_, predecessor) in incoming {
+        if !predecessors.contains(predecessor) {
+            report_at(verifier_state, block, instruction, format!("{} is not a predecessor of {}", verifier_state.block_name(*predecessor), verifier_state.block_name(block)));
+        } else if !seen.insert(predecessor) {
+            report_at(verifier_state, block, instruction, format!("phi lists {} more than once", verifier_state.block_name(*predecessor)));
+        }
+    }
+}
+
+
+/* * * * * * * * */
+/*  - HELPERS -  */
+/* * * * * * * * */
+fn check_operand(verifier_state: &mut VerifierState, block: BlockId, instruction: &Instruction, operand: &Operand, expected: IrType) {
+    let actual = match operand {
+        Operand::Register(register) => match verifier_state.register_types.get(register) {
+            Some(ty) => *ty,
+            None => return report_at(verifier_state, block, instruction, format!("use of undefined register {}", verifier_state.register_name(*register))),
+        },
+
+        Operand::Global(name) => {
+            if !verifier_state.symbols.contains_key(name.as_str()) {
+                return report_at(verifier_state, block, instruction, format!("use of undefined symbol `@{}`", name));
+            }
+
+            IrType::Ptr
+        }
+
+        Operand::Integer(_) if expected.is_integer() => expected,
+        Operand::Floating(_) if expected.is_floating() => expected,
+        Operand::Integer(_) => IrType::I64,
+        Operand::Floating(_) => IrType::F64,
+        Operand::Null => IrType::Ptr,
+        Operand::Undef => expected,
+    };
+
+    if actual != expected {
+        report_at(verifier_state, block, instruction, format!("operand `{}` has type {}, expected {}", verifier_state.operand_name(operand), actual, expected));
+    }
+}
+
+
+fn check_type(verifier_state: &mut VerifierState, block: BlockId, instruction: &Instruction, expected: IrType) {
+    if instruction.ty != expected {
+        report_at(verifier_state, block, instruction, format!("instruction has type {}, expected {}", instruction.ty, expected));
+    }
+}
+
+
+fn check_value_type(verifier_state: &mut VerifierState, block: BlockId, instruction: &Instruction) {
+    if instruction.ty == IrType::Void {
+        report_at(verifier_state, block, instruction, "instruction cannot have type void".to_string());
+    }
+}
+
+
+fn is_valid_conversion(operator: ConversionOperator, from: IrType, to: IrType) -> bool {
+    match operator {
+        ConversionOperator::Trunc => from.is_integer() && to.is_integer() && from.bits() > to.bits(),
+        ConversionOperator::ZExt | ConversionOperator::SExt => from.is_integer() && to.is_integer() && from.bits() < to.bits(),
+        ConversionOperator::FpTrunc => from == IrType::F64 && to == IrType::F32,
+        ConversionOperator::FpExt => from == IrType::F32 && to == IrType::F64,
+        ConversionOperator::FpToSi | ConversionOperator::FpToUi => from.is_floating() && to.is_integer(),
+        ConversionOperator::SiToFp | ConversionOperator::UiToFp => from.is_integer() && to.is_floating(),
+        ConversionOperator::PtrToInt => from == IrType::Ptr && to.is_integer(),
+        ConversionOperator::IntToPtr => from.is_integer() && to == IrType::Ptr,
+    }
+}
+
+
+fn report(verifier_state: &mut VerifierState, message: String) {
+    report_line(verifier_state, None, message);
+}
+
+
+fn report_line(verifier_state: &mut VerifierState, line: Option<u16>, message: String) {
+    let message = match verifier_state.function {
+        Some(function) => format!("in `@{}`: {}", function.name, message),
+        None => message,
+    };
+
+    let message = match line {
+        Some(line) => format!("{}: {}", line, message),
+        None => message,
+    };
+
+    verifier_state.diagnostics.push(Diagnostic::new(Severity::Error, message));
+}
+
+
+fn report_at(verifier_state: &mut VerifierState, block: BlockId, instruction: &Instruction, message: String) {
+    let index = verifier_state.function
+        .and_then(|function| function.blocks.get(block.0 as usize))
+        .and_then(|candidate| candidate.instructions.iter().position(|other| std::ptr::eq(other, instruction)));
+
+    let name = verifier_state.block_name(block);
+
+    match index.and_then(|index| verifier_state.source_line(block, index)) {
+        Some((line, text)) => report_line(verifier_state, Some(line), format!("{}: `{}`: {}", name, text, message)),
+        None => report(verifier_state, format!("{}: `{}`: {}", name, instruction, message)),
+    }
+}
@@ -8,38 +8,125 @@ use std::io::Write;
 use data::Token;
 use logic::lexer_start;
 use logic::parser_start;
+use logic::sym_table_start;
+use logic::semantic_start;
+use logic::dataflow_start;
+use logic::ir_start;
+use logic::ir_parser_start;
+use logic::verifier_start;
+use logic::verifier_start_with_source;
+use logic::pass_manager_start;
+use logic::interpreter_start;
+use logic::dump_start;
+use logic::printer_start;
 
-use crate::data::TokenType;
+use crate::data::{ Module, PassOptions, Target, TokenType, Warnings };
+use crate::data::types::{ DumpFormat, Severity };
 
 const INPUT_CODE: &str = "./examples/oddEven.c";
 const DEBUG: bool = false;
 const MAX_TO_PRINT: usize = 20;
 const PRINT_TYPE: u8 = 4; // 0=All, 1=Keyword, 2=Operator, 3=Literal, 4=Identifier
 
+type Arguments = (String, Option<DumpFormat>, bool, bool, bool, PassOptions, Option<String>, Warnings);
+
 fn main() {
-    let preproces_source = match preproces_source(INPUT_CODE) {
+    let (input_path, dump_format, print_ast, emit_ir, run, pass_options, output_path, warnings) = match process_arguments() {
+        Ok(arguments) => arguments,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if input_path.ends_with(".ir") {
+        return process_ir_file(&input_path, emit_ir, run, &pass_options, output_path.as_deref());
+    }
+
+    let preproces_source = match preproces_source(&input_path) {
         Ok(src) => src,
         Err(e) => {
             eprintln!("Preprocessing failed: {}", e);
-            return;
+            std::process::exit(1);
         }
     };
 
     let token = match lexer_start(&preproces_source) {
         Ok(token) => token,
         Err(e) => {
-            println!("\nError: {:?}\n", e);
-            return;
+            eprintln!("\nError: {:?}\n", e);
+            std::process::exit(1);
         }
     };
 
-    let _parser_return = match parser_start(&token) {
-        Ok(parser_return) => parser_return,
-        Err(e) => {
-            println!("\nError: {:?}\n", e);
-            return;
+    let (mut ast, errors) = parser_start(&token);
+
+    if !errors.is_empty() {
+        for e in &errors {
+            eprintln!("Error: {}", e);
         }
-    };
+
+        std::process::exit(1);
+    }
+
+    let target = Target::x86_64_sysv();
+    let (symbol_table, mut diagnostics) = sym_table_start(&ast);
+    let (types, semantic_diagnostics) = semantic_start(&mut ast, &symbol_table, &target);
+    diagnostics.extend(semantic_diagnostics);
+
+    if !diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error) {
+        diagnostics.extend(dataflow_start(&ast, &symbol_table, &types, &warnings));
+    }
+
+    for diagnostic in &diagnostics {
+        eprintln!("{}", diagnostic);
+    }
+
+    if let Some(format) = dump_format {
+        let dump = dump_start(&ast, &types, format);
+
+        if let Err(e) = write_output(output_path.as_deref(), &dump) {
+            eprintln!("Failed to write AST dump: {}", e);
+        }
+    }
+
+    if print_ast && let Err(e) = write_output(output_path.as_deref(), &printer_start(&ast)) {
+        eprintln!("Failed to write printed source: {}", e);
+    }
+
+    if diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error) {
+        std::process::exit(1);
+    }
+
+    let inspect_passes = !pass_options.print_after.is_empty() || pass_options.time_passes;
+
+    if emit_ir || run || inspect_passes {
+        let (mut module, mut ir_diagnostics) = ir_start(&ast, &symbol_table, &types, &target);
+
+        if cfg!(debug_assertions) && ir_diagnostics.is_empty() {
+            ir_diagnostics.extend(verifier_start(&module));
+        }
+
+        if ir_diagnostics.is_empty() {
+            ir_diagnostics.extend(pass_manager_start(&mut module, &pass_options));
+        }
+
+        for diagnostic in &ir_diagnostics {
+            eprintln!("{}", diagnostic);
+        }
+
+        if !ir_diagnostics.is_empty() {
+            std::process::exit(1);
+        }
+
+        if emit_ir && let Err(e) = write_output(output_path.as_deref(), &module.to_string()) {
+            eprintln!("Failed to write IR: {}", e);
+        }
+
+        if run {
+            run_module(&module, &input_path);
+        }
+    }
 
     if DEBUG {
         preproces_out(preproces_source);
@@ -48,6 +135,119 @@ fn main() {
 }
 
 
+fn process_arguments() -> Result<Arguments, String> {
+    let mut input_path = None;
+    let mut dump_format = None;
+    let mut print_ast = false;
+    let mut emit_ir = false;
+    let mut run = false;
+    let mut pass_options = PassOptions::default();
+    let mut output_path = None;
+    let mut warnings = Warnings::default();
+    let mut arguments = std::env::args().skip(1);
+
+    while let Some(argument) = arguments.next() {
+        if let Some(format) = argument.strip_prefix("--dump-ast=") {
+            dump_format = Some(format.parse::<DumpFormat>()?);
+        } else if argument == "--dump-ast" {
+            dump_format = Some(DumpFormat::Tree);
+        } else if argument == "--print-ast" {
+            print_ast = true;
+        } else if argument == "--emit-ir" {
+            emit_ir = true;
+        } else if argument == "--run" {
+            run = true;
+        } else if let Some(level) = argument.strip_prefix("-O") {
+            pass_options.level = match level {
+                "" => 1,
+                "0" | "1" | "2" => level.parse().unwrap_or(0),
+                _ => return Err(format!("unknown optimization level '{}'", level)),
+            };
+        } else if let Some(passes) = argument.strip_prefix("--passes=") {
+            pass_options.passes = Some(passes.split(',').filter(|pass| !pass.is_empty()).map(str::parse).collect::<Result<_, _>>()?);
+        } else if let Some(passes) = argument.strip_prefix("--print-after=") {
+            for pass in passes.split(',') {
+                pass_options.print_after.push(pass.parse()?);
+            }
+        } else if argument == "--time-passes" {
+            pass_options.time_passes = true;
+        } else if argument == "-o" {
+            output_path = Some(arguments.next().ok_or("missing file name after '-o'")?);
+        } else if let Some(option) = argument.strip_prefix("-W") {
+            warnings.apply(option)?;
+        } else if argument.starts_with('-') {
+            return Err(format!("unknown option '{}'", argument));
+        } else if input_path.replace(argument).is_some() {
+            return Err("multiple input files given".to_string());
+        }
+    }
+
+    Ok((input_path.unwrap_or_else(|| INPUT_CODE.to_string()), dump_format, print_ast, emit_ir, run, pass_options, output_path, warnings))
+}
+
+
+fn process_ir_file(input_path: &str, emit_ir: bool, run: bool, pass_options: &PassOptions, output_path: Option<&str>) {
+    let source = match fs::read_to_string(input_path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", input_path, e);
+            std::process::exit(1);
+        }
+    };
+
+    let (mut module, source_map, errors) = ir_parser_start(&source);
+
+    if !errors.is_empty() {
+        for e in &errors {
+            eprintln!("Error: {}", e);
+        }
+
+        std::process::exit(1);
+    }
+
+    let mut diagnostics = verifier_start_with_source(&module, &source_map);
+
+    if diagnostics.is_empty() {
+        diagnostics.extend(pass_manager_start(&mut module, pass_options));
+    }
+
+    for diagnostic in &diagnostics {
+        eprintln!("{}", diagnostic);
+    }
+
+    if !diagnostics.is_empty() {
+        std::process::exit(1);
+    }
+
+    if emit_ir && let Err(e) = write_output(output_path, &module.to_string()) {
+        eprintln!("Failed to write IR: {}", e);
+    }
+
+    if run {
+        run_module(&module, input_path);
+    }
+}
+
+
+fn run_module(module: &Module, program: &str) {
+    match interpreter_start(module, program, &mut std::io::stdout()) {
+        Ok(code) => std::process::exit(code),
+        Err(diagnostic) => {
+            eprintln!("{}", diagnostic);
+            std::process::exit(1);
+        }
+    }
+}
+
+
+fn write_output(output_path: Option<&str>, content: &str) -> std::io::Result<()> {
+    match output_path {
+        Some(path) => fs::write(path, content),
+        None => std::io::stdout().write_all(content.as_bytes()),
+    }
+}
+
+
 fn preproces_source(file_path: &str) -> Result<String, Box<dyn std::error::Error>> {
     let output = Command::new("cpp")
         .arg(file_path)